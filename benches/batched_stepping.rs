@@ -0,0 +1,66 @@
+//! Compares per-step execution ([`Computable::compute_completable`]) with batched execution
+//! ([`Computable::compute_batched`]) for a very fine-grained step.
+//!
+//! Run using `cargo bench --bench batched_stepping`. Every configuration is measured both
+//! without any cancellation trigger and with an active (never triggered) thread-local
+//! trigger, since checking the trigger is the cost that batching avoids.
+
+use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+use computation_process::bench::{StepOverhead, measure_batched_overhead};
+use computation_process::{Completable, ComputationStep, Incomplete};
+use std::time::Duration;
+
+/// The number of steps of one measured run.
+const STEPS: u64 = 10_000_000;
+
+/// The measured batch sizes.
+const BATCHES: [usize; 5] = [1, 8, 64, 512, 4096];
+
+/// Adds the step counter to a checksum; one addition per step.
+struct SumStep;
+
+impl ComputationStep<u64, (u64, u64), u64> for SumStep {
+    fn step(target: &u64, (count, sum): &mut (u64, u64)) -> Completable<u64> {
+        *count += 1;
+        *sum = sum.wrapping_add(*count);
+        if *count >= *target {
+            Ok(*sum)
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+fn measure(batch: usize) -> (StepOverhead, StepOverhead) {
+    measure_batched_overhead::<_, _, _, SumStep>(STEPS, (0, 0), batch)
+}
+
+/// The average time of one step in nanoseconds (with sub-nanosecond precision).
+fn nanos_per_step(total: Duration, steps: u32) -> f64 {
+    total.as_secs_f64() * 1e9 / f64::from(steps.max(1))
+}
+
+fn report(label: &str, batch: usize, (unbatched, batched): (StepOverhead, StepOverhead)) {
+    println!(
+        "{label:>10} | batch {batch:>5} | per-step {:>6.2} ns/step ({:>6.1}%) | batched {:>6.2} ns/step ({:>6.1}%)",
+        nanos_per_step(unbatched.suspended(), unbatched.steps()),
+        unbatched.relative_overhead() * 100.0,
+        nanos_per_step(batched.suspended(), batched.steps()),
+        batched.relative_overhead() * 100.0,
+    );
+}
+
+fn main() {
+    println!("{STEPS} steps; the overhead is relative to a plain loop over the step function.");
+    for batch in BATCHES {
+        report("no trigger", batch, measure(batch));
+    }
+    for batch in BATCHES {
+        let result = on_trigger(CancelAtomic::new(), || Ok::<_, Cancelled>(measure(batch)));
+        report(
+            "trigger",
+            batch,
+            result.expect("The trigger is never cancelled."),
+        );
+    }
+}
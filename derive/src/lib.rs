@@ -0,0 +1,366 @@
+//! Derive and state machine macros for the
+//! [`computation-process`](https://crates.io/crates/computation-process) crate. Use them through the `derive` feature of `computation-process` instead of depending
+//! on this crate directly.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input};
+
+mod state_machine;
+
+/// Compile straight-line code with `suspend!()` markers into a `ComputationStep`.
+///
+/// The macro expects a unit struct declaration (the name of the generated step type)
+/// followed by a closure-like function taking a reference to the computation `CONTEXT`
+/// and returning the `OUTPUT`. The body is regular Rust code with the following rules:
+///
+///  - `suspend!();` creates a suspend point. It can appear as a statement in the function
+///    body, or (arbitrarily nested) in the bodies of `while` loops and `if`/`else` branches.
+///    Using it in other constructs (e.g., `for` loops or `match` arms) is an error.
+///  - Variables that live across suspend points must be declared using a top-level `let`
+///    with an explicit type (e.g., `let mut total: u64 = 0;`). Variables declared within
+///    loops or branches cannot be used across suspend points.
+///  - `return`, `break`, `continue`, and `?` cannot cross a suspend point (they are only
+///    allowed within blocks that do not contain `suspend!()`).
+///  - `return` and `?` exit the step function, which returns `Completable<OUTPUT>`.
+///    Returning `Ok(output)` completes the computation. Otherwise, all variables are kept
+///    and the next step executes the statements since the last suspend point (or the
+///    start of the current loop iteration or branch) again.
+///
+/// The macro generates the step struct, a `<Name>State` struct (which is `Default`)
+/// storing the position in the code and all variables, and a `<Name>::start(context)`
+/// constructor of the resulting `Computation`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Computable, Incomplete, computation};
+///
+/// computation! {
+///     /// Sums the items of a vector, suspending after every item.
+///     struct SumItems;
+///     fn(items: &Vec<u64>) -> u64 {
+///         let mut index: usize = 0;
+///         let mut total: u64 = 0;
+///         while index < items.len() {
+///             total += items[index];
+///             index += 1;
+///             suspend!();
+///         }
+///         total
+///     }
+/// }
+///
+/// let mut sum = SumItems::start(vec![1, 2, 3]);
+/// assert_eq!(sum.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(sum.compute().unwrap(), 6);
+/// ```
+#[proc_macro]
+pub fn computation(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as state_machine::MachineInput);
+    match state_machine::expand(input, state_machine::Kind::Computation) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Compile straight-line code with `emit!(item)` and `suspend!()` markers into a
+/// `GeneratorStep`.
+///
+/// This is the generator counterpart of `computation!` (the same rules apply). Each
+/// `emit!(item);` statement yields one item of the generator. The function "returns"
+/// the item type of the generator, and its body must not end with a value.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Generatable, generator};
+///
+/// generator! {
+///     pub struct Squares;
+///     fn(limit: &u32) -> u32 {
+///         let mut i: u32 = 1;
+///         while i * i <= *limit {
+///             emit!(i * i);
+///             i += 1;
+///         }
+///     }
+/// }
+///
+/// let squares: Vec<u32> = Squares::start(20).map(|item| item.unwrap()).collect();
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// ```
+#[proc_macro]
+pub fn generator(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as state_machine::MachineInput);
+    match state_machine::expand(input, state_machine::Kind::Generator) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive a `ComputationStep` implementation from an enum of computation phases.
+///
+/// The derived enum is used as the `STATE` of a `Computation`. Each variant represents one
+/// phase of the computation and is processed by a handler function of the enum
+/// (by default, the `snake_case` name of the variant; use `#[phase(handler = name)]`
+/// to override it). The handler receives the computation `CONTEXT` followed by mutable
+/// references to all fields of the variant, and returns
+/// `Completable<Transition<Self, OUTPUT>>`:
+///
+///  - `Ok(Transition::Next(phase))` moves the computation to a different phase and suspends.
+///  - `Ok(Transition::Done(output))` completes the computation.
+///  - `Err(Incomplete::Suspended)` suspends the computation in the current phase.
+///
+/// The enum must be annotated with `#[computation(context = ..., output = ...)]`.
+/// The macro then generates a unit struct (named `<Enum>Step` unless specified using
+/// `step = ...`) which implements `ComputationStep<CONTEXT, Enum, OUTPUT>`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{
+///     Completable, Computable, Computation, ComputationState, Incomplete, FromParts, Transition,
+/// };
+///
+/// #[derive(ComputationState)]
+/// #[computation(context = Vec<i32>, output = i32, step = SumStep)]
+/// enum SumPhase {
+///     Start,
+///     Summing { index: usize, total: i32 },
+/// }
+///
+/// impl SumPhase {
+///     fn start(_: &Vec<i32>) -> Completable<Transition<Self, i32>> {
+///         Ok(Transition::Next(SumPhase::Summing { index: 0, total: 0 }))
+///     }
+///
+///     fn summing(
+///         items: &Vec<i32>,
+///         index: &mut usize,
+///         total: &mut i32,
+///     ) -> Completable<Transition<Self, i32>> {
+///         let Some(item) = items.get(*index) else {
+///             return Ok(Transition::Done(*total));
+///         };
+///         *index += 1;
+///         *total += item;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// let mut sum = Computation::<Vec<i32>, SumPhase, i32, SumStep>::from_parts(vec![1, 2, 3], SumPhase::Start);
+/// assert_eq!(sum.compute().unwrap(), 6);
+/// ```
+#[proc_macro_derive(ComputationState, attributes(computation, phase))]
+pub fn derive_computation_state(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// The arguments of the `#[computation(...)]` attribute.
+struct ComputationArgs {
+    context: Type,
+    output: Type,
+    step: Ident,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`ComputationState` does not support generic types.",
+        ));
+    }
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`ComputationState` can only be derived for enums.",
+        ));
+    };
+
+    let args = parse_computation_args(&input)?;
+    let name = &input.ident;
+    let visibility = &input.vis;
+    let ComputationArgs {
+        context,
+        output,
+        step,
+    } = &args;
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let handler = parse_handler(variant)?;
+        let arm = match &variant.fields {
+            Fields::Unit => quote! {
+                #name::#variant_name => #name::#handler(context)
+            },
+            Fields::Named(fields) => {
+                let names: Vec<&Ident> = fields
+                    .named
+                    .iter()
+                    .map(|f| f.ident.as_ref().expect("Named field."))
+                    .collect();
+                quote! {
+                    #name::#variant_name { #(#names),* } => #name::#handler(context, #(#names),*)
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let names: Vec<Ident> = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("field_{}", i))
+                    .collect();
+                quote! {
+                    #name::#variant_name ( #(#names),* ) => #name::#handler(context, #(#names),*)
+                }
+            }
+        };
+        arms.push(arm);
+    }
+
+    let doc = format!("The `ComputationStep` generated for the phases of [`{name}`].");
+    Ok(quote! {
+        #[doc = #doc]
+        #visibility struct #step;
+
+        impl ::computation_process::ComputationStep<#context, #name, #output> for #step {
+            fn step(
+                context: &#context,
+                state: &mut #name,
+            ) -> ::computation_process::Completable<#output> {
+                let transition = match state {
+                    #(#arms,)*
+                }?;
+                match transition {
+                    ::computation_process::Transition::Next(phase) => {
+                        *state = phase;
+                        Err(::computation_process::Incomplete::Suspended)
+                    }
+                    ::computation_process::Transition::Done(output) => Ok(output),
+                }
+            }
+        }
+    })
+}
+
+fn parse_computation_args(input: &DeriveInput) -> syn::Result<ComputationArgs> {
+    let mut context = None;
+    let mut output = None;
+    let mut step = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("computation"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("context") {
+                context = Some(meta.value()?.parse::<Type>()?);
+            } else if meta.path.is_ident("output") {
+                output = Some(meta.value()?.parse::<Type>()?);
+            } else if meta.path.is_ident("step") {
+                step = Some(meta.value()?.parse::<Ident>()?);
+            } else {
+                return Err(meta.error("Expected `context`, `output`, or `step`."));
+            }
+            Ok(())
+        })?;
+    }
+    let missing = |key: &str| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("Missing `{key}` in the `#[computation(...)]` attribute."),
+        )
+    };
+    Ok(ComputationArgs {
+        context: context.ok_or_else(|| missing("context"))?,
+        output: output.ok_or_else(|| missing("output"))?,
+        step: step.unwrap_or_else(|| format_ident!("{}Step", input.ident)),
+    })
+}
+
+fn parse_handler(variant: &syn::Variant) -> syn::Result<Ident> {
+    let mut handler = None;
+    for attr in variant.attrs.iter().filter(|a| a.path().is_ident("phase")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("handler") {
+                handler = Some(meta.value()?.parse::<Ident>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `handler`."))
+            }
+        })?;
+    }
+    Ok(handler.unwrap_or_else(|| {
+        Ident::new(
+            &to_snake_case(&variant.ident.to_string()),
+            variant.ident.span(),
+        )
+    }))
+}
+
+/// Convert a `CamelCase` variant name into a `snake_case` method name.
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("Init"), "init");
+        assert_eq!(to_snake_case("SummingItems"), "summing_items");
+        assert_eq!(to_snake_case("done"), "done");
+    }
+
+    #[test]
+    fn test_rejects_structs() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[computation(context = u32, output = u32)]
+            struct NotAnEnum;
+        };
+        let error = expand(input).unwrap_err();
+        assert!(error.to_string().contains("only be derived for enums"));
+    }
+
+    #[test]
+    fn test_requires_output() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[computation(context = u32)]
+            enum Phase { Start }
+        };
+        let error = expand(input).unwrap_err();
+        assert!(error.to_string().contains("Missing `output`"));
+    }
+
+    #[test]
+    fn test_default_step_name() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[computation(context = u32, output = u32)]
+            enum Phase { Start, #[phase(handler = finish)] End(u32) }
+        };
+        let tokens = expand(input).unwrap().to_string();
+        assert!(tokens.contains("struct PhaseStep"));
+        assert!(tokens.contains("Phase :: start (context)"));
+        assert!(tokens.contains("Phase :: finish (context , field_0)"));
+    }
+}
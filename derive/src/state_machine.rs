@@ -0,0 +1,602 @@
+//! Implementation of the `computation!` and `generator!` macros.
+//!
+//! The body of the macro is lowered into a list of basic blocks. Each block is a sequence of
+//! plain statements followed by a [`Terminator`] (jump, branch, suspend, emit, or return).
+//! The generated step function then dispatches on the index of the current block, which is
+//! stored (together with all persistent variables) in a generated state struct.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Attribute, Block, Expr, Ident, Item, Pat, Stmt, Token, Type, Visibility, parse_quote};
+
+/// Distinguishes the `computation!` and `generator!` macros.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Computation,
+    Generator,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Computation => "computation!",
+            Kind::Generator => "generator!",
+        }
+    }
+}
+
+/// The input of the macro:
+///
+/// ```text
+/// #[attributes]
+/// pub struct Name;
+/// fn(context: &CONTEXT) -> OUTPUT { body }
+/// ```
+pub struct MachineInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    context: Ident,
+    context_type: Type,
+    output: Type,
+    body: Block,
+}
+
+impl Parse for MachineInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![;]>()?;
+        input.parse::<Token![fn]>()?;
+        let arguments;
+        syn::parenthesized!(arguments in input);
+        let context = arguments.parse()?;
+        arguments.parse::<Token![:]>()?;
+        arguments.parse::<Token![&]>()?;
+        let context_type = arguments.parse()?;
+        input.parse::<Token![->]>()?;
+        let output = input.parse()?;
+        let body = input.parse()?;
+        Ok(MachineInput {
+            attrs,
+            vis,
+            name,
+            context,
+            context_type,
+            output,
+            body,
+        })
+    }
+}
+
+enum Terminator {
+    Goto(usize),
+    Branch(Expr, usize, usize),
+    Suspend(usize),
+    Emit(Expr, usize),
+    Return(TokenStream),
+    End,
+}
+
+struct BasicBlock {
+    /// Persistent variables available when the block starts.
+    inputs: Vec<Ident>,
+    /// Persistent variables available when the block ends.
+    outputs: Vec<Ident>,
+    statements: Vec<Stmt>,
+    terminator: Option<Terminator>,
+}
+
+struct Builder {
+    kind: Kind,
+    blocks: Vec<BasicBlock>,
+    current: usize,
+    /// Persistent variables declared so far (in program order).
+    declared: Vec<(Ident, Type)>,
+    finished: bool,
+}
+
+impl Builder {
+    fn new_block(&mut self) -> usize {
+        self.blocks.push(BasicBlock {
+            inputs: self.declared.iter().map(|(i, _)| i.clone()).collect(),
+            outputs: Vec::new(),
+            statements: Vec::new(),
+            terminator: None,
+        });
+        self.blocks.len() - 1
+    }
+
+    fn terminate(&mut self, block: usize, terminator: Terminator) {
+        let outputs = self.declared.iter().map(|(i, _)| i.clone()).collect();
+        let block = &mut self.blocks[block];
+        block.outputs = outputs;
+        block.terminator = Some(terminator);
+    }
+
+    /// Rewrite all early exits (`?` and `return`) of the step function in `node`, such that
+    /// the persistent variables declared so far are stored back into the state first.
+    ///
+    /// Without this, an early exit would lose the variables (they are moved out of the state
+    /// at the beginning of every block), and the next step would fail.
+    fn guard_exits<T>(&self, mut node: T, visit: fn(&mut EarlyExits, &mut T)) -> T {
+        let state = Ident::new("state", Span::mixed_site());
+        let names = self.declared.iter().map(|(i, _)| i);
+        let mut exits = EarlyExits {
+            kind: self.kind,
+            store: quote! { #( #state.#names = Some(#names); )* },
+        };
+        visit(&mut exits, &mut node);
+        node
+    }
+
+    fn lower(&mut self, statements: &[Stmt], top_level: bool) -> syn::Result<()> {
+        for (index, statement) in statements.iter().enumerate() {
+            if self.finished {
+                return Err(syn::Error::new_spanned(
+                    statement,
+                    "Unreachable statement after the result of the computation.",
+                ));
+            }
+            if let Some(marker) = as_marker(statement) {
+                let (name, tokens) = marker;
+                let next = self.new_block();
+                let terminator = if name == "suspend" {
+                    if !tokens.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            tokens,
+                            "`suspend!()` takes no arguments.",
+                        ));
+                    }
+                    Terminator::Suspend(next)
+                } else if self.kind == Kind::Generator {
+                    let item = syn::parse2(tokens)?;
+                    Terminator::Emit(self.guard_exits(item, EarlyExits::visit_expr_mut), next)
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        statement,
+                        "`emit!(..)` can only be used in `generator!`.",
+                    ));
+                };
+                self.terminate(self.current, terminator);
+                self.current = next;
+                continue;
+            }
+            if !contains_marker(statement.to_token_stream()) {
+                let is_tail = top_level
+                    && index + 1 == statements.len()
+                    && matches!(statement, Stmt::Expr(_, None));
+                if is_tail {
+                    if self.kind == Kind::Generator {
+                        return Err(syn::Error::new_spanned(
+                            statement,
+                            "`generator!` cannot return a value; use `emit!(..)` instead.",
+                        ));
+                    }
+                    let statement = self.guard_exits(statement.clone(), EarlyExits::visit_stmt_mut);
+                    self.terminate(
+                        self.current,
+                        Terminator::Return(statement.to_token_stream()),
+                    );
+                    self.finished = true;
+                } else {
+                    let guarded = self.guard_exits(statement.clone(), EarlyExits::visit_stmt_mut);
+                    if top_level && let Stmt::Local(local) = statement {
+                        self.declare(local)?;
+                    }
+                    self.blocks[self.current].statements.push(guarded);
+                }
+                continue;
+            }
+            match statement {
+                Stmt::Expr(Expr::While(expr), _) if expr.label.is_none() => {
+                    let header = self.new_block();
+                    self.terminate(self.current, Terminator::Goto(header));
+                    let body = self.new_block();
+                    self.current = body;
+                    self.lower(&expr.body.stmts, false)?;
+                    self.terminate(self.current, Terminator::Goto(header));
+                    let exit = self.new_block();
+                    let condition =
+                        self.guard_exits(*expr.cond.clone(), EarlyExits::visit_expr_mut);
+                    self.terminate(header, Terminator::Branch(condition, body, exit));
+                    self.current = exit;
+                }
+                Stmt::Expr(Expr::If(expr), _) => {
+                    let condition = self.current;
+                    let then_branch = self.new_block();
+                    self.current = then_branch;
+                    self.lower(&expr.then_branch.stmts, false)?;
+                    let then_end = self.current;
+                    let else_branch = self.new_block();
+                    self.current = else_branch;
+                    if let Some((_, else_expr)) = &expr.else_branch {
+                        let else_statement = match &**else_expr {
+                            Expr::Block(block) => block.block.stmts.clone(),
+                            other => vec![Stmt::Expr(other.clone(), None)],
+                        };
+                        self.lower(&else_statement, false)?;
+                    }
+                    let join = self.new_block();
+                    self.terminate(self.current, Terminator::Goto(join));
+                    self.terminate(then_end, Terminator::Goto(join));
+                    let guard = self.guard_exits(*expr.cond.clone(), EarlyExits::visit_expr_mut);
+                    let branch = Terminator::Branch(guard, then_branch, else_branch);
+                    self.terminate(condition, branch);
+                    self.current = join;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        statement,
+                        format!(
+                            "Suspend points in `{}` are only supported in plain statements, \
+                             `while` loops, and `if` expressions.",
+                            self.kind.name()
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Register a top-level `let` statement as a persistent variable.
+    fn declare(&mut self, local: &syn::Local) -> syn::Result<()> {
+        let Pat::Type(typed) = &local.pat else {
+            return Err(syn::Error::new_spanned(
+                local,
+                "Variables of a state machine need an explicit type, e.g. `let mut x: u32 = 0;`.",
+            ));
+        };
+        let Pat::Ident(ident) = &*typed.pat else {
+            return Err(syn::Error::new_spanned(
+                &typed.pat,
+                "Variables of a state machine must be simple identifiers.",
+            ));
+        };
+        if local.init.is_none() {
+            return Err(syn::Error::new_spanned(
+                local,
+                "Variables of a state machine must be initialized.",
+            ));
+        }
+        let name = ident.ident.clone();
+        if name == "pc" || self.declared.iter().any(|(i, _)| *i == name) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "Variables of a state machine must have unique names (`pc` is reserved).",
+            ));
+        }
+        self.declared.push((name, (*typed.ty).clone()));
+        Ok(())
+    }
+}
+
+/// Rewrites the early exits of the step function (`expr?` and `return expr`), such that
+/// the persistent variables are stored (`store`) before the function returns.
+///
+/// The current block is not left, i.e., the next step executes it again from the beginning.
+/// The only exception is a `return` which completes the computation (or the generator).
+/// Closures, `async` blocks, and nested items have their own exits and are not rewritten.
+struct EarlyExits {
+    kind: Kind,
+    store: TokenStream,
+}
+
+impl VisitMut for EarlyExits {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if matches!(expr, Expr::Closure(_) | Expr::Async(_)) {
+            return;
+        }
+        visit_mut::visit_expr_mut(self, expr);
+        let store = &self.store;
+        let state = Ident::new("state", Span::mixed_site());
+        let value = Ident::new("value", Span::mixed_site());
+        let error = Ident::new("error", Span::mixed_site());
+        match expr {
+            Expr::Try(inner) => {
+                let inner = &inner.expr;
+                *expr = parse_quote! {
+                    match #inner {
+                        ::core::result::Result::Ok(#value) => #value,
+                        ::core::result::Result::Err(#error) => {
+                            #store
+                            return ::core::result::Result::Err(::core::convert::From::from(#error));
+                        }
+                    }
+                };
+            }
+            Expr::Return(inner) => {
+                let result = match &inner.expr {
+                    Some(result) => quote!(#result),
+                    None => quote!(()),
+                };
+                let finished = match self.kind {
+                    Kind::Computation => quote!(::core::result::Result::Ok(_)),
+                    Kind::Generator => {
+                        quote!(::core::result::Result::Ok(::core::option::Option::None))
+                    }
+                };
+                *expr = parse_quote! {
+                    {
+                        let #value = #result;
+                        if ::core::matches!(#value, #finished) {
+                            #state.pc = usize::MAX;
+                        } else {
+                            #store
+                        }
+                        return #value;
+                    }
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut Item) {}
+}
+
+/// If the statement is `suspend!(..)` or `emit!(..)`, return the marker name and its arguments.
+fn as_marker(statement: &Stmt) -> Option<(String, TokenStream)> {
+    let mac = match statement {
+        Stmt::Macro(mac) => &mac.mac,
+        Stmt::Expr(Expr::Macro(mac), _) => &mac.mac,
+        _ => return None,
+    };
+    let name = mac.path.get_ident()?.to_string();
+    (name == "suspend" || name == "emit").then(|| (name, mac.tokens.clone()))
+}
+
+/// Returns `true` if the tokens contain a `suspend!` or `emit!` invocation.
+fn contains_marker(tokens: TokenStream) -> bool {
+    let mut previous_marker = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => previous_marker = ident == "suspend" || ident == "emit",
+            TokenTree::Punct(punct) if punct.as_char() == '!' && previous_marker => return true,
+            TokenTree::Group(group) => {
+                if contains_marker(group.stream()) {
+                    return true;
+                }
+                previous_marker = false;
+            }
+            _ => previous_marker = false,
+        }
+    }
+    false
+}
+
+pub fn expand(input: MachineInput, kind: Kind) -> syn::Result<TokenStream> {
+    let mut builder = Builder {
+        kind,
+        blocks: Vec::new(),
+        current: 0,
+        declared: Vec::new(),
+        finished: false,
+    };
+    builder.current = builder.new_block();
+    builder.lower(&input.body.stmts, true)?;
+    if !builder.finished {
+        let terminator = match kind {
+            Kind::Computation => Terminator::Return(quote!(())),
+            Kind::Generator => Terminator::End,
+        };
+        builder.terminate(builder.current, terminator);
+    }
+
+    let MachineInput {
+        attrs,
+        vis,
+        name,
+        context,
+        context_type,
+        output,
+        ..
+    } = input;
+    let state_name = format_ident!("{}State", name);
+    let state = Ident::new("state", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let done = quote!(usize::MAX);
+    let names: Vec<&Ident> = builder.declared.iter().map(|(i, _)| i).collect();
+    let types: Vec<&Type> = builder.declared.iter().map(|(_, t)| t).collect();
+
+    let arms = builder.blocks.iter().enumerate().map(|(index, block)| {
+        let inputs = &block.inputs;
+        let outputs = &block.outputs;
+        let statements = &block.statements;
+        let store = quote! { #( #state.#outputs = Some(#outputs); )* };
+        let action = match block
+            .terminator
+            .as_ref()
+            .expect("Every block is terminated.")
+        {
+            Terminator::Goto(next) => quote! {
+                #store
+                #state.pc = #next;
+            },
+            Terminator::Branch(condition, then_block, else_block) => quote! {
+                let #value = #condition;
+                #store
+                #state.pc = if #value { #then_block } else { #else_block };
+            },
+            Terminator::Suspend(next) => quote! {
+                #store
+                #state.pc = #next;
+                return Err(::computation_process::Incomplete::Suspended);
+            },
+            Terminator::Emit(item, next) => quote! {
+                let #value = #item;
+                #store
+                #state.pc = #next;
+                return Ok(Some(#value));
+            },
+            Terminator::Return(result) => {
+                let result = match kind {
+                    Kind::Computation => quote!(#result),
+                    Kind::Generator => unreachable!("Generators cannot return a value."),
+                };
+                quote! {
+                    #state.pc = #done;
+                    return Ok(#result);
+                }
+            }
+            Terminator::End => quote! {
+                #state.pc = #done;
+                return Ok(None);
+            },
+        };
+        quote! {
+            #index => {
+                #(
+                    let mut #inputs = #state.#inputs.take()
+                        .expect("Variable of a state machine is not initialized.");
+                )*
+                #(#statements)*
+                #action
+            }
+        }
+    });
+
+    let (step_trait, step_output) = match kind {
+        Kind::Computation => (
+            quote!(::computation_process::ComputationStep<#context_type, #state_name, #output>),
+            quote!(#output),
+        ),
+        Kind::Generator => (
+            quote!(::computation_process::GeneratorStep<#context_type, #state_name, #output>),
+            quote!(Option<#output>),
+        ),
+    };
+    let (wrapper, start_doc) = match kind {
+        Kind::Computation => (
+            quote!(::computation_process::Computation<#context_type, #state_name, #output, #name>),
+            "Create a new `Computation` driven by this step, starting in the initial state.",
+        ),
+        Kind::Generator => (
+            quote!(::computation_process::Generator<#context_type, #state_name, #output, #name>),
+            "Create a new `Generator` driven by this step, starting in the initial state.",
+        ),
+    };
+    let state_doc = format!("The state of [`{name}`], generated by `{}`.", kind.name());
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #name;
+
+        #[doc = #state_doc]
+        #[derive(Default)]
+        #vis struct #state_name {
+            pc: usize,
+            #( #names: Option<#types>, )*
+        }
+
+        impl #name {
+            #[doc = #start_doc]
+            #vis fn start(context: #context_type) -> #wrapper {
+                <#wrapper as ::computation_process::FromParts<#context_type, #state_name>>::from_parts(
+                    context,
+                    #state_name::default(),
+                )
+            }
+        }
+
+        impl #step_trait for #name {
+            #[allow(unused_mut, unused_variables, unreachable_code, clippy::needless_return)]
+            fn step(
+                #context: &#context_type,
+                #state: &mut #state_name,
+            ) -> ::computation_process::Completable<#step_output> {
+                loop {
+                    match #state.pc {
+                        #(#arms)*
+                        _ => return Err(::computation_process::Incomplete::Exhausted),
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(kind: Kind, input: TokenStream) -> syn::Result<String> {
+        expand(syn::parse2(input)?, kind).map(|t| t.to_string())
+    }
+
+    #[test]
+    fn test_contains_marker() {
+        assert!(contains_marker(quote!(if x {
+            suspend!();
+        })));
+        assert!(contains_marker(quote!(emit!(1))));
+        assert!(!contains_marker(quote!(let suspend = 1; x != suspend)));
+    }
+
+    #[test]
+    fn test_requires_typed_variables() {
+        let error = expand_str(
+            Kind::Computation,
+            quote! { struct Test; fn(x: &u32) -> u32 { let y = 1; suspend!(); y } },
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("explicit type"));
+    }
+
+    #[test]
+    fn test_emit_only_in_generator() {
+        let error = expand_str(
+            Kind::Computation,
+            quote! { struct Test; fn(x: &u32) -> u32 { emit!(1); 2 } },
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("only be used in `generator!`"));
+    }
+
+    #[test]
+    fn test_unsupported_construct() {
+        let error = expand_str(
+            Kind::Generator,
+            quote! { struct Test; fn(x: &u32) -> u32 { for i in 0..*x { emit!(i); } } },
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("only supported in plain statements")
+        );
+    }
+
+    #[test]
+    fn test_guards_early_exits() {
+        let tokens = expand_str(
+            Kind::Computation,
+            quote! { struct Test; fn(x: &u32) -> u32 { let mut t: u32 = f()?; suspend!(); t } },
+        )
+        .unwrap();
+        // The error branch of `?` does not store `t`, which is not initialized yet.
+        assert!(tokens.contains("Err (error) => { return"));
+        let tokens = expand_str(
+            Kind::Computation,
+            quote! { struct Test; fn(x: &u32) -> u32 { let mut t: u32 = 0; g(t)?; suspend!(); t } },
+        )
+        .unwrap();
+        assert!(tokens.contains("Err (error) => { state . t = Some (t) ; return"));
+    }
+
+    #[test]
+    fn test_generates_state() {
+        let tokens = expand_str(
+            Kind::Computation,
+            quote! { pub struct Sum; fn(x: &u32) -> u32 { let mut t: u32 = 0; suspend!(); t } },
+        )
+        .unwrap();
+        assert!(tokens.contains("pub struct SumState"));
+        assert!(tokens.contains("t : Option < u32 >"));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{Completable, Computable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// The weight of the latest measurement in the moving average of the step duration.
+const SMOOTHING: f64 = 0.5;
+
+/// A [`Computable`] (or [`Generatable`]) that performs a variable number of inner steps
+/// per outer step, such that each outer step takes roughly the `target` amount of time.
+///
+/// The wrapper measures the wall-clock time of inner steps and keeps an exponential moving
+/// average of the time per step. Based on this estimate, it adjusts the number of inner
+/// steps (the *budget*) performed by the next outer step. To avoid overshooting after a
+/// series of cheap steps, the budget can at most double between two outer steps, and it
+/// never exceeds the configured maximum (see [`AdaptiveBudget::with_max_budget`]).
+///
+/// An outer step ends early once the inner computation completes (or produces an item),
+/// is cancelled, or is exhausted.
+///
+/// See also [`crate::ComputableExt::with_adaptive_budget`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{AdaptiveBudget, Completable, Computation, ComputationStep, Incomplete};
+/// use std::time::Duration;
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u64, u64, u64> for CountStep {
+///     fn step(target: &u64, count: &mut u64) -> Completable<u64> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let computation = Computation::<u64, u64, u64, CountStep>::from_parts(1_000_000, 0);
+/// let mut adaptive = computation.with_adaptive_budget(Duration::from_millis(5));
+/// assert_eq!(adaptive.budget(), 1);
+/// assert_eq!(adaptive.try_compute(), Err(Incomplete::Suspended));
+/// // Steps are cheap, hence the budget increases.
+/// assert_eq!(adaptive.budget(), 2);
+/// assert_eq!(adaptive.compute().unwrap(), 1_000_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveBudget<T, C> {
+    inner: C,
+    target: Duration,
+    budget: u64,
+    max_budget: u64,
+    average_step: Option<f64>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C> AdaptiveBudget<T, C> {
+    /// Wrap the `inner` computation (or generator) such that each outer step takes
+    /// roughly `target` time.
+    pub fn new(inner: C, target: Duration) -> Self {
+        AdaptiveBudget {
+            inner,
+            target,
+            budget: 1,
+            max_budget: u64::MAX,
+            average_step: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Limit the maximal number of inner steps performed by one outer step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_budget` is zero.
+    pub fn with_max_budget(mut self, max_budget: u64) -> Self {
+        assert!(max_budget > 0, "Maximal budget must be positive.");
+        self.max_budget = max_budget;
+        self.budget = self.budget.min(max_budget);
+        self
+    }
+
+    /// The number of inner steps that will be performed by the next outer step.
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// The target duration of one outer step.
+    pub fn target(&self) -> Duration {
+        self.target
+    }
+
+    /// The current estimate of the duration of one inner step (if at least one
+    /// step was measured).
+    pub fn average_step(&self) -> Option<Duration> {
+        self.average_step.map(Duration::from_secs_f64)
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Run inner steps using `step` until the budget is spent or the step returns
+    /// something else than [`Incomplete::Suspended`].
+    fn run_budget<R, F: FnMut(&mut C) -> Completable<R>>(&mut self, mut step: F) -> Completable<R> {
+        let started = Instant::now();
+        let mut performed = 0u64;
+        let result = loop {
+            let result = step(&mut self.inner);
+            performed += 1;
+            if !matches!(result, Err(Incomplete::Suspended)) || performed >= self.budget {
+                break result;
+            }
+        };
+        self.update(started.elapsed(), performed);
+        result
+    }
+
+    /// Update the step estimate and the budget based on a new measurement.
+    fn update(&mut self, elapsed: Duration, performed: u64) {
+        let sample = elapsed.as_secs_f64() / performed as f64;
+        let average = match self.average_step {
+            None => sample,
+            Some(average) => SMOOTHING * sample + (1.0 - SMOOTHING) * average,
+        };
+        self.average_step = Some(average);
+        let ideal = if average > 0.0 {
+            self.target.as_secs_f64() / average
+        } else {
+            f64::INFINITY
+        };
+        // Float to int conversion saturates, i.e., infinity is converted to u64::MAX.
+        let ideal = ideal as u64;
+        self.budget = ideal
+            .min(self.budget.saturating_mul(2))
+            .clamp(1, self.max_budget);
+    }
+}
+
+impl<T, C: Computable<T>> Computable<T> for AdaptiveBudget<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        self.run_budget(|inner| inner.try_compute())
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for AdaptiveBudget<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for AdaptiveBudget<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let mut finished = false;
+        let result = self.run_budget(|inner| match inner.try_next() {
+            Some(result) => result,
+            None => {
+                finished = true;
+                // Stops the budget loop; replaced by `None` below.
+                Err(Incomplete::Exhausted)
+            }
+        });
+        if finished { None } else { Some(result) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ComputableExt, Computation, ComputationStep, FromParts, Generator, GeneratorStep,
+        StatefulRef, test_fixtures::Count,
+    };
+
+    struct SleepStep;
+
+    impl ComputationStep<u64, u64, u64> for SleepStep {
+        fn step(target: &u64, count: &mut u64) -> Completable<u64> {
+            std::thread::sleep(Duration::from_millis(2));
+            *count += 1;
+            if *count >= *target {
+                Ok(*count)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    struct SparseStep;
+
+    impl GeneratorStep<u64, u64, u64> for SparseStep {
+        fn step(limit: &u64, value: &mut u64) -> Completable<Option<u64>> {
+            if *value >= *limit {
+                return Ok(None);
+            }
+            *value += 1;
+            if value.is_multiple_of(10) {
+                Ok(Some(*value))
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    #[test]
+    fn test_budget_grows_for_cheap_steps() {
+        let mut adaptive = Count::from_parts(1_000, 0).with_adaptive_budget(Duration::from_secs(1));
+        let mut budgets = Vec::new();
+        for _ in 0..4 {
+            assert_eq!(adaptive.try_compute(), Err(Incomplete::Suspended));
+            budgets.push(adaptive.budget());
+        }
+        assert_eq!(budgets, vec![2, 4, 8, 16]);
+        assert_eq!(*adaptive.inner().state(), 1 + 2 + 4 + 8);
+        assert!(adaptive.average_step().is_some());
+        assert_eq!(adaptive.compute(), Ok(1_000));
+    }
+
+    #[test]
+    fn test_budget_follows_target() {
+        let computation = Computation::<u64, u64, u64, SleepStep>::from_parts(1_000, 0);
+        let mut adaptive = computation.with_adaptive_budget(Duration::from_millis(4));
+        for _ in 0..6 {
+            assert_eq!(adaptive.try_compute(), Err(Incomplete::Suspended));
+        }
+        // Every step takes at least 2ms, hence the budget cannot grow beyond two steps.
+        assert!(adaptive.budget() <= 2);
+        assert!(adaptive.average_step().unwrap() >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_max_budget() {
+        let mut adaptive = AdaptiveBudget::new(Count::from_parts(1_000, 0), Duration::from_secs(1))
+            .with_max_budget(3);
+        for _ in 0..5 {
+            assert_eq!(adaptive.try_compute(), Err(Incomplete::Suspended));
+        }
+        assert_eq!(adaptive.budget(), 3);
+        assert_eq!(adaptive.target(), Duration::from_secs(1));
+        assert_eq!(*adaptive.into_inner().state(), 1 + 2 + 3 + 3 + 3);
+    }
+
+    #[test]
+    fn test_early_completion() {
+        let mut adaptive = Count::from_parts(3, 0).with_adaptive_budget(Duration::from_secs(1));
+        assert_eq!(adaptive.try_compute(), Err(Incomplete::Suspended));
+        // The second outer step performs two inner steps and completes the computation.
+        assert_eq!(adaptive.budget(), 2);
+        assert_eq!(adaptive.try_compute(), Ok(3));
+        assert_eq!(*adaptive.inner().state(), 3);
+    }
+
+    #[test]
+    fn test_generator() {
+        let generator = Generator::<u64, u64, u64, SparseStep>::from_parts(35, 0);
+        let mut adaptive =
+            AdaptiveBudget::new(generator, Duration::from_secs(1)).with_max_budget(100);
+        let mut suspended = 0;
+        let mut items = Vec::new();
+        while let Some(result) = adaptive.try_next() {
+            match result {
+                Ok(item) => items.push(item),
+                Err(Incomplete::Suspended) => suspended += 1,
+                Err(e) => panic!("Unexpected {e:?}"),
+            }
+        }
+        assert_eq!(items, vec![10, 20, 30]);
+        assert!(suspended < 35);
+        assert_eq!(adaptive.try_next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_zero_max_budget() {
+        let _ = AdaptiveBudget::<u32, _>::new(Count::from_parts(3, 0), Duration::from_secs(1))
+            .with_max_budget(0);
+    }
+}
@@ -1,39 +1,26 @@
 use crate::generatable::Generatable;
-use crate::{Collector, Computable, DynAlgorithm, DynGenAlgorithm};
+use crate::{
+    Collector, Computable, DynAlgorithm, DynAlgorithmSend, DynGenAlgorithm, DynGenAlgorithmSend,
+    FromParts, StatefulRef,
+};
 use cancel_this::Cancellable;
 
 /// A shared interface of objects that provide access to
-/// an immutable `CONTEXT` and mutable `STATE`.
-pub trait Stateful<CONTEXT, STATE> {
-    /// Create new [`Stateful`] instance using values that can be
-    /// converted to `CONTEXT` and `STATE`.
-    fn configure<I1: Into<CONTEXT>, I2: Into<STATE>>(context: I1, initial_state: I2) -> Self
-    where
-        Self: Sized + 'static,
-    {
-        Self::from_parts(context.into(), initial_state.into())
-    }
-
-    /// Create new [`Stateful`] instance from `CONTEXT` and `STATE`.
-    fn from_parts(context: CONTEXT, state: STATE) -> Self
-    where
-        Self: Sized + 'static;
-
-    /// Destruct the [`Stateful`] instance into `CONTEXT` and `STATE` objects.
-    fn into_parts(self) -> (CONTEXT, STATE);
-
-    /// Access to the underlying immutable `CONTEXT`.
-    fn context(&self) -> &CONTEXT;
-
-    /// Access to the underlying `STATE`.
-    fn state(&self) -> &STATE;
+/// an immutable `CONTEXT` and mutable `STATE` (see [`StatefulRef`]), and that can be
+/// constructed from (and destructed into) these parts (see [`FromParts`]).
+///
+/// The trait is implemented automatically for every type that implements both parts.
+/// The accessor part is object-safe and can be used on its own (e.g., for computations
+/// that cannot be constructed from parts, like [`crate::FnComputation`]). See also
+/// [`crate::StatefulMut`] for objects whose `CONTEXT` can be changed between two steps.
+pub trait Stateful<CONTEXT, STATE>:
+    StatefulRef<CONTEXT, STATE> + FromParts<CONTEXT, STATE>
+{
+}
 
-    /// Access to the underlying `STATE` as a mutable reference.
-    ///
-    /// Keep in mind that having a consistent state is important for the correctness
-    /// of [`Algorithm`] and [`GenAlgorithm`]. You should modify the internal state
-    /// of a [`Stateful`] object only in rare, well-defined situations.
-    fn state_mut(&mut self) -> &mut STATE;
+impl<CONTEXT, STATE, S> Stateful<CONTEXT, STATE> for S where
+    S: StatefulRef<CONTEXT, STATE> + FromParts<CONTEXT, STATE> + ?Sized
+{
 }
 
 /// Extends [`Computable`] trait with immutable `CONTEXT` and mutable `STATE`.
@@ -56,12 +43,55 @@ pub trait Algorithm<CONTEXT, STATE, OUTPUT>: Computable<OUTPUT> + Stateful<CONTE
     {
         Box::new(self)
     }
+
+    /// Convert to a dynamic [`Algorithm`] variant that can be sent to other threads.
+    fn dyn_algorithm_send(self) -> DynAlgorithmSend<CONTEXT, STATE, OUTPUT>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
 }
 
 /// Extends [`Generatable`] trait with immutable `CONTEXT` and mutable `STATE`.
 pub trait GenAlgorithm<CONTEXT, STATE, OUTPUT>:
     Generatable<OUTPUT> + Stateful<CONTEXT, STATE>
 {
+    /// Configure and immediately execute the generator, collecting all values into
+    /// a `COLLECTION` and skipping over all suspended states.
+    ///
+    /// This is the [`GenAlgorithm`] counterpart of [`Algorithm::run`].
+    fn run_collect<COLLECTION: Default + Extend<OUTPUT> + 'static>(
+        context: impl Into<CONTEXT>,
+        initial_state: impl Into<STATE>,
+    ) -> Cancellable<COLLECTION>
+    where
+        Self: Sized + 'static,
+    {
+        Self::from_parts(context.into(), initial_state.into())
+            .computation::<COLLECTION>()
+            .compute()
+    }
+
+    /// Configure and immediately execute the generator, passing every value to `action`
+    /// and skipping over all suspended states.
+    ///
+    /// Unlike [`GenAlgorithm::run_collect`], the values are not stored, and `action` can
+    /// observe them as soon as they are generated.
+    fn run_for_each<F: FnMut(OUTPUT)>(
+        context: impl Into<CONTEXT>,
+        initial_state: impl Into<STATE>,
+        mut action: F,
+    ) -> Cancellable<()>
+    where
+        Self: Sized + 'static,
+    {
+        for item in Self::from_parts(context.into(), initial_state.into()) {
+            action(item?);
+        }
+        Ok(())
+    }
+
     /// Convert a [`GenAlgorithm`] into a [`Computable`] object that collects all values
     /// into a `COLLECTION`.
     fn computation<COLLECTION: Default + Extend<OUTPUT> + 'static>(
@@ -80,6 +110,14 @@ pub trait GenAlgorithm<CONTEXT, STATE, OUTPUT>:
     {
         Box::new(self)
     }
+
+    /// Convert to a dynamic [`GenAlgorithm`] variant that can be sent to other threads.
+    fn dyn_algorithm_send(self) -> DynGenAlgorithmSend<CONTEXT, STATE, OUTPUT>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +224,42 @@ mod tests {
         assert!(result.contains("42-2"));
     }
 
+    #[test]
+    fn test_gen_algorithm_run_collect() {
+        type TestGenerator = Generator<i32, u32, String, TestGeneratorStep>;
+        let result = TestGenerator::run_collect::<Vec<_>>(7, 0u32).unwrap();
+        assert_eq!(result, vec!["7-1", "7-2"]);
+        // The initial state is respected.
+        let result = TestGenerator::run_collect::<Vec<_>>(7i16, 1u16).unwrap();
+        assert_eq!(result, vec!["7-2"]);
+    }
+
+    #[test]
+    fn test_gen_algorithm_run_for_each() {
+        let mut items = Vec::new();
+        Generator::<i32, u32, String, TestGeneratorStep>::run_for_each(5, 0u32, |it| {
+            items.push(it)
+        })
+        .unwrap();
+        assert_eq!(items, vec!["5-1", "5-2"]);
+    }
+
+    #[test]
+    fn test_gen_algorithm_run_cancelled() {
+        use cancel_this::{CancelAtomic, on_trigger};
+        type TestGenerator = Generator<i32, u32, String, TestGeneratorStep>;
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut items = Vec::new();
+        let result = on_trigger(trigger.clone(), || {
+            TestGenerator::run_for_each(5, 0u32, |it| items.push(it))
+        });
+        assert!(result.is_err());
+        assert!(items.is_empty());
+        let result = on_trigger(trigger, || TestGenerator::run_collect::<Vec<_>>(5, 0u32));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_gen_algorithm_dyn_algorithm() {
         let generator = Generator::<i32, u32, String, TestGeneratorStep>::from_parts(100, 0);
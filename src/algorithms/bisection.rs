@@ -0,0 +1,304 @@
+use crate::{Child, Completable, Computable, Computation, ComputationStep, Incomplete};
+use std::marker::PhantomData;
+
+/// A point type of a [`Bisection`] search (integers and floats).
+pub trait BisectionPoint: Copy + PartialOrd {
+    /// A point strictly between `low` and `high`, or `None` if the interval cannot be split
+    /// further, i.e., there is no such point, or the interval is not wider than `tolerance`.
+    fn split(low: Self, high: Self, tolerance: Option<Self>) -> Option<Self>;
+}
+
+macro_rules! impl_integer_point {
+    ($($point:ty),+) => {$(
+        impl BisectionPoint for $point {
+            fn split(low: Self, high: Self, tolerance: Option<Self>) -> Option<Self> {
+                let mid = low.midpoint(high);
+                let wide = tolerance.is_none_or(|it| high.abs_diff(low) > it.abs_diff(0));
+                (wide && mid != low && mid != high).then_some(mid)
+            }
+        }
+    )+};
+}
+
+impl_integer_point!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! impl_float_point {
+    ($($point:ty),+) => {$(
+        impl BisectionPoint for $point {
+            fn split(low: Self, high: Self, tolerance: Option<Self>) -> Option<Self> {
+                let mid = low.midpoint(high);
+                let wide = tolerance.is_none_or(|it| high - low > it);
+                (wide && mid > low && mid < high).then_some(mid)
+            }
+        }
+    )+};
+}
+
+impl_float_point!(f32, f64);
+
+/// The problem-specific part of a [`Bisection`] search: a monotone predicate whose
+/// evaluation is itself a (suspendable) computation.
+pub trait BisectionStep<CONTEXT, X> {
+    /// The computation which evaluates the predicate at one point.
+    type Evaluation: Computable<bool>;
+
+    /// Create the computation that evaluates the predicate at `point`.
+    fn evaluate(context: &CONTEXT, point: X) -> Self::Evaluation;
+}
+
+/// The state of a [`Bisection`] search: the current interval and the running evaluation
+/// of the predicate (a [`Child`] computation).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "X: serde::Serialize + for<'a> serde::Deserialize<'a>, E: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct BisectionState<X, E> {
+    low: X,
+    high: X,
+    tolerance: Option<X>,
+    evaluation: Child<bool, E>,
+}
+
+impl<X: BisectionPoint, E> BisectionState<X, E> {
+    /// Search the interval between `low` and `high` until no point can be found
+    /// between them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is greater than `high`.
+    pub fn new(low: X, high: X) -> Self {
+        assert!(low <= high, "The interval must not be empty.");
+        BisectionState {
+            low,
+            high,
+            tolerance: None,
+            evaluation: Child::empty(),
+        }
+    }
+
+    /// Stop once the interval is not wider than `tolerance` (e.g., for floats).
+    pub fn with_tolerance(mut self, tolerance: X) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// The current interval (`low`, `high`).
+    pub fn interval(&self) -> (X, X) {
+        (self.low, self.high)
+    }
+
+    /// The running evaluation of the predicate.
+    pub fn evaluation(&self) -> &Child<bool, E> {
+        &self.evaluation
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`BisectionStep`] (see [`Bisection`]).
+pub struct BisectionEngine<STEP>(PhantomData<STEP>);
+
+impl<CONTEXT, X, STEP> ComputationStep<CONTEXT, BisectionState<X, STEP::Evaluation>, (X, X)>
+    for BisectionEngine<STEP>
+where
+    X: BisectionPoint,
+    STEP: BisectionStep<CONTEXT, X>,
+{
+    fn step(
+        context: &CONTEXT,
+        state: &mut BisectionState<X, STEP::Evaluation>,
+    ) -> Completable<(X, X)> {
+        let Some(mid) = X::split(state.low, state.high, state.tolerance) else {
+            return Ok((state.low, state.high));
+        };
+        let holds = state
+            .evaluation
+            .step_child(|| STEP::evaluate(context, mid))?;
+        if holds {
+            state.high = mid;
+        } else {
+            state.low = mid;
+        }
+        Err(Incomplete::Suspended)
+    }
+}
+
+/// A suspendable bisection (binary search) of a monotone predicate.
+///
+/// The predicate is assumed to be `false` below some threshold and `true` above it, and
+/// the initial interval (`low`, `high`) is assumed to contain the threshold (the endpoints
+/// themselves are never evaluated). Every round evaluates the predicate at the midpoint of
+/// the interval and replaces one of the endpoints by it. The search completes with the final
+/// interval once no point lies strictly between the endpoints (or the interval is not wider
+/// than the tolerance). For integers, `high` is then the smallest point where the predicate
+/// holds.
+///
+/// The predicate is evaluated by a nested [`Computable`] (see [`BisectionStep`]), which is
+/// stored in the [`BisectionState`]: every suspend point of the evaluation is a suspend
+/// point of the search, cancellation interrupts the evaluation, and (with the `serde`
+/// feature) the running evaluation is saved together with the interval.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{Bisection, BisectionState, BisectionStep};
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// /// Checks `x * x >= target` by repeated addition, one addition per step.
+/// struct SquareAtLeast;
+///
+/// impl ComputationStep<(u64, u64), (u64, u64), bool> for SquareAtLeast {
+///     fn step(&(x, target): &(u64, u64), (i, square): &mut (u64, u64)) -> Completable<bool> {
+///         if *i == x {
+///             return Ok(*square >= target);
+///         }
+///         *i += 1;
+///         *square += x;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// type Check = Computation<(u64, u64), (u64, u64), bool, SquareAtLeast>;
+///
+/// /// Finds the integer square root (rounded up) of the context.
+/// struct Root;
+///
+/// impl BisectionStep<u64, u64> for Root {
+///     type Evaluation = Check;
+///
+///     fn evaluate(target: &u64, point: u64) -> Check {
+///         Check::from_parts((point, *target), (0, 0))
+///     }
+/// }
+///
+/// let mut root = Bisection::<u64, u64, Root>::from_parts(50, BisectionState::new(0, 50));
+/// assert_eq!(root.try_compute(), Err(Incomplete::Suspended));
+/// assert!(root.state().evaluation().is_running());
+/// assert_eq!(root.compute(), Ok((7, 8)));
+/// ```
+pub type Bisection<CONTEXT, X, STEP> = Computation<
+    CONTEXT,
+    BisectionState<X, <STEP as BisectionStep<CONTEXT, X>>::Evaluation>,
+    (X, X),
+    BisectionEngine<STEP>,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableIdentity, FromParts, StatefulRef};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Checks `x >= threshold` in two steps.
+    struct AtLeastStep;
+
+    impl ComputationStep<(i64, i64), bool, bool> for AtLeastStep {
+        fn step(&(x, threshold): &(i64, i64), started: &mut bool) -> Completable<bool> {
+            if !*started {
+                *started = true;
+                return Err(Incomplete::Suspended);
+            }
+            Ok(x >= threshold)
+        }
+    }
+
+    type AtLeast = Computation<(i64, i64), bool, bool, AtLeastStep>;
+
+    struct Threshold;
+
+    impl BisectionStep<i64, i64> for Threshold {
+        type Evaluation = AtLeast;
+
+        fn evaluate(threshold: &i64, point: i64) -> AtLeast {
+            AtLeast::from_parts((point, *threshold), false)
+        }
+    }
+
+    type Search = Bisection<i64, i64, Threshold>;
+
+    #[test]
+    fn test_integer_threshold() {
+        for threshold in [-99, -3, 0, 1, 42, 100] {
+            let state = BisectionState::new(-100, 100);
+            let mut search = Search::from_parts(threshold, state);
+            assert_eq!(search.compute(), Ok((threshold - 1, threshold)));
+        }
+    }
+
+    #[test]
+    fn test_integer_extremes() {
+        let state = BisectionState::new(i64::MIN, i64::MAX);
+        let mut search = Search::from_parts(i64::MAX - 5, state);
+        assert_eq!(search.compute(), Ok((i64::MAX - 6, i64::MAX - 5)));
+        let mut search = Search::from_parts(0, BisectionState::new(3, 3));
+        assert_eq!(search.try_compute(), Ok((3, 3)));
+    }
+
+    #[test]
+    fn test_integer_tolerance() {
+        let state = BisectionState::new(0, 100).with_tolerance(10);
+        let (low, high) = Search::from_parts(37, state).compute().unwrap();
+        assert!(high - low <= 10);
+        assert!(low < 37 && 37 <= high);
+    }
+
+    #[test]
+    fn test_evaluation_suspends_search() {
+        let mut search = Search::from_parts(30, BisectionState::new(0, 100));
+        // The first evaluation (at 50) suspends once before it completes.
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.state().interval(), (0, 100));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.state().interval(), (0, 50));
+        assert!(search.state().evaluation().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_evaluation_resumes() {
+        let mut search = Search::from_parts(30, BisectionState::new(0, 100));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(search.try_compute())).unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert!(search.state().evaluation().is_running());
+        assert_eq!(search.compute(), Ok((29, 30)));
+    }
+
+    /// Evaluates `x * x >= 2` immediately.
+    struct Sqrt2;
+
+    impl BisectionStep<(), f64> for Sqrt2 {
+        type Evaluation = ComputableIdentity<bool>;
+
+        fn evaluate(_context: &(), point: f64) -> ComputableIdentity<bool> {
+            (point * point >= 2.0).into()
+        }
+    }
+
+    #[test]
+    fn test_float_tolerance() {
+        let state = BisectionState::new(0.0, 2.0).with_tolerance(1e-9);
+        let mut search = Bisection::<(), f64, Sqrt2>::from_parts((), state);
+        let (low, high) = search.compute().unwrap();
+        assert!(high - low <= 1e-9);
+        assert!(low <= 2f64.sqrt() && 2f64.sqrt() <= high);
+        // Without tolerance, the interval shrinks to neighboring floats.
+        let state = BisectionState::new(0.0f64, 2.0);
+        let (low, high) = Bisection::<(), f64, Sqrt2>::from_parts((), state)
+            .compute()
+            .unwrap();
+        assert_eq!(low.next_up(), high);
+    }
+
+    #[test]
+    #[should_panic(expected = "The interval must not be empty.")]
+    fn test_empty_interval() {
+        let _ = BisectionState::<i64, AtLeast>::new(1, 0);
+    }
+}
@@ -0,0 +1,439 @@
+use crate::{
+    BestSoFar, Completable, Computable, Computation, ComputationStep, Incomplete, Progress,
+    StatefulRef,
+};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+
+/// The problem-specific part of a [`BranchAndBound`] search.
+///
+/// The search maximizes the value `V` (to minimize, use [`std::cmp::Reverse`] values,
+/// as with [`BestSoFar`]).
+pub trait BranchAndBoundStep<CONTEXT, N, V> {
+    /// An upper bound on the values of all solutions in the subtree of `node`.
+    fn bound(context: &CONTEXT, node: &N) -> V;
+
+    /// The value of `node` if it is a (feasible) solution.
+    fn evaluate(context: &CONTEXT, node: &N) -> Option<V>;
+
+    /// The children of `node` (empty for leaves).
+    fn branch(context: &CONTEXT, node: &N) -> Vec<N>;
+}
+
+/// A node in the open queue of a [`BranchAndBound`] search, ordered by its bound
+/// (ties are broken by insertion order).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct OpenNode<N, V> {
+    bound: V,
+    order: u64,
+    node: N,
+}
+
+impl<N, V: Ord> PartialEq for OpenNode<N, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, V: Ord> Eq for OpenNode<N, V> {}
+
+impl<N, V: Ord> PartialOrd for OpenNode<N, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, V: Ord> Ord for OpenNode<N, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound
+            .cmp(&other.bound)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+/// The state of a [`BranchAndBound`] search: the priority queue of open nodes,
+/// the incumbent (a [`BestSoFar`] value together with its solution node), and statistics.
+///
+/// With the `serde` feature, the whole state (including the open queue) is serializable,
+/// hence the search can be saved and resumed at any suspend point.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "N: serde::Serialize + for<'a> serde::Deserialize<'a>, V: Ord + serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct BranchAndBoundState<N, V: Ord> {
+    roots: Vec<N>,
+    open: BinaryHeap<OpenNode<N, V>>,
+    inserted: u64,
+    incumbent: BestSoFar<V>,
+    best: Option<N>,
+    expanded: u64,
+    pruned: u64,
+}
+
+impl<N, V: Ord> BranchAndBoundState<N, V> {
+    /// Start the search in the `roots` nodes.
+    pub fn new<I: IntoIterator<Item = N>>(roots: I) -> Self {
+        BranchAndBoundState {
+            roots: roots.into_iter().collect(),
+            open: BinaryHeap::new(),
+            inserted: 0,
+            incumbent: BestSoFar::new(),
+            best: None,
+            expanded: 0,
+            pruned: 0,
+        }
+    }
+
+    /// Start with a known lower bound on the optimal value (e.g., the value of a heuristic
+    /// solution or of a [`BestSoFar`] shared through a [`crate::Blackboard`]), which is used
+    /// for pruning. The search only reports solutions that are strictly better.
+    pub fn with_incumbent(mut self, incumbent: BestSoFar<V>) -> Self {
+        self.incumbent = incumbent;
+        self
+    }
+
+    /// The incumbent value tracker.
+    pub fn incumbent(&self) -> &BestSoFar<V> {
+        &self.incumbent
+    }
+
+    /// The best solution found so far together with its value.
+    pub fn best(&self) -> Option<(&N, &V)> {
+        self.best.as_ref().zip(self.incumbent.peek())
+    }
+
+    /// The number of open nodes (not counting the roots before the first step).
+    pub fn open(&self) -> usize {
+        self.open.len()
+    }
+
+    /// The number of expanded nodes.
+    pub fn expanded(&self) -> u64 {
+        self.expanded
+    }
+
+    /// The number of nodes that were discarded because their bound could not improve
+    /// the incumbent.
+    pub fn pruned(&self) -> u64 {
+        self.pruned
+    }
+
+    /// Destruct the state into the best solution and its value.
+    pub fn into_best(self) -> Option<(N, V)> {
+        self.best.zip(self.incumbent.into_inner())
+    }
+
+    fn push<CONTEXT, STEP>(&mut self, context: &CONTEXT, node: N)
+    where
+        STEP: BranchAndBoundStep<CONTEXT, N, V>,
+    {
+        let bound = STEP::bound(context, &node);
+        if self.incumbent.can_improve(&bound) {
+            self.open.push(OpenNode {
+                bound,
+                order: self.inserted,
+                node,
+            });
+            self.inserted += 1;
+        } else {
+            self.pruned += 1;
+        }
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`BranchAndBoundStep`] (see [`BranchAndBound`]).
+pub struct BranchAndBoundEngine<STEP>(PhantomData<STEP>);
+
+impl<CONTEXT, N, V, STEP> ComputationStep<CONTEXT, BranchAndBoundState<N, V>, Option<(N, V)>>
+    for BranchAndBoundEngine<STEP>
+where
+    N: Clone,
+    V: Ord + Clone,
+    STEP: BranchAndBoundStep<CONTEXT, N, V>,
+{
+    fn step(
+        context: &CONTEXT,
+        state: &mut BranchAndBoundState<N, V>,
+    ) -> Completable<Option<(N, V)>> {
+        for root in std::mem::take(&mut state.roots) {
+            state.push::<CONTEXT, STEP>(context, root);
+        }
+        let Some(open) = state.open.pop() else {
+            return Ok(state.best.clone().zip(state.incumbent.peek().cloned()));
+        };
+        if !state.incumbent.can_improve(&open.bound) {
+            // The queue is ordered by bound, hence no open node can improve the incumbent.
+            state.pruned += 1 + state.open.len() as u64;
+            state.open.clear();
+            return Ok(state.best.clone().zip(state.incumbent.peek().cloned()));
+        }
+        state.expanded += 1;
+        if let Some(value) = STEP::evaluate(context, &open.node)
+            && state.incumbent.offer(value)
+        {
+            state.best = Some(open.node.clone());
+        }
+        for child in STEP::branch(context, &open.node) {
+            state.push::<CONTEXT, STEP>(context, child);
+        }
+        Err(Incomplete::Suspended)
+    }
+}
+
+/// A suspendable best-first branch-and-bound search.
+///
+/// The search keeps a priority queue of open nodes ordered by their
+/// [`BranchAndBoundStep::bound`]. Every step expands the open node with the highest bound:
+/// if the node is a solution ([`BranchAndBoundStep::evaluate`]), it is offered to
+/// the incumbent [`BestSoFar`], and its children ([`BranchAndBoundStep::branch`]) are
+/// added to the queue unless their bound cannot improve the incumbent. The search suspends
+/// after every expansion and completes with the optimal solution and its value (`None` if
+/// there is no solution) once no open node can improve the incumbent.
+///
+/// The incumbent is available at any suspend point (see [`BranchAndBoundState::best`]);
+/// [`BranchAndBound::compute_incumbent`] returns it even if the search is cancelled.
+/// The number of expanded nodes is reported through [`Progress`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{BranchAndBound, BranchAndBoundState, BranchAndBoundStep};
+/// use computation_process::prelude::*;
+///
+/// /// A 0/1 knapsack: items are (weight, value) pairs and the context also holds the capacity.
+/// /// A node is the index of the next item to decide, the used weight, and the value so far.
+/// struct Knapsack;
+///
+/// type Items = (Vec<(u32, u32)>, u32);
+/// type Node = (usize, u32, u32);
+///
+/// impl BranchAndBoundStep<Items, Node, u32> for Knapsack {
+///     fn bound((items, _): &Items, &(next, _, value): &Node) -> u32 {
+///         value + items[next..].iter().map(|(_, v)| v).sum::<u32>()
+///     }
+///
+///     fn evaluate(_items: &Items, &(_, _, value): &Node) -> Option<u32> {
+///         Some(value)
+///     }
+///
+///     fn branch((items, capacity): &Items, &(next, weight, value): &Node) -> Vec<Node> {
+///         let Some(&(w, v)) = items.get(next) else {
+///             return Vec::new();
+///         };
+///         let mut children = vec![(next + 1, weight, value)];
+///         if weight + w <= *capacity {
+///             children.push((next + 1, weight + w, value + v));
+///         }
+///         children
+///     }
+/// }
+///
+/// let items = (vec![(5, 10), (4, 40), (6, 30), (3, 50)], 10);
+/// let state = BranchAndBoundState::new([(0, 0, 0)]);
+/// let mut search = BranchAndBound::<Items, _, _, Knapsack>::from_parts(items, state);
+/// let (_, value) = search.compute().unwrap().unwrap();
+/// assert_eq!(value, 90);
+/// assert!(search.state().pruned() > 0);
+/// ```
+pub type BranchAndBound<CONTEXT, N, V, STEP> =
+    Computation<CONTEXT, BranchAndBoundState<N, V>, Option<(N, V)>, BranchAndBoundEngine<STEP>>;
+
+impl<CONTEXT, N, V, STEP> BranchAndBound<CONTEXT, N, V, STEP>
+where
+    N: Clone,
+    V: Ord + Clone,
+    STEP: BranchAndBoundStep<CONTEXT, N, V>,
+{
+    /// Drive the search until it completes or is interrupted (cancelled or out of resources),
+    /// returning the incumbent and `true` if the search completed (i.e., the incumbent
+    /// is optimal). An interrupted search can be resumed later.
+    pub fn compute_incumbent(&mut self) -> (Option<(N, V)>, bool) {
+        match self.compute_completable() {
+            Ok(best) => (best, true),
+            Err(_) => {
+                let best = self.state().best();
+                (
+                    best.map(|(node, value)| (node.clone(), value.clone())),
+                    false,
+                )
+            }
+        }
+    }
+}
+
+/// Completed units are the expanded nodes; the total also includes the open nodes.
+impl<CONTEXT, N, V, STEP> Progress for BranchAndBound<CONTEXT, N, V, STEP>
+where
+    N: Clone,
+    V: Ord + Clone,
+    STEP: BranchAndBoundStep<CONTEXT, N, V>,
+{
+    fn completed(&self) -> u64 {
+        self.state().expanded()
+    }
+
+    fn total(&self) -> Option<u64> {
+        let state = self.state();
+        Some(state.expanded() + (state.open() + state.roots.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromParts;
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+    use std::cmp::Reverse;
+
+    /// A 0/1 knapsack; a node is (next item, used weight, value, taken items).
+    struct Knapsack;
+
+    type Items = (Vec<(u32, u32)>, u32);
+    type Node = (usize, u32, u32, Vec<usize>);
+
+    impl BranchAndBoundStep<Items, Node, u32> for Knapsack {
+        fn bound((items, _): &Items, (next, _, value, _): &Node) -> u32 {
+            value + items[*next..].iter().map(|(_, v)| v).sum::<u32>()
+        }
+
+        fn evaluate(_items: &Items, (_, _, value, _): &Node) -> Option<u32> {
+            Some(*value)
+        }
+
+        fn branch((items, capacity): &Items, (next, weight, value, taken): &Node) -> Vec<Node> {
+            let Some(&(w, v)) = items.get(*next) else {
+                return Vec::new();
+            };
+            let mut children = vec![(next + 1, *weight, *value, taken.clone())];
+            if weight + w <= *capacity {
+                let mut taken = taken.clone();
+                taken.push(*next);
+                children.push((next + 1, weight + w, value + v, taken));
+            }
+            children
+        }
+    }
+
+    type Search = BranchAndBound<Items, Node, u32, Knapsack>;
+
+    fn items() -> Items {
+        let items = vec![(12, 4), (2, 2), (1, 1), (4, 10), (1, 2), (7, 7), (3, 5)];
+        (items, 15)
+    }
+
+    /// The optimum computed by enumerating all subsets.
+    fn brute_force((items, capacity): &Items) -> u32 {
+        (0u32..1 << items.len())
+            .filter_map(|subset| {
+                let chosen = || {
+                    items
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| subset >> i & 1 == 1)
+                };
+                let weight: u32 = chosen().map(|(_, (w, _))| w).sum();
+                (weight <= *capacity).then(|| chosen().map(|(_, (_, v))| v).sum())
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_optimal_with_pruning() {
+        let mut search = Search::from_parts(items(), BranchAndBoundState::new([(0, 0, 0, vec![])]));
+        assert_eq!(search.total(), Some(1));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.completed(), 1);
+        assert_eq!(search.state().open(), 2);
+        let (node, value) = search.compute().unwrap().unwrap();
+        assert_eq!(value, brute_force(&items()));
+        let taken: u32 = node.3.iter().map(|i| items().0[*i].1).sum();
+        assert_eq!(taken, value);
+        assert!(search.state().pruned() > 0);
+        // The full tree has 2^8 - 1 nodes.
+        assert!(search.state().expanded() < 255);
+        assert_eq!(search.state().open(), 0);
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let state = BranchAndBoundState::<Node, u32>::new([]);
+        let mut search = Search::from_parts(items(), state);
+        assert_eq!(search.try_compute(), Ok(None));
+    }
+
+    #[test]
+    fn test_initial_incumbent() {
+        // Nothing beats the optimum, hence no solution is reported.
+        let optimum = brute_force(&items());
+        let state = BranchAndBoundState::new([(0, 0, 0, vec![])]).with_incumbent(optimum.into());
+        let mut search = Search::from_parts(items(), state);
+        assert_eq!(search.compute(), Ok(None));
+        assert_eq!(search.state().incumbent().peek(), Some(&optimum));
+        // A root whose bound is the optimum is pruned immediately.
+        let state =
+            BranchAndBoundState::new([(6, 0, optimum - 5, vec![])]).with_incumbent(optimum.into());
+        let mut search = Search::from_parts(items(), state);
+        assert_eq!(search.try_compute(), Ok(None));
+        assert_eq!(search.state().pruned(), 1);
+        assert_eq!(search.state().expanded(), 0);
+    }
+
+    #[test]
+    fn test_cancellation_returns_incumbent() {
+        let mut search = Search::from_parts(items(), BranchAndBoundState::new([(0, 0, 0, vec![])]));
+        for _ in 0..3 {
+            assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        }
+        let incumbent = search.state().best().map(|(_, value)| *value);
+        assert!(incumbent.is_some());
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let (best, optimal) =
+            on_trigger(trigger, || Ok::<_, Cancelled>(search.compute_incumbent())).unwrap();
+        assert!(!optimal);
+        assert_eq!(best.map(|(_, value)| value), incumbent);
+        // The search resumes after cancellation.
+        let (best, optimal) = search.compute_incumbent();
+        assert!(optimal);
+        assert_eq!(best.unwrap().1, brute_force(&items()));
+    }
+
+    /// Finds the smallest number in `1..=max` divisible by all numbers in the context.
+    struct Divisible;
+
+    impl BranchAndBoundStep<(Vec<u32>, u32), u32, Reverse<u32>> for Divisible {
+        fn bound(_context: &(Vec<u32>, u32), node: &u32) -> Reverse<u32> {
+            Reverse(*node)
+        }
+
+        fn evaluate((divisors, _): &(Vec<u32>, u32), node: &u32) -> Option<Reverse<u32>> {
+            divisors
+                .iter()
+                .all(|d| node.is_multiple_of(*d))
+                .then_some(Reverse(*node))
+        }
+
+        fn branch((_, max): &(Vec<u32>, u32), node: &u32) -> Vec<u32> {
+            (*node < *max).then_some(node + 1).into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn test_minimization() {
+        let context = (vec![2, 3, 4], 100);
+        let state = BranchAndBoundState::new([1]);
+        let mut search =
+            BranchAndBound::<_, u32, Reverse<u32>, Divisible>::from_parts(context, state);
+        assert_eq!(search.compute(), Ok(Some((12, Reverse(12)))));
+        // The successor of 12 cannot improve the incumbent.
+        assert_eq!(search.state().expanded(), 12);
+        assert_eq!(search.state().pruned(), 1);
+    }
+}
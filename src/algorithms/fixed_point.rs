@@ -0,0 +1,317 @@
+use crate::{Completable, Computation, ComputationStep, Incomplete, Progress, StatefulRef};
+use std::marker::PhantomData;
+
+/// The problem-specific part of a [`FixedPoint`] iteration.
+pub trait FixedPointStep<CONTEXT, S> {
+    /// Perform one iteration, updating the `state` and returning the magnitude of
+    /// the change (the delta).
+    fn iterate(context: &CONTEXT, state: &mut S) -> f64;
+
+    /// Returns `true` if the iteration converged after an iteration with the given `delta`.
+    ///
+    /// By default, the iteration converges once the delta is at most `epsilon`.
+    fn is_converged(context: &CONTEXT, state: &S, delta: f64, epsilon: f64) -> bool {
+        let _ = (context, state);
+        delta <= epsilon
+    }
+}
+
+/// The reason why a [`FixedPoint`] iteration stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FixedPointOutcome {
+    /// The convergence criterion is satisfied (see [`FixedPointStep::is_converged`]).
+    Converged,
+    /// The maximal number of iterations was performed without convergence
+    /// (see [`FixedPointState::with_max_iterations`]).
+    IterationLimit,
+    /// The delta did not improve for too many iterations
+    /// (see [`FixedPointState::with_stagnation`]).
+    Stagnated,
+}
+
+/// The state of a [`FixedPoint`] iteration: the user state `S`, the iteration statistics,
+/// and the stopping policies.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "S: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct FixedPointState<S> {
+    data: S,
+    epsilon: f64,
+    max_iterations: Option<u64>,
+    patience: Option<u64>,
+    iterations: u64,
+    last_delta: Option<f64>,
+    best_delta: Option<f64>,
+    stagnant: u64,
+}
+
+impl<S> FixedPointState<S> {
+    /// Start the iteration from `data`, converging once the delta is at most `epsilon`.
+    pub fn new(data: S, epsilon: f64) -> Self {
+        FixedPointState {
+            data,
+            epsilon,
+            max_iterations: None,
+            patience: None,
+            iterations: 0,
+            last_delta: None,
+            best_delta: None,
+            stagnant: 0,
+        }
+    }
+
+    /// Stop with [`FixedPointOutcome::IterationLimit`] after `max_iterations` iterations.
+    pub fn with_max_iterations(mut self, max_iterations: u64) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    /// Stop with [`FixedPointOutcome::Stagnated`] once `patience` consecutive iterations
+    /// do not improve (decrease) the smallest delta seen so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patience` is zero.
+    pub fn with_stagnation(mut self, patience: u64) -> Self {
+        assert!(patience > 0, "Patience must be positive.");
+        self.patience = Some(patience);
+        self
+    }
+
+    /// The user state.
+    pub fn data(&self) -> &S {
+        &self.data
+    }
+
+    /// Mutable access to the user state.
+    pub fn data_mut(&mut self) -> &mut S {
+        &mut self.data
+    }
+
+    /// The number of performed iterations.
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// The delta of the last iteration (if any).
+    pub fn last_delta(&self) -> Option<f64> {
+        self.last_delta
+    }
+
+    /// The smallest delta of all iterations so far (if any).
+    pub fn best_delta(&self) -> Option<f64> {
+        self.best_delta
+    }
+
+    /// The configured maximal number of iterations (if any).
+    pub fn max_iterations(&self) -> Option<u64> {
+        self.max_iterations
+    }
+
+    /// Destruct the state into the user state.
+    pub fn into_data(self) -> S {
+        self.data
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`FixedPointStep`] (see [`FixedPoint`]).
+pub struct FixedPointEngine<STEP>(PhantomData<STEP>);
+
+impl<CONTEXT, S, STEP> ComputationStep<CONTEXT, FixedPointState<S>, (S, FixedPointOutcome)>
+    for FixedPointEngine<STEP>
+where
+    S: Clone,
+    STEP: FixedPointStep<CONTEXT, S>,
+{
+    fn step(
+        context: &CONTEXT,
+        state: &mut FixedPointState<S>,
+    ) -> Completable<(S, FixedPointOutcome)> {
+        let outcome = |state: &FixedPointState<S>, outcome| Ok((state.data.clone(), outcome));
+        if let Some(delta) = state.last_delta {
+            // Repeated calls after completion report the same outcome.
+            if STEP::is_converged(context, &state.data, delta, state.epsilon) {
+                return outcome(state, FixedPointOutcome::Converged);
+            }
+        }
+        if state
+            .max_iterations
+            .is_some_and(|it| state.iterations >= it)
+        {
+            return outcome(state, FixedPointOutcome::IterationLimit);
+        }
+        if state.patience.is_some_and(|it| state.stagnant >= it) {
+            return outcome(state, FixedPointOutcome::Stagnated);
+        }
+
+        let delta = STEP::iterate(context, &mut state.data);
+        state.iterations += 1;
+        state.last_delta = Some(delta);
+        if state.best_delta.is_none_or(|best| delta < best) {
+            state.best_delta = Some(delta);
+            state.stagnant = 0;
+        } else {
+            state.stagnant += 1;
+        }
+        Err(Incomplete::Suspended)
+    }
+}
+
+/// A suspendable fixed-point iteration.
+///
+/// Every step performs one iteration ([`FixedPointStep::iterate`]) and suspends. Before
+/// the next iteration, the algorithm checks the stopping criteria: convergence (the delta
+/// of the last iteration is at most the epsilon, see [`FixedPointStep::is_converged`]),
+/// the iteration limit, and stagnation (see [`FixedPointState`]). Once one of them
+/// is satisfied, the algorithm completes with (a copy of) the user state and the
+/// [`FixedPointOutcome`]. The number of iterations is reported through [`Progress`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{
+///     FixedPoint, FixedPointOutcome, FixedPointState, FixedPointStep,
+/// };
+/// use computation_process::prelude::*;
+///
+/// /// Computes the square root of the context using Newton's method.
+/// struct Newton;
+///
+/// impl FixedPointStep<f64, f64> for Newton {
+///     fn iterate(target: &f64, x: &mut f64) -> f64 {
+///         let next = (*x + target / *x) / 2.0;
+///         let delta = (next - *x).abs();
+///         *x = next;
+///         delta
+///     }
+/// }
+///
+/// let state = FixedPointState::new(1.0, 1e-12).with_max_iterations(100);
+/// let mut sqrt = FixedPoint::<f64, f64, Newton>::from_parts(2.0, state);
+/// let (root, outcome) = sqrt.compute().unwrap();
+/// assert_eq!(outcome, FixedPointOutcome::Converged);
+/// assert!((root - 2f64.sqrt()).abs() < 1e-12);
+/// assert!(sqrt.state().iterations() < 10);
+/// ```
+pub type FixedPoint<CONTEXT, S, STEP> =
+    Computation<CONTEXT, FixedPointState<S>, (S, FixedPointOutcome), FixedPointEngine<STEP>>;
+
+/// Completed units are the iterations; the total is the iteration limit (if any).
+impl<CONTEXT, S, STEP> Progress for FixedPoint<CONTEXT, S, STEP>
+where
+    S: Clone,
+    STEP: FixedPointStep<CONTEXT, S>,
+{
+    fn completed(&self) -> u64 {
+        self.state().iterations()
+    }
+
+    fn total(&self) -> Option<u64> {
+        self.state().max_iterations()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, FromParts};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Halves the distance to the context; the delta is the distance moved.
+    struct Halve;
+
+    impl FixedPointStep<f64, f64> for Halve {
+        fn iterate(target: &f64, x: &mut f64) -> f64 {
+            let delta = (*target - *x) / 2.0;
+            *x += delta;
+            delta.abs()
+        }
+    }
+
+    type HalveTo = FixedPoint<f64, f64, Halve>;
+
+    #[test]
+    fn test_converged() {
+        let mut test = HalveTo::from_parts(1.0, FixedPointState::new(0.0, 0.1));
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(test.state().last_delta(), Some(0.5));
+        let (x, outcome) = test.compute().unwrap();
+        assert_eq!(outcome, FixedPointOutcome::Converged);
+        // Deltas 0.5, 0.25, 0.125, 0.0625.
+        assert_eq!(test.completed(), 4);
+        assert_eq!(x, 0.9375);
+        // The outcome is stable.
+        assert_eq!(
+            test.try_compute(),
+            Ok((0.9375, FixedPointOutcome::Converged))
+        );
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let state = FixedPointState::new(0.0, 0.0).with_max_iterations(3);
+        let mut test = HalveTo::from_parts(1.0, state);
+        assert_eq!(test.total(), Some(3));
+        assert_eq!(
+            test.compute(),
+            Ok((0.875, FixedPointOutcome::IterationLimit))
+        );
+        assert_eq!(test.completed(), 3);
+    }
+
+    /// Oscillates between two values, hence the delta never improves.
+    struct Oscillate;
+
+    impl FixedPointStep<(), i32> for Oscillate {
+        fn iterate(_context: &(), x: &mut i32) -> f64 {
+            *x = -*x;
+            2.0
+        }
+    }
+
+    #[test]
+    fn test_stagnation() {
+        let state = FixedPointState::new(1, 0.5).with_stagnation(3);
+        let mut test = FixedPoint::<(), i32, Oscillate>::from_parts((), state);
+        assert_eq!(test.compute(), Ok((1, FixedPointOutcome::Stagnated)));
+        // The first iteration sets the best delta, then three iterations stagnate.
+        assert_eq!(test.state().iterations(), 4);
+        assert_eq!(test.state().best_delta(), Some(2.0));
+    }
+
+    /// Converges once the state reaches the target exactly (ignoring the epsilon).
+    struct Count;
+
+    impl FixedPointStep<u32, u32> for Count {
+        fn iterate(_target: &u32, x: &mut u32) -> f64 {
+            *x += 1;
+            1.0
+        }
+
+        fn is_converged(target: &u32, x: &u32, _delta: f64, _epsilon: f64) -> bool {
+            x == target
+        }
+    }
+
+    #[test]
+    fn test_custom_convergence_and_cancellation() {
+        let mut test = FixedPoint::<u32, u32, Count>::from_parts(5, FixedPointState::new(0, 0.0));
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(test.try_compute())).unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(test.state().iterations(), 1);
+        assert_eq!(test.compute(), Ok((5, FixedPointOutcome::Converged)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Patience must be positive.")]
+    fn test_zero_patience() {
+        let _ = FixedPointState::new((), 0.0).with_stagnation(0);
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{
+    Completable, Computable, Computation, ComputationStep, Incomplete, Progress, StatefulRef,
+};
+use std::marker::PhantomData;
+
+/// The problem-specific part of a [`LocalSearch`]: the neighborhood, the cost function,
+/// the temperature schedule, and the acceptance policy (all parametrized by the `CONTEXT`).
+pub trait LocalSearchStep<CONTEXT, S> {
+    /// A random neighbor of the `current` solution. The `random` number is uniformly
+    /// distributed (and determined by the seed of the [`LocalSearchState`]).
+    fn neighbor(context: &CONTEXT, current: &S, random: u64) -> S;
+
+    /// The cost of a `solution` (the search minimizes the cost).
+    fn cost(context: &CONTEXT, solution: &S) -> f64;
+
+    /// The temperature in the given (zero-based) `iteration`.
+    fn temperature(context: &CONTEXT, iteration: u64) -> f64;
+
+    /// Returns `true` if a neighbor whose cost differs from the current cost by `delta`
+    /// should replace the current solution. The `uniform` number is random in `[0, 1)`.
+    ///
+    /// By default, this is the Metropolis criterion: improvements are always accepted,
+    /// and a worse neighbor is accepted with probability `exp(-delta / temperature)`
+    /// (i.e., never once the temperature drops to zero).
+    fn accept(context: &CONTEXT, delta: f64, temperature: f64, uniform: f64) -> bool {
+        let _ = context;
+        delta <= 0.0 || (temperature > 0.0 && uniform < (-delta / temperature).exp())
+    }
+}
+
+/// The state of a [`LocalSearch`]: the current solution, the incumbent (the best solution
+/// found so far), the iteration counter, and the state of the random number generator.
+///
+/// The incumbent is available at any suspend point (see [`LocalSearchState::best`]), e.g.,
+/// after the search was cancelled. With the `serde` feature, the whole state is
+/// serializable, and a restored search continues exactly as the original one would.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "S: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct LocalSearchState<S> {
+    current: S,
+    current_cost: Option<f64>,
+    best: S,
+    best_cost: Option<f64>,
+    iteration: u64,
+    max_iterations: u64,
+    accepted: u64,
+    random: u64,
+}
+
+impl<S: Clone> LocalSearchState<S> {
+    /// Start the search in the `initial` solution, performing `max_iterations` iterations.
+    ///
+    /// The random number generator is seeded with zero (see [`LocalSearchState::with_seed`]).
+    pub fn new(initial: S, max_iterations: u64) -> Self {
+        LocalSearchState {
+            best: initial.clone(),
+            current: initial,
+            current_cost: None,
+            best_cost: None,
+            iteration: 0,
+            max_iterations,
+            accepted: 0,
+            random: 0,
+        }
+    }
+}
+
+impl<S> LocalSearchState<S> {
+    /// Seed the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.random = seed;
+        self
+    }
+
+    /// The current solution.
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// The best solution found so far (the incumbent).
+    pub fn best(&self) -> &S {
+        &self.best
+    }
+
+    /// The cost of the incumbent (`None` before the first step).
+    pub fn best_cost(&self) -> Option<f64> {
+        self.best_cost
+    }
+
+    /// The number of performed iterations.
+    pub fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    /// The number of iterations of the whole search.
+    pub fn max_iterations(&self) -> u64 {
+        self.max_iterations
+    }
+
+    /// The number of accepted neighbors.
+    pub fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Destruct the state into the incumbent.
+    pub fn into_best(self) -> S {
+        self.best
+    }
+
+    /// The next number of the (SplitMix64) random number generator.
+    fn next_random(&mut self) -> u64 {
+        self.random = self.random.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.random;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`LocalSearchStep`] (see [`LocalSearch`]).
+pub struct LocalSearchEngine<STEP>(PhantomData<STEP>);
+
+impl<CONTEXT, S, STEP> ComputationStep<CONTEXT, LocalSearchState<S>, (S, f64)>
+    for LocalSearchEngine<STEP>
+where
+    S: Clone,
+    STEP: LocalSearchStep<CONTEXT, S>,
+{
+    fn step(context: &CONTEXT, state: &mut LocalSearchState<S>) -> Completable<(S, f64)> {
+        let current_cost = *state
+            .current_cost
+            .get_or_insert_with(|| STEP::cost(context, &state.current));
+        let best_cost = *state.best_cost.get_or_insert(current_cost);
+        if state.iteration >= state.max_iterations {
+            return Ok((state.best.clone(), best_cost));
+        }
+
+        let temperature = STEP::temperature(context, state.iteration);
+        let random = state.next_random();
+        let candidate = STEP::neighbor(context, &state.current, random);
+        let cost = STEP::cost(context, &candidate);
+        let uniform = (state.next_random() >> 11) as f64 / (1u64 << 53) as f64;
+        if STEP::accept(context, cost - current_cost, temperature, uniform) {
+            if cost < best_cost {
+                state.best = candidate.clone();
+                state.best_cost = Some(cost);
+            }
+            state.current = candidate;
+            state.current_cost = Some(cost);
+            state.accepted += 1;
+        }
+        state.iteration += 1;
+        Err(Incomplete::Suspended)
+    }
+}
+
+/// A suspendable local search (simulated annealing) with a fixed number of iterations.
+///
+/// Every iteration generates a random neighbor of the current solution
+/// ([`LocalSearchStep::neighbor`]) and decides whether it replaces the current solution
+/// ([`LocalSearchStep::accept`], based on the cost difference and the current
+/// [`LocalSearchStep::temperature`]). The search suspends after every iteration and
+/// completes with (a copy of) the incumbent and its cost. The number of iterations
+/// is reported through [`Progress`].
+///
+/// This is an "anytime" algorithm: the incumbent can be extracted from the state at any
+/// suspend point, in particular after the search was cancelled (the state remains
+/// available, since computations are driven through `&mut` references). For long runs,
+/// [`LocalSearch::run_with_checkpoints`] periodically hands the state to a callback
+/// (e.g., to serialize it), such that the search can later be resumed.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{LocalSearch, LocalSearchState, LocalSearchStep};
+/// use computation_process::prelude::*;
+///
+/// /// Minimizes `(x - target)^2` by moving `x` randomly by one.
+/// struct Parabola;
+///
+/// impl LocalSearchStep<i64, i64> for Parabola {
+///     fn neighbor(_target: &i64, x: &i64, random: u64) -> i64 {
+///         if random.is_multiple_of(2) { x + 1 } else { x - 1 }
+///     }
+///
+///     fn cost(target: &i64, x: &i64) -> f64 {
+///         ((x - target) * (x - target)) as f64
+///     }
+///
+///     fn temperature(_target: &i64, iteration: u64) -> f64 {
+///         10.0 * 0.9f64.powi(iteration as i32)
+///     }
+/// }
+///
+/// let state = LocalSearchState::new(0, 1000).with_seed(7);
+/// let mut search = LocalSearch::<i64, i64, Parabola>::from_parts(12, state);
+/// assert_eq!(search.compute(), Ok((12, 0.0)));
+/// ```
+pub type LocalSearch<CONTEXT, S, STEP> =
+    Computation<CONTEXT, LocalSearchState<S>, (S, f64), LocalSearchEngine<STEP>>;
+
+impl<CONTEXT, S, STEP> LocalSearch<CONTEXT, S, STEP>
+where
+    S: Clone,
+    STEP: LocalSearchStep<CONTEXT, S>,
+{
+    /// Run the search to completion, passing the state to `checkpoint` after every
+    /// `checkpoint_every` iterations, and once more if the search is interrupted
+    /// (cancelled or out of resources) before the error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `checkpoint_every` is zero.
+    pub fn run_with_checkpoints<F: FnMut(&LocalSearchState<S>)>(
+        &mut self,
+        checkpoint_every: u64,
+        mut checkpoint: F,
+    ) -> Completable<(S, f64)> {
+        assert!(
+            checkpoint_every > 0,
+            "Checkpoint interval must be positive."
+        );
+        loop {
+            match self.try_compute() {
+                Err(Incomplete::Suspended) => {
+                    if self.state().iteration() % checkpoint_every == 0 {
+                        checkpoint(self.state());
+                    }
+                }
+                Err(error) => {
+                    checkpoint(self.state());
+                    return Err(error);
+                }
+                Ok(result) => return Ok(result),
+            }
+        }
+    }
+}
+
+/// Completed units are the iterations; the total is the number of iterations of the search.
+impl<CONTEXT, S, STEP> Progress for LocalSearch<CONTEXT, S, STEP>
+where
+    S: Clone,
+    STEP: LocalSearchStep<CONTEXT, S>,
+{
+    fn completed(&self) -> u64 {
+        self.state().iteration()
+    }
+
+    fn total(&self) -> Option<u64> {
+        Some(self.state().max_iterations())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromParts;
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Minimizes the distance of `x` to the context; moves by one (random direction).
+    struct Distance;
+
+    impl LocalSearchStep<i64, i64> for Distance {
+        fn neighbor(_target: &i64, x: &i64, random: u64) -> i64 {
+            if random.is_multiple_of(2) {
+                x + 1
+            } else {
+                x - 1
+            }
+        }
+
+        fn cost(target: &i64, x: &i64) -> f64 {
+            (x - target).abs() as f64
+        }
+
+        fn temperature(_target: &i64, _iteration: u64) -> f64 {
+            0.0
+        }
+    }
+
+    type Search = LocalSearch<i64, i64, Distance>;
+
+    #[test]
+    fn test_greedy_descent() {
+        let mut search = Search::from_parts(5, LocalSearchState::new(0, 200));
+        assert_eq!(search.total(), Some(200));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.state().iteration(), 1);
+        assert_eq!(search.compute(), Ok((5, 0.0)));
+        assert_eq!(search.completed(), 200);
+        // With zero temperature, only improvements (or equal costs) are accepted.
+        assert_eq!(search.state().current(), &5);
+        assert_eq!(search.state().accepted(), 5);
+    }
+
+    #[test]
+    fn test_zero_iterations() {
+        let mut search = Search::from_parts(5, LocalSearchState::new(2, 0));
+        assert_eq!(search.try_compute(), Ok((2, 3.0)));
+    }
+
+    #[test]
+    fn test_seed_determinism() {
+        let run = |seed| {
+            let state = LocalSearchState::new(0, 10).with_seed(seed);
+            let mut search = Search::from_parts(100, state);
+            search.compute().unwrap();
+            search.into_parts().1
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    /// Always accepts the neighbor (a random walk), hence the incumbent and the current
+    /// solution differ.
+    struct Walk;
+
+    impl LocalSearchStep<i64, i64> for Walk {
+        fn neighbor(target: &i64, x: &i64, random: u64) -> i64 {
+            Distance::neighbor(target, x, random)
+        }
+
+        fn cost(target: &i64, x: &i64) -> f64 {
+            Distance::cost(target, x)
+        }
+
+        fn temperature(_target: &i64, _iteration: u64) -> f64 {
+            f64::INFINITY
+        }
+    }
+
+    #[test]
+    fn test_incumbent_is_best() {
+        let state = LocalSearchState::new(0, 100).with_seed(3);
+        let mut search = LocalSearch::<i64, i64, Walk>::from_parts(4, state);
+        let (best, cost) = search.compute().unwrap();
+        assert_eq!(search.state().accepted(), 100);
+        assert_eq!(cost, Walk::cost(&4, &best));
+        assert!(cost <= Walk::cost(&4, search.state().current()));
+    }
+
+    #[test]
+    fn test_metropolis() {
+        assert!(Distance::accept(&0, -1.0, 0.0, 0.99));
+        assert!(!Distance::accept(&0, 1.0, 0.0, 0.0));
+        // exp(-1) is about 0.37.
+        assert!(Distance::accept(&0, 1.0, 1.0, 0.3));
+        assert!(!Distance::accept(&0, 1.0, 1.0, 0.4));
+    }
+
+    #[test]
+    fn test_checkpoints_and_cancellation() {
+        let mut search = Search::from_parts(5, LocalSearchState::new(0, 10));
+        let mut checkpoints = Vec::new();
+        let result = search.run_with_checkpoints(4, |state| checkpoints.push(state.iteration()));
+        let best = (*search.state().best(), search.state().best_cost().unwrap());
+        assert_eq!(result, Ok(best));
+        assert_eq!(checkpoints, vec![4, 8]);
+
+        let mut search = Search::from_parts(5, LocalSearchState::new(0, 10));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut checkpoints = Vec::new();
+        let result = on_trigger(trigger, || {
+            Ok::<_, Cancelled>(
+                search.run_with_checkpoints(4, |state| checkpoints.push(state.clone())),
+            )
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        // The partial result is available in the checkpoint and in the search itself.
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].best_cost(), search.state().best_cost());
+        assert_eq!(search.state().iteration(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Checkpoint interval must be positive.")]
+    fn test_zero_checkpoint_interval() {
+        let mut search = Search::from_parts(5, LocalSearchState::new(0, 10));
+        let _ = search.run_with_checkpoints(0, |_| ());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{Completable, Computation, ComputationStep, Incomplete, Progress, StatefulRef};
+use std::marker::PhantomData;
+
+/// The problem-specific part of a [`MapReduce`] algorithm: a mapper [`ComputationStep`]
+/// which processes one input chunk into a partial result, and an associative reducer
+/// of partial results.
+pub trait MapReduceStep<CHUNK, MSTATE, R>: ComputationStep<CHUNK, MSTATE, R> {
+    /// The initial state of the mapper for the given `chunk`.
+    fn start(chunk: &CHUNK) -> MSTATE;
+
+    /// Combine two partial results (`left` belongs to the chunks before `right`).
+    fn reduce(left: R, right: R) -> R;
+}
+
+/// The state of a [`MapReduce`] algorithm: the index of the current chunk, the state of its
+/// mapper, and the reduced result of all completed chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "MSTATE: serde::Serialize + for<'a> serde::Deserialize<'a>, R: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct MapReduceState<MSTATE, R> {
+    chunk: usize,
+    mapper: Option<MSTATE>,
+    reduced: Option<R>,
+}
+
+impl<MSTATE, R> Default for MapReduceState<MSTATE, R> {
+    fn default() -> Self {
+        MapReduceState::new()
+    }
+}
+
+impl<MSTATE, R> MapReduceState<MSTATE, R> {
+    /// Create a state that starts with the first chunk.
+    pub fn new() -> Self {
+        MapReduceState {
+            chunk: 0,
+            mapper: None,
+            reduced: None,
+        }
+    }
+
+    /// The number of completed chunks (which is also the index of the current chunk).
+    pub fn completed_chunks(&self) -> usize {
+        self.chunk
+    }
+
+    /// The state of the mapper of the current chunk (if the chunk was started).
+    pub fn mapper(&self) -> Option<&MSTATE> {
+        self.mapper.as_ref()
+    }
+
+    /// The reduced result of all completed chunks (`None` if no chunk is completed).
+    pub fn reduced(&self) -> Option<&R> {
+        self.reduced.as_ref()
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`MapReduceStep`] (see [`MapReduce`]).
+pub struct MapReduceEngine<STEP>(PhantomData<STEP>);
+
+impl<CHUNK, MSTATE, R, STEP> ComputationStep<Vec<CHUNK>, MapReduceState<MSTATE, R>, Option<R>>
+    for MapReduceEngine<STEP>
+where
+    R: Clone,
+    STEP: MapReduceStep<CHUNK, MSTATE, R>,
+{
+    fn step(chunks: &Vec<CHUNK>, state: &mut MapReduceState<MSTATE, R>) -> Completable<Option<R>> {
+        let Some(chunk) = chunks.get(state.chunk) else {
+            return Ok(state.reduced.clone());
+        };
+        let mapper = state.mapper.get_or_insert_with(|| STEP::start(chunk));
+        let partial = STEP::step(chunk, mapper)?;
+        state.reduced = Some(match state.reduced.take() {
+            Some(reduced) => STEP::reduce(reduced, partial),
+            None => partial,
+        });
+        state.mapper = None;
+        state.chunk += 1;
+        if state.chunk == chunks.len() {
+            Ok(state.reduced.clone())
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+/// A suspendable map-reduce over a list of input chunks (the `CONTEXT`).
+///
+/// The chunks are mapped one after another: every step of the algorithm performs one step
+/// of the mapper of the current chunk (hence every suspend point of the mapper is a suspend
+/// point of the algorithm, and there is an extra suspend point after every chunk). Once
+/// a chunk is mapped, its partial result is immediately reduced into the result of
+/// the previous chunks, such that the [`MapReduceState`] only stores the reduced result and
+/// the mapper state of the current chunk. With the `serde` feature, the state can be saved
+/// at any suspend point, and the completed chunks are never mapped again after a restore.
+///
+/// The algorithm completes with the reduced result of all chunks (`None` if there are no
+/// chunks). The number of completed chunks is reported through [`Progress`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{MapReduce, MapReduceState, MapReduceStep};
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, ComputationStep, Incomplete, Progress};
+///
+/// /// Counts the words of a chunk of lines, one line per step.
+/// struct WordCount;
+///
+/// impl ComputationStep<Vec<String>, (usize, usize), usize> for WordCount {
+///     fn step(lines: &Vec<String>, (line, words): &mut (usize, usize)) -> Completable<usize> {
+///         let Some(text) = lines.get(*line) else {
+///             return Ok(*words);
+///         };
+///         *words += text.split_whitespace().count();
+///         *line += 1;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// impl MapReduceStep<Vec<String>, (usize, usize), usize> for WordCount {
+///     fn start(_chunk: &Vec<String>) -> (usize, usize) {
+///         (0, 0)
+///     }
+///
+///     fn reduce(left: usize, right: usize) -> usize {
+///         left + right
+///     }
+/// }
+///
+/// let chunks = vec![
+///     vec!["a b c".to_string(), "d".to_string()],
+///     vec!["e f".to_string()],
+/// ];
+/// let mut count = MapReduce::<_, _, usize, WordCount>::from_parts(chunks, MapReduceState::new());
+/// assert_eq!(count.compute(), Ok(Some(6)));
+/// assert_eq!(count.completed(), 2);
+/// ```
+pub type MapReduce<CHUNK, MSTATE, R, STEP> =
+    Computation<Vec<CHUNK>, MapReduceState<MSTATE, R>, Option<R>, MapReduceEngine<STEP>>;
+
+/// Completed units are the mapped chunks; the total is the number of chunks.
+impl<CHUNK, MSTATE, R, STEP> Progress for MapReduce<CHUNK, MSTATE, R, STEP>
+where
+    R: Clone,
+    STEP: MapReduceStep<CHUNK, MSTATE, R>,
+{
+    fn completed(&self) -> u64 {
+        self.state().completed_chunks() as u64
+    }
+
+    fn total(&self) -> Option<u64> {
+        Some(self.context().len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, FromParts};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Maps a range of numbers to their concatenation, one number per step (the reducer
+    /// is associative, but not commutative).
+    struct Concat;
+
+    impl ComputationStep<(u32, u32), u32, String> for Concat {
+        fn step(&(_, end): &(u32, u32), next: &mut u32) -> Completable<String> {
+            if *next == end {
+                return Ok(format!("[{end}]"));
+            }
+            *next += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    impl MapReduceStep<(u32, u32), u32, String> for Concat {
+        fn start(&(start, _): &(u32, u32)) -> u32 {
+            start
+        }
+
+        fn reduce(left: String, right: String) -> String {
+            left + &right
+        }
+    }
+
+    type Test = MapReduce<(u32, u32), u32, String, Concat>;
+
+    #[test]
+    fn test_chunks_in_order() {
+        let mut test = Test::from_parts(vec![(0, 2), (5, 5), (1, 3)], MapReduceState::new());
+        assert_eq!(test.total(), Some(3));
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(test.state().mapper(), Some(&1));
+        assert_eq!(test.state().reduced(), None);
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        // The first chunk completes with an extra suspend point.
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(test.state().reduced(), Some(&"[2]".to_string()));
+        assert_eq!(test.state().mapper(), None);
+        assert_eq!(test.completed(), 1);
+        assert_eq!(test.compute(), Ok(Some("[2][5][3]".to_string())));
+        assert_eq!(test.completed(), 3);
+    }
+
+    #[test]
+    fn test_no_chunks() {
+        let mut test = Test::from_parts(Vec::new(), MapReduceState::default());
+        assert_eq!(test.try_compute(), Ok(None));
+    }
+
+    #[test]
+    fn test_cancellation_keeps_completed_chunks() {
+        let mut test = Test::from_parts(vec![(0, 0), (0, 100)], MapReduceState::new());
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(test.compute_completable()));
+        assert!(matches!(result, Ok(Err(Incomplete::Cancelled(_)))));
+        assert_eq!(test.state().completed_chunks(), 1);
+        assert_eq!(test.state().mapper(), Some(&1));
+        assert_eq!(test.compute(), Ok(Some("[0][100]".to_string())));
+    }
+}
@@ -0,0 +1,35 @@
+//! Reusable suspendable algorithm skeletons.
+//!
+//! The types in this module implement the generic "driver" part of common algorithm
+//! shapes on top of [`crate::Computation`]: the user provides the problem-specific
+//! operations (usually as a trait implemented by a marker type, similar to
+//! [`crate::ComputationStep`]), and the crate handles suspension, cancellation, and
+//! (with the `serde` feature) serialization of the algorithm state.
+//!
+//! - [`Bisection`]: binary search of a monotone predicate that is evaluated by a nested
+//!   computation.
+//! - [`BranchAndBound`]: best-first branch-and-bound with a serializable open queue and
+//!   a [`crate::BestSoFar`] incumbent.
+//! - [`FixedPoint`]: iterate until convergence, with iteration limit and stagnation detection.
+//! - [`LocalSearch`]: simulated annealing with an incumbent that is available at any suspend
+//!   point.
+//! - [`MapReduce`]: map input chunks using a suspendable mapper and reduce the partial results.
+//! - [`Worklist`]: graph exploration and fixed-point computations over a frontier of nodes.
+
+mod bisection;
+mod branch_and_bound;
+mod fixed_point;
+mod local_search;
+mod map_reduce;
+mod worklist;
+
+pub use bisection::{Bisection, BisectionEngine, BisectionPoint, BisectionState, BisectionStep};
+pub use branch_and_bound::{
+    BranchAndBound, BranchAndBoundEngine, BranchAndBoundState, BranchAndBoundStep,
+};
+pub use fixed_point::{
+    FixedPoint, FixedPointEngine, FixedPointOutcome, FixedPointState, FixedPointStep,
+};
+pub use local_search::{LocalSearch, LocalSearchEngine, LocalSearchState, LocalSearchStep};
+pub use map_reduce::{MapReduce, MapReduceEngine, MapReduceState, MapReduceStep};
+pub use worklist::{Worklist, WorklistEngine, WorklistState, WorklistStep};
@@ -0,0 +1,262 @@
+use crate::{Completable, Computation, ComputationStep, Incomplete, Progress, StatefulRef};
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// The problem-specific part of a [`Worklist`] algorithm.
+///
+/// The engine calls [`WorklistStep::process`] for every node of the frontier (exactly once
+/// per node) and adds the returned successors that were not seen before to the frontier.
+pub trait WorklistStep<CONTEXT, STATE, N> {
+    /// Process a single `node`, updating the user `state` and returning the successors
+    /// of the node.
+    fn process(node: &N, context: &CONTEXT, state: &mut STATE) -> Vec<N>;
+}
+
+/// The state of a [`Worklist`] algorithm: the user `STATE`, the frontier of nodes that
+/// still need to be processed, and the set of all nodes seen so far.
+///
+/// With the `serde` feature, the whole state (including the frontier) is serializable,
+/// hence the exploration can be saved and resumed at any suspend point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "STATE: serde::Serialize + for<'a> serde::Deserialize<'a>, N: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct WorklistState<STATE, N>
+where
+    N: Clone + Eq + Hash,
+{
+    data: STATE,
+    frontier: VecDeque<N>,
+    seen: HashSet<N>,
+    processed: u64,
+    batch: usize,
+}
+
+impl<STATE, N: Clone + Eq + Hash> WorklistState<STATE, N> {
+    /// Create a new state with the user `data` and the `initial` nodes.
+    ///
+    /// By default, the engine suspends after every processed node
+    /// (see [`WorklistState::with_batch`]).
+    pub fn new<I: IntoIterator<Item = N>>(data: STATE, initial: I) -> Self {
+        let mut state = WorklistState {
+            data,
+            frontier: VecDeque::new(),
+            seen: HashSet::new(),
+            processed: 0,
+            batch: 1,
+        };
+        state.push_all(initial);
+        state
+    }
+
+    /// Process up to `batch` nodes between two suspend points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is zero.
+    pub fn with_batch(mut self, batch: usize) -> Self {
+        assert!(batch > 0, "Batch size must be positive.");
+        self.batch = batch;
+        self
+    }
+
+    /// Add `nodes` that were not seen before to the frontier.
+    pub fn push_all<I: IntoIterator<Item = N>>(&mut self, nodes: I) {
+        for node in nodes {
+            if self.seen.insert(node.clone()) {
+                self.frontier.push_back(node);
+            }
+        }
+    }
+
+    /// The user state.
+    pub fn data(&self) -> &STATE {
+        &self.data
+    }
+
+    /// Mutable access to the user state.
+    pub fn data_mut(&mut self) -> &mut STATE {
+        &mut self.data
+    }
+
+    /// The nodes that still need to be processed (in processing order).
+    pub fn frontier(&self) -> &VecDeque<N> {
+        &self.frontier
+    }
+
+    /// All nodes seen so far (processed or in the frontier).
+    pub fn seen(&self) -> &HashSet<N> {
+        &self.seen
+    }
+
+    /// The number of processed nodes.
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// The number of nodes processed between two suspend points.
+    pub fn batch(&self) -> usize {
+        self.batch
+    }
+
+    /// Destruct the state into the user state and the set of seen nodes.
+    pub fn into_parts(self) -> (STATE, HashSet<N>) {
+        (self.data, self.seen)
+    }
+}
+
+/// The [`ComputationStep`] which drives a [`WorklistStep`] (see [`Worklist`]).
+pub struct WorklistEngine<STEP>(PhantomData<STEP>);
+
+impl<CONTEXT, STATE, N, STEP> ComputationStep<CONTEXT, WorklistState<STATE, N>, STATE>
+    for WorklistEngine<STEP>
+where
+    STATE: Clone,
+    N: Clone + Eq + Hash,
+    STEP: WorklistStep<CONTEXT, STATE, N>,
+{
+    fn step(context: &CONTEXT, state: &mut WorklistState<STATE, N>) -> Completable<STATE> {
+        for _ in 0..state.batch {
+            let Some(node) = state.frontier.pop_front() else {
+                return Ok(state.data.clone());
+            };
+            let successors = STEP::process(&node, context, &mut state.data);
+            state.processed += 1;
+            state.push_all(successors);
+        }
+        if state.frontier.is_empty() {
+            Ok(state.data.clone())
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+/// A suspendable worklist (graph exploration) algorithm.
+///
+/// Starting with the initial nodes, the algorithm repeatedly removes a node from
+/// the frontier, processes it using [`WorklistStep::process`], and adds its successors that
+/// were not seen before to the frontier (breadth-first). It suspends after every
+/// [`WorklistState::batch`] processed nodes and completes with (a copy of) the user `STATE`
+/// once the frontier is empty. Cancellation is checked between batches, and the whole
+/// [`WorklistState`] (frontier and seen nodes included) can be serialized at any suspend
+/// point. The number of processed nodes is reported through [`Progress`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::algorithms::{Worklist, WorklistState, WorklistStep};
+/// use computation_process::Incomplete;
+/// use computation_process::prelude::*;
+///
+/// /// Counts the numbers reachable from the initial nodes using `x -> 2x mod m`.
+/// struct Doubling;
+///
+/// impl WorklistStep<u32, usize, u32> for Doubling {
+///     fn process(node: &u32, modulus: &u32, count: &mut usize) -> Vec<u32> {
+///         *count += 1;
+///         vec![(2 * node) % modulus]
+///     }
+/// }
+///
+/// let state = WorklistState::new(0, [1]).with_batch(2);
+/// let mut reachable = Worklist::<u32, usize, u32, Doubling>::from_parts(11, state);
+/// assert_eq!(reachable.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(reachable.state().processed(), 2);
+/// // 2 is a primitive root modulo 11, hence all non-zero numbers are reachable.
+/// assert_eq!(reachable.compute(), Ok(10));
+/// ```
+pub type Worklist<CONTEXT, STATE, N, STEP> =
+    Computation<CONTEXT, WorklistState<STATE, N>, STATE, WorklistEngine<STEP>>;
+
+/// Completed units are the processed nodes; the total is the number of seen nodes.
+impl<CONTEXT, STATE, N, STEP> Progress for Worklist<CONTEXT, STATE, N, STEP>
+where
+    STATE: Clone,
+    N: Clone + Eq + Hash,
+    STEP: WorklistStep<CONTEXT, STATE, N>,
+{
+    fn completed(&self) -> u64 {
+        self.state().processed()
+    }
+
+    fn total(&self) -> Option<u64> {
+        Some(self.state().seen().len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, FromParts};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// A small directed graph given as adjacency lists; collects the visited nodes.
+    struct Visit;
+
+    impl WorklistStep<Vec<Vec<usize>>, Vec<usize>, usize> for Visit {
+        fn process(node: &usize, graph: &Vec<Vec<usize>>, visited: &mut Vec<usize>) -> Vec<usize> {
+            visited.push(*node);
+            graph[*node].clone()
+        }
+    }
+
+    type Reachability = Worklist<Vec<Vec<usize>>, Vec<usize>, usize, Visit>;
+
+    fn graph() -> Vec<Vec<usize>> {
+        vec![vec![1, 2], vec![2, 0], vec![3], vec![], vec![0]]
+    }
+
+    #[test]
+    fn test_breadth_first_with_dedup() {
+        let mut search = Reachability::from_parts(graph(), WorklistState::new(Vec::new(), [0]));
+        assert_eq!(search.total(), Some(1));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.state().frontier(), &VecDeque::from([1, 2]));
+        assert_eq!(search.compute(), Ok(vec![0, 1, 2, 3]));
+        // Every node is processed exactly once; node 4 is not reachable.
+        assert_eq!((search.completed(), search.total()), (4, Some(4)));
+        let (_, seen) = search.into_parts().1.into_parts();
+        assert!(!seen.contains(&4));
+    }
+
+    #[test]
+    fn test_batches() {
+        let state = WorklistState::new(Vec::new(), [4, 4]).with_batch(3);
+        assert_eq!(state.frontier().len(), 1);
+        let mut search = Reachability::from_parts(graph(), state);
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(search.state().processed(), 3);
+        // The frontier empties within the second batch.
+        assert_eq!(search.try_compute(), Ok(vec![4, 0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_empty_frontier() {
+        let mut search = Reachability::from_parts(graph(), WorklistState::new(vec![7], []));
+        assert_eq!(search.try_compute(), Ok(vec![7]));
+    }
+
+    #[test]
+    fn test_cancellation_between_batches() {
+        let mut search = Reachability::from_parts(graph(), WorklistState::new(Vec::new(), [0]));
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(search.try_compute())).unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(search.state().processed(), 1);
+        assert_eq!(search.compute(), Ok(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch size must be positive.")]
+    fn test_zero_batch() {
+        let _ = WorklistState::<(), u32>::new((), []).with_batch(0);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{Completable, Computable, ComputationStep, Incomplete};
+use cancel_this::{Cancellable, is_cancelled};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that runs many instances of one [`ComputationStep`] over a shared `CONTEXT`,
+/// interleaved at their suspend points, and completes with the outputs of all instances
+/// (in the order of their initial states).
+///
+/// This corresponds to the common "evaluate N candidates" pattern, where a [`crate::JoinAll`]
+/// of [`crate::Computation`] objects would require a copy of the context for every instance.
+/// Every call to [`Computable::try_compute`] advances one pending instance by a single step
+/// (in a round-robin fashion). Cancellation is checked before every step and stops
+/// the whole batch; the interrupted instance is resumed first. If any instance is exhausted
+/// before producing its output, the whole [`Batch`] is exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Batch, Completable, ComputationStep, Incomplete};
+///
+/// /// Counts down from the initial state and returns the number of steps times the context.
+/// struct CountdownStep;
+///
+/// impl ComputationStep<u32, (u32, u32), u32> for CountdownStep {
+///     fn step(scale: &u32, (remaining, steps): &mut (u32, u32)) -> Completable<u32> {
+///         if *remaining == 0 {
+///             return Ok(*steps * scale);
+///         }
+///         *remaining -= 1;
+///         *steps += 1;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// let states = vec![(3, 0), (0, 0), (1, 0)];
+/// let outputs = Batch::<u32, (u32, u32), u32, CountdownStep>::run_all(10, states).unwrap();
+/// assert_eq!(outputs, vec![30, 0, 10]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "CONTEXT: serde::Serialize + for<'a> serde::Deserialize<'a>, STATE: serde::Serialize + for<'a> serde::Deserialize<'a>, OUTPUT: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Batch<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+{
+    context: CONTEXT,
+    states: Vec<Option<STATE>>,
+    outputs: Vec<Option<OUTPUT>>,
+    remaining: usize,
+    cursor: usize,
+    finished: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<STEP>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> Batch<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+{
+    /// Create a new [`Batch`] of instances starting in the given `states`.
+    pub fn new(context: CONTEXT, states: Vec<STATE>) -> Self {
+        let remaining = states.len();
+        Batch {
+            context,
+            outputs: states.iter().map(|_| None).collect(),
+            states: states.into_iter().map(Some).collect(),
+            remaining,
+            cursor: 0,
+            finished: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Run all instances starting in the given `states` to completion and return their outputs.
+    pub fn run_all(context: CONTEXT, states: Vec<STATE>) -> Cancellable<Vec<OUTPUT>> {
+        Self::new(context, states).compute()
+    }
+
+    /// The shared context of all instances.
+    pub fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    /// The total number of instances (including the completed ones).
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns `true` if there are no instances.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// The number of instances that did not complete yet.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// The state of the instance at `index`, assuming it did not complete yet.
+    pub fn state(&self, index: usize) -> Option<&STATE> {
+        self.states.get(index).and_then(|it| it.as_ref())
+    }
+
+    /// The output of the instance at `index` (if it completed and the outputs were not
+    /// returned yet).
+    pub fn output(&self, index: usize) -> Option<&OUTPUT> {
+        self.outputs.get(index).and_then(|it| it.as_ref())
+    }
+
+    /// Advance the next pending instance by one step.
+    fn advance(&mut self) -> Completable<()> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        if self.remaining == 0 {
+            return Ok(());
+        }
+        is_cancelled!()?;
+        let count = self.states.len();
+        let index = (0..count)
+            .map(|i| (self.cursor + i) % count)
+            .find(|i| self.states[*i].is_some())
+            .expect("There is at least one pending instance.");
+        let state = self.states[index]
+            .as_mut()
+            .expect("The instance is pending.");
+        match STEP::step(&self.context, state) {
+            Ok(output) => {
+                self.states[index] = None;
+                self.outputs[index] = Some(output);
+                self.remaining -= 1;
+                self.cursor = index + 1;
+                Ok(())
+            }
+            Err(Incomplete::Suspended) => {
+                self.cursor = index + 1;
+                Err(Incomplete::Suspended)
+            }
+            Err(Incomplete::Exhausted) => {
+                self.finished = true;
+                self.states.clear();
+                self.outputs.clear();
+                self.remaining = 0;
+                Err(Incomplete::Exhausted)
+            }
+            Err(e) => {
+                self.cursor = index;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> Computable<Vec<OUTPUT>> for Batch<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+{
+    fn try_compute(&mut self) -> Completable<Vec<OUTPUT>> {
+        self.advance()?;
+        if self.remaining > 0 {
+            return Err(Incomplete::Suspended);
+        }
+        self.finished = true;
+        self.states.clear();
+        Ok(self
+            .outputs
+            .drain(..)
+            .map(|it| it.expect("All instances completed."))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cancel_this::{CancelAtomic, on_trigger};
+
+    /// Sums the context items starting at the index in the state, one item per step.
+    struct SuffixSumStep;
+
+    impl ComputationStep<Vec<i32>, (usize, i32), i32> for SuffixSumStep {
+        fn step(items: &Vec<i32>, (index, sum): &mut (usize, i32)) -> Completable<i32> {
+            match items.get(*index) {
+                Some(item) => {
+                    *index += 1;
+                    *sum += item;
+                    Err(Incomplete::Suspended)
+                }
+                None if *sum < 0 => Err(Incomplete::Exhausted),
+                None => Ok(*sum),
+            }
+        }
+    }
+
+    type SuffixSums = Batch<Vec<i32>, (usize, i32), i32, SuffixSumStep>;
+
+    #[test]
+    fn test_interleaving() {
+        let mut batch = SuffixSums::new(vec![1, 2, 3], vec![(0, 0), (2, 0), (3, 0)]);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.context(), &vec![1, 2, 3]);
+        assert_eq!(batch.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(batch.state(0), Some(&(1, 1)));
+        assert_eq!(batch.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(batch.state(1), Some(&(3, 3)));
+        // The third instance completes immediately.
+        assert_eq!(batch.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(batch.remaining(), 2);
+        assert_eq!(batch.output(2), Some(&0));
+        assert_eq!(batch.state(2), None);
+        assert_eq!(batch.compute(), Ok(vec![6, 3, 0]));
+        assert_eq!(batch.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let batch = SuffixSums::new(vec![1], vec![]);
+        assert!(batch.is_empty());
+        assert_eq!(SuffixSums::run_all(vec![1], vec![]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_exhausted_instance() {
+        let mut batch = SuffixSums::new(vec![-1], vec![(0, 0), (1, 0)]);
+        assert_eq!(batch.compute_completable(), Err(Incomplete::Exhausted));
+        assert_eq!(batch.remaining(), 0);
+    }
+
+    #[test]
+    fn test_cancellation_stops_batch() {
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut batch = SuffixSums::new(vec![1, 2], vec![(0, 0), (1, 0)]);
+        let result = on_trigger(trigger, || batch.compute());
+        assert!(result.is_err());
+        assert_eq!(batch.remaining(), 2);
+        assert_eq!(batch.state(0), Some(&(0, 0)));
+        assert_eq!(batch.compute(), Ok(vec![3, 2]));
+    }
+}
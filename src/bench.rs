@@ -0,0 +1,312 @@
+//! Tools for measuring the overhead of suspend points.
+//!
+//! Every call to [`Computable::try_compute`] checks cancellation and returns to the driver,
+//! which costs a few nanoseconds. For coarse steps, this is negligible, but very fine-grained
+//! steps can be dominated by it. [`measure_step_overhead`] runs a [`ComputationStep`]
+//! once in a plain loop and once through [`Computable::try_compute`], and reports the
+//! difference as a [`StepOverhead`]. Using [`StepOverhead::recommended_batch`], the step
+//! can then be made coarser (e.g., by processing several items per step, or by using
+//! [`crate::yield_point!`] with a [`crate::YieldPolicy`]). Alternatively,
+//! [`measure_batched_overhead`] shows how much of the overhead is removed by
+//! [`Computable::compute_batched`], which checks cancellation only once per batch of steps.
+//!
+//! The measurement uses wall-clock time, hence it is only meaningful in optimized builds
+//! and with enough steps to average out the noise.
+//!
+//! The `batched_stepping` benchmark (`cargo bench --bench batched_stepping`) uses
+//! [`measure_batched_overhead`] to compare per-step and batched execution of a very
+//! fine-grained step for several batch sizes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use computation_process::bench::measure_step_overhead;
+//! use computation_process::{Completable, ComputationStep, Incomplete};
+//!
+//! /// Adds one item per step.
+//! struct SumStep;
+//!
+//! impl ComputationStep<Vec<u64>, (usize, u64), u64> for SumStep {
+//!     fn step(items: &Vec<u64>, (index, sum): &mut (usize, u64)) -> Completable<u64> {
+//!         *sum += items[*index % items.len()];
+//!         *index += 1;
+//!         Err(Incomplete::Suspended)
+//!     }
+//! }
+//!
+//! let items = (0..1000).collect::<Vec<_>>();
+//! let overhead = measure_step_overhead::<_, _, _, SumStep>(items, (0, 0), 10_000);
+//! assert_eq!(overhead.steps(), 10_000);
+//! println!("{overhead}");
+//! // Merging this many steps into one suspend point keeps the overhead below 1%.
+//! assert!(overhead.recommended_batch(0.01) >= 1);
+//! ```
+
+use crate::{Computable, Computation, ComputationStep, FromParts, Incomplete};
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The result of [`measure_step_overhead`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepOverhead {
+    steps: u32,
+    direct: Duration,
+    suspended: Duration,
+}
+
+impl StepOverhead {
+    /// Create a measurement of `steps` steps which took `direct` time in a plain loop
+    /// and `suspended` time through [`Computable::try_compute`].
+    pub fn new(steps: u32, direct: Duration, suspended: Duration) -> Self {
+        StepOverhead {
+            steps,
+            direct,
+            suspended,
+        }
+    }
+
+    /// The number of measured steps.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// The total time of all steps in a plain loop.
+    pub fn direct(&self) -> Duration {
+        self.direct
+    }
+
+    /// The total time of all steps through [`Computable::try_compute`].
+    pub fn suspended(&self) -> Duration {
+        self.suspended
+    }
+
+    /// The average time of one step in a plain loop.
+    pub fn direct_per_step(&self) -> Duration {
+        self.direct / self.steps.max(1)
+    }
+
+    /// The average time of one step through [`Computable::try_compute`].
+    pub fn suspended_per_step(&self) -> Duration {
+        self.suspended / self.steps.max(1)
+    }
+
+    /// The average cost of one suspend point (zero if it is below the measurement noise).
+    pub fn overhead_per_step(&self) -> Duration {
+        self.suspended.saturating_sub(self.direct) / self.steps.max(1)
+    }
+
+    /// The overhead of suspend points relative to the direct run (e.g., `0.5` means
+    /// that suspended execution is 50% slower).
+    pub fn relative_overhead(&self) -> f64 {
+        if self.direct.is_zero() {
+            return 0.0;
+        }
+        self.suspended.saturating_sub(self.direct).as_secs_f64() / self.direct.as_secs_f64()
+    }
+
+    /// The minimal number of measured steps that should be merged into one suspend point,
+    /// such that the relative overhead is at most `tolerance` (e.g., `0.01` for 1%).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn recommended_batch(&self, tolerance: f64) -> usize {
+        assert!(tolerance > 0.0, "Tolerance must be positive.");
+        let batch = self.relative_overhead() / tolerance;
+        (batch.ceil() as usize).max(1)
+    }
+}
+
+impl Display for StepOverhead {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} steps: {:?}/step direct, {:?}/step suspended, {:?}/step overhead ({:.1}%)",
+            self.steps,
+            self.direct_per_step(),
+            self.suspended_per_step(),
+            self.overhead_per_step(),
+            self.relative_overhead() * 100.0,
+        )
+    }
+}
+
+/// Measure the overhead of suspend points for the step function `STEP`.
+///
+/// The step is executed `steps` times starting in `state`: first directly in a plain loop,
+/// and then (starting again in `state`) through [`Computable::try_compute`] of
+/// a [`Computation`]. The step is executed exactly `steps` times regardless of its
+/// outcomes, hence it should not complete within the measured steps (or it should
+/// tolerate being called again after completion).
+pub fn measure_step_overhead<CONTEXT: 'static, STATE: Clone + 'static, OUTPUT: 'static, STEP>(
+    context: CONTEXT,
+    state: STATE,
+    steps: u32,
+) -> StepOverhead
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT> + 'static,
+{
+    let mut direct_state = state.clone();
+    let start = Instant::now();
+    for _ in 0..steps {
+        let _ = black_box(STEP::step(black_box(&context), &mut direct_state));
+    }
+    let direct = start.elapsed();
+
+    let mut computation = Computation::<CONTEXT, STATE, OUTPUT, STEP>::from_parts(context, state);
+    let start = Instant::now();
+    for _ in 0..steps {
+        let _ = black_box(black_box(&mut computation).try_compute());
+    }
+    let suspended = start.elapsed();
+
+    StepOverhead::new(steps, direct, suspended)
+}
+
+/// Measure how much [`Computable::compute_batched`] reduces the overhead of suspend points
+/// for the step function `STEP`.
+///
+/// The computation starting in `state` is executed to completion three times: directly
+/// in a plain loop (which also counts the steps), using [`Computable::compute_completable`],
+/// and using [`Computable::compute_batched`] with the given `batch`. The result contains
+/// the overhead of the unbatched run and the overhead of the batched run (both relative
+/// to the direct run).
+///
+/// # Panics
+///
+/// Panics if `batch` is zero. The method never returns if the computation never completes.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::bench::measure_batched_overhead;
+/// use computation_process::{Completable, ComputationStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let (unbatched, batched) = measure_batched_overhead::<_, _, _, CountStep>(100_000, 0, 64);
+/// assert_eq!(unbatched.steps(), 100_000);
+/// println!("unbatched: {unbatched}");
+/// println!("batched: {batched}");
+/// ```
+pub fn measure_batched_overhead<CONTEXT, STATE, OUTPUT, STEP>(
+    context: CONTEXT,
+    state: STATE,
+    batch: usize,
+) -> (StepOverhead, StepOverhead)
+where
+    CONTEXT: Clone + 'static,
+    STATE: Clone + 'static,
+    OUTPUT: 'static,
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT> + 'static,
+{
+    assert!(batch > 0, "Batch size must be positive.");
+    let mut direct_state = state.clone();
+    let mut steps = 0u32;
+    let start = Instant::now();
+    loop {
+        steps = steps.saturating_add(1);
+        if !matches!(
+            black_box(STEP::step(black_box(&context), &mut direct_state)),
+            Err(Incomplete::Suspended)
+        ) {
+            break;
+        }
+    }
+    let direct = start.elapsed();
+
+    let run = |batch: Option<usize>| {
+        let mut computation =
+            Computation::<CONTEXT, STATE, OUTPUT, STEP>::from_parts(context.clone(), state.clone());
+        let start = Instant::now();
+        let _ = black_box(match batch {
+            None => computation.compute_completable(),
+            Some(batch) => computation.compute_batched(batch),
+        });
+        start.elapsed()
+    };
+    let unbatched = StepOverhead::new(steps, direct, run(None));
+    let batched = StepOverhead::new(steps, direct, run(Some(batch)));
+    (unbatched, batched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Completable;
+
+    struct CountStep;
+
+    impl ComputationStep<(), u64, u64> for CountStep {
+        fn step(_context: &(), count: &mut u64) -> Completable<u64> {
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    #[test]
+    fn test_recommended_batch() {
+        let ms = Duration::from_millis;
+        let overhead = StepOverhead::new(100, ms(100), ms(150));
+        assert_eq!(overhead.direct_per_step(), ms(1));
+        assert_eq!(overhead.overhead_per_step(), Duration::from_micros(500));
+        assert_eq!(overhead.relative_overhead(), 0.5);
+        assert_eq!(overhead.recommended_batch(0.01), 50);
+        assert_eq!(overhead.recommended_batch(1.0), 1);
+        // Noise can make the suspended run faster.
+        let overhead = StepOverhead::new(100, ms(100), ms(90));
+        assert_eq!(overhead.overhead_per_step(), Duration::ZERO);
+        assert_eq!(overhead.recommended_batch(0.01), 1);
+        assert!(
+            overhead
+                .to_string()
+                .starts_with("100 steps: 1ms/step direct")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Tolerance must be positive.")]
+    fn test_invalid_tolerance() {
+        StepOverhead::new(1, Duration::ZERO, Duration::ZERO).recommended_batch(0.0);
+    }
+
+    #[test]
+    fn test_measure() {
+        let overhead = measure_step_overhead::<_, _, _, CountStep>((), 0, 1000);
+        assert_eq!(overhead.steps(), 1000);
+        assert!(overhead.recommended_batch(0.5) >= 1);
+        let empty = measure_step_overhead::<_, _, _, CountStep>((), 0, 0);
+        assert_eq!(empty.steps(), 0);
+        assert!(empty.recommended_batch(0.01) >= 1);
+    }
+
+    /// Completes after the given number of steps.
+    struct TargetStep;
+
+    impl ComputationStep<u32, u32, u32> for TargetStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+            *count += 1;
+            if *count >= *target {
+                Ok(*count)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    #[test]
+    fn test_measure_batched() {
+        let (unbatched, batched) = measure_batched_overhead::<_, _, _, TargetStep>(500, 0, 16);
+        assert_eq!(unbatched.steps(), 500);
+        assert_eq!(batched.steps(), 500);
+        assert_eq!(unbatched.direct(), batched.direct());
+    }
+}
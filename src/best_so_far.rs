@@ -0,0 +1,177 @@
+use crate::{Blackboard, BlackboardKey};
+
+/// The best (i.e., largest) value offered so far, typically an incumbent solution
+/// of an optimization problem.
+///
+/// Multiple computations that run in one [`crate::Scheduler`] can share a [`BestSoFar`]
+/// stored in the scheduler's [`Blackboard`] (see [`BestSoFar::offer_shared`] and
+/// [`BestSoFar::peek_shared`]) and use it to prune work that cannot improve on the best
+/// value found by any of them. To minimize instead of maximize, wrap the values in
+/// [`std::cmp::Reverse`].
+///
+/// With the `serde` feature, [`BestSoFar`] can be serialized, such that it can be saved
+/// from the blackboard together with a checkpoint of the computations and later restored.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::BestSoFar;
+/// use std::cmp::Reverse;
+///
+/// let mut shortest = BestSoFar::new();
+/// assert!(shortest.offer(Reverse(10)));
+/// assert!(!shortest.offer(Reverse(12)));
+/// assert!(shortest.offer(Reverse(7)));
+/// assert_eq!(shortest.peek(), Some(&Reverse(7)));
+/// // A partial solution of length 8 cannot improve the incumbent.
+/// assert!(!shortest.can_improve(&Reverse(8)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BestSoFar<T> {
+    best: Option<T>,
+    improvements: u64,
+}
+
+impl<T> Default for BestSoFar<T> {
+    fn default() -> Self {
+        BestSoFar {
+            best: None,
+            improvements: 0,
+        }
+    }
+}
+
+impl<T> From<T> for BestSoFar<T> {
+    fn from(value: T) -> Self {
+        BestSoFar {
+            best: Some(value),
+            improvements: 0,
+        }
+    }
+}
+
+impl<T: Ord> BestSoFar<T> {
+    /// Create a new [`BestSoFar`] without any value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offer a new `value`. Returns `true` if the value is strictly better than
+    /// the best value so far (it then becomes the new best value).
+    pub fn offer(&mut self, value: T) -> bool {
+        if self.best.as_ref().is_some_and(|best| value <= *best) {
+            return false;
+        }
+        self.best = Some(value);
+        self.improvements += 1;
+        true
+    }
+
+    /// The best value so far (if any).
+    pub fn peek(&self) -> Option<&T> {
+        self.best.as_ref()
+    }
+
+    /// Returns `true` if a value bounded by `bound` could still be strictly better than
+    /// the best value so far (i.e., the work leading to such a value should not be pruned).
+    pub fn can_improve(&self, bound: &T) -> bool {
+        self.best.as_ref().is_none_or(|best| bound > best)
+    }
+
+    /// The number of offered values that improved the best value.
+    pub fn improvements(&self) -> u64 {
+        self.improvements
+    }
+
+    /// Take the best value so far.
+    pub fn into_inner(self) -> Option<T> {
+        self.best
+    }
+}
+
+impl<T: Ord + Clone + 'static> BestSoFar<T> {
+    /// Offer a `value` to the [`BestSoFar`] stored under `key` in the [`Blackboard`]
+    /// of the active scheduler (a new entry is created if necessary).
+    ///
+    /// Returns `None` if no blackboard is available (see [`Blackboard::access`]),
+    /// otherwise the result of [`BestSoFar::offer`].
+    pub fn offer_shared(key: BlackboardKey<BestSoFar<T>>, value: T) -> Option<bool> {
+        Blackboard::access(|board| board.get_or_insert_with(key, BestSoFar::new).offer(value))
+    }
+
+    /// A copy of the best value of the [`BestSoFar`] stored under `key` in the [`Blackboard`]
+    /// of the active scheduler.
+    ///
+    /// Returns `None` if no blackboard is available, or no value was offered yet.
+    pub fn peek_shared(key: BlackboardKey<BestSoFar<T>>) -> Option<T> {
+        Blackboard::access(|board| board.get(key).and_then(|it| it.peek().cloned())).flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Completable, Computation, ComputationStep, FromParts, Generatable, Incomplete, Scheduler,
+    };
+    use cancel_this::Cancellable;
+
+    #[test]
+    fn test_offer() {
+        let mut best = BestSoFar::new();
+        assert_eq!(best.peek(), None);
+        assert!(best.can_improve(&0));
+        assert!(best.offer(3));
+        assert!(!best.offer(3));
+        assert!(!best.offer(1));
+        assert!(best.offer(5));
+        assert_eq!(best.peek(), Some(&5));
+        assert_eq!(best.improvements(), 2);
+        assert!(best.can_improve(&6));
+        assert!(!best.can_improve(&5));
+        assert_eq!(best.into_inner(), Some(5));
+
+        let initial = BestSoFar::from(10);
+        assert_eq!(initial.improvements(), 0);
+        assert!(!initial.can_improve(&10));
+    }
+
+    const BEST: BlackboardKey<BestSoFar<u32>> = BlackboardKey::new("best");
+
+    /// Offers the values of its context one per step, counting the values that
+    /// could not improve the shared best value.
+    struct OfferStep;
+
+    impl ComputationStep<Vec<u32>, (usize, u32), u32> for OfferStep {
+        fn step(values: &Vec<u32>, (position, pruned): &mut (usize, u32)) -> Completable<u32> {
+            let Some(value) = values.get(*position) else {
+                return Ok(*pruned);
+            };
+            *position += 1;
+            if BestSoFar::offer_shared(BEST, *value) == Some(false) {
+                *pruned += 1;
+            }
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Offer = Computation<Vec<u32>, (usize, u32), u32, OfferStep>;
+
+    #[test]
+    fn test_shared_in_scheduler() {
+        assert_eq!(BestSoFar::offer_shared(BEST, 1), None);
+        assert_eq!(BestSoFar::peek_shared(BEST), None);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Offer::from_parts(vec![1, 4, 6], (0, 0)));
+        scheduler.spawn(Offer::from_parts(vec![3, 2, 8], (0, 0)));
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        // Pruned: 2 (after 4 was found).
+        assert_eq!(outputs, vec![(0, 0), (1, 1)]);
+        let best = scheduler.blackboard().get(BEST).unwrap();
+        assert_eq!(best.peek(), Some(&8));
+        assert_eq!(best.improvements(), 5);
+        assert_eq!(scheduler.try_next(), None);
+    }
+}
@@ -0,0 +1,307 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// A typed key of a [`Blackboard`] entry.
+///
+/// Keys are typically declared as constants, such that all computations agree on
+/// the name and the type of the entry:
+///
+/// ```rust
+/// use computation_process::BlackboardKey;
+///
+/// const UPPER_BOUND: BlackboardKey<u64> = BlackboardKey::new("upper_bound");
+/// ```
+pub struct BlackboardKey<V> {
+    name: &'static str,
+    _phantom: PhantomData<fn() -> V>,
+}
+
+impl<V> BlackboardKey<V> {
+    /// Create a new key with the given `name`.
+    pub const fn new(name: &'static str) -> Self {
+        BlackboardKey {
+            name,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The name of this key.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+// Clone/Copy/Debug are implemented manually, because derive would require the same
+// traits for `V`.
+
+impl<V> Clone for BlackboardKey<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for BlackboardKey<V> {}
+
+impl<V> Debug for BlackboardKey<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BlackboardKey({})", self.name)
+    }
+}
+
+thread_local! {
+    static ACTIVE_BLACKBOARD: RefCell<Option<Blackboard>> = const { RefCell::new(None) };
+}
+
+/// A typed key-value store shared by computations that run in one [`crate::Scheduler`].
+///
+/// Cooperative algorithms often need to exchange information, e.g., the best bound found
+/// so far in a branch-and-bound search. The scheduler owns the blackboard and makes it
+/// available to a computation only while the computation is performing a step
+/// (see [`Blackboard::access`]). Since all computations of a scheduler run on one thread
+/// and a step is never interrupted by another computation, the entries are only observed
+/// and modified between suspend points, and no locking is necessary.
+///
+/// Each entry is identified by a [`BlackboardKey`], which also determines its type.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Blackboard, BlackboardKey};
+///
+/// const COUNTER: BlackboardKey<u32> = BlackboardKey::new("counter");
+///
+/// let mut board = Blackboard::new();
+/// assert_eq!(board.get(COUNTER), None);
+/// *board.get_or_insert_with(COUNTER, || 0) += 5;
+/// assert_eq!(board.get(COUNTER), Some(&5));
+/// ```
+#[derive(Default)]
+pub struct Blackboard {
+    entries: HashMap<&'static str, Box<dyn Any>>,
+}
+
+impl Debug for Blackboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut keys = self.entries.keys().collect::<Vec<_>>();
+        keys.sort();
+        f.debug_struct("Blackboard").field("keys", &keys).finish()
+    }
+}
+
+impl Blackboard {
+    /// Create a new empty blackboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of the `key` entry, returning the previous value (if any).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry exists but has a different type.
+    pub fn insert<V: 'static>(&mut self, key: BlackboardKey<V>, value: V) -> Option<V> {
+        let previous = self.remove(key);
+        self.entries.insert(key.name, Box::new(value));
+        previous
+    }
+
+    /// Access the value of the `key` entry (if it exists).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry exists but has a different type.
+    pub fn get<V: 'static>(&self, key: BlackboardKey<V>) -> Option<&V> {
+        self.entries
+            .get(key.name)
+            .map(|it| it.downcast_ref().unwrap_or_else(|| type_mismatch(key)))
+    }
+
+    /// Mutable access to the value of the `key` entry (if it exists).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry exists but has a different type.
+    pub fn get_mut<V: 'static>(&mut self, key: BlackboardKey<V>) -> Option<&mut V> {
+        self.entries
+            .get_mut(key.name)
+            .map(|it| it.downcast_mut().unwrap_or_else(|| type_mismatch(key)))
+    }
+
+    /// Mutable access to the value of the `key` entry, which is initialized using
+    /// `default` if it does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry exists but has a different type.
+    pub fn get_or_insert_with<V: 'static, F: FnOnce() -> V>(
+        &mut self,
+        key: BlackboardKey<V>,
+        default: F,
+    ) -> &mut V {
+        self.entries
+            .entry(key.name)
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut()
+            .unwrap_or_else(|| type_mismatch(key))
+    }
+
+    /// Remove the `key` entry, returning its value (if it exists).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry exists but has a different type.
+    pub fn remove<V: 'static>(&mut self, key: BlackboardKey<V>) -> Option<V> {
+        let value = self.entries.remove(key.name)?;
+        match value.downcast::<V>() {
+            Ok(value) => Some(*value),
+            Err(_) => type_mismatch(key),
+        }
+    }
+
+    /// Returns `true` if an entry with the name of `key` exists.
+    pub fn contains<V>(&self, key: BlackboardKey<V>) -> bool {
+        self.entries.contains_key(key.name)
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Run `action` with the blackboard of the scheduler that is currently executing
+    /// a step on this thread.
+    ///
+    /// Returns `None` if no blackboard is available (i.e., the computation is not driven
+    /// by a [`crate::Scheduler`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called recursively from within `action`.
+    pub fn access<R, F: FnOnce(&mut Blackboard) -> R>(action: F) -> Option<R> {
+        ACTIVE_BLACKBOARD.with(|it| {
+            let mut active = it
+                .try_borrow_mut()
+                .expect("Blackboard::access cannot be called recursively.");
+            active.as_mut().map(action)
+        })
+    }
+
+    /// Make this blackboard available through [`Blackboard::access`] while `action` runs.
+    pub(crate) fn install<R, F: FnOnce() -> R>(&mut self, action: F) -> R {
+        let board = std::mem::take(self);
+        let previous = ACTIVE_BLACKBOARD.with(|it| it.replace(Some(board)));
+        // Move the blackboard back even if the action panics.
+        struct Restore<'a> {
+            target: &'a mut Blackboard,
+            previous: Option<Option<Blackboard>>,
+        }
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                let previous = self.previous.take().unwrap_or_default();
+                let board = ACTIVE_BLACKBOARD.with(|it| it.replace(previous));
+                *self.target = board.unwrap_or_default();
+            }
+        }
+        let _restore = Restore {
+            target: self,
+            previous: Some(previous),
+        };
+        action()
+    }
+}
+
+fn type_mismatch<V, R>(key: BlackboardKey<V>) -> R {
+    panic!(
+        "Blackboard entry `{}` is not of type `{}`.",
+        key.name,
+        std::any::type_name::<V>()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMBER: BlackboardKey<u64> = BlackboardKey::new("number");
+    const NAMES: BlackboardKey<Vec<String>> = BlackboardKey::new("names");
+    const WRONG: BlackboardKey<bool> = BlackboardKey::new("number");
+
+    #[test]
+    fn test_entries() {
+        let mut board = Blackboard::new();
+        assert!(board.is_empty());
+        assert_eq!(board.insert(NUMBER, 3), None);
+        assert_eq!(board.insert(NUMBER, 4), Some(3));
+        *board.get_mut(NUMBER).unwrap() += 1;
+        assert_eq!(board.get(NUMBER), Some(&5));
+
+        board
+            .get_or_insert_with(NAMES, Vec::new)
+            .push("a".to_string());
+        board
+            .get_or_insert_with(NAMES, Vec::new)
+            .push("b".to_string());
+        assert_eq!(board.get(NAMES).unwrap().len(), 2);
+        assert_eq!(board.len(), 2);
+        assert_eq!(
+            format!("{board:?}"),
+            "Blackboard { keys: [\"names\", \"number\"] }"
+        );
+        assert_eq!(format!("{NUMBER:?}"), "BlackboardKey(number)");
+
+        assert_eq!(board.remove(NUMBER), Some(5));
+        assert!(!board.contains(NUMBER));
+        board.clear();
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not of type")]
+    fn test_type_mismatch() {
+        let mut board = Blackboard::new();
+        board.insert(NUMBER, 1);
+        board.get(WRONG);
+    }
+
+    #[test]
+    fn test_access() {
+        assert_eq!(Blackboard::access(|_| ()), None);
+        let mut board = Blackboard::new();
+        board.insert(NUMBER, 1);
+        let result = board.install(|| {
+            Blackboard::access(|board| {
+                *board.get_mut(NUMBER).unwrap() += 1;
+                board.insert(NAMES, vec!["x".to_string()]);
+            })
+        });
+        assert_eq!(result, Some(()));
+        assert_eq!(board.get(NUMBER), Some(&2));
+        assert!(board.contains(NAMES));
+        assert_eq!(Blackboard::access(|_| ()), None);
+    }
+
+    #[test]
+    fn test_install_restores_after_panic() {
+        let mut board = Blackboard::new();
+        board.insert(NUMBER, 7);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            board.install(|| panic!("failure"));
+        }));
+        assert!(result.is_err());
+        assert_eq!(board.get(NUMBER), Some(&7));
+        assert_eq!(Blackboard::access(|_| ()), None);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Generatable, Incomplete};
+use cancel_this::{Cancellable, Cancelled};
+use std::marker::PhantomData;
+
+/// Determines how a [`BlockingIter`] reacts to cancellation of the underlying [`Generatable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CancelPolicy {
+    /// Panic when the generator is canceled.
+    #[default]
+    Panic,
+    /// Stop the iteration when the generator is canceled. The cancellation error
+    /// is then available through [`BlockingIter::cancelled`].
+    Stop,
+}
+
+/// An [`Iterator`] view of a [`Generatable`] that yields plain `T` items.
+///
+/// The iterator skips over all suspended states and reacts to cancellation according
+/// to the chosen [`CancelPolicy`]. This makes it possible to pass a [`Generatable`] to
+/// existing APIs that expect an `Iterator<Item = T>`.
+///
+/// See [`Generatable::blocking_iter`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{CancelPolicy, Generator, GeneratorStep, Completable, Generatable, FromParts};
+///
+/// struct CountStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for CountStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// fn total(items: impl Iterator<Item = u32>) -> u32 {
+///     items.sum()
+/// }
+///
+/// let mut generator = Generator::<u32, u32, u32, CountStep>::from_parts(4, 0);
+/// assert_eq!(total(generator.blocking_iter(CancelPolicy::Panic)), 10);
+/// ```
+#[derive(Debug)]
+pub struct BlockingIter<'a, T, G: Generatable<T> + ?Sized> {
+    generator: &'a mut G,
+    policy: CancelPolicy,
+    cancelled: Option<Cancelled>,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T, G: Generatable<T> + ?Sized> BlockingIter<'a, T, G> {
+    /// Create a new [`BlockingIter`] view of the given `generator`.
+    pub fn new(generator: &'a mut G, policy: CancelPolicy) -> Self {
+        BlockingIter {
+            generator,
+            policy,
+            cancelled: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The cancellation error that stopped this iterator (only relevant
+    /// for [`CancelPolicy::Stop`]).
+    pub fn cancelled(&self) -> Option<&Cancelled> {
+        self.cancelled.as_ref()
+    }
+
+    /// Consume this iterator view and report whether it was stopped due to cancellation.
+    pub fn into_result(self) -> Cancellable<()> {
+        match self.cancelled {
+            None => Ok(()),
+            Some(c) => Err(c),
+        }
+    }
+}
+
+impl<T, G: Generatable<T> + ?Sized> Iterator for BlockingIter<'_, T, G> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.cancelled.is_some() {
+            return None;
+        }
+        match next_skip_suspended(self.generator)? {
+            Ok(item) => Some(item),
+            Err(c) => match self.policy {
+                CancelPolicy::Panic => panic!("`BlockingIter` generator was canceled: {}", c),
+                CancelPolicy::Stop => {
+                    self.cancelled = Some(c);
+                    None
+                }
+            },
+        }
+    }
+}
+
+/// Advance the given [`Generatable`] until it produces an item, is canceled, or is exhausted,
+/// skipping over all suspended states.
+///
+/// This is the shared implementation of [`Iterator::next`] for all [`Generatable`] adapters.
+pub(crate) fn next_skip_suspended<T, G: Generatable<T> + ?Sized>(
+    generator: &mut G,
+) -> Option<Cancellable<T>> {
+    loop {
+        match generator.try_next()? {
+            Ok(item) => return Some(Ok(item)),
+            Err(Incomplete::Suspended) => continue,
+            Err(Incomplete::Cancelled(c)) => return Some(Err(c)),
+            Err(Incomplete::ResourceExceeded(e)) => return Some(Err(e.into())),
+            Err(Incomplete::Exhausted) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Completable, FromParts, Generator, GeneratorStep};
+
+    struct SuspendingStep;
+
+    impl GeneratorStep<u32, u32, u32> for SuspendingStep {
+        fn step(max: &u32, state: &mut u32) -> Completable<Option<u32>> {
+            *state += 1;
+            if *state > 2 * *max {
+                Ok(None)
+            } else if state.is_multiple_of(2) {
+                Ok(Some(*state / 2))
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type TestGenerator = Generator<u32, u32, u32, SuspendingStep>;
+
+    #[test]
+    fn test_blocking_iter_skips_suspensions() {
+        let mut generator = TestGenerator::from_parts(3, 0);
+        let items: Vec<u32> = generator.blocking_iter(CancelPolicy::Panic).collect();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_blocking_iter_partial_consumption() {
+        let mut generator = TestGenerator::from_parts(3, 0);
+        assert_eq!(generator.blocking_iter(CancelPolicy::Panic).next(), Some(1));
+        let rest: Vec<u32> = generator.blocking_iter(CancelPolicy::Panic).collect();
+        assert_eq!(rest, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_blocking_iter_stop_on_cancel() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut generator = TestGenerator::from_parts(3, 0);
+        let result: Cancellable<Vec<u32>> = on_trigger(trigger, || {
+            let mut iter = generator.blocking_iter(CancelPolicy::Stop);
+            let items: Vec<u32> = iter.by_ref().collect();
+            assert!(items.is_empty());
+            assert!(iter.cancelled().is_some());
+            iter.into_result().map(|_| items)
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "canceled")]
+    fn test_blocking_iter_panic_on_cancel() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut generator = TestGenerator::from_parts(3, 0);
+        let _: Cancellable<Vec<u32>> = on_trigger(trigger, || {
+            Ok(generator.blocking_iter(CancelPolicy::Panic).collect())
+        });
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{Completable, Computable, Generatable, Incomplete, StatefulRef};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// A bounded FIFO buffer of items passed from a producer to a consumer computation
+/// (see [`ProducerConsumer`]).
+///
+/// The buffer is a plain (serializable) value, which is typically stored in the `STATE` of
+/// the consumer. The consumer takes items using [`BoundedBuffer::pop`], which returns
+/// [`Incomplete::Suspended`] while the buffer is empty, so the consumer can simply suspend
+/// using the `?` operator until the producer catches up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundedBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+    closed: bool,
+}
+
+impl<T> BoundedBuffer<T> {
+    /// Create a new empty buffer with the given `capacity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Buffer capacity must be positive.");
+        BoundedBuffer {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+            closed: false,
+        }
+    }
+
+    /// The maximal number of buffered items.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of buffered items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no items are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns `true` if the buffer cannot accept more items.
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= self.capacity
+    }
+
+    /// Returns `true` if the producer finished (no more items will be added).
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Mark the buffer as closed: once the remaining items are consumed,
+    /// [`BoundedBuffer::pop`] returns `Ok(None)`.
+    pub fn close(&mut self) {
+        self.closed = true;
+    }
+
+    /// Add an item to the buffer. Returns the item back if the buffer is full or closed.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.closed || self.is_full() {
+            Err(item)
+        } else {
+            self.items.push_back(item);
+            Ok(())
+        }
+    }
+
+    /// Take the oldest item from the buffer.
+    ///
+    /// Returns [`Incomplete::Suspended`] if the buffer is empty, but not closed,
+    /// and `Ok(None)` once the buffer is empty and closed.
+    pub fn pop(&mut self) -> Completable<Option<T>> {
+        match self.items.pop_front() {
+            Some(item) => Ok(Some(item)),
+            None if self.closed => Ok(None),
+            None => Err(Incomplete::Suspended),
+        }
+    }
+}
+
+impl<T> AsMut<BoundedBuffer<T>> for BoundedBuffer<T> {
+    fn as_mut(&mut self) -> &mut BoundedBuffer<T> {
+        self
+    }
+}
+
+/// A [`Computable`] connecting a producer [`Generatable`] (e.g., a [`crate::GenAlgorithm`])
+/// with a consumer [`crate::Algorithm`] through a [`BoundedBuffer`] stored in the `STATE`
+/// of the consumer.
+///
+/// Every step advances either the producer or the consumer (alternating between them
+/// when both can make progress). The producer is suspended (i.e., not advanced) while
+/// the buffer is full. The consumer suspends itself when the buffer is empty (see
+/// [`BoundedBuffer::pop`]), hence it is only advanced once the producer added an item
+/// or finished. Once the producer is exhausted, the buffer is closed. The output
+/// of the consumer is the output of the whole assembly.
+///
+/// Since the buffer is a part of the consumer state, the whole assembly can be serialized
+/// at any suspend point (if both computations are serializable).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     BoundedBuffer, Completable, Computation, ComputationStep, Generator, GeneratorStep,
+///     Incomplete, ProducerConsumer,
+/// };
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         Ok((*current <= *max).then_some(*current))
+///     }
+/// }
+///
+/// struct SumState {
+///     buffer: BoundedBuffer<u32>,
+///     sum: u32,
+/// }
+///
+/// impl AsMut<BoundedBuffer<u32>> for SumState {
+///     fn as_mut(&mut self) -> &mut BoundedBuffer<u32> {
+///         &mut self.buffer
+///     }
+/// }
+///
+/// struct SumStep;
+///
+/// impl ComputationStep<(), SumState, u32> for SumStep {
+///     fn step(_context: &(), state: &mut SumState) -> Completable<u32> {
+///         match state.buffer.pop()? {
+///             Some(item) => {
+///                 state.sum += item;
+///                 Err(Incomplete::Suspended)
+///             }
+///             None => Ok(state.sum),
+///         }
+///     }
+/// }
+///
+/// let producer = Generator::<u32, u32, u32, RangeStep>::from_parts(10, 0);
+/// let state = SumState { buffer: BoundedBuffer::new(2), sum: 0 };
+/// let consumer = Computation::<(), SumState, u32, SumStep>::from_parts((), state);
+/// let mut assembly = ProducerConsumer::new(producer, consumer);
+/// assert_eq!(assembly.compute().unwrap(), 55);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "P: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct ProducerConsumer<T, OUTPUT, CONTEXT, STATE, P, C>
+where
+    STATE: AsMut<BoundedBuffer<T>>,
+    P: Generatable<T>,
+    C: Computable<OUTPUT> + StatefulRef<CONTEXT, STATE>,
+{
+    producer: P,
+    consumer: C,
+    /// Set if the producer should be advanced by the next step (if possible).
+    produce_next: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<(T, OUTPUT, CONTEXT, STATE)>,
+}
+
+impl<T, OUTPUT, CONTEXT, STATE, P, C> ProducerConsumer<T, OUTPUT, CONTEXT, STATE, P, C>
+where
+    STATE: AsMut<BoundedBuffer<T>>,
+    P: Generatable<T>,
+    C: Computable<OUTPUT> + StatefulRef<CONTEXT, STATE>,
+{
+    /// Connect the `producer` with the `consumer` (which stores the [`BoundedBuffer`]
+    /// in its state).
+    pub fn new(producer: P, consumer: C) -> Self {
+        ProducerConsumer {
+            producer,
+            consumer,
+            produce_next: true,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Access to the producer.
+    pub fn producer(&self) -> &P {
+        &self.producer
+    }
+
+    /// Access to the consumer.
+    pub fn consumer(&self) -> &C {
+        &self.consumer
+    }
+
+    /// Mutable access to the buffer connecting the two computations.
+    pub fn buffer_mut(&mut self) -> &mut BoundedBuffer<T> {
+        self.consumer.state_mut().as_mut()
+    }
+
+    /// Unwrap the producer and the consumer.
+    pub fn into_parts(self) -> (P, C) {
+        (self.producer, self.consumer)
+    }
+}
+
+impl<T, OUTPUT, CONTEXT, STATE, P, C> Computable<OUTPUT>
+    for ProducerConsumer<T, OUTPUT, CONTEXT, STATE, P, C>
+where
+    STATE: AsMut<BoundedBuffer<T>>,
+    P: Generatable<T>,
+    C: Computable<OUTPUT> + StatefulRef<CONTEXT, STATE>,
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        let produce_next = self.produce_next;
+        let buffer = self.buffer_mut();
+        let can_produce = !buffer.is_closed() && !buffer.is_full();
+        let produce = can_produce && (produce_next || buffer.is_empty());
+        if !produce {
+            self.produce_next = true;
+            return self.consumer.try_compute();
+        }
+        self.produce_next = false;
+        match self.producer.try_next() {
+            None => {
+                self.buffer_mut().close();
+                Err(Incomplete::Suspended)
+            }
+            Some(Ok(item)) => {
+                if self.buffer_mut().push(item).is_err() {
+                    unreachable!("The buffer is not full.");
+                }
+                Err(Incomplete::Suspended)
+            }
+            Some(Err(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, FromParts, StatefulRef, test_fixtures::Items};
+
+    #[test]
+    fn test_buffer() {
+        let mut buffer = BoundedBuffer::new(2);
+        assert_eq!(buffer.capacity(), 2);
+        assert_eq!(buffer.pop(), Err(Incomplete::Suspended));
+        assert_eq!(buffer.push(1), Ok(()));
+        assert_eq!(buffer.push(2), Ok(()));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(3), Err(3));
+        assert_eq!(buffer.pop(), Ok(Some(1)));
+        buffer.close();
+        assert_eq!(buffer.push(3), Err(3));
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.pop(), Ok(Some(2)));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), Ok(None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Buffer capacity must be positive.")]
+    fn test_zero_capacity() {
+        BoundedBuffer::<u32>::new(0);
+    }
+
+    /// Consumes all items and produces the capacity of the buffer.
+    struct CollectStep;
+
+    impl ComputationStep<(), BoundedBuffer<i32>, usize> for CollectStep {
+        fn step(_context: &(), buffer: &mut BoundedBuffer<i32>) -> Completable<usize> {
+            match buffer.pop()? {
+                Some(_) => Err(Incomplete::Suspended),
+                None => Ok(buffer.capacity()),
+            }
+        }
+    }
+
+    type Collect = Computation<(), BoundedBuffer<i32>, usize, CollectStep>;
+
+    #[test]
+    fn test_producer_waits_for_consumer() {
+        let producer = Items::from_parts(vec![1, 2, 0, 3], 0);
+        let consumer = Collect::from_parts((), BoundedBuffer::new(1));
+        let mut assembly = ProducerConsumer::new(producer, consumer);
+        assert_eq!(assembly.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(assembly.buffer_mut().len(), 1);
+        // The buffer is full, hence the consumer is advanced.
+        assert_eq!(assembly.try_compute(), Err(Incomplete::Suspended));
+        assert!(assembly.buffer_mut().is_empty());
+        assert_eq!(*assembly.producer().state(), 1);
+        // Producer: 2, consumer: 2, producer: suspension.
+        for _ in 0..3 {
+            assert_eq!(assembly.try_compute(), Err(Incomplete::Suspended));
+        }
+        // The buffer is empty, hence the producer is advanced again.
+        assert_eq!(assembly.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*assembly.producer().state(), 4);
+        assert_eq!(assembly.buffer_mut().len(), 1);
+        assert_eq!(assembly.compute(), Ok(1));
+        let (_, consumer) = assembly.into_parts();
+        assert!(consumer.state().is_closed());
+    }
+}
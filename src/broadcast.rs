@@ -0,0 +1,245 @@
+use crate::{Completable, Computable, DynComputable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Internal state shared between a [`Broadcast`] and its [`BroadcastReceiver`]s.
+#[derive(Debug)]
+struct BroadcastShared<T> {
+    queues: Vec<VecDeque<T>>,
+    closed: Vec<bool>,
+    exhausted: bool,
+}
+
+/// A [`Computable`] that broadcasts the items of one [`Generatable`] to several consumers.
+///
+/// Each consumer is a [`Computable`] built on top of a [`BroadcastReceiver`], which is
+/// a [`Generatable`] that receives a clone of every item produced by the source generator.
+/// This way, several aggregates (e.g., a [`crate::Collector`] and a [`crate::Folder`]) can be
+/// computed in a single pass over the source.
+///
+/// Every call to [`Computable::try_compute`] pulls at most one item from the source and then
+/// advances each unfinished consumer once, such that all consumers proceed in lockstep.
+/// Once all consumers complete, their results are returned in the order of registration.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Broadcast, Collector, Completable, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(4, 0);
+/// let mut broadcast = Broadcast::new(generator);
+/// broadcast.add_consumer(|items| items.folder(0, |acc, x| acc + x));
+/// broadcast.add_consumer(|items| items.folder(0, |acc, x| acc.max(x)));
+/// assert_eq!(broadcast.compute().unwrap(), vec![10, 4]);
+/// ```
+pub struct Broadcast<T, OUTPUT, G = DynGeneratable<T>>
+where
+    G: Generatable<T>,
+{
+    generator: G,
+    shared: Rc<RefCell<BroadcastShared<T>>>,
+    consumers: Vec<DynComputable<OUTPUT>>,
+    results: Vec<Option<OUTPUT>>,
+    finished: bool,
+}
+
+impl<T: Clone, OUTPUT, G: Generatable<T>> Broadcast<T, OUTPUT, G> {
+    /// Create a new [`Broadcast`] of the items produced by `generator`.
+    pub fn new(generator: G) -> Self {
+        Broadcast {
+            generator,
+            shared: Rc::new(RefCell::new(BroadcastShared {
+                queues: Vec::new(),
+                closed: Vec::new(),
+                exhausted: false,
+            })),
+            consumers: Vec::new(),
+            results: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Register a new consumer which is created by `build` from a fresh [`BroadcastReceiver`].
+    ///
+    /// Returns the index of the consumer's result in the output vector. Consumers should be
+    /// registered before the broadcast is first advanced, otherwise they miss the items that
+    /// were already produced.
+    pub fn add_consumer<C, F>(&mut self, build: F) -> usize
+    where
+        C: Computable<OUTPUT> + 'static,
+        F: FnOnce(BroadcastReceiver<T>) -> C,
+    {
+        let index = {
+            let mut shared = self.shared.borrow_mut();
+            shared.queues.push(VecDeque::new());
+            shared.closed.push(false);
+            shared.queues.len() - 1
+        };
+        let receiver = BroadcastReceiver {
+            shared: self.shared.clone(),
+            index,
+        };
+        self.consumers.push(Box::new(build(receiver)));
+        self.results.push(None);
+        index
+    }
+
+    /// The number of registered consumers.
+    pub fn consumer_count(&self) -> usize {
+        self.consumers.len()
+    }
+}
+
+impl<T: Clone, OUTPUT, G: Generatable<T>> Computable<Vec<OUTPUT>> for Broadcast<T, OUTPUT, G> {
+    fn try_compute(&mut self) -> Completable<Vec<OUTPUT>> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        if !self.shared.borrow().exhausted {
+            match self.generator.try_next() {
+                Some(Ok(item)) => {
+                    let mut shared = self.shared.borrow_mut();
+                    let shared = &mut *shared;
+                    for (queue, closed) in shared.queues.iter_mut().zip(&shared.closed) {
+                        if !*closed {
+                            queue.push_back(item.clone());
+                        }
+                    }
+                }
+                Some(Err(Incomplete::Exhausted)) | None => {
+                    self.shared.borrow_mut().exhausted = true;
+                }
+                Some(Err(e)) => return Err(e),
+            }
+        }
+
+        for (index, consumer) in self.consumers.iter_mut().enumerate() {
+            if self.results[index].is_some() {
+                continue;
+            }
+            match consumer.try_compute() {
+                Ok(value) => {
+                    self.results[index] = Some(value);
+                    let mut shared = self.shared.borrow_mut();
+                    shared.closed[index] = true;
+                    shared.queues[index].clear();
+                }
+                Err(Incomplete::Suspended) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let done = if self.consumers.is_empty() {
+            self.shared.borrow().exhausted
+        } else {
+            self.results.iter().all(|it| it.is_some())
+        };
+        if done {
+            self.finished = true;
+            self.consumers.clear();
+            Ok(self.results.drain(..).flatten().collect())
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+/// A [`Generatable`] that receives the items of a [`Broadcast`].
+///
+/// When no item is available yet, the receiver returns [`Incomplete::Suspended`].
+/// Once the source generator of the [`Broadcast`] is exhausted and all items are
+/// consumed, the receiver is exhausted as well.
+#[derive(Debug)]
+pub struct BroadcastReceiver<T> {
+    shared: Rc<RefCell<BroadcastShared<T>>>,
+    index: usize,
+}
+
+impl<T> Iterator for BroadcastReceiver<T> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The receiver is driven by its broadcast; if nothing is queued, the iterator
+        // cannot make progress on its own.
+        let mut shared = self.shared.borrow_mut();
+        shared.queues[self.index].pop_front().map(Ok)
+    }
+}
+
+impl<T> Generatable<T> for BroadcastReceiver<T> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let mut shared = self.shared.borrow_mut();
+        if let Some(item) = shared.queues[self.index].pop_front() {
+            Some(Ok(item))
+        } else if shared.exhausted {
+            None
+        } else {
+            Some(Err(Incomplete::Suspended))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collector, FromParts, GeneratableExt, OverflowPolicy, test_fixtures::Items};
+
+    #[test]
+    fn test_broadcast_collectors() {
+        let generator = Items::from_parts(vec![3, 1, 2], 0);
+        let mut broadcast = Broadcast::new(generator);
+        broadcast.add_consumer(Collector::<i32, Vec<i32>, _>::new);
+        broadcast.add_consumer(|items| {
+            Collector::<i32, Vec<i32>, _>::bounded(items, 1, OverflowPolicy::Truncate)
+        });
+        assert_eq!(broadcast.consumer_count(), 2);
+        let result = broadcast.compute().unwrap();
+        assert_eq!(result, vec![vec![3, 1, 2], vec![3]]);
+        assert_eq!(broadcast.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_broadcast_lockstep() {
+        let generator = Items::from_parts(vec![1, 2], 0);
+        let mut broadcast = Broadcast::new(generator);
+        broadcast.add_consumer(|items| items.folder(0, |acc, x| acc + x));
+        broadcast.add_consumer(|items| items.folder(1, |acc, x| acc * x));
+        assert_eq!(broadcast.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(broadcast.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(broadcast.try_compute(), Ok(vec![3, 2]));
+    }
+
+    #[test]
+    fn test_broadcast_no_consumers() {
+        let generator = Items::from_parts(vec![1], 0);
+        let mut broadcast: Broadcast<i32, i32, _> = Broadcast::new(generator);
+        assert_eq!(broadcast.compute().unwrap(), Vec::<i32>::new());
+        assert_eq!(broadcast.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_broadcast_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let generator = Items::from_parts(vec![1, 2], 0);
+        let mut broadcast = Broadcast::new(generator);
+        broadcast.add_consumer(|items| items.folder(0, |acc, x| acc + x));
+        let result = on_trigger(trigger, || broadcast.compute());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable};
+use cancel_this::Cancellable;
+use std::collections::VecDeque;
+
+/// A [`Generatable`] adapter that remembers the last `capacity` items of another
+/// [`Generatable`] and can re-emit them (see [`Buffered::rewind`]).
+///
+/// The adapter behaves like a cursor over the produced items: [`Buffered::recent`]
+/// returns the remembered items before the cursor, and [`Buffered::rewind`] moves the
+/// cursor back, such that the following calls to [`Generatable::try_next`] produce the same
+/// items again before the inner generator is advanced further. Suspensions and cancellation
+/// of the inner generator are passed through.
+///
+/// See also [`crate::GeneratableExt::buffered`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Buffered, Completable, Generator, GeneratorStep};
+///
+/// struct CountStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for CountStep {
+///     fn step(max: &u32, count: &mut u32) -> Completable<Option<u32>> {
+///         *count += 1;
+///         Ok((*count <= *max).then_some(*count))
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, CountStep>::from_parts(5, 0);
+/// let mut buffered = generator.buffered(2);
+/// assert_eq!(buffered.try_next(), Some(Ok(1)));
+/// assert_eq!(buffered.try_next(), Some(Ok(2)));
+/// assert_eq!(buffered.try_next(), Some(Ok(3)));
+/// assert_eq!(buffered.recent().copied().collect::<Vec<_>>(), vec![2, 3]);
+/// // Only the last two items can be replayed.
+/// assert_eq!(buffered.rewind(5), 2);
+/// let items = buffered.map(|it| it.unwrap()).collect::<Vec<_>>();
+/// assert_eq!(items, vec![2, 3, 4, 5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Buffered<ITEM, G = DynGeneratable<ITEM>>
+where
+    ITEM: Clone,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    capacity: usize,
+    /// The last `capacity` items produced by the inner generator.
+    history: VecDeque<ITEM>,
+    /// The number of items at the end of the history that are waiting to be re-emitted.
+    replay: usize,
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Buffered<ITEM, G> {
+    /// Create a new [`Buffered`] adapter that remembers the last `capacity` items
+    /// of the given generator.
+    pub fn new(generator: G, capacity: usize) -> Self {
+        Buffered {
+            generator,
+            capacity,
+            history: VecDeque::with_capacity(capacity),
+            replay: 0,
+        }
+    }
+
+    /// The maximal number of remembered items.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The remembered items before the current position (oldest first).
+    pub fn recent(&self) -> impl DoubleEndedIterator<Item = &ITEM> + ExactSizeIterator {
+        self.history.range(..self.history.len() - self.replay)
+    }
+
+    /// The number of items that will be re-emitted before the inner generator is advanced.
+    pub fn replaying(&self) -> usize {
+        self.replay
+    }
+
+    /// Move back by `count` items, such that they are produced again. The position can be
+    /// moved back by at most the number of [`Buffered::recent`] items. Returns the number of
+    /// items by which the position actually moved.
+    pub fn rewind(&mut self, count: usize) -> usize {
+        let count = count.min(self.history.len() - self.replay);
+        self.replay += count;
+        count
+    }
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Iterator for Buffered<ITEM, G> {
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Generatable<ITEM> for Buffered<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        if self.replay > 0 {
+            let item = self.history[self.history.len() - self.replay].clone();
+            self.replay -= 1;
+            return Some(Ok(item));
+        }
+        match self.generator.try_next()? {
+            Ok(item) => {
+                if self.capacity > 0 {
+                    if self.history.len() == self.capacity {
+                        self.history.pop_front();
+                    }
+                    self.history.push_back(item.clone());
+                }
+                Some(Ok(item))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GeneratableExt, Incomplete, test_fixtures::Items};
+
+    #[test]
+    fn test_rewind_partially() {
+        let mut buffered = Buffered::new(Items::from_parts(vec![1, 0, 2, 3], 0), 3);
+        assert_eq!(buffered.try_next(), Some(Ok(1)));
+        assert_eq!(buffered.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(buffered.try_next(), Some(Ok(2)));
+        assert_eq!(buffered.rewind(1), 1);
+        assert_eq!(buffered.replaying(), 1);
+        assert_eq!(buffered.recent().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(buffered.rewind(1), 1);
+        assert_eq!(buffered.rewind(1), 0);
+        assert_eq!(buffered.recent().len(), 0);
+        assert_eq!(buffered.try_next(), Some(Ok(1)));
+        assert_eq!(buffered.try_next(), Some(Ok(2)));
+        assert_eq!(buffered.try_next(), Some(Ok(3)));
+        assert_eq!(buffered.try_next(), None);
+        assert_eq!(
+            buffered.recent().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_window_is_bounded() {
+        let mut buffered = Items::from_parts(vec![1, 2, 3, 4], 0).buffered(2);
+        assert_eq!(buffered.capacity(), 2);
+        let items = buffered.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+        assert_eq!(
+            buffered.recent().rev().copied().collect::<Vec<_>>(),
+            vec![4, 3]
+        );
+        assert_eq!(buffered.rewind(3), 2);
+        let items = buffered.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut buffered = Items::from_parts(vec![1], 0).buffered(0);
+        assert_eq!(buffered.try_next(), Some(Ok(1)));
+        assert_eq!(buffered.rewind(1), 0);
+        assert_eq!(buffered.try_next(), None);
+    }
+}
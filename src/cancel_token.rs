@@ -0,0 +1,154 @@
+use cancel_this::CancellationTrigger;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A node of the [`CancelToken`] tree.
+#[derive(Debug, Default)]
+struct TokenNode {
+    cancelled: AtomicBool,
+    parent: Option<Arc<TokenNode>>,
+}
+
+/// A hierarchical cancellation token: cancelling a token also cancels all of its
+/// descendants (created using [`CancelToken::child`]), but not its ancestors or siblings.
+///
+/// Clones of a token refer to the same node of the tree. Creating a child token is cheap
+/// (a single allocation), so computations can create child tokens at suspend points (e.g.,
+/// for nested or spawned computations). The token is a `cancel-this`
+/// [`CancellationTrigger`], so it can be attached to a computation using
+/// [`crate::Computation::with_cancel_token`], or used with `cancel_this::on_trigger`.
+///
+/// See also [`crate::Scheduler::cancel_token`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::CancelToken;
+///
+/// let root = CancelToken::new();
+/// let child = root.child();
+/// let grandchild = child.child();
+/// let sibling = root.child();
+/// child.cancel();
+/// assert!(child.is_cancelled() && grandchild.is_cancelled());
+/// assert!(!root.is_cancelled() && !sibling.is_cancelled());
+/// root.cancel();
+/// assert!(sibling.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<TokenNode>);
+
+impl CancelToken {
+    /// Create a new root token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new token that is cancelled whenever this token is cancelled.
+    pub fn child(&self) -> CancelToken {
+        CancelToken(Arc::new(TokenNode {
+            cancelled: AtomicBool::new(false),
+            parent: Some(self.0.clone()),
+        }))
+    }
+
+    /// The parent of this token (if any).
+    pub fn parent(&self) -> Option<CancelToken> {
+        self.0.parent.clone().map(CancelToken)
+    }
+
+    /// The number of ancestors of this token (zero for a root token).
+    pub fn depth(&self) -> usize {
+        self.ancestors().count() - 1
+    }
+
+    /// Cancel this token and all of its descendants. Can be safely called multiple times.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if this token or any of its ancestors was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.ancestors()
+            .any(|node| node.cancelled.load(Ordering::SeqCst))
+    }
+
+    /// Returns `true` if both tokens refer to the same node of the tree.
+    pub fn ptr_eq(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Iterate over the nodes from this token to the root.
+    fn ancestors(&self) -> impl Iterator<Item = &TokenNode> {
+        std::iter::successors(Some(self.0.as_ref()), |node| node.parent.as_deref())
+    }
+}
+
+impl CancellationTrigger for CancelToken {
+    fn is_cancelled(&self) -> bool {
+        CancelToken::is_cancelled(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        "CancelToken"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Completable, Computable, Computation, ComputationStep, FromParts, Incomplete};
+    use cancel_this::{Cancelled, on_trigger};
+
+    #[test]
+    fn test_token_tree() {
+        let root = CancelToken::new();
+        let child = root.child();
+        let grandchild = child.child();
+        assert_eq!(root.depth(), 0);
+        assert_eq!(grandchild.depth(), 2);
+        assert!(root.parent().is_none());
+        assert!(grandchild.parent().unwrap().ptr_eq(&child));
+        assert!(!child.ptr_eq(&root.child()));
+        assert!(child.ptr_eq(&child.clone()));
+
+        grandchild.cancel();
+        assert!(grandchild.is_cancelled());
+        assert!(!child.is_cancelled());
+        // Children created after cancellation are cancelled as well.
+        root.clone().cancel();
+        assert!(child.is_cancelled() && child.child().is_cancelled());
+    }
+
+    struct CountStep;
+
+    impl ComputationStep<(), u32, u32> for CountStep {
+        fn step(_context: &(), count: &mut u32) -> Completable<u32> {
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    #[test]
+    fn test_nested_computations() {
+        let root = CancelToken::new();
+        let mut outer = Computation::<(), u32, u32, CountStep>::from_parts((), 0)
+            .with_cancel_token(root.clone());
+        let mut inner = Computation::<(), u32, u32, CountStep>::from_parts((), 0)
+            .with_cancel_token(root.child());
+        assert_eq!(outer.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(inner.try_compute(), Err(Incomplete::Suspended));
+        root.cancel();
+        assert!(matches!(outer.try_compute(), Err(Incomplete::Cancelled(_))));
+        assert!(matches!(inner.try_compute(), Err(Incomplete::Cancelled(_))));
+    }
+
+    #[test]
+    fn test_thread_local_trigger() {
+        let root = CancelToken::new();
+        let child = root.child();
+        root.cancel();
+        let result = on_trigger(child, || cancel_this::is_cancelled!());
+        assert_eq!(result, Err(Cancelled::new("CancelToken")));
+    }
+}
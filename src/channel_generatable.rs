@@ -0,0 +1,118 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError, channel};
+
+/// A [`Generatable`] that produces the items received through a [`std::sync::mpsc`] channel.
+///
+/// [`Generatable::try_next`] never blocks: if no item is available, it returns
+/// [`Incomplete::Suspended`], and once all senders are dropped (and all items are received),
+/// the generator is exhausted. This way, data arriving from other threads can feed
+/// suspendable pipelines.
+///
+/// Keep in mind that blocking iteration repeatedly polls the channel while waiting for data.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ChannelGeneratable, Incomplete};
+///
+/// let (sender, mut generator) = ChannelGeneratable::channel();
+/// assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+/// let producer = std::thread::spawn(move || {
+///     for i in 0..3 {
+///         sender.send(i).unwrap();
+///     }
+/// });
+/// producer.join().unwrap();
+/// let items = generator.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(items, vec![0, 1, 2]);
+/// ```
+#[derive(Debug)]
+pub struct ChannelGeneratable<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T> ChannelGeneratable<T> {
+    /// Create a new [`ChannelGeneratable`] producing the items received by `receiver`.
+    pub fn new(receiver: Receiver<T>) -> Self {
+        ChannelGeneratable { receiver }
+    }
+
+    /// Create a new channel and return its sender together with a [`ChannelGeneratable`]
+    /// producing the items sent through it.
+    pub fn channel() -> (Sender<T>, Self) {
+        let (sender, receiver) = channel();
+        (sender, ChannelGeneratable::new(receiver))
+    }
+
+    /// Access to the underlying receiver.
+    pub fn receiver(&self) -> &Receiver<T> {
+        &self.receiver
+    }
+
+    /// Unwrap the underlying receiver.
+    pub fn into_inner(self) -> Receiver<T> {
+        self.receiver
+    }
+}
+
+impl<T> From<Receiver<T>> for ChannelGeneratable<T> {
+    fn from(value: Receiver<T>) -> Self {
+        ChannelGeneratable::new(value)
+    }
+}
+
+impl<T> Iterator for ChannelGeneratable<T> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T> Generatable<T> for ChannelGeneratable<T> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        match self.receiver.try_recv() {
+            Ok(item) => Some(Ok(item)),
+            Err(TryRecvError::Empty) => Some(Err(Incomplete::Suspended)),
+            Err(TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, GeneratableExt};
+    use std::sync::mpsc::sync_channel;
+
+    #[test]
+    fn test_empty_and_disconnected() {
+        let (sender, receiver) = channel();
+        let mut generator = ChannelGeneratable::from(receiver);
+        assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+        sender.send("a").unwrap();
+        sender.send("b").unwrap();
+        assert_eq!(generator.try_next(), Some(Ok("a")));
+        drop(sender);
+        // Buffered items are still produced after the sender is dropped.
+        assert_eq!(generator.try_next(), Some(Ok("b")));
+        assert_eq!(generator.try_next(), None);
+        assert_eq!(generator.try_next(), None);
+    }
+
+    #[test]
+    fn test_pipeline_across_threads() {
+        let (sender, receiver) = sync_channel(2);
+        let producer = std::thread::spawn(move || {
+            for i in 1..=10u32 {
+                sender.send(i).unwrap();
+            }
+        });
+        let mut sum = ChannelGeneratable::new(receiver).folder(0, |acc, item| acc + item);
+        assert_eq!(sum.compute(), Ok(55));
+        producer.join().unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Completable, Incomplete, Sink};
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+
+/// A [`Sink`] backed by a bounded [`std::sync::mpsc`] channel.
+///
+/// If the channel is full, the item is kept by the sink and [`Incomplete::Suspended`] is
+/// returned (i.e., the sink never blocks the thread). Once the receiver is dropped,
+/// the sink returns [`Incomplete::Exhausted`] and the undelivered items are discarded.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{ChannelSink, Incomplete, Sink};
+///
+/// let (mut sink, receiver) = ChannelSink::channel(1);
+/// assert_eq!(sink.try_send(1), Ok(()));
+/// // The channel is full, the item is buffered by the sink.
+/// assert_eq!(sink.try_send(2), Err(Incomplete::Suspended));
+/// assert_eq!(sink.try_flush(), Err(Incomplete::Suspended));
+/// assert_eq!(receiver.recv(), Ok(1));
+/// assert_eq!(sink.try_flush(), Ok(()));
+/// assert_eq!(receiver.recv(), Ok(2));
+/// ```
+#[derive(Debug)]
+pub struct ChannelSink<T> {
+    sender: SyncSender<T>,
+    pending: VecDeque<T>,
+}
+
+impl<T> ChannelSink<T> {
+    /// Create a new [`ChannelSink`] sending items through `sender`.
+    pub fn new(sender: SyncSender<T>) -> Self {
+        ChannelSink {
+            sender,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Create a new channel with the given `bound` and return a [`ChannelSink`] sending
+    /// into it together with its receiver (see also [`crate::ChannelGeneratable`]).
+    pub fn channel(bound: usize) -> (Self, Receiver<T>) {
+        let (sender, receiver) = sync_channel(bound);
+        (ChannelSink::new(sender), receiver)
+    }
+
+    /// The number of items buffered by the sink (not yet delivered to the channel).
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Unwrap the underlying sender, discarding the undelivered items.
+    pub fn into_inner(self) -> SyncSender<T> {
+        self.sender
+    }
+}
+
+impl<T> From<SyncSender<T>> for ChannelSink<T> {
+    fn from(value: SyncSender<T>) -> Self {
+        ChannelSink::new(value)
+    }
+}
+
+impl<T> Sink<T> for ChannelSink<T> {
+    fn try_send(&mut self, item: T) -> Completable<()> {
+        self.pending.push_back(item);
+        self.try_flush()
+    }
+
+    fn try_flush(&mut self) -> Completable<()> {
+        while let Some(item) = self.pending.pop_front() {
+            match self.sender.try_send(item) {
+                Ok(()) => continue,
+                Err(TrySendError::Full(item)) => {
+                    self.pending.push_front(item);
+                    return Err(Incomplete::Suspended);
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    self.pending.clear();
+                    return Err(Incomplete::Exhausted);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backpressure() {
+        let (sender, receiver) = sync_channel(2);
+        let mut sink = ChannelSink::from(sender);
+        assert_eq!(sink.try_send(1), Ok(()));
+        assert_eq!(sink.try_send(2), Ok(()));
+        assert_eq!(sink.try_send(3), Err(Incomplete::Suspended));
+        // Ignoring the backpressure only grows the buffer of the sink.
+        assert_eq!(sink.try_send(4), Err(Incomplete::Suspended));
+        assert_eq!(sink.pending(), 2);
+        assert_eq!(receiver.try_recv(), Ok(1));
+        assert_eq!(sink.try_flush(), Err(Incomplete::Suspended));
+        assert_eq!(sink.pending(), 1);
+        let items = [receiver.recv(), receiver.recv()];
+        assert_eq!(items, [Ok(2), Ok(3)]);
+        assert_eq!(sink.try_flush(), Ok(()));
+        assert_eq!(receiver.recv(), Ok(4));
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let (mut sink, receiver) = ChannelSink::channel(0);
+        assert_eq!(sink.try_send(1), Err(Incomplete::Suspended));
+        drop(receiver);
+        assert_eq!(sink.try_flush(), Err(Incomplete::Exhausted));
+        assert_eq!(sink.pending(), 0);
+        assert_eq!(sink.try_send(2), Err(Incomplete::Exhausted));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Completable, Computable, StatefulRef};
+use std::marker::PhantomData;
+
+/// A [`Computable`] wrapper that verifies an invariant of the `CONTEXT` and `STATE`
+/// of another computation after every step.
+///
+/// The invariant is only checked in debug builds (with `debug_assertions`) or when
+/// the `debug-invariants` feature is enabled (see [`CheckedComputation::is_enabled`]).
+/// Otherwise, the wrapper only forwards the calls to the inner computation.
+///
+/// # Panics
+///
+/// The wrapper fails fast: once the invariant is violated, [`Computable::try_compute`]
+/// panics with the message returned by the check, as well as the number of the step that
+/// violated it and its outcome.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{CheckedComputation, Completable, Computation, ComputationStep, Incomplete};
+///
+/// /// Moves items from the context into the state, one per step.
+/// struct CopyStep;
+///
+/// impl ComputationStep<Vec<u32>, Vec<u32>, usize> for CopyStep {
+///     fn step(items: &Vec<u32>, copied: &mut Vec<u32>) -> Completable<usize> {
+///         match items.get(copied.len()) {
+///             None => Ok(copied.len()),
+///             Some(item) => {
+///                 copied.push(*item);
+///                 Err(Incomplete::Suspended)
+///             }
+///         }
+///     }
+/// }
+///
+/// fn is_prefix(items: &Vec<u32>, copied: &Vec<u32>) -> Result<(), String> {
+///     if items.starts_with(copied) {
+///         Ok(())
+///     } else {
+///         Err(format!("{copied:?} is not a prefix of {items:?}"))
+///     }
+/// }
+///
+/// let computation = Computation::<Vec<u32>, Vec<u32>, usize, CopyStep>::from_parts(vec![1, 2, 3], Vec::new());
+/// let mut checked = CheckedComputation::new(computation, is_prefix);
+/// assert_eq!(checked.compute().unwrap(), 3);
+/// assert_eq!(checked.steps(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CheckedComputation<CONTEXT, STATE, OUTPUT, C> {
+    inner: C,
+    check: fn(&CONTEXT, &STATE) -> Result<(), String>,
+    steps: u64,
+    _phantom: PhantomData<OUTPUT>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, C: StatefulRef<CONTEXT, STATE>>
+    CheckedComputation<CONTEXT, STATE, OUTPUT, C>
+{
+    /// Wrap the `inner` computation such that `check` is called after each of its steps.
+    pub fn new(inner: C, check: fn(&CONTEXT, &STATE) -> Result<(), String>) -> Self {
+        CheckedComputation {
+            inner,
+            check,
+            steps: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the invariants are checked in this build.
+    pub const fn is_enabled() -> bool {
+        cfg!(any(debug_assertions, feature = "debug-invariants"))
+    }
+
+    /// The number of steps of the inner computation performed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, C> Computable<OUTPUT> for CheckedComputation<CONTEXT, STATE, OUTPUT, C>
+where
+    C: Computable<OUTPUT> + StatefulRef<CONTEXT, STATE>,
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        let result = self.inner.try_compute();
+        self.steps += 1;
+        if Self::is_enabled()
+            && let Err(message) = (self.check)(self.inner.context(), self.inner.state())
+        {
+            let outcome = match &result {
+                Ok(_) => "completed".to_string(),
+                Err(e) => format!("{e:?}"),
+            };
+            panic!(
+                "Invariant violated after step {} ({}): {}",
+                self.steps, outcome, message
+            );
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, FromParts, Incomplete, StatefulRef};
+
+    /// Counts down to zero, but skips from 3 directly to 0 if the context says so.
+    struct CountdownStep;
+
+    impl ComputationStep<bool, i32, i32> for CountdownStep {
+        fn step(broken: &bool, value: &mut i32) -> Completable<i32> {
+            if *value == 0 {
+                return Ok(0);
+            }
+            *value -= if *broken && *value == 3 { 4 } else { 1 };
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Countdown = Computation<bool, i32, i32, CountdownStep>;
+
+    fn non_negative(_: &bool, value: &i32) -> Result<(), String> {
+        if *value >= 0 {
+            Ok(())
+        } else {
+            Err(format!("value {value} is negative"))
+        }
+    }
+
+    #[test]
+    fn test_valid_computation() {
+        let mut checked = CheckedComputation::new(Countdown::from_parts(false, 5), non_negative);
+        assert_eq!(checked.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(checked.inner().state(), &4);
+        assert_eq!(checked.compute(), Ok(0));
+        assert_eq!(checked.steps(), 6);
+        assert_eq!(checked.into_inner().state(), &0);
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, feature = "debug-invariants"))]
+    #[should_panic(expected = "Invariant violated after step 3 (Suspended): value -1 is negative")]
+    fn test_violated_invariant() {
+        assert!(CheckedComputation::<bool, i32, i32, Countdown>::is_enabled());
+        let mut checked = CheckedComputation::new(Countdown::from_parts(true, 5), non_negative);
+        let _ = checked.compute();
+    }
+}
@@ -0,0 +1,148 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+
+/// A [`Generatable`] that groups the items of another [`Generatable`] into fixed-size chunks.
+///
+/// Each produced chunk contains exactly `chunk_size` items, except for the last chunk,
+/// which contains the remaining items (if any). The adapter consumes one item per
+/// [`Generatable::try_next`] call and returns [`Incomplete::Suspended`] until a chunk
+/// is complete, hence it remains suspendable even for large chunk sizes.
+///
+/// See also [`crate::GeneratableExt::chunks`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ChunkingCollector, Completable, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(5, 0);
+/// let chunks: Vec<Vec<u32>> = generator.chunks(2).map(|it| it.unwrap()).collect();
+/// assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct ChunkingCollector<ITEM, G = DynGeneratable<ITEM>>
+where
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    chunk_size: usize,
+    chunk: Vec<ITEM>,
+    exhausted: bool,
+}
+
+impl<ITEM, G: Generatable<ITEM>> ChunkingCollector<ITEM, G> {
+    /// Create a new chunking adapter producing chunks of `chunk_size` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn new(generator: G, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "`chunk_size` must be positive.");
+        ChunkingCollector {
+            generator,
+            chunk_size,
+            chunk: Vec::with_capacity(chunk_size),
+            exhausted: false,
+        }
+    }
+
+    /// The items of the current (incomplete) chunk.
+    pub fn pending(&self) -> &[ITEM] {
+        &self.chunk
+    }
+}
+
+impl<ITEM, G: Generatable<ITEM>> Iterator for ChunkingCollector<ITEM, G> {
+    type Item = Cancellable<Vec<ITEM>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM, G: Generatable<ITEM>> Generatable<Vec<ITEM>> for ChunkingCollector<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<Vec<ITEM>>> {
+        if self.exhausted {
+            return None;
+        }
+        match self.generator.try_next() {
+            Some(Ok(item)) => {
+                self.chunk.push(item);
+                if self.chunk.len() >= self.chunk_size {
+                    let chunk =
+                        std::mem::replace(&mut self.chunk, Vec::with_capacity(self.chunk_size));
+                    Some(Ok(chunk))
+                } else {
+                    Some(Err(Incomplete::Suspended))
+                }
+            }
+            Some(Err(Incomplete::Exhausted)) | None => {
+                self.exhausted = true;
+                if self.chunk.is_empty() {
+                    None
+                } else {
+                    Some(Ok(std::mem::take(&mut self.chunk)))
+                }
+            }
+            Some(Err(e)) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GeneratableExt, test_fixtures::Items};
+
+    #[test]
+    fn test_chunking_try_next() {
+        let generator = Items::from_parts(vec![1, 2, 3], 0);
+        let mut chunks = ChunkingCollector::new(generator, 2);
+        assert_eq!(chunks.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(chunks.pending(), &[1]);
+        assert_eq!(chunks.try_next(), Some(Ok(vec![1, 2])));
+        assert_eq!(chunks.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(chunks.try_next(), Some(Ok(vec![3])));
+        assert_eq!(chunks.try_next(), None);
+        assert_eq!(chunks.try_next(), None);
+    }
+
+    #[test]
+    fn test_chunking_exact_multiple() {
+        let generator = Items::from_parts(vec![1, 2, 3, 4], 0);
+        let chunks: Vec<_> = generator.chunks(2).collect();
+        assert_eq!(chunks, vec![Ok(vec![1, 2]), Ok(vec![3, 4])]);
+    }
+
+    #[test]
+    fn test_chunking_empty() {
+        let generator = Items::from_parts(vec![], 0);
+        let mut chunks = generator.chunks(3);
+        assert_eq!(chunks.try_next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunking_zero_size() {
+        let generator = Items::from_parts(vec![], 0);
+        let _ = generator.chunks(0);
+    }
+}
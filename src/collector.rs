@@ -1,15 +1,41 @@
-use crate::{Completable, Computable, DynGeneratable, Generatable, Incomplete};
+use crate::{
+    Completable, Computable, DynGeneratable, Generatable, Incomplete, Resource, ResourceExceeded,
+};
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 
+/// Determines what a bounded [`Collector`] does once the generator produces more items
+/// than the configured capacity limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowPolicy {
+    /// Fail with [`Incomplete::ResourceExceeded`] (with [`Resource::Items`]) once
+    /// the generator produces an item beyond the limit. The overflowing item is retained
+    /// (see [`Collector::into_partial`]) and the generator is not polled again.
+    Error,
+    /// Keep only the most recent items, dropping the oldest ones. The generator is
+    /// still fully consumed.
+    DropOldest,
+    /// Keep only the first items and complete as soon as the limit is reached,
+    /// without consuming the rest of the generator.
+    Truncate,
+}
+
 /// A [`Computable`] that collects all items from a [`Generatable`] into a collection.
 ///
 /// This is useful for converting a generator/stream of items into a single collected result.
 /// The collection type must implement [`Default`] and [`Extend`].
 ///
+/// A [`Collector`] over a concrete generator type is most easily created using
+/// [`crate::GeneratableExt::collector`].
+///
+/// To avoid exhausting memory when the generator produces an unexpectedly large number
+/// of items, use [`Collector::bounded`] with an appropriate [`OverflowPolicy`].
+///
 /// # Example
 ///
 /// ```rust
-/// use computation_process::{Generator, GeneratorStep, Completable, Computable, Collector, Stateful, Generatable};
+/// use computation_process::{Generator, GeneratorStep, Completable, Computable, Collector, FromParts, Generatable};
 ///
 /// struct RangeStep;
 ///
@@ -44,6 +70,23 @@ where
 {
     generator: G,
     collector: Option<COLLECTION>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    limit: Option<(usize, OverflowPolicy)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
+    /// The most recent items retained by [`OverflowPolicy::DropOldest`]. These are only
+    /// moved into the collection once the generator is exhausted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            bound = "ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+        )
+    )]
+    recent: VecDeque<ITEM>,
+    /// Set once the limit is exceeded with [`OverflowPolicy::Error`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    overflowed: bool,
     #[cfg_attr(feature = "serde", serde(skip))]
     _phantom: PhantomData<ITEM>,
 }
@@ -58,9 +101,94 @@ where
         Collector {
             generator,
             collector: Some(Default::default()),
+            limit: None,
+            count: 0,
+            recent: VecDeque::new(),
+            overflowed: false,
             _phantom: Default::default(),
         }
     }
+
+    /// Create a new collector for the given generator that collects at most `limit` items.
+    ///
+    /// Once the generator produces more than `limit` items, the collector follows
+    /// the given [`OverflowPolicy`]. Note that the limit applies to the number of
+    /// items produced by the generator, not the size of the resulting collection
+    /// (e.g., a `HashSet` can contain fewer items due to duplicates).
+    pub fn bounded(generator: G, limit: usize, policy: OverflowPolicy) -> Self {
+        let mut collector = Self::new(generator);
+        collector.limit = Some((limit, policy));
+        collector
+    }
+
+    /// A reference to the items collected so far, assuming the collection has not been
+    /// returned yet.
+    ///
+    /// For [`OverflowPolicy::DropOldest`], the retained items are only moved into the
+    /// collection once the generator is exhausted. Use [`Collector::into_partial`]
+    /// to obtain all retained items.
+    pub fn partial(&self) -> Option<&COLLECTION> {
+        self.collector.as_ref()
+    }
+
+    /// Destruct the collector and return the items collected so far, assuming the collection
+    /// has not been returned yet.
+    ///
+    /// This is typically used to salvage partial results once the underlying generator
+    /// is canceled (or overflows with [`OverflowPolicy::Error`], in which case the result
+    /// also contains the overflowing item).
+    pub fn into_partial(mut self) -> Option<COLLECTION> {
+        self.finish().ok()
+    }
+
+    /// Add a single item to the collection, respecting the configured limit.
+    fn push(&mut self, item: ITEM) -> Completable<()> {
+        let Some(collector) = self.collector.as_mut() else {
+            return Err(Incomplete::Exhausted);
+        };
+        match self.limit {
+            None => collector.extend(std::iter::once(item)),
+            Some((limit, OverflowPolicy::DropOldest)) => {
+                self.recent.push_back(item);
+                if self.recent.len() > limit {
+                    self.recent.pop_front();
+                }
+            }
+            Some((limit, OverflowPolicy::Error)) => {
+                if self.count >= limit {
+                    // Keep the item such that it is not lost, but stop polling the generator.
+                    self.recent.push_back(item);
+                    self.overflowed = true;
+                    self.count += 1;
+                    return Err(self.overflow_error(limit));
+                }
+                collector.extend(std::iter::once(item));
+            }
+            Some((limit, OverflowPolicy::Truncate)) => {
+                if self.count < limit {
+                    collector.extend(std::iter::once(item));
+                }
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// The error reported once the limit is exceeded with [`OverflowPolicy::Error`].
+    fn overflow_error(&self, limit: usize) -> Incomplete {
+        Incomplete::ResourceExceeded(ResourceExceeded {
+            resource: Resource::Items,
+            limit: limit as u64,
+            used: self.count as u64,
+        })
+    }
+
+    /// Finalize and return the collection (if not returned already).
+    fn finish(&mut self) -> Completable<COLLECTION> {
+        let mut collector = self.collector.take().ok_or(Incomplete::Exhausted)?;
+        collector.extend(self.recent.drain(..));
+        Ok(collector)
+    }
 }
 
 impl<ITEM, COLLECTION: Default + Extend<ITEM>> From<DynGeneratable<ITEM>>
@@ -77,25 +205,22 @@ where
     G: Generatable<ITEM>,
 {
     fn try_compute(&mut self) -> Completable<COLLECTION> {
-        match self.generator.try_next() {
-            None => {
-                if let Some(collector) = self.collector.take() {
-                    Ok(collector)
-                } else {
-                    Err(Incomplete::Exhausted)
-                }
+        match self.limit {
+            Some((limit, OverflowPolicy::Truncate)) if self.count >= limit => {
+                return self.finish();
+            }
+            Some((limit, OverflowPolicy::Error)) if self.overflowed => {
+                return Err(self.overflow_error(limit));
             }
+            _ => (),
+        }
+        match self.generator.try_next() {
+            None => self.finish(),
             Some(Ok(item)) => {
-                if let Some(collector) = self.collector.as_mut() {
-                    collector.extend(std::iter::once(item));
-                    Err(Incomplete::Suspended)
-                } else {
-                    Err(Incomplete::Exhausted)
-                }
+                self.push(item)?;
+                Err(Incomplete::Suspended)
             }
-            Some(Err(Incomplete::Suspended)) => Err(Incomplete::Suspended),
-            Some(Err(Incomplete::Cancelled(c))) => Err(Incomplete::Cancelled(c)),
-            Some(Err(Incomplete::Exhausted)) => Err(Incomplete::Exhausted),
+            Some(Err(e)) => Err(e),
         }
     }
 }
@@ -103,7 +228,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Computable, Generatable, Incomplete};
+    use crate::{Computable, FromParts, Generatable, Incomplete};
     use cancel_this::Cancellable;
 
     struct TestGenerator {
@@ -137,6 +262,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generatable_collector() {
+        use crate::GeneratableExt;
+        let generator = TestGenerator {
+            items: vec![3, 1, 2, 1],
+            index: 0,
+        };
+        let mut collector = generator.collector::<std::collections::BTreeSet<_>>();
+        assert_eq!(collector.compute(), Ok([1, 2, 3].into()));
+    }
+
     #[test]
     fn test_collector_from() {
         let generator = TestGenerator {
@@ -346,4 +482,117 @@ mod tests {
         let result = collector.try_compute();
         assert!(matches!(result, Err(Incomplete::Cancelled(_))));
     }
+
+    #[test]
+    fn test_bounded_collector_error() {
+        let generator = TestGenerator {
+            items: vec![1, 2, 3, 4],
+            index: 0,
+        };
+        let mut collector =
+            Collector::<i32, Vec<i32>, _>::bounded(generator, 2, OverflowPolicy::Error);
+        let exceeded = Incomplete::ResourceExceeded(ResourceExceeded {
+            resource: Resource::Items,
+            limit: 2,
+            used: 3,
+        });
+        assert_eq!(collector.compute_completable(), Err(exceeded.clone()));
+        // Repeated polls report the same error without draining the generator.
+        assert_eq!(collector.try_compute(), Err(exceeded));
+        assert_eq!(collector.generator.index, 3);
+        // The overflowing item is not lost.
+        assert_eq!(collector.into_partial(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_bounded_collector_within_limit() {
+        let generator = TestGenerator {
+            items: vec![1, 2],
+            index: 0,
+        };
+        let mut collector =
+            Collector::<i32, Vec<i32>, _>::bounded(generator, 2, OverflowPolicy::Error);
+        assert_eq!(collector.compute().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_bounded_collector_drop_oldest() {
+        let generator = TestGenerator {
+            items: vec![1, 2, 3, 4, 5],
+            index: 0,
+        };
+        let mut collector =
+            Collector::<i32, Vec<i32>, _>::bounded(generator, 2, OverflowPolicy::DropOldest);
+        assert_eq!(collector.compute().unwrap(), vec![4, 5]);
+        assert_eq!(collector.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_bounded_collector_truncate() {
+        let generator = TestGenerator {
+            items: vec![1, 2, 3, 4, 5],
+            index: 0,
+        };
+        let mut collector =
+            Collector::<i32, Vec<i32>, _>::bounded(generator, 2, OverflowPolicy::Truncate);
+        assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+        // The limit is reached, so the collector completes without consuming more items.
+        assert_eq!(collector.try_compute(), Ok(vec![1, 2]));
+        assert_eq!(collector.generator.index, 2);
+    }
+
+    #[test]
+    fn test_collector_partial_after_cancellation() {
+        use crate::{Generator, GeneratorStep};
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        struct CountStep;
+
+        impl GeneratorStep<(), u32, u32> for CountStep {
+            fn step(_: &(), state: &mut u32) -> Completable<Option<u32>> {
+                *state += 1;
+                Ok(Some(*state))
+            }
+        }
+
+        let generator = Generator::<(), u32, u32, CountStep>::from_parts((), 0);
+        let mut collector = Collector::<u32, Vec<u32>, _>::new(generator);
+        for _ in 0..3 {
+            assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+        }
+        assert_eq!(collector.partial(), Some(&vec![1, 2, 3]));
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || collector.compute());
+        assert!(result.is_err());
+        assert_eq!(collector.into_partial(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_collector_into_partial_drop_oldest() {
+        let generator = TestGenerator {
+            items: vec![1, 2, 3, 4],
+            index: 0,
+        };
+        let mut collector =
+            Collector::<i32, Vec<i32>, _>::bounded(generator, 2, OverflowPolicy::DropOldest);
+        for _ in 0..3 {
+            assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+        }
+        assert_eq!(collector.into_partial(), Some(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_collector_into_partial_after_completion() {
+        let generator = TestGenerator {
+            items: vec![1],
+            index: 0,
+        };
+        let mut collector: Collector<i32, Vec<i32>> = generator.dyn_generatable().into();
+        assert_eq!(collector.compute().unwrap(), vec![1]);
+        assert_eq!(collector.partial(), None);
+        assert_eq!(collector.into_partial(), None);
+    }
 }
@@ -17,6 +17,63 @@ pub enum Incomplete {
     /// This is returned when a [`crate::Computable`] or [`crate::Generatable`] is polled
     /// after it has already produced its final result.
     Exhausted,
+    /// The computation was stopped because it exceeded a resource limit (e.g., a memory
+    /// budget). The computation can be resumed once the limit is raised.
+    ResourceExceeded(ResourceExceeded),
+}
+
+/// The [`Cancelled::cause`] used when an [`Incomplete::ResourceExceeded`] outcome is reported
+/// by an API that only supports cancellation (e.g., [`crate::Computable::compute`]).
+pub const RESOURCE_EXCEEDED: &str = "ResourceExceeded";
+
+/// A resource that can be limited by a computation wrapper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Resource {
+    /// The (approximate) memory used by the computation state, in bytes.
+    Memory,
+    /// The (wall-clock) time spent in computation steps, in nanoseconds.
+    StepTime,
+    /// The number of items produced by a generator (e.g., collected by a bounded
+    /// [`crate::Collector`]).
+    Items,
+}
+
+/// Describes which resource limit was exceeded by a computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceExceeded {
+    /// The exceeded resource.
+    pub resource: Resource,
+    /// The configured limit (in the units of the resource).
+    pub limit: u64,
+    /// The amount of the resource that is used (in the units of the resource).
+    pub used: u64,
+}
+
+impl Display for ResourceExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Resource limit exceeded ({:?}: {} of {})",
+            self.resource, self.used, self.limit
+        )
+    }
+}
+
+impl std::error::Error for ResourceExceeded {}
+
+impl From<ResourceExceeded> for Cancelled {
+    fn from(_: ResourceExceeded) -> Self {
+        Cancelled::new(RESOURCE_EXCEEDED)
+    }
+}
+
+impl From<ResourceExceeded> for Incomplete {
+    fn from(value: ResourceExceeded) -> Self {
+        Incomplete::ResourceExceeded(value)
+    }
 }
 
 /// A [`Completable`] result is a value eventually computed by an algorithm where
@@ -35,6 +92,7 @@ impl Display for Incomplete {
             Incomplete::Suspended => write!(f, "Operation suspended"),
             Incomplete::Exhausted => write!(f, "Computation exhausted"),
             Incomplete::Cancelled(c) => write!(f, "{}", c),
+            Incomplete::ResourceExceeded(e) => write!(f, "{}", e),
         }
     }
 }
@@ -120,6 +178,22 @@ mod tests {
         assert_eq!(format!("{}", incomplete), "Computation exhausted");
     }
 
+    #[test]
+    fn test_incomplete_resource_exceeded() {
+        let exceeded = ResourceExceeded {
+            resource: Resource::Memory,
+            limit: 100,
+            used: 120,
+        };
+        let incomplete = Incomplete::from(exceeded);
+        assert_eq!(incomplete, Incomplete::ResourceExceeded(exceeded));
+        assert_eq!(
+            format!("{}", incomplete),
+            "Resource limit exceeded (Memory: 120 of 100)"
+        );
+        assert_eq!(Cancelled::from(exceeded).cause(), RESOURCE_EXCEEDED);
+    }
+
     #[test]
     fn test_completable_err_exhausted() {
         let result: Completable<i32> = Err(Incomplete::Exhausted);
@@ -0,0 +1,483 @@
+use crate::{Completable, Computable, Incomplete};
+use std::marker::PhantomData;
+
+/// The current phase of a [`Child`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+enum ChildPhase<T, C> {
+    Empty,
+    Running(C),
+    Finished(T),
+}
+
+/// A slot for a child computation that is stored in the `STATE` of a composite algorithm.
+///
+/// Hierarchical algorithms (e.g., an outer loop that repeatedly invokes inner solvers)
+/// can keep their sub-computations as `Child` values in their state. The step function
+/// of the parent then only calls [`Child::step_child`] and propagates the incomplete
+/// results using `?`:
+///
+///  - The parent suspends whenever the child suspends, hence every suspend point of the
+///    child is also a suspend point of the parent.
+///  - Cancellation is aggregated: the child observes the same cancellation triggers as the
+///    parent (see `cancel-this`), and its [`Incomplete::Cancelled`] result is propagated to
+///    the parent.
+///  - With the `serde` feature, `Child` is serializable whenever the child computation and
+///    its output are, hence the whole tree of computations is saved as part of the parent
+///    state.
+///
+/// For a single sub-computation that is created together with the parent state, see also
+/// [`SubComputation`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Child, Completable, Computation, ComputationStep, Incomplete};
+///
+/// /// Computes `n * n` by repeated addition, suspending after every addition.
+/// struct SquareStep;
+///
+/// impl ComputationStep<u64, (u64, u64), u64> for SquareStep {
+///     fn step(n: &u64, (i, result): &mut (u64, u64)) -> Completable<u64> {
+///         if *i == *n {
+///             return Ok(*result);
+///         }
+///         *i += 1;
+///         *result += *n;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// type Square = Computation<u64, (u64, u64), u64, SquareStep>;
+///
+/// /// Sums the squares of the context numbers, one child computation per number.
+/// struct SumOfSquaresStep;
+///
+/// type SumState = (usize, u64, Child<u64, Square>);
+///
+/// impl ComputationStep<Vec<u64>, SumState, u64> for SumOfSquaresStep {
+///     fn step(numbers: &Vec<u64>, (index, sum, child): &mut SumState) -> Completable<u64> {
+///         while *index < numbers.len() {
+///             let n = numbers[*index];
+///             *sum += child.step_child(|| Square::from_parts(n, (0, 0)))?;
+///             *index += 1;
+///         }
+///         Ok(*sum)
+///     }
+/// }
+///
+/// let mut sum = Computation::<Vec<u64>, SumState, u64, SumOfSquaresStep>::from_parts(
+///     vec![1, 2, 3],
+///     (0, 0, Child::empty()),
+/// );
+/// assert_eq!(sum.try_compute(), Err(Incomplete::Suspended));
+/// assert!(sum.state().2.is_running());
+/// assert_eq!(sum.compute().unwrap(), 14);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Child<T, C> {
+    phase: ChildPhase<T, C>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C> Default for Child<T, C> {
+    fn default() -> Self {
+        Child::empty()
+    }
+}
+
+impl<T, C: Computable<T>> From<C> for Child<T, C> {
+    fn from(value: C) -> Self {
+        Child::new(value)
+    }
+}
+
+impl<T, C> Child<T, C> {
+    /// Create an empty slot (no child computation is running).
+    pub fn empty() -> Self {
+        Child {
+            phase: ChildPhase::Empty,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Returns `true` if no child computation is running and no output is stored.
+    pub fn is_empty(&self) -> bool {
+        matches!(self.phase, ChildPhase::Empty)
+    }
+
+    /// Returns `true` if a child computation is running.
+    pub fn is_running(&self) -> bool {
+        matches!(self.phase, ChildPhase::Running(_))
+    }
+
+    /// Returns `true` if the child computation finished and its output is stored.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, ChildPhase::Finished(_))
+    }
+
+    /// Access to the running child computation (if any).
+    pub fn computation(&self) -> Option<&C> {
+        match &self.phase {
+            ChildPhase::Running(computation) => Some(computation),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to the running child computation (if any).
+    pub fn computation_mut(&mut self) -> Option<&mut C> {
+        match &mut self.phase {
+            ChildPhase::Running(computation) => Some(computation),
+            _ => None,
+        }
+    }
+
+    /// Access to the stored output of a finished child computation (if any).
+    pub fn output(&self) -> Option<&T> {
+        match &self.phase {
+            ChildPhase::Finished(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Remove the stored output of a finished child computation, making the slot empty.
+    ///
+    /// If the child is not finished, the slot is not modified and `None` is returned.
+    pub fn take_output(&mut self) -> Option<T> {
+        if !self.is_finished() {
+            return None;
+        }
+        match std::mem::replace(&mut self.phase, ChildPhase::Empty) {
+            ChildPhase::Finished(output) => Some(output),
+            _ => unreachable!("The child is finished."),
+        }
+    }
+
+    /// Make the slot empty, dropping the running child computation or the stored output.
+    pub fn reset(&mut self) {
+        self.phase = ChildPhase::Empty;
+    }
+}
+
+impl<T, C: Computable<T>> Child<T, C> {
+    /// Create a slot with a running child `computation`.
+    pub fn new(computation: C) -> Self {
+        Child {
+            phase: ChildPhase::Running(computation),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Replace the contents of this slot with a new running child `computation`.
+    pub fn start(&mut self, computation: C) {
+        self.phase = ChildPhase::Running(computation);
+    }
+
+    /// Advance the child computation by one step and return a reference to its output
+    /// once it finishes.
+    ///
+    /// The output stays stored in the slot (see [`Child::output`] and
+    /// [`Child::take_output`]), and repeated calls return it again without advancing
+    /// the child. If the slot is empty, returns [`Incomplete::Exhausted`].
+    pub fn step(&mut self) -> Completable<&T> {
+        if let ChildPhase::Running(computation) = &mut self.phase {
+            let output = computation.try_compute()?;
+            self.phase = ChildPhase::Finished(output);
+        }
+        match &self.phase {
+            ChildPhase::Finished(output) => Ok(output),
+            ChildPhase::Empty => Err(Incomplete::Exhausted),
+            ChildPhase::Running(_) => unreachable!("The child is finished."),
+        }
+    }
+
+    /// Advance the child computation by one step, starting it using `factory` if the slot
+    /// is empty. Once the child finishes, its output is returned and the slot becomes empty
+    /// again, such that the next call starts a new child.
+    ///
+    /// If the slot already holds the output of a finished child, the output is returned
+    /// without calling `factory`.
+    pub fn step_child<F: FnOnce() -> C>(&mut self, factory: F) -> Completable<T> {
+        if self.is_empty() {
+            self.start(factory());
+        }
+        self.step()?;
+        Ok(self.take_output().expect("The child is finished."))
+    }
+}
+
+/// A single sub-computation that is stored in the `STATE` of another computation.
+///
+/// Unlike [`Child`], which is a reusable slot that also keeps the output of the finished
+/// child, [`SubComputation`] is a plain wrapper of the inner computation `C`, created
+/// together with the outer state. The outer step function advances it using [`drive_sub`]
+/// and propagates the incomplete results using `?`, hence every suspend point of the inner
+/// computation is also a suspend point of the outer computation. With the `serde`
+/// feature, [`SubComputation`] is serialized as the inner computation itself.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, Incomplete, SubComputation, drive_sub,
+/// };
+///
+/// /// Counts up to the context, suspending after every increment.
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         if *count == *target {
+///             return Ok(*count);
+///         }
+///         *count += 1;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// /// Doubles the result of the inner count.
+/// struct DoubleStep;
+///
+/// impl ComputationStep<(), SubComputation<Count>, u32> for DoubleStep {
+///     fn step(_context: &(), inner: &mut SubComputation<Count>) -> Completable<u32> {
+///         let count = drive_sub(inner)?;
+///         Ok(2 * count)
+///     }
+/// }
+///
+/// let inner = SubComputation::new(Count::from_parts(2, 0));
+/// let mut double = Computation::<(), _, u32, DoubleStep>::from_parts((), inner);
+/// assert_eq!(double.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(double.state().inner().state(), &1);
+/// assert_eq!(double.compute(), Ok(4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        transparent,
+        bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct SubComputation<C> {
+    inner: C,
+}
+
+impl<C> SubComputation<C> {
+    /// Wrap the `inner` computation.
+    pub fn new(inner: C) -> Self {
+        SubComputation { inner }
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Mutable access to the inner computation.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> From<C> for SubComputation<C> {
+    fn from(value: C) -> Self {
+        SubComputation::new(value)
+    }
+}
+
+/// Advance the sub-computation by one step, returning its output once it completes.
+///
+/// The outcome of the inner step is returned as it is, such that it can be propagated
+/// by the outer step function using `?`: an inner suspension becomes an outer suspension
+/// and an inner cancellation (or exceeded resource) cancels the outer step. Once the inner
+/// computation completed, it should not be driven again (most computations then return
+/// [`Incomplete::Exhausted`]).
+pub fn drive_sub<T, C: Computable<T>>(sub: &mut SubComputation<C>) -> Completable<T> {
+    sub.inner.try_compute()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableIdentity, Computation, ComputationStep, FromParts, StatefulRef};
+
+    struct CountStep;
+
+    impl ComputationStep<u64, u64, u64> for CountStep {
+        fn step(target: &u64, count: &mut u64) -> Completable<u64> {
+            if *count >= *target {
+                return Ok(*count * 10);
+            }
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Count = Computation<u64, u64, u64, CountStep>;
+
+    /// Runs one child per context number, collecting the outputs.
+    struct ParentStep;
+
+    type ParentState = (Vec<u64>, Child<u64, Count>);
+
+    impl ComputationStep<Vec<u64>, ParentState, Vec<u64>> for ParentStep {
+        fn step(targets: &Vec<u64>, (outputs, child): &mut ParentState) -> Completable<Vec<u64>> {
+            while outputs.len() < targets.len() {
+                let target = targets[outputs.len()];
+                outputs.push(child.step_child(|| Count::from_parts(target, 0))?);
+            }
+            Ok(outputs.clone())
+        }
+    }
+
+    type Parent = Computation<Vec<u64>, ParentState, Vec<u64>, ParentStep>;
+
+    #[test]
+    fn test_child_lifecycle() {
+        let mut child = Child::<u64, Count>::empty();
+        assert!(child.is_empty());
+        assert_eq!(child.step(), Err(Incomplete::Exhausted));
+
+        child.start(Count::from_parts(1, 0));
+        assert!(child.is_running());
+        assert_eq!(*child.computation().unwrap().context(), 1);
+        assert_eq!(child.step(), Err(Incomplete::Suspended));
+        assert_eq!(child.step(), Ok(&10));
+        assert!(child.is_finished());
+        assert!(child.computation().is_none());
+        // The output is retained.
+        assert_eq!(child.step(), Ok(&10));
+        assert_eq!(child.output(), Some(&10));
+        assert_eq!(child.take_output(), Some(10));
+        assert!(child.is_empty());
+        assert_eq!(child.take_output(), None);
+    }
+
+    #[test]
+    fn test_parent_delegates_to_children() {
+        let mut parent = Parent::from_parts(vec![2, 0, 1], (Vec::new(), Child::empty()));
+        let mut suspensions = 0;
+        let result = loop {
+            match parent.try_compute() {
+                Ok(result) => break result,
+                Err(Incomplete::Suspended) => suspensions += 1,
+                Err(e) => panic!("Unexpected {e:?}"),
+            }
+        };
+        assert_eq!(result, vec![20, 0, 10]);
+        // Every suspension of a child is also a suspension of the parent.
+        assert_eq!(suspensions, 3);
+        assert!(parent.state().1.is_empty());
+    }
+
+    #[test]
+    fn test_step_child_returns_stored_output() {
+        let mut child = Child::from(ComputableIdentity::from(5u64));
+        assert_eq!(child.step(), Ok(&5));
+        assert_eq!(
+            child.step_child(|| unreachable!("The output is stored.")),
+            Ok(5)
+        );
+        assert!(child.is_empty());
+    }
+
+    #[test]
+    fn test_modify_running_child() {
+        let mut child = Child::new(Count::from_parts(100, 0));
+        *child.computation_mut().unwrap().state_mut() = 100;
+        assert_eq!(child.step(), Ok(&1000));
+        child.reset();
+        assert!(child.is_empty());
+    }
+
+    /// Sums the results of two counts which are embedded in the state.
+    struct SumStep;
+
+    type SumState = (SubComputation<Count>, SubComputation<Count>, Option<u64>);
+
+    impl ComputationStep<(), SumState, u64> for SumStep {
+        fn step(_context: &(), (first, second, partial): &mut SumState) -> Completable<u64> {
+            let first = match partial {
+                Some(first) => *first,
+                None => *partial.insert(drive_sub(first)?),
+            };
+            Ok(first + drive_sub(second)?)
+        }
+    }
+
+    #[test]
+    fn test_drive_sub() {
+        let state = (
+            SubComputation::new(Count::from_parts(2, 0)),
+            Count::from_parts(1, 0).into(),
+            None,
+        );
+        let mut sum = Computation::<(), SumState, u64, SumStep>::from_parts((), state);
+        let mut suspensions = 0;
+        let result = loop {
+            match sum.try_compute() {
+                Ok(result) => break result,
+                Err(Incomplete::Suspended) => suspensions += 1,
+                Err(e) => panic!("Unexpected {e:?}"),
+            }
+        };
+        assert_eq!(result, 30);
+        // Every inner suspension is an outer suspension.
+        assert_eq!(suspensions, 3);
+        let (first, second, _) = sum.into_parts().1;
+        assert_eq!(first.into_inner().state(), &2);
+        assert_eq!(second.inner().state(), &1);
+    }
+
+    #[test]
+    fn test_drive_sub_exhausted() {
+        let mut sub = SubComputation::new(ComputableIdentity::from(1));
+        assert_eq!(drive_sub(&mut sub), Ok(1));
+        assert_eq!(drive_sub(&mut sub), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_cancellation_is_propagated() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut parent = Parent::from_parts(vec![5], (Vec::new(), Child::empty()));
+        assert_eq!(parent.try_compute(), Err(Incomplete::Suspended));
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut child = parent.state().1.clone();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(child.step().cloned())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        // The child is still running and can be resumed.
+        assert!(child.is_running());
+        assert_eq!(*child.computation().unwrap().state(), 1);
+    }
+}
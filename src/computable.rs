@@ -1,5 +1,9 @@
-use crate::{Completable, DynComputable, Incomplete};
+use crate::{
+    Algorithm, Completable, DynComputable, DynComputableSend, FromParts, Incomplete, StatefulMut,
+    StatefulRef,
+};
 use cancel_this::Cancellable;
+use core::task::Poll;
 
 /// A generic trait implemented by types that represent a "computation".
 ///
@@ -30,13 +34,33 @@ pub trait Computable<T> {
         }
     }
 
+    /// Like [`Computable::compute_completable`], but allows the implementation to check
+    /// cancellation only once every `batch` steps.
+    ///
+    /// For very fine-grained steps, the cancellation check performed by every call to
+    /// [`Computable::try_compute`] can dominate the running time. [`crate::Computation`]
+    /// implements this method by calling its step function directly and checking
+    /// cancellation before every `batch` consecutive steps (hence a cancellation can be
+    /// observed up to `batch` steps later). The default implementation ignores `batch`
+    /// and is identical to [`Computable::compute_completable`].
+    ///
+    /// See also [`crate::bench::measure_batched_overhead`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is zero.
+    fn compute_batched(&mut self, batch: usize) -> Completable<T> {
+        assert!(batch > 0, "Batch size must be positive.");
+        self.compute_completable()
+    }
+
     /// Advance this computation until completion, skipping over all suspended states.
     ///
     /// # Panics
     ///
     /// Panics if called on an exhausted computation, i.e., if [`Computable::try_compute`] returns
     /// [`Incomplete::Exhausted`]. If you want to handle exhaustion gracefully, use
-    /// [`Computable::compute_completable`] instead.
+    /// [`Computable::compute_completable`] or [`Computable::compute_opt`] instead.
     fn compute(&mut self) -> Cancellable<T> {
         match self.compute_completable() {
             Ok(value) => Ok(value),
@@ -44,10 +68,28 @@ pub trait Computable<T> {
                 "`compute_completable` never returns `Incomplete::Suspended` by definition."
             ),
             Err(Incomplete::Cancelled(c)) => Err(c),
+            Err(Incomplete::ResourceExceeded(e)) => Err(e.into()),
             Err(Incomplete::Exhausted) => panic!("Called `compute` on an exhausted `Computable`."),
         }
     }
 
+    /// Advance this computation until completion, skipping over all suspended states,
+    /// and returning `Ok(None)` if the computation is exhausted.
+    ///
+    /// This is the non-panicking variant of [`Computable::compute`], which is useful
+    /// when driving computations that may have already finished.
+    fn compute_opt(&mut self) -> Cancellable<Option<T>> {
+        match self.compute_completable() {
+            Ok(value) => Ok(Some(value)),
+            Err(Incomplete::Suspended) => unreachable!(
+                "`compute_completable` never returns `Incomplete::Suspended` by definition."
+            ),
+            Err(Incomplete::Cancelled(c)) => Err(c),
+            Err(Incomplete::ResourceExceeded(e)) => Err(e.into()),
+            Err(Incomplete::Exhausted) => Ok(None),
+        }
+    }
+
     /// Utility method to convert this [`Computable`] to a dynamic type.
     fn dyn_computable(self) -> DynComputable<T>
     where
@@ -55,14 +97,47 @@ pub trait Computable<T> {
     {
         Box::new(self)
     }
+
+    /// Utility method to convert this [`Computable`] to a dynamic type that can be
+    /// sent to other threads.
+    fn dyn_computable_send(self) -> DynComputableSend<T>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
 }
 
 /// A result-like object that stores the result of a [`Computable`] for later use.
+///
+/// Besides the result, the wrapper also remembers a terminal [`Incomplete::Cancelled`] or
+/// [`Incomplete::Exhausted`] outcome of the inner computation and returns it consistently
+/// on later polls (see [`ComputableResult::outcome`]) instead of re-driving the computation.
+/// This way, the wrapper can be used as a "promise" shared by several consumers.
+/// A [`crate::Scheduler`] accepts and hands out computations as such handles (see
+/// [`crate::Scheduler::spawn_result`] and [`crate::Scheduler::take_result`]).
+/// A cached failure can be discarded using [`ComputableResult::clear_failure`].
+///
+/// Once the result is moved out using [`ComputableResult::take_result`], the wrapper remains
+/// finished (further attempts to compute the result return [`Incomplete::Exhausted`]) until
+/// it is reused with a new computation using [`ComputableResult::reset_with`].
+///
+/// If `T: Clone`, the wrapper is also a [`Computable`] which produces a copy of the stored
+/// result once, after which it is exhausted like any other [`Computable`]. The stored result
+/// remains available through [`ComputableResult::result_ref`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComputableResult<T, C: Computable<T>> {
     computable: C,
     result: Option<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    finished: bool,
+    /// Set once the result was produced through the [`Computable`] interface.
+    #[cfg_attr(feature = "serde", serde(default))]
+    delivered: bool,
+    /// The cached terminal failure (not serialized).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    failure: Option<Incomplete>,
 }
 
 impl<T, C: Computable<T>> From<C> for ComputableResult<T, C> {
@@ -70,6 +145,9 @@ impl<T, C: Computable<T>> From<C> for ComputableResult<T, C> {
         ComputableResult {
             computable: value,
             result: None,
+            finished: false,
+            delivered: false,
+            failure: None,
         }
     }
 }
@@ -82,10 +160,29 @@ impl<T, C: Computable<T>> ComputableResult<T, C> {
 
     /// Advance the inner [`Computable`] and return its result or return a reference
     /// to the already computed result.
+    ///
+    /// If the inner computation was cancelled or exhausted, the same outcome is returned
+    /// without advancing the computation again.
     pub fn try_compute(&mut self) -> Completable<&T> {
         if self.result.is_none() {
-            let result = self.computable.try_compute()?;
+            if let Some(failure) = self.failure.as_ref() {
+                return Err(failure.clone());
+            }
+            if self.finished {
+                return Err(Incomplete::Exhausted);
+            }
+            let result = match self.computable.try_compute() {
+                Ok(result) => result,
+                Err(e @ (Incomplete::Cancelled(_) | Incomplete::Exhausted)) => {
+                    self.failure = Some(e.clone());
+                    return Err(e);
+                }
+                Err(e @ (Incomplete::Suspended | Incomplete::ResourceExceeded(_))) => {
+                    return Err(e);
+                }
+            };
             self.result = Some(result);
+            self.finished = true;
         }
 
         if let Some(result) = self.result.as_ref() {
@@ -95,6 +192,19 @@ impl<T, C: Computable<T>> ComputableResult<T, C> {
         unreachable!("Both `result` and `computable` cannot be `None`.")
     }
 
+    /// Like [`ComputableResult::try_compute`], but reports a suspended computation as
+    /// [`Poll::Pending`] (similar to polling a future).
+    ///
+    /// Once the result is available, [`Poll::Ready`] with a reference to the result is
+    /// returned on every poll. Cancellation, exhaustion, and exceeded resources are also
+    /// reported as [`Poll::Ready`].
+    pub fn poll_ref(&mut self) -> Poll<Completable<&T>> {
+        match self.try_compute() {
+            Err(Incomplete::Suspended) => Poll::Pending,
+            result => Poll::Ready(result),
+        }
+    }
+
     /// A reference to the computed result, assuming it is already available.
     pub fn result_ref(&self) -> Option<&T> {
         self.result.as_ref()
@@ -105,22 +215,212 @@ impl<T, C: Computable<T>> ComputableResult<T, C> {
         self.result
     }
 
+    /// Move the computed result out of this wrapper, assuming it is available.
+    ///
+    /// The wrapper stays finished, i.e., the inner computation is not advanced again.
+    pub fn take_result(&mut self) -> Option<T> {
+        self.result.take()
+    }
+
+    /// Returns `true` if the wrapper reached a terminal outcome, i.e., the result was already
+    /// computed (even if it was taken since), or the computation was cancelled or exhausted.
+    pub fn is_finished(&self) -> bool {
+        self.finished || self.failure.is_some()
+    }
+
+    /// The terminal outcome of the computation, or `None` if the computation can still
+    /// be advanced.
+    ///
+    /// Once the result is taken (see [`ComputableResult::take_result`]), the outcome
+    /// is [`Incomplete::Exhausted`].
+    pub fn outcome(&self) -> Option<Completable<&T>> {
+        if let Some(result) = self.result.as_ref() {
+            Some(Ok(result))
+        } else if let Some(failure) = self.failure.as_ref() {
+            Some(Err(failure.clone()))
+        } else if self.finished {
+            Some(Err(Incomplete::Exhausted))
+        } else {
+            None
+        }
+    }
+
+    /// Discard the cached failure (if any), such that the inner computation is advanced
+    /// again (e.g., to resume a cancelled computation). Returns the discarded failure.
+    pub fn clear_failure(&mut self) -> Option<Incomplete> {
+        self.failure.take()
+    }
+
+    /// Discard the stored result or failure (if any) and start over with a new `computable`.
+    pub fn reset_with(&mut self, computable: C) {
+        self.computable = computable;
+        self.result = None;
+        self.finished = false;
+        self.delivered = false;
+        self.failure = None;
+    }
+
     /// A reference to the underlying computation, assuming it is still available.
     pub fn computable_ref(&self) -> &C {
         &self.computable
     }
 
+    /// A mutable reference to the underlying computation.
+    ///
+    /// Note that modifying the computation does not discard an already computed result.
+    pub(crate) fn computable_mut(&mut self) -> &mut C {
+        &mut self.computable
+    }
+
     /// The underlying computation, assuming it is still available.
     pub fn computable(self) -> C {
         self.computable
     }
 }
 
+/// Produces a copy of the stored result once; later polls return [`Incomplete::Exhausted`].
+impl<T: Clone, C: Computable<T>> Computable<T> for ComputableResult<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        if self.delivered {
+            return Err(Incomplete::Exhausted);
+        }
+        // Resolves to the inherent method, which caches the result.
+        let result = self.try_compute().cloned()?;
+        self.delivered = true;
+        Ok(result)
+    }
+}
+
+impl<CONTEXT, STATE, T, C> FromParts<CONTEXT, STATE> for ComputableResult<T, C>
+where
+    C: Computable<T> + FromParts<CONTEXT, STATE>,
+{
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        ComputableResult::new(C::from_parts(context, state))
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        self.computable.into_parts()
+    }
+}
+
+/// The [`StatefulRef`] accessors of the inner computation.
+///
+/// Note that modifying the `CONTEXT` or `STATE` does not discard an already computed
+/// result (see [`ComputableResult::reset_with`]).
+impl<CONTEXT, STATE, T, C> StatefulRef<CONTEXT, STATE> for ComputableResult<T, C>
+where
+    C: Computable<T> + StatefulRef<CONTEXT, STATE>,
+{
+    fn context(&self) -> &CONTEXT {
+        self.computable.context()
+    }
+
+    fn state(&self) -> &STATE {
+        self.computable.state()
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        self.computable.state_mut()
+    }
+}
+
+impl<CONTEXT, STATE, T, C> StatefulMut<CONTEXT, STATE> for ComputableResult<T, C>
+where
+    C: Computable<T> + StatefulMut<CONTEXT, STATE>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        self.computable.context_mut()
+    }
+}
+
+impl<CONTEXT, STATE, T: Clone, C> Algorithm<CONTEXT, STATE, T> for ComputableResult<T, C> where
+    C: Algorithm<CONTEXT, STATE, T>
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{ComputableIdentity, Incomplete};
 
+    #[test]
+    fn test_default_compute_batched() {
+        let mut identity = ComputableIdentity::from(42);
+        assert_eq!(identity.compute_batched(10), Ok(42));
+        assert_eq!(identity.compute_batched(10), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_compute_opt() {
+        let mut identity = ComputableIdentity::from(42);
+        assert_eq!(identity.compute_opt(), Ok(Some(42)));
+        assert_eq!(identity.compute_opt(), Ok(None));
+    }
+
+    #[test]
+    fn test_compute_opt_cancelled() {
+        use cancel_this::{CancelAtomic, on_trigger};
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut computation = crate::FnComputation::new((), (), |_: &(), _: &mut ()| Ok(42));
+        assert!(on_trigger(trigger, || computation.compute_opt()).is_err());
+        // The computation is not exhausted by the cancellation.
+        assert_eq!(computation.compute_opt(), Ok(Some(42)));
+    }
+
+    #[test]
+    fn test_computable_result_poll_ref() {
+        use crate::{Computation, ComputationStep};
+
+        struct TwoSteps;
+
+        impl ComputationStep<(), u32, u32> for TwoSteps {
+            fn step(_context: &(), state: &mut u32) -> Completable<u32> {
+                *state += 1;
+                if *state == 2 {
+                    Ok(*state)
+                } else {
+                    Err(Incomplete::Suspended)
+                }
+            }
+        }
+
+        let computation = Computation::<(), u32, u32, TwoSteps>::from_parts((), 0);
+        let mut result = ComputableResult::new(computation);
+        assert_eq!(result.poll_ref(), Poll::Pending);
+        assert_eq!(result.poll_ref(), Poll::Ready(Ok(&2)));
+        assert_eq!(result.poll_ref(), Poll::Ready(Ok(&2)));
+        assert_eq!(result.take_result(), Some(2));
+        assert_eq!(result.poll_ref(), Poll::Ready(Err(Incomplete::Exhausted)));
+    }
+
+    #[test]
+    fn test_computable_result_stateful() {
+        use crate::{Computation, ComputationStep};
+
+        struct AddStep;
+
+        impl ComputationStep<u32, u32, u32> for AddStep {
+            fn step(context: &u32, state: &mut u32) -> Completable<u32> {
+                Ok(*context + *state)
+            }
+        }
+
+        type Add = ComputableResult<u32, Computation<u32, u32, u32, AddStep>>;
+        let mut result = Add::from_parts(1, 2);
+        assert_eq!((result.context(), result.state()), (&1, &2));
+        result.replace_context(10);
+        *result.state_mut() = 20;
+        assert_eq!(result.try_compute(), Ok(&30));
+        assert_eq!(result.into_parts(), (10, 20));
+        // The wrapper is an `Algorithm` when the result can be cloned.
+        assert_eq!(Add::run(3u32, 4u32), Ok(7));
+    }
+
     #[test]
     fn test_computable_result_from() {
         let identity: ComputableIdentity<i32> = 42.into();
@@ -189,6 +489,118 @@ mod tests {
         assert_eq!(value, 42);
     }
 
+    #[test]
+    fn test_computable_result_take_and_reset() {
+        let mut result = ComputableResult::new(SuspendingComputable {
+            count: 0,
+            target: 2,
+        });
+        assert!(!result.is_finished());
+        assert_eq!(result.take_result(), None);
+        assert_eq!(result.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(result.try_compute(), Ok(&2));
+        assert!(result.is_finished());
+        assert_eq!(result.take_result(), Some(2));
+        assert_eq!(result.take_result(), None);
+        // The inner computation is not advanced again.
+        assert!(result.is_finished());
+        assert_eq!(result.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(result.computable_ref().count, 2);
+
+        result.reset_with(SuspendingComputable {
+            count: 5,
+            target: 0,
+        });
+        assert!(!result.is_finished());
+        assert_eq!(result.try_compute(), Ok(&6));
+    }
+
+    #[test]
+    fn test_computable_result_caches_cancellation() {
+        use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+        let mut result = ComputableResult::new(crate::FnComputation::new(
+            (),
+            0,
+            |_: &(), count: &mut u32| {
+                *count += 1;
+                Ok(*count)
+            },
+        ));
+        assert_eq!(result.outcome(), None);
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let cancelled = on_trigger(trigger, || {
+            Ok::<_, Cancelled>(result.try_compute().cloned())
+        });
+        assert!(matches!(cancelled, Ok(Err(Incomplete::Cancelled(_)))));
+        assert!(result.is_finished());
+        // The cancellation is reported again without advancing the computation.
+        assert!(matches!(
+            result.try_compute(),
+            Err(Incomplete::Cancelled(_))
+        ));
+        assert!(matches!(
+            result.outcome(),
+            Some(Err(Incomplete::Cancelled(_)))
+        ));
+        assert!(matches!(
+            result.clear_failure(),
+            Some(Incomplete::Cancelled(_))
+        ));
+        assert!(!result.is_finished());
+        assert_eq!(result.try_compute(), Ok(&1));
+        assert_eq!(result.outcome(), Some(Ok(&1)));
+        assert_eq!(result.take_result(), Some(1));
+        assert_eq!(result.outcome(), Some(Err(Incomplete::Exhausted)));
+    }
+
+    #[test]
+    fn test_computable_result_caches_exhaustion() {
+        struct ExhaustedComputable {
+            polls: u32,
+        }
+
+        impl Computable<u32> for ExhaustedComputable {
+            fn try_compute(&mut self) -> Completable<u32> {
+                self.polls += 1;
+                Err(Incomplete::Exhausted)
+            }
+        }
+
+        let mut result = ComputableResult::new(ExhaustedComputable { polls: 0 });
+        assert_eq!(result.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(result.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(result.outcome(), Some(Err(Incomplete::Exhausted)));
+        assert_eq!(result.computable_ref().polls, 1);
+        result.reset_with(ExhaustedComputable { polls: 0 });
+        assert_eq!(result.outcome(), None);
+    }
+
+    #[test]
+    fn test_computable_result_is_computable() {
+        let mut result = ComputableResult::new(SuspendingComputable {
+            count: 0,
+            target: 3,
+        });
+        assert_eq!(
+            Computable::try_compute(&mut result),
+            Err(Incomplete::Suspended)
+        );
+        assert_eq!(result.compute(), Ok(3));
+        // The result is delivered only once, but it stays available.
+        assert_eq!(result.compute_opt(), Ok(None));
+        assert_eq!(result.result_ref(), Some(&3));
+        assert_eq!(result.computable_ref().count, 3);
+        result.reset_with(SuspendingComputable {
+            count: 0,
+            target: 2,
+        });
+        let mut dynamic = result.dyn_computable();
+        assert_eq!(dynamic.compute(), Ok(2));
+        assert_eq!(dynamic.try_compute(), Err(Incomplete::Exhausted));
+    }
+
     #[test]
     fn test_dyn_computable() {
         let identity: ComputableIdentity<i32> = 42.into();
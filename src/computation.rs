@@ -1,6 +1,10 @@
-use crate::{Algorithm, Completable, Computable, Stateful};
-use cancel_this::is_cancelled;
+use crate::{Algorithm, Completable, Computable, FromParts, Incomplete, StatefulMut, StatefulRef};
+use cancel_this::{
+    CancellationTrigger, DynamicCancellationTrigger, check_cancellation, is_cancelled,
+};
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 
 /// Defines a single step of a [`Computation`].
 ///
@@ -38,7 +42,7 @@ pub trait ComputationStep<CONTEXT, STATE, OUTPUT> {
 /// # Example
 ///
 /// ```rust
-/// use computation_process::{Computation, ComputationStep, Completable, Incomplete, Computable, Stateful};
+/// use computation_process::{Computation, ComputationStep, Completable, Incomplete, Computable, FromParts};
 ///
 /// struct SumStep;
 ///
@@ -59,7 +63,34 @@ pub trait ComputationStep<CONTEXT, STATE, OUTPUT> {
 /// );
 /// assert_eq!(computation.compute().unwrap(), 15);
 /// ```
-#[derive(Debug)]
+///
+/// # Cancellation
+///
+/// Before each step, the computation checks the thread-local `cancel-this` triggers.
+/// Additionally, a per-instance trigger can be attached using
+/// [`Computation::with_cancel_token`]. This makes it possible to cancel a single
+/// computation among many computations interleaved on the same thread (e.g., in a
+/// [`crate::Scheduler`]):
+///
+/// ```rust
+/// # use computation_process::{Computation, ComputationStep, Completable, Incomplete, Computable, FromParts};
+/// use cancel_this::CancelAtomic;
+///
+/// struct ForeverStep;
+///
+/// impl ComputationStep<(), (), ()> for ForeverStep {
+///     fn step(_context: &(), _state: &mut ()) -> Completable<()> {
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// let token = CancelAtomic::new();
+/// let mut computation = Computation::<(), (), (), ForeverStep>::from_parts((), ())
+///     .with_cancel_token(token.clone());
+/// assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+/// token.cancel();
+/// assert!(matches!(computation.try_compute(), Err(Incomplete::Cancelled(_))));
+/// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     feature = "serde",
@@ -70,21 +101,175 @@ pub trait ComputationStep<CONTEXT, STATE, OUTPUT> {
 pub struct Computation<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> {
     context: CONTEXT,
     state: STATE,
+    /// The per-instance cancellation trigger (not serialized, since it represents
+    /// a runtime signal).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cancel_token: Option<DynamicCancellationTrigger>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "crate::computation::default_cancel_check_interval")
+    )]
+    cancel_check_interval: NonZeroUsize,
+    /// The number of steps since the last cancellation check.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    since_check: usize,
     #[cfg_attr(feature = "serde", serde(skip))]
     _phantom: PhantomData<(OUTPUT, STEP)>,
 }
 
+// Debug/Clone/PartialEq/Eq are implemented manually, because derive would also require
+// `OUTPUT` and `STEP` to implement the same traits.
+
+impl<CONTEXT: Debug, STATE: Debug, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Debug
+    for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Computation")
+            .field("context", &self.context)
+            .field("state", &self.state)
+            .field(
+                "cancel_token",
+                &self.cancel_token.as_ref().map(|it| it.type_name()),
+            )
+            .field("cancel_check_interval", &self.cancel_check_interval)
+            .finish()
+    }
+}
+
+impl<CONTEXT: Clone, STATE: Clone, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Clone
+    for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    /// Create an independent copy of this computation. When cloned at a suspend point,
+    /// both copies can be advanced separately (e.g., to explore different branches).
+    ///
+    /// The copy shares the cancellation token of the original computation (if any).
+    fn clone(&self) -> Self {
+        Computation {
+            context: self.context.clone(),
+            state: self.state.clone(),
+            cancel_token: self.cancel_token.clone(),
+            cancel_check_interval: self.cancel_check_interval,
+            since_check: self.since_check,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<CONTEXT: PartialEq, STATE: PartialEq, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
+    PartialEq for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    /// The cancellation tokens and the cancellation check intervals are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.context == other.context && self.state == other.state
+    }
+}
+
+impl<CONTEXT: Eq, STATE: Eq, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Eq
+    for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
+    Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    /// Mutable access to both the context and the state (used by wrappers that modify
+    /// the context at suspend points).
+    pub(crate) fn parts_mut(&mut self) -> (&mut CONTEXT, &mut STATE) {
+        (&mut self.context, &mut self.state)
+    }
+
+    /// Attach a per-instance cancellation `token` to this computation (replacing the
+    /// previous one). The token is checked before each step, in addition to the thread-local
+    /// `cancel-this` triggers.
+    pub fn with_cancel_token<T: CancellationTrigger + 'static>(mut self, token: T) -> Self {
+        self.set_cancel_token(token);
+        self
+    }
+
+    /// Attach a per-instance cancellation `token` to this computation (replacing the
+    /// previous one). See [`Computation::with_cancel_token`].
+    pub fn set_cancel_token<T: CancellationTrigger + 'static>(&mut self, token: T) {
+        self.cancel_token = Some(Box::new(token));
+    }
+
+    /// Detach and return the per-instance cancellation token (if any).
+    pub fn take_cancel_token(&mut self) -> Option<DynamicCancellationTrigger> {
+        self.cancel_token.take()
+    }
+
+    /// The per-instance cancellation token (if any).
+    pub fn cancel_token(&self) -> Option<&DynamicCancellationTrigger> {
+        self.cancel_token.as_ref()
+    }
+
+    /// Check cancellation only before every `interval`-th step (the default is `1`, i.e.,
+    /// before every step). This reduces the overhead of very cheap steps, but a cancellation
+    /// can then be observed up to `interval - 1` steps later. Applies to both the
+    /// thread-local triggers and the per-instance token.
+    ///
+    /// See also [`Computable::compute_batched`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn with_cancel_check_interval(mut self, interval: usize) -> Self {
+        self.set_cancel_check_interval(interval);
+        self
+    }
+
+    /// Change the cancellation check interval. See [`Computation::with_cancel_check_interval`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn set_cancel_check_interval(&mut self, interval: usize) {
+        self.cancel_check_interval =
+            NonZeroUsize::new(interval).expect("Cancel check interval must be positive.");
+        self.since_check = 0;
+    }
+
+    /// The number of steps per cancellation check.
+    pub fn cancel_check_interval(&self) -> usize {
+        self.cancel_check_interval.get()
+    }
+
+    /// Check the thread-local triggers and the per-instance token.
+    fn check_cancelled(&self) -> Completable<()> {
+        is_cancelled!()?;
+        if let Some(token) = &self.cancel_token {
+            check_cancellation(token)?;
+        }
+        Ok(())
+    }
+}
+
 impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Computable<OUTPUT>
     for Computation<CONTEXT, STATE, OUTPUT, STEP>
 {
     fn try_compute(&mut self) -> Completable<OUTPUT> {
-        is_cancelled!()?;
+        if self.since_check == 0 {
+            self.check_cancelled()?;
+        }
+        self.since_check = (self.since_check + 1) % self.cancel_check_interval.get();
         STEP::step(&self.context, &mut self.state)
     }
+
+    fn compute_batched(&mut self, batch: usize) -> Completable<OUTPUT> {
+        assert!(batch > 0, "Batch size must be positive.");
+        loop {
+            self.check_cancelled()?;
+            for _ in 0..batch {
+                match STEP::step(&self.context, &mut self.state) {
+                    Err(Incomplete::Suspended) => continue,
+                    result => return result,
+                }
+            }
+        }
+    }
 }
 
-impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Stateful<CONTEXT, STATE>
-    for Computation<CONTEXT, STATE, OUTPUT, STEP>
+impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
+    FromParts<CONTEXT, STATE> for Computation<CONTEXT, STATE, OUTPUT, STEP>
 {
     fn from_parts(context: CONTEXT, state: STATE) -> Self
     where
@@ -93,6 +278,9 @@ impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Stat
         Computation {
             context,
             state,
+            cancel_token: None,
+            cancel_check_interval: NonZeroUsize::MIN,
+            since_check: 0,
             _phantom: Default::default(),
         }
     }
@@ -100,7 +288,11 @@ impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Stat
     fn into_parts(self) -> (CONTEXT, STATE) {
         (self.context, self.state)
     }
+}
 
+impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
+    StatefulRef<CONTEXT, STATE> for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
     fn context(&self) -> &CONTEXT {
         &self.context
     }
@@ -114,15 +306,29 @@ impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Stat
     }
 }
 
+impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
+    StatefulMut<CONTEXT, STATE> for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
 impl<CONTEXT, STATE, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>>
     Algorithm<CONTEXT, STATE, OUTPUT> for Computation<CONTEXT, STATE, OUTPUT, STEP>
 {
 }
 
+/// The default value of the cancellation check interval (used by deserialization).
+#[cfg(feature = "serde")]
+pub(crate) fn default_cancel_check_interval() -> NonZeroUsize {
+    NonZeroUsize::MIN
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Algorithm, Computable, Incomplete, Stateful};
+    use crate::{Algorithm, Computable, Incomplete};
 
     struct SimpleStep;
 
@@ -137,6 +343,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_computation_clone_at_suspend_point() {
+        let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+
+        let mut fork = computation.clone();
+        assert!(fork == computation);
+        *fork.state_mut() = 2;
+        assert!(fork != computation);
+
+        assert_eq!(fork.try_compute().unwrap(), "context=42, state=3");
+        assert_eq!(*computation.state(), 1);
+        assert_eq!(computation.compute().unwrap(), "context=42, state=3");
+    }
+
     #[test]
     fn test_computation_from_parts() {
         let computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
@@ -159,6 +380,15 @@ mod tests {
         assert_eq!(*computation.state(), 10);
     }
 
+    #[test]
+    fn test_computation_replace_context() {
+        let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.replace_context(7), 42);
+        computation.update_context(|context| *context *= 2);
+        assert_eq!(computation.compute().unwrap(), "context=14, state=3");
+    }
+
     #[test]
     fn test_computation_try_compute() {
         let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
@@ -229,6 +459,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_computation_cancel_token() {
+        use cancel_this::CancelAtomic;
+
+        let token = CancelAtomic::new();
+        let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0)
+            .with_cancel_token(token.clone());
+        let mut other = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        let mut fork = computation.clone();
+        assert!(fork.cancel_token().is_some());
+        assert!(other.cancel_token().is_none());
+        assert_eq!(other.try_compute(), Err(Incomplete::Suspended));
+
+        token.cancel();
+        assert!(matches!(
+            computation.try_compute(),
+            Err(Incomplete::Cancelled(_))
+        ));
+        assert!(matches!(fork.try_compute(), Err(Incomplete::Cancelled(_))));
+        // The state is not advanced by a cancelled step.
+        assert_eq!(*computation.state(), 1);
+        // Only computations with the token are affected.
+        assert_eq!(other.try_compute(), Err(Incomplete::Suspended));
+
+        assert!(computation.take_cancel_token().is_some());
+        assert_eq!(computation.compute().unwrap(), "context=42, state=3");
+    }
+
+    /// Cancels the token in the context after five steps.
+    struct CancelStep;
+
+    impl ComputationStep<cancel_this::CancelAtomic, u32, u32> for CancelStep {
+        fn step(token: &cancel_this::CancelAtomic, count: &mut u32) -> Completable<u32> {
+            *count += 1;
+            if *count == 5 {
+                token.cancel();
+            }
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    #[test]
+    fn test_computation_compute_batched() {
+        let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
+        assert_eq!(
+            computation.compute_batched(2).unwrap(),
+            "context=42, state=3"
+        );
+
+        // Cancellation is only observed at batch boundaries.
+        let token = cancel_this::CancelAtomic::new();
+        let mut computation = Computation::<_, u32, u32, CancelStep>::from_parts(token.clone(), 0)
+            .with_cancel_token(token);
+        assert!(matches!(
+            computation.compute_batched(4),
+            Err(Incomplete::Cancelled(_))
+        ));
+        assert_eq!(*computation.state(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch size must be positive.")]
+    fn test_computation_compute_batched_zero() {
+        let mut computation = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0);
+        let _ = computation.compute_batched(0);
+    }
+
+    #[test]
+    fn test_computation_cancel_check_interval() {
+        let token = cancel_this::CancelAtomic::new();
+        let mut computation = Computation::<_, u32, u32, CancelStep>::from_parts(token.clone(), 0)
+            .with_cancel_token(token)
+            .with_cancel_check_interval(3);
+        assert_eq!(computation.cancel_check_interval(), 3);
+        for _ in 0..5 {
+            assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        }
+        // Cancelled by the fifth step, but only checked before the seventh step.
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert!(matches!(
+            computation.try_compute(),
+            Err(Incomplete::Cancelled(_))
+        ));
+        assert!(matches!(
+            computation.try_compute(),
+            Err(Incomplete::Cancelled(_))
+        ));
+        assert_eq!(*computation.state(), 6);
+        let fork = computation.clone().with_cancel_check_interval(1);
+        assert_eq!(fork.cancel_check_interval(), 1);
+        assert_eq!(computation.cancel_check_interval(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cancel check interval must be positive.")]
+    fn test_computation_zero_cancel_check_interval() {
+        let _ = Computation::<i32, u32, String, SimpleStep>::from_parts(42, 0)
+            .with_cancel_check_interval(0);
+    }
+
     #[test]
     fn test_computation_never_completes() {
         let mut computation = Computation::<(), (), i32, NeverCompleteStep>::from_parts((), ());
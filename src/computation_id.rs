@@ -0,0 +1,68 @@
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The source of fresh [`ComputationId`] values (shared by the whole process).
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An opaque identifier of a computation managed by a [`crate::Scheduler`] (or another
+/// executor).
+///
+/// Identifiers are assigned when a computation enters a scheduler and are unique within
+/// the process (unlike task indices, which are only unique within one scheduler).
+/// Identifiers are serializable, such that they can be stored in checkpoints or logs.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{ComputableIdentity, ComputationId, Scheduler};
+///
+/// let mut scheduler = Scheduler::<i32>::new();
+/// let index = scheduler.spawn(Box::new(ComputableIdentity::from(1)));
+/// let id = scheduler.id(index).unwrap();
+/// assert_eq!(scheduler.index_of(id), Some(index));
+/// assert_ne!(id, ComputationId::fresh());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComputationId(u64);
+
+impl ComputationId {
+    /// Generate a fresh identifier which is different from all previously
+    /// generated identifiers.
+    pub fn fresh() -> Self {
+        ComputationId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for ComputationId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_ids_are_unique() {
+        let ids = (0..100)
+            .map(|_| ComputationId::fresh())
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 100);
+        let threads = (0..4)
+            .map(|_| std::thread::spawn(ComputationId::fresh))
+            .collect::<Vec<_>>();
+        for thread in threads {
+            assert!(!ids.contains(&thread.join().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let id = ComputationId::fresh();
+        assert!(id.to_string().starts_with('#'));
+        assert!(ComputationId::fresh() > id);
+    }
+}
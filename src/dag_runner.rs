@@ -0,0 +1,355 @@
+use crate::{Algorithm, Completable, Computable, Incomplete};
+use std::marker::PhantomData;
+
+/// The phase of a single task in a [`DagRunner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum TaskPhase<T, STATE, C> {
+    /// The task waits for its dependencies, the initial state is stored.
+    Waiting(STATE),
+    /// The task is running.
+    Running(C),
+    /// The task completed with the given output.
+    Finished(T),
+    /// The output of the task was returned by the [`DagRunner`].
+    Taken,
+}
+
+/// A task of a [`DagRunner`] together with its dependencies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct DagTask<T, STATE, C> {
+    dependencies: Vec<usize>,
+    phase: TaskPhase<T, STATE, C>,
+}
+
+/// A [`Computable`] that runs a directed acyclic graph of dependent computations
+/// and completes with the outputs of all tasks (in the order in which they were added).
+///
+/// Each task is an [`Algorithm`] whose `CONTEXT` is the list of outputs of its dependencies
+/// (in the order in which the dependencies were given to [`DagRunner::add_task`]). A task
+/// is only started (using [`crate::FromParts::from_parts`] with its initial state) once all
+/// of its dependencies have finished. Since dependencies must be added before the tasks
+/// that depend on them, the graph is always acyclic.
+///
+/// Every call to [`Computable::try_compute`] advances one running (or startable) task
+/// by a single step, cycling through the tasks in a round-robin fashion. Cancellation
+/// interrupts the current task, which is then resumed first. If any task is exhausted before
+/// producing its output, the whole [`DagRunner`] is exhausted. With the `serde` feature,
+/// the whole graph (including waiting, running, and finished tasks) can be serialized.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, DagRunner};
+///
+/// /// Sums the outputs of the dependencies and adds the value given as state.
+/// struct SumStep;
+///
+/// impl ComputationStep<Vec<u32>, u32, u32> for SumStep {
+///     fn step(inputs: &Vec<u32>, value: &mut u32) -> Completable<u32> {
+///         Ok(inputs.iter().sum::<u32>() + *value)
+///     }
+/// }
+///
+/// type Sum = Computation<Vec<u32>, u32, u32, SumStep>;
+///
+/// let mut dag = DagRunner::<u32, u32, Sum>::new();
+/// let a = dag.add_task(1, &[]);
+/// let b = dag.add_task(2, &[]);
+/// let c = dag.add_task(10, &[a, b]);
+/// dag.add_task(100, &[c, a]);
+/// assert_eq!(dag.compute().unwrap(), vec![1, 2, 13, 114]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, STATE: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct DagRunner<T, STATE, C>
+where
+    C: Algorithm<Vec<T>, STATE, T>,
+{
+    tasks: Vec<DagTask<T, STATE, C>>,
+    remaining: usize,
+    cursor: usize,
+    finished: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<STATE>,
+}
+
+impl<T, STATE, C: Algorithm<Vec<T>, STATE, T>> Default for DagRunner<T, STATE, C> {
+    fn default() -> Self {
+        DagRunner {
+            tasks: Vec::new(),
+            remaining: 0,
+            cursor: 0,
+            finished: false,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<T: Clone, STATE, C: Algorithm<Vec<T>, STATE, T> + 'static> DagRunner<T, STATE, C> {
+    /// Create a new empty [`DagRunner`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a task with the given `initial_state` that depends on the outputs of the tasks
+    /// in `dependencies`, and return the index of the new task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the dependencies is not a previously added task.
+    pub fn add_task(&mut self, initial_state: STATE, dependencies: &[usize]) -> usize {
+        let index = self.tasks.len();
+        if let Some(unknown) = dependencies.iter().find(|it| **it >= index) {
+            panic!("Unknown dependency `{unknown}` of task `{index}`.");
+        }
+        self.tasks.push(DagTask {
+            dependencies: dependencies.to_vec(),
+            phase: TaskPhase::Waiting(initial_state),
+        });
+        self.remaining += 1;
+        index
+    }
+
+    /// The total number of tasks (including the finished ones).
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there are no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// The number of tasks that did not finish yet.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// The dependencies of the task at `index`.
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.tasks[index].dependencies
+    }
+
+    /// Returns `true` if the task at `index` has been started but did not finish yet.
+    pub fn is_running(&self, index: usize) -> bool {
+        matches!(self.tasks[index].phase, TaskPhase::Running(_))
+    }
+
+    /// Returns `true` if the task at `index` has finished.
+    pub fn is_finished(&self, index: usize) -> bool {
+        matches!(
+            self.tasks[index].phase,
+            TaskPhase::Finished(_) | TaskPhase::Taken
+        )
+    }
+
+    /// Access to the running task at `index` (if it has been started and did not finish yet).
+    pub fn task(&self, index: usize) -> Option<&C> {
+        match &self.tasks.get(index)?.phase {
+            TaskPhase::Running(task) => Some(task),
+            _ => None,
+        }
+    }
+
+    /// Access to the output of the task at `index` (if it finished and the outputs
+    /// were not returned yet).
+    pub fn output(&self, index: usize) -> Option<&T> {
+        match &self.tasks.get(index)?.phase {
+            TaskPhase::Finished(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the task at `index` can be advanced (it is running, or it is
+    /// waiting and all its dependencies finished).
+    fn is_ready(&self, index: usize) -> bool {
+        match &self.tasks[index].phase {
+            TaskPhase::Running(_) => true,
+            TaskPhase::Waiting(_) => self.tasks[index]
+                .dependencies
+                .iter()
+                .all(|it| matches!(self.tasks[*it].phase, TaskPhase::Finished(_))),
+            _ => false,
+        }
+    }
+
+    /// Start the waiting task at `index` using the outputs of its dependencies.
+    fn start(&mut self, index: usize) {
+        let inputs = self.tasks[index]
+            .dependencies
+            .iter()
+            .map(|it| match &self.tasks[*it].phase {
+                TaskPhase::Finished(output) => output.clone(),
+                _ => unreachable!("Dependencies are finished."),
+            })
+            .collect::<Vec<T>>();
+        let task = &mut self.tasks[index];
+        if let TaskPhase::Waiting(state) = std::mem::replace(&mut task.phase, TaskPhase::Taken) {
+            task.phase = TaskPhase::Running(C::from_parts(inputs, state));
+        }
+    }
+}
+
+impl<T: Clone, STATE, C: Algorithm<Vec<T>, STATE, T> + 'static> Computable<Vec<T>>
+    for DagRunner<T, STATE, C>
+{
+    fn try_compute(&mut self) -> Completable<Vec<T>> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        if self.remaining == 0 {
+            self.finished = true;
+            let outputs = self
+                .tasks
+                .iter_mut()
+                .map(
+                    |it| match std::mem::replace(&mut it.phase, TaskPhase::Taken) {
+                        TaskPhase::Finished(output) => output,
+                        _ => unreachable!("All tasks are finished."),
+                    },
+                )
+                .collect();
+            return Ok(outputs);
+        }
+
+        let count = self.tasks.len();
+        let index = (0..count)
+            .map(|i| (self.cursor + i) % count)
+            .find(|i| self.is_ready(*i))
+            .expect("The graph is acyclic, hence some task is ready.");
+        if matches!(self.tasks[index].phase, TaskPhase::Waiting(_)) {
+            self.start(index);
+        }
+        let TaskPhase::Running(task) = &mut self.tasks[index].phase else {
+            unreachable!("The task is running.");
+        };
+        match task.try_compute() {
+            Ok(output) => {
+                self.tasks[index].phase = TaskPhase::Finished(output);
+                self.remaining -= 1;
+                self.cursor = index + 1;
+                if self.remaining == 0 {
+                    self.try_compute()
+                } else {
+                    Err(Incomplete::Suspended)
+                }
+            }
+            Err(Incomplete::Suspended) => {
+                self.cursor = index + 1;
+                Err(Incomplete::Suspended)
+            }
+            Err(Incomplete::Exhausted) => {
+                self.finished = true;
+                Err(Incomplete::Exhausted)
+            }
+            Err(e) => {
+                self.cursor = index;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, StatefulRef};
+
+    /// Sums the outputs of the dependencies after the number of steps given by the state.
+    struct DelayedSumStep;
+
+    impl ComputationStep<Vec<u32>, u32, u32> for DelayedSumStep {
+        fn step(inputs: &Vec<u32>, delay: &mut u32) -> Completable<u32> {
+            if *delay > 0 {
+                *delay -= 1;
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(inputs.iter().sum::<u32>() + 1)
+            }
+        }
+    }
+
+    type DelayedSum = Computation<Vec<u32>, u32, u32, DelayedSumStep>;
+
+    #[test]
+    fn test_dependencies_are_respected() {
+        let mut dag = DagRunner::<u32, u32, DelayedSum>::new();
+        let a = dag.add_task(2, &[]);
+        let b = dag.add_task(0, &[a]);
+        let c = dag.add_task(1, &[]);
+        assert_eq!(dag.len(), 3);
+        assert_eq!(dag.dependencies(b), &[a]);
+
+        // a: step 1 (suspended), b is not ready, c: step 1 (suspended).
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+        assert!(dag.is_running(a));
+        assert!(!dag.is_running(b));
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+        assert!(dag.is_running(c));
+        // a: step 2 (suspended), c: finished.
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(dag.output(c), Some(&1));
+        assert_eq!(dag.remaining(), 2);
+        // a: finished, then b starts with the output of a.
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+        assert!(dag.is_finished(a));
+        assert!(dag.task(b).is_none());
+        assert_eq!(dag.try_compute(), Ok(vec![1, 2, 1]));
+        assert_eq!(dag.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_diamond() {
+        let mut dag = DagRunner::<u32, u32, DelayedSum>::new();
+        let root = dag.add_task(1, &[]);
+        let left = dag.add_task(3, &[root]);
+        let right = dag.add_task(0, &[root]);
+        dag.add_task(0, &[left, right, root]);
+        assert_eq!(dag.compute().unwrap(), vec![1, 2, 2, 6]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut dag = DagRunner::<u32, u32, DelayedSum>::new();
+        assert!(dag.is_empty());
+        assert_eq!(dag.try_compute(), Ok(vec![]));
+        assert_eq!(dag.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown dependency")]
+    fn test_unknown_dependency() {
+        let mut dag = DagRunner::<u32, u32, DelayedSum>::new();
+        dag.add_task(0, &[0]);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut dag = DagRunner::<u32, u32, DelayedSum>::new();
+        let a = dag.add_task(1, &[]);
+        dag.add_task(0, &[a]);
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(dag.try_compute())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(dag.task(a).unwrap().state(), &0);
+        assert_eq!(dag.compute().unwrap(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// A [`Generatable`] that removes consecutive repeated items of another [`Generatable`].
+///
+/// A repeated item is replaced by [`Incomplete::Suspended`], so the adapter never loops
+/// internally. Suspensions and cancellation of the inner generator are passed through.
+///
+/// See also [`Unique`] and [`crate::GeneratableExt::dedup`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Dedup<ITEM, G = DynGeneratable<ITEM>>
+where
+    ITEM: Clone + PartialEq,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    last: Option<ITEM>,
+}
+
+impl<ITEM: Clone + PartialEq, G: Generatable<ITEM>> Dedup<ITEM, G> {
+    /// Create a new [`Dedup`] adapter for the given generator.
+    pub fn new(generator: G) -> Self {
+        Dedup {
+            generator,
+            last: None,
+        }
+    }
+}
+
+impl<ITEM: Clone + PartialEq, G: Generatable<ITEM>> Iterator for Dedup<ITEM, G> {
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM: Clone + PartialEq, G: Generatable<ITEM>> Generatable<ITEM> for Dedup<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        match self.generator.try_next()? {
+            Ok(item) if self.last.as_ref() == Some(&item) => Some(Err(Incomplete::Suspended)),
+            Ok(item) => {
+                self.last = Some(item.clone());
+                Some(Ok(item))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A [`Generatable`] that removes all repeated items of another [`Generatable`],
+/// i.e., every item is produced at most once.
+///
+/// The adapter remembers all produced items in a [`HashSet`]. A repeated item is replaced
+/// by [`Incomplete::Suspended`], so the adapter never loops internally. Suspensions and
+/// cancellation of the inner generator are passed through.
+///
+/// See also [`Dedup`] and [`crate::GeneratableExt::unique`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Unique<ITEM, G = DynGeneratable<ITEM>>
+where
+    ITEM: Clone + Eq + Hash,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    seen: HashSet<ITEM>,
+}
+
+impl<ITEM: Clone + Eq + Hash, G: Generatable<ITEM>> Unique<ITEM, G> {
+    /// Create a new [`Unique`] adapter for the given generator.
+    pub fn new(generator: G) -> Self {
+        Unique {
+            generator,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// The set of all items produced so far.
+    pub fn seen(&self) -> &HashSet<ITEM> {
+        &self.seen
+    }
+}
+
+impl<ITEM: Clone + Eq + Hash, G: Generatable<ITEM>> Iterator for Unique<ITEM, G> {
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM: Clone + Eq + Hash, G: Generatable<ITEM>> Generatable<ITEM> for Unique<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        match self.generator.try_next()? {
+            Ok(item) if self.seen.contains(&item) => Some(Err(Incomplete::Suspended)),
+            Ok(item) => {
+                self.seen.insert(item.clone());
+                Some(Ok(item))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GeneratableExt, test_fixtures::Items};
+
+    #[test]
+    fn test_dedup() {
+        let generator = Items::from_parts(vec![1, 1, 2, 2, 2, 1, 3, 3], 0);
+        let items: Vec<i32> = generator.dedup().map(|it| it.unwrap()).collect();
+        assert_eq!(items, vec![1, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_dedup_suspends_on_repeat() {
+        let generator = Items::from_parts(vec![5, 5], 0);
+        let mut dedup = Dedup::new(generator);
+        assert_eq!(dedup.try_next(), Some(Ok(5)));
+        assert_eq!(dedup.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(dedup.try_next(), None);
+    }
+
+    #[test]
+    fn test_unique() {
+        let generator = Items::from_parts(vec![1, 1, 2, 2, 2, 1, 3, 3], 0);
+        let mut unique = generator.unique();
+        let items: Vec<i32> = unique.by_ref().map(|it| it.unwrap()).collect();
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(unique.seen().len(), 3);
+    }
+
+    #[test]
+    fn test_unique_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let generator = Items::from_parts(vec![1, 2], 0);
+        let mut unique = generator.unique();
+        let result = on_trigger(trigger, || unique.try_next().unwrap());
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+    }
+}
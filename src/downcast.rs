@@ -0,0 +1,160 @@
+use crate::{Computable, Generatable};
+use std::any::Any;
+
+/// A [`Computable`] that can be downcast to its concrete type when used as a trait object.
+///
+/// The trait is implemented automatically for every `'static` [`Computable`]. Using
+/// [`crate::DynAnyComputable`] instead of [`crate::DynComputable`], a scheduler (or any other
+/// owner of boxed computations) can recover the concrete computation, e.g., to serialize
+/// it or inspect its state (see [`downcast_ref`](#method.downcast_ref)).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, DynAnyComputable, Scheduler};
+///
+/// let mut scheduler = Scheduler::<i32, DynAnyComputable<i32>>::new();
+/// scheduler.spawn(Box::new(ComputableIdentity::from(1)));
+/// scheduler.spawn(Box::new(ComputableIdentity::from(2).map(|x| x * 10)));
+/// let task = scheduler.task(0).unwrap();
+/// assert!(task.is::<ComputableIdentity<i32>>());
+/// assert!(scheduler.task(1).unwrap().downcast_ref::<ComputableIdentity<i32>>().is_none());
+/// ```
+pub trait AnyComputable<T>: Computable<T> + Any {}
+
+impl<T, C: Computable<T> + Any> AnyComputable<T> for C {}
+
+impl<T> dyn AnyComputable<T> {
+    /// This computation as [`Any`].
+    pub fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// This computation as mutable [`Any`].
+    pub fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// Returns `true` if the concrete type of this computation is `C`.
+    pub fn is<C: Any>(&self) -> bool {
+        self.as_any().is::<C>()
+    }
+
+    /// A reference to the concrete computation, assuming it is of type `C`.
+    pub fn downcast_ref<C: Any>(&self) -> Option<&C> {
+        self.as_any().downcast_ref()
+    }
+
+    /// A mutable reference to the concrete computation, assuming it is of type `C`.
+    pub fn downcast_mut<C: Any>(&mut self) -> Option<&mut C> {
+        self.as_any_mut().downcast_mut()
+    }
+
+    /// Recover the concrete computation, assuming it is of type `C`. Otherwise,
+    /// the computation is returned unchanged.
+    pub fn downcast<C: Any>(self: Box<Self>) -> Result<Box<C>, Box<Self>> {
+        if self.is::<C>() {
+            let any: Box<dyn Any> = self;
+            Ok(any.downcast().expect("The type was checked."))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// A [`Generatable`] that can be downcast to its concrete type when used as a trait object.
+///
+/// This is the [`Generatable`] counterpart of [`AnyComputable`] (see also
+/// [`crate::DynAnyGeneratable`]).
+pub trait AnyGeneratable<T>: Generatable<T> + Any {}
+
+impl<T, G: Generatable<T> + Any> AnyGeneratable<T> for G {}
+
+impl<T> dyn AnyGeneratable<T> {
+    /// This generator as [`Any`].
+    pub fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// This generator as mutable [`Any`].
+    pub fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// Returns `true` if the concrete type of this generator is `G`.
+    pub fn is<G: Any>(&self) -> bool {
+        self.as_any().is::<G>()
+    }
+
+    /// A reference to the concrete generator, assuming it is of type `G`.
+    pub fn downcast_ref<G: Any>(&self) -> Option<&G> {
+        self.as_any().downcast_ref()
+    }
+
+    /// A mutable reference to the concrete generator, assuming it is of type `G`.
+    pub fn downcast_mut<G: Any>(&mut self) -> Option<&mut G> {
+        self.as_any_mut().downcast_mut()
+    }
+
+    /// Recover the concrete generator, assuming it is of type `G`. Otherwise,
+    /// the generator is returned unchanged.
+    pub fn downcast<G: Any>(self: Box<Self>) -> Result<Box<G>, Box<Self>> {
+        if self.is::<G>() {
+            let any: Box<dyn Any> = self;
+            Ok(any.downcast().expect("The type was checked."))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::{Count, CountGenerator};
+    use crate::{
+        Computable, DynAnyComputable, DynAnyGeneratable, FromParts, Generatable, Incomplete,
+        Scheduler, StatefulRef,
+    };
+
+    #[test]
+    fn test_recover_scheduler_task() {
+        let mut scheduler = Scheduler::<u32, DynAnyComputable<u32>>::new();
+        scheduler.spawn(Box::new(Count::from_parts(3, 0)));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        let task = scheduler.task(0).unwrap();
+        assert!(task.is::<Count>());
+        assert_eq!(task.downcast_ref::<Count>().unwrap().state(), &1);
+        *scheduler
+            .task_mut(0)
+            .unwrap()
+            .downcast_mut::<Count>()
+            .unwrap()
+            .state_mut() = 2;
+        assert_eq!(scheduler.try_next(), Some(Ok((0, 3))));
+    }
+
+    #[test]
+    fn test_downcast_box() {
+        let boxed: DynAnyComputable<u32> = Box::new(Count::from_parts(2, 0));
+        let Err(boxed) = boxed.downcast::<CountGenerator>() else {
+            panic!("The computation is not a generator.");
+        };
+        let Ok(mut count) = boxed.downcast::<Count>() else {
+            panic!("The computation is a `Count`.");
+        };
+        assert_eq!(count.compute(), Ok(2));
+    }
+
+    #[test]
+    fn test_downcast_generator() {
+        let mut boxed: DynAnyGeneratable<u32> = Box::new(CountGenerator::from_parts(2, 0));
+        assert_eq!(boxed.try_next(), Some(Ok(1)));
+        assert!(boxed.downcast_ref::<Count>().is_none());
+        assert_eq!(boxed.downcast_mut::<CountGenerator>().unwrap().state(), &1);
+        let Ok(generator) = boxed.downcast::<CountGenerator>() else {
+            panic!("The generator is a `CountGenerator`.");
+        };
+        assert_eq!(generator.into_parts(), (2, 1));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Completable, Computable, Incomplete, take_retry_hint};
+use cancel_this::is_cancelled;
+use std::time::{Duration, Instant};
+
+/// Drive the `computation` to completion on the current thread, parking the thread while
+/// the computation waits for its retry hint (see [`crate::suspend_for`]).
+///
+/// This is the blocking counterpart of an executor's `block_on`: suspensions without
+/// a hint are resumed immediately (as in [`Computable::compute_completable`]), but when
+/// a step suspends with a retry hint, the thread is parked until the hint elapses instead
+/// of busy-polling the computation. While parked, the thread wakes up at least once every
+/// `poll_interval` to check cancellation, such that a waiting computation can still be
+/// cancelled promptly. Unparking the thread (using [`std::thread::Thread::unpark`])
+/// makes it poll the computation again right away.
+///
+/// Cancellation, exhaustion, and exceeded resources are returned as they are.
+///
+/// # Panics
+///
+/// Panics if `poll_interval` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, FromParts, drive_blocking, suspend_for,
+/// };
+/// use std::time::{Duration, Instant};
+///
+/// /// Completes once the deadline passes, asking to be polled again only when it is due.
+/// struct TimerStep;
+///
+/// impl ComputationStep<Instant, u32, u32> for TimerStep {
+///     fn step(deadline: &Instant, polls: &mut u32) -> Completable<u32> {
+///         *polls += 1;
+///         match deadline.checked_duration_since(Instant::now()) {
+///             Some(remaining) if !remaining.is_zero() => Err(suspend_for(remaining)),
+///             _ => Ok(*polls),
+///         }
+///     }
+/// }
+///
+/// let deadline = Instant::now() + Duration::from_millis(20);
+/// let mut timer = Computation::<Instant, u32, u32, TimerStep>::from_parts(deadline, 0);
+/// let polls = drive_blocking(&mut timer, Duration::from_millis(5)).unwrap();
+/// // The timer is not polled in a busy loop.
+/// assert!(polls < 10);
+/// assert!(Instant::now() >= deadline);
+/// ```
+pub fn drive_blocking<T, C: Computable<T> + ?Sized>(
+    computation: &mut C,
+    poll_interval: Duration,
+) -> Completable<T> {
+    assert!(!poll_interval.is_zero(), "Poll interval must be positive.");
+    // Discard a stale hint that was not recorded by this computation.
+    let _ = take_retry_hint();
+    loop {
+        match computation.try_compute() {
+            Err(Incomplete::Suspended) => {
+                if let Some(delay) = take_retry_hint() {
+                    park_for(delay, poll_interval)?;
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Park the current thread for `delay`, checking cancellation every `poll_interval`.
+///
+/// Returns early if the thread is unparked.
+fn park_for(delay: Duration, poll_interval: Duration) -> Completable<()> {
+    let wake_at = Instant::now() + delay;
+    loop {
+        is_cancelled!()?;
+        let Some(remaining) = wake_at.checked_duration_since(Instant::now()) else {
+            return Ok(());
+        };
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        let before = Instant::now();
+        let timeout = remaining.min(poll_interval);
+        std::thread::park_timeout(timeout);
+        if before.elapsed() < timeout {
+            // Unparked (or spuriously woken up): poll the computation again.
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Computation, ComputationStep, FromParts, StatefulRef, suspend_for, test_fixtures::CountStep,
+    };
+    use cancel_this::{CancelAtomic, on_trigger};
+
+    /// Completes after the given number of polls, asking for a long delay between them.
+    struct SlowStep;
+
+    impl ComputationStep<u32, u32, u32> for SlowStep {
+        fn step(target: &u32, polls: &mut u32) -> Completable<u32> {
+            *polls += 1;
+            if *polls >= *target {
+                Ok(*polls)
+            } else {
+                Err(suspend_for(Duration::from_secs(60)))
+            }
+        }
+    }
+
+    type Slow = Computation<u32, u32, u32, SlowStep>;
+
+    #[test]
+    fn test_plain_suspensions() {
+        let mut computation = Computation::<u32, u32, u32, CountStep>::from_parts(100, 0);
+        assert_eq!(
+            drive_blocking(&mut computation, Duration::from_secs(1)),
+            Ok(100)
+        );
+    }
+
+    #[test]
+    fn test_short_hints() {
+        struct ShortStep;
+
+        impl ComputationStep<(), u32, u32> for ShortStep {
+            fn step(_context: &(), polls: &mut u32) -> Completable<u32> {
+                *polls += 1;
+                if *polls == 3 {
+                    Ok(*polls)
+                } else {
+                    Err(suspend_for(Duration::from_millis(5)))
+                }
+            }
+        }
+
+        let mut computation = Computation::<(), u32, u32, ShortStep>::from_parts((), 0);
+        let start = Instant::now();
+        assert_eq!(
+            drive_blocking(&mut computation, Duration::from_secs(1)),
+            Ok(3)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        assert_eq!(take_retry_hint(), None);
+    }
+
+    #[test]
+    fn test_cancelled_while_parked() {
+        let trigger = CancelAtomic::new();
+        let cancel = trigger.clone();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            cancel.cancel();
+        });
+        let mut computation = Slow::from_parts(10, 0);
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(drive_blocking(
+                &mut computation,
+                Duration::from_millis(2),
+            ))
+        })
+        .unwrap();
+        canceller.join().unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        // The computation was polled once, then waited for its hint.
+        assert_eq!(computation.state(), &1);
+    }
+
+    #[test]
+    fn test_unpark_polls_again() {
+        let driver = std::thread::spawn(|| {
+            let mut computation = Slow::from_parts(2, 0);
+            drive_blocking(&mut computation, Duration::from_secs(60))
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        driver.thread().unpark();
+        assert_eq!(driver.join().unwrap(), Ok(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Poll interval must be positive.")]
+    fn test_zero_poll_interval() {
+        let mut computation = Slow::from_parts(1, 0);
+        let _ = drive_blocking(&mut computation, Duration::ZERO);
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A thread-safe flag that can be used to wake computations waiting for an external event
+/// (see [`crate::WaitUntil::flag`]).
+///
+/// Clones of the flag share the same value, so one clone can be given to the producer
+/// of the event (e.g., an I/O thread) while the computation waits for the other one.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::EventFlag;
+///
+/// let flag = EventFlag::new();
+/// let producer = flag.clone();
+/// std::thread::spawn(move || producer.set()).join().unwrap();
+/// assert!(flag.is_set());
+/// flag.reset();
+/// assert!(!flag.is_set());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventFlag(Arc<AtomicBool>);
+
+impl EventFlag {
+    /// Create a new flag that is not set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the flag (waking the computations waiting for it).
+    pub fn set(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Clear the flag, such that it can be used to signal another event.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the flag is set.
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if both flags share the same value.
+    pub fn ptr_eq(&self, other: &EventFlag) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_flag() {
+        let flag = EventFlag::new();
+        let other = flag.clone();
+        assert!(flag.ptr_eq(&other));
+        assert!(!flag.ptr_eq(&EventFlag::new()));
+        assert!(!other.is_set());
+        flag.set();
+        flag.set();
+        assert!(other.is_set());
+        other.reset();
+        assert!(!flag.is_set());
+    }
+}
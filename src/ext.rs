@@ -0,0 +1,196 @@
+use crate::{
+    AdaptiveBudget, Buffered, ChunkingCollector, Collector, Computable, Dedup, Folder, Generatable,
+    IntoGenerator, LastItem, Map, Named, Tee, Throttled, Unique, Windows, YieldPolicy, Yielding,
+};
+#[cfg(feature = "indicatif")]
+use crate::{Progress, WithProgressBar};
+use std::borrow::Cow;
+use std::hash::Hash;
+use std::time::Duration;
+
+/// Combinator methods available on every [`Computable`].
+///
+/// The methods of this trait wrap the computation into adapter types that are again
+/// [`Computable`]. The trait is implemented automatically for all [`Computable`] types,
+/// so it is sufficient to import it (e.g., using [`crate::prelude`]).
+pub trait ComputableExt<T>: Computable<T> {
+    /// Transform the result of this computation using `function`.
+    fn map<R, F: FnOnce(T) -> R>(self, function: F) -> Map<T, Self, F>
+    where
+        Self: Sized,
+    {
+        Map::new(self, function)
+    }
+
+    /// Run every step of this computation with the given [`YieldPolicy`] active.
+    ///
+    /// See [`Yielding`].
+    fn with_yield_policy(self, policy: YieldPolicy) -> Yielding<T, Self>
+    where
+        Self: Sized,
+    {
+        Yielding::new(self, policy)
+    }
+
+    /// Perform a variable number of steps of this computation per outer step, such that
+    /// each outer step takes roughly `target` time.
+    ///
+    /// See [`AdaptiveBudget`].
+    fn with_adaptive_budget(self, target: Duration) -> AdaptiveBudget<T, Self>
+    where
+        Self: Sized,
+    {
+        AdaptiveBudget::new(self, target)
+    }
+
+    /// Enforce a minimum wall-clock `interval` between the steps of this computation.
+    ///
+    /// See [`Throttled`].
+    fn throttled(self, interval: Duration) -> Throttled<T, Self>
+    where
+        Self: Sized,
+    {
+        Throttled::new(self, interval)
+    }
+
+    /// Report the [`Progress`] of this computation using the given progress `bar`.
+    ///
+    /// See [`WithProgressBar`].
+    #[cfg(feature = "indicatif")]
+    fn with_progress_bar(self, bar: indicatif::ProgressBar) -> WithProgressBar<T, Self>
+    where
+        Self: Sized + Progress,
+    {
+        WithProgressBar::new(self, bar)
+    }
+
+    /// Attach a human-readable `name` to this computation.
+    ///
+    /// See [`Named`].
+    fn named<N: Into<Cow<'static, str>>>(self, name: N) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        Named::new(name, self)
+    }
+
+    /// Produce the result of this computation as the only item of a generator.
+    ///
+    /// See [`IntoGenerator`].
+    fn into_generator(self) -> IntoGenerator<T, Self>
+    where
+        Self: Sized,
+    {
+        IntoGenerator::new(self)
+    }
+}
+
+impl<T, C: Computable<T>> ComputableExt<T> for C {}
+
+/// Combinator methods available on every [`Generatable`].
+///
+/// The trait is implemented automatically for all [`Generatable`] types,
+/// so it is sufficient to import it (e.g., using [`crate::prelude`]).
+pub trait GeneratableExt<T>: Generatable<T> {
+    /// Reduce the items of this generator into `init` using `function`.
+    ///
+    /// See [`Folder`].
+    fn folder<ACC, F: FnMut(ACC, T) -> ACC>(self, init: ACC, function: F) -> Folder<T, ACC, F, Self>
+    where
+        Self: Sized,
+    {
+        Folder::new(self, init, function)
+    }
+
+    /// Group the items of this generator into chunks of `chunk_size` items.
+    ///
+    /// See [`ChunkingCollector`].
+    fn chunks(self, chunk_size: usize) -> ChunkingCollector<T, Self>
+    where
+        Self: Sized,
+    {
+        ChunkingCollector::new(self, chunk_size)
+    }
+
+    /// Remove consecutive repeated items of this generator.
+    ///
+    /// See [`Dedup`].
+    fn dedup(self) -> Dedup<T, Self>
+    where
+        Self: Sized,
+        T: Clone + PartialEq,
+    {
+        Dedup::new(self)
+    }
+
+    /// Remove all repeated items of this generator.
+    ///
+    /// See [`Unique`].
+    fn unique(self) -> Unique<T, Self>
+    where
+        Self: Sized,
+        T: Clone + Eq + Hash,
+    {
+        Unique::new(self)
+    }
+
+    /// Remember the last `capacity` items of this generator, such that they can be re-emitted.
+    ///
+    /// See [`Buffered`].
+    fn buffered(self, capacity: usize) -> Buffered<T, Self>
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        Buffered::new(self, capacity)
+    }
+
+    /// Split the items of this generator into two independent handles, such that one handle
+    /// is at most `max_lag` items ahead of the other.
+    ///
+    /// See [`Tee`].
+    fn tee(self, max_lag: usize) -> (Tee<T, Self>, Tee<T, Self>)
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        crate::tee(self, max_lag)
+    }
+
+    /// Produce overlapping sliding windows of `size` consecutive items of this generator.
+    ///
+    /// See [`Windows`].
+    fn windows(self, size: usize) -> Windows<T, Self>
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        Windows::new(self, size)
+    }
+
+    /// Collect all items of this generator into a `COLLECTION`, without boxing
+    /// the generator.
+    ///
+    /// (The method is not called `collect_into` to avoid a collision with the unstable
+    /// [`Iterator`] method of the same name.)
+    ///
+    /// See [`Collector`] and [`Collector::bounded`].
+    fn collector<COLLECTION: Default + Extend<T>>(self) -> Collector<T, COLLECTION, Self>
+    where
+        Self: Sized,
+    {
+        Collector::new(self)
+    }
+
+    /// Compute the last item of this generator.
+    ///
+    /// See [`LastItem`].
+    fn last_item(self) -> LastItem<T, Self>
+    where
+        Self: Sized,
+    {
+        LastItem::new(self)
+    }
+}
+
+impl<T, G: Generatable<T>> GeneratableExt<T> for G {}
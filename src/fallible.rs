@@ -0,0 +1,352 @@
+use crate::{Algorithm, Completable, Computable, FromParts, Incomplete, StatefulMut, StatefulRef};
+use cancel_this::{Cancelled, is_cancelled};
+use std::marker::PhantomData;
+
+/// A [`Computable`] whose result is a [`Result`], i.e., a computation that can fail
+/// with an error of type `E`.
+///
+/// This trait is implemented automatically for every `Computable<Result<T, E>>` and provides
+/// combinators that operate on the successful value or the error of such computation.
+/// A default step-based implementation is provided by [`FallibleComputation`].
+pub trait TryComputable<T, E>: Computable<Result<T, E>> {
+    /// Advance this computation until completion, skipping over all suspended states.
+    ///
+    /// Unlike [`Computable::compute`], the error of the computation and the cancellation
+    /// are merged into a single error type `E` (following the convention of `cancel-this`,
+    /// `E` should implement `From<Cancelled>`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an exhausted computation.
+    fn compute_result(&mut self) -> Result<T, E>
+    where
+        E: From<Cancelled>,
+    {
+        self.compute()?
+    }
+
+    /// Transform the error of this computation using `function`.
+    fn map_err<E2, F: FnOnce(E) -> E2>(self, function: F) -> MapErr<T, E, Self, F>
+    where
+        Self: Sized,
+    {
+        MapErr {
+            computable: self,
+            function: Some(function),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Once this computation succeeds, use its value to create a follow-up computation
+    /// using `function` and continue with it. If this computation fails, the error is
+    /// returned immediately (short-circuit) and `function` is never called.
+    fn and_then<U, C2, F>(self, function: F) -> AndThen<T, U, E, Self, C2, F>
+    where
+        Self: Sized,
+        C2: Computable<Result<U, E>>,
+        F: FnOnce(T) -> C2,
+    {
+        AndThen {
+            first: Some(self),
+            function: Some(function),
+            second: None,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<T, E, C: Computable<Result<T, E>>> TryComputable<T, E> for C {}
+
+/// A [`TryComputable`] that transforms the error of another [`TryComputable`].
+///
+/// See [`TryComputable::map_err`].
+#[derive(Debug, Clone)]
+pub struct MapErr<T, E, C: Computable<Result<T, E>>, F> {
+    computable: C,
+    function: Option<F>,
+    _phantom: PhantomData<(T, E)>,
+}
+
+impl<T, E, E2, C: Computable<Result<T, E>>, F: FnOnce(E) -> E2> Computable<Result<T, E2>>
+    for MapErr<T, E, C, F>
+{
+    fn try_compute(&mut self) -> Completable<Result<T, E2>> {
+        let result = self.computable.try_compute()?;
+        let function = self.function.take().ok_or(Incomplete::Exhausted)?;
+        Ok(result.map_err(function))
+    }
+}
+
+/// A [`TryComputable`] that chains two fallible computations.
+///
+/// See [`TryComputable::and_then`].
+pub struct AndThen<T, U, E, C1, C2, F>
+where
+    C1: Computable<Result<T, E>>,
+    C2: Computable<Result<U, E>>,
+    F: FnOnce(T) -> C2,
+{
+    first: Option<C1>,
+    function: Option<F>,
+    second: Option<C2>,
+    _phantom: PhantomData<(T, U, E)>,
+}
+
+impl<T, U, E, C1, C2, F> Computable<Result<U, E>> for AndThen<T, U, E, C1, C2, F>
+where
+    C1: Computable<Result<T, E>>,
+    C2: Computable<Result<U, E>>,
+    F: FnOnce(T) -> C2,
+{
+    fn try_compute(&mut self) -> Completable<Result<U, E>> {
+        if let Some(second) = self.second.as_mut() {
+            return second.try_compute();
+        }
+        let first = self.first.as_mut().ok_or(Incomplete::Exhausted)?;
+        match first.try_compute()? {
+            Ok(value) => {
+                self.first = None;
+                let function = self.function.take().ok_or(Incomplete::Exhausted)?;
+                self.second = Some(function(value));
+                // The follow-up computation is advanced in the next step.
+                Err(Incomplete::Suspended)
+            }
+            Err(e) => {
+                self.first = None;
+                self.function = None;
+                Ok(Err(e))
+            }
+        }
+    }
+}
+
+/// Defines a single step of a [`FallibleComputation`].
+///
+/// Compared to [`crate::ComputationStep`], the step can also fail with an `ERROR`.
+/// A failure immediately completes the computation.
+pub trait FallibleStep<CONTEXT, STATE, OUTPUT, ERROR> {
+    /// Execute one step of the computation.
+    fn step(context: &CONTEXT, state: &mut STATE) -> Result<Completable<OUTPUT>, ERROR>;
+}
+
+/// A stateful computation that can be suspended, resumed, and that can fail.
+///
+/// `FallibleComputation` is a variant of [`crate::Computation`] based on a [`FallibleStep`].
+/// It implements `Algorithm<CONTEXT, STATE, Result<OUTPUT, ERROR>>` (and hence also
+/// [`TryComputable`]). Once the step fails, the error is returned as the result of the
+/// computation and the computation becomes exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, FallibleComputation, FallibleStep, Incomplete};
+///
+/// struct ParseStep;
+///
+/// impl FallibleStep<Vec<String>, (usize, i32), i32, String> for ParseStep {
+///     fn step(items: &Vec<String>, state: &mut (usize, i32)) -> Result<Completable<i32>, String> {
+///         let Some(item) = items.get(state.0) else {
+///             return Ok(Ok(state.1));
+///         };
+///         state.0 += 1;
+///         state.1 += item.parse::<i32>().map_err(|e| format!("`{item}`: {e}"))?;
+///         Ok(Err(Incomplete::Suspended))
+///     }
+/// }
+///
+/// type Sum = FallibleComputation<Vec<String>, (usize, i32), i32, String, ParseStep>;
+///
+/// let good = vec!["1".to_string(), "2".to_string()];
+/// assert_eq!(Sum::from_parts(good, (0, 0)).compute().unwrap(), Ok(3));
+///
+/// let bad = vec!["1".to_string(), "x".to_string(), "3".to_string()];
+/// assert!(Sum::from_parts(bad, (0, 0)).compute().unwrap().is_err());
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "CONTEXT: serde::Serialize + for<'a> serde::Deserialize<'a>, STATE: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+    context: CONTEXT,
+    state: STATE,
+    failed: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<(OUTPUT, ERROR, STEP)>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, ERROR, STEP> Computable<Result<OUTPUT, ERROR>>
+    for FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+    fn try_compute(&mut self) -> Completable<Result<OUTPUT, ERROR>> {
+        if self.failed {
+            return Err(Incomplete::Exhausted);
+        }
+        is_cancelled!()?;
+        match STEP::step(&self.context, &mut self.state) {
+            Ok(result) => result.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Ok(Err(e))
+            }
+        }
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, ERROR, STEP> FromParts<CONTEXT, STATE>
+    for FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        FallibleComputation {
+            context,
+            state,
+            failed: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        (self.context, self.state)
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, ERROR, STEP> StatefulRef<CONTEXT, STATE>
+    for FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+    fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        &mut self.state
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, ERROR, STEP> StatefulMut<CONTEXT, STATE>
+    for FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, ERROR, STEP> Algorithm<CONTEXT, STATE, Result<OUTPUT, ERROR>>
+    for FallibleComputation<CONTEXT, STATE, OUTPUT, ERROR, STEP>
+where
+    STEP: FallibleStep<CONTEXT, STATE, OUTPUT, ERROR>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComputableIdentity;
+
+    /// Counts down from the context; fails when the state reaches a forbidden value.
+    struct CountdownStep;
+
+    impl FallibleStep<u32, u32, &'static str, String> for CountdownStep {
+        fn step(forbidden: &u32, state: &mut u32) -> Result<Completable<&'static str>, String> {
+            if *state == *forbidden {
+                return Err(format!("reached {}", state));
+            }
+            if *state == 0 {
+                return Ok(Ok("done"));
+            }
+            *state -= 1;
+            Ok(Err(Incomplete::Suspended))
+        }
+    }
+
+    type Countdown = FallibleComputation<u32, u32, &'static str, String, CountdownStep>;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Message(String),
+        Cancelled,
+    }
+
+    impl From<Cancelled> for TestError {
+        fn from(_: Cancelled) -> Self {
+            TestError::Cancelled
+        }
+    }
+
+    #[test]
+    fn test_fallible_success() {
+        let mut computation = Countdown::from_parts(100, 2);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute(), Ok(Ok("done")));
+    }
+
+    #[test]
+    fn test_fallible_short_circuit() {
+        let mut computation = Countdown::from_parts(1, 3);
+        assert_eq!(computation.compute(), Ok(Err("reached 1".to_string())));
+        assert_eq!(computation.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(*computation.state(), 1);
+    }
+
+    #[test]
+    fn test_compute_result_and_map_err() {
+        let computation = Countdown::from_parts(1, 3);
+        let mut mapped = computation.map_err(TestError::Message);
+        assert_eq!(
+            mapped.compute_result(),
+            Err(TestError::Message("reached 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_compute_result_cancelled() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut computation = Countdown::from_parts(100, 3).map_err(TestError::Message);
+        let result = on_trigger(trigger, || computation.compute_result());
+        assert_eq!(result, Err(TestError::Cancelled));
+    }
+
+    #[test]
+    fn test_and_then() {
+        let computation = Countdown::from_parts(100, 2);
+        let mut chained = computation
+            .and_then(|message| ComputableIdentity::from(Ok::<usize, String>(message.len())));
+        assert_eq!(chained.compute(), Ok(Ok(4)));
+        assert_eq!(chained.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_and_then_short_circuit() {
+        let computation = Countdown::from_parts(2, 3);
+        let mut chained = computation.and_then(|_| -> ComputableIdentity<Result<usize, String>> {
+            unreachable!("The first computation fails.")
+        });
+        assert_eq!(chained.compute(), Ok(Err("reached 2".to_string())));
+    }
+}
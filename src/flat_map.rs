@@ -0,0 +1,149 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that maps every item of another [`Generatable`] to a sub-generator and
+/// produces all items of the sub-generators (analogous to [`Iterator::flat_map`]).
+///
+/// Each sub-generator is driven to exhaustion before the next item of the outer generator
+/// is mapped, which makes [`FlatMap`] suitable for tree or graph expansion. Suspensions and
+/// cancellation of both the outer generator and the sub-generators are passed through.
+/// As with [`crate::Flatten`], creating a new sub-generator is reported as
+/// [`Incomplete::Suspended`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, FlatMap, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// /// Produces the multiples `i, 2i, ..., i * i` of `i`.
+/// struct MultiplesStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for MultiplesStep {
+///     fn step(i: &u32, k: &mut u32) -> Completable<Option<u32>> {
+///         *k += 1;
+///         Ok((*k <= *i).then(|| *k * *i))
+///     }
+/// }
+///
+/// type Range = Generator<u32, u32, u32, RangeStep>;
+/// type Multiples = Generator<u32, u32, u32, MultiplesStep>;
+///
+/// let multiples = FlatMap::new(Range::from_parts(3, 0), |i| Multiples::from_parts(i, 0));
+/// let items = multiples.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(items, vec![1, 2, 4, 3, 6, 9]);
+/// ```
+pub struct FlatMap<ITEM, OUT, INNER, F, G = DynGeneratable<ITEM>>
+where
+    INNER: Generatable<OUT>,
+    F: FnMut(ITEM) -> INNER,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    function: F,
+    /// The sub-generator whose items are currently produced.
+    current: Option<INNER>,
+    _phantom: PhantomData<(ITEM, OUT)>,
+}
+
+impl<ITEM, OUT, INNER, F, G> FlatMap<ITEM, OUT, INNER, F, G>
+where
+    INNER: Generatable<OUT>,
+    F: FnMut(ITEM) -> INNER,
+    G: Generatable<ITEM>,
+{
+    /// Create a new [`FlatMap`] which expands the items of `generator` using `function`.
+    pub fn new(generator: G, function: F) -> Self {
+        FlatMap {
+            generator,
+            function,
+            current: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The sub-generator whose items are currently produced, if any.
+    pub fn current(&self) -> Option<&INNER> {
+        self.current.as_ref()
+    }
+}
+
+impl<ITEM, OUT, INNER, F, G> Iterator for FlatMap<ITEM, OUT, INNER, F, G>
+where
+    INNER: Generatable<OUT>,
+    F: FnMut(ITEM) -> INNER,
+    G: Generatable<ITEM>,
+{
+    type Item = Cancellable<OUT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM, OUT, INNER, F, G> Generatable<OUT> for FlatMap<ITEM, OUT, INNER, F, G>
+where
+    INNER: Generatable<OUT>,
+    F: FnMut(ITEM) -> INNER,
+    G: Generatable<ITEM>,
+{
+    fn try_next(&mut self) -> Option<Completable<OUT>> {
+        if let Some(inner) = self.current.as_mut() {
+            match inner.try_next() {
+                Some(Err(Incomplete::Exhausted)) | None => self.current = None,
+                result => return result,
+            }
+        }
+        match self.generator.try_next()? {
+            Ok(item) => {
+                self.current = Some((self.function)(item));
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, test_fixtures::Items};
+
+    #[test]
+    fn test_expand_with_suspensions() {
+        // Every item `i` is expanded into `i`, a suspension, and `-i`.
+        let mut expanded = FlatMap::new(Items::from_parts(vec![1, 0, 2], 0), |i| {
+            Items::from_parts(vec![i, 0, -i], 0)
+        });
+        assert!(expanded.current().is_none());
+        assert_eq!(expanded.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(expanded.try_next(), Some(Ok(1)));
+        assert_eq!(expanded.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(expanded.try_next(), Some(Ok(-1)));
+        let rest = expanded.by_ref().collect::<Cancellable<Vec<_>>>();
+        assert_eq!(rest, Ok(vec![2, -2]));
+        assert_eq!(expanded.try_next(), None);
+    }
+
+    #[test]
+    fn test_tree_expansion() {
+        // Node `n` has children `10n + 1` and `10n + 2`; the leaves are enumerated depth-first.
+        fn children(node: i32) -> Items {
+            Items::from_parts(vec![10 * node + 1, 10 * node + 2], 0)
+        }
+        let leaves = FlatMap::new(children(0), |child| FlatMap::new(children(child), children));
+        let items = leaves.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![111, 112, 121, 122, 211, 212, 221, 222]);
+    }
+}
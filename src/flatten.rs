@@ -0,0 +1,181 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that produces all items of the generators produced by another
+/// [`Generatable`] (analogous to [`Iterator::flatten`]).
+///
+/// The items of each inner generator are produced before the outer generator is advanced
+/// again, so nesting [`Flatten`] yields a depth-first enumeration. Suspensions and cancellation
+/// of both the outer and the inner generators are passed through. To keep the amount of work
+/// per call bounded, obtaining a new inner generator is reported as [`Incomplete::Suspended`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Flatten, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// type Range = Generator<u32, u32, u32, RangeStep>;
+///
+/// /// For every `i` in `1..=max`, produces the generator of `1..=i`.
+/// struct TriangleStep;
+///
+/// impl GeneratorStep<u32, u32, Range> for TriangleStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<Range>> {
+///         *current += 1;
+///         Ok((*current <= *max).then(|| Range::from_parts(*current, 0)))
+///     }
+/// }
+///
+/// let triangle = Generator::<u32, u32, Range, TriangleStep>::from_parts(3, 0);
+/// let items = Flatten::new(triangle).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(items, vec![1, 1, 2, 1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, INNER: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Flatten<ITEM, INNER, G = DynGeneratable<INNER>>
+where
+    INNER: Generatable<ITEM>,
+    G: Generatable<INNER>,
+{
+    generator: G,
+    /// The inner generator whose items are currently produced.
+    current: Option<INNER>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<ITEM>,
+}
+
+impl<ITEM, INNER, G> Flatten<ITEM, INNER, G>
+where
+    INNER: Generatable<ITEM>,
+    G: Generatable<INNER>,
+{
+    /// Create a new [`Flatten`] adapter over the generators produced by `generator`.
+    pub fn new(generator: G) -> Self {
+        Flatten {
+            generator,
+            current: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The inner generator whose items are currently produced, if any.
+    pub fn current(&self) -> Option<&INNER> {
+        self.current.as_ref()
+    }
+}
+
+impl<ITEM, INNER, G> Iterator for Flatten<ITEM, INNER, G>
+where
+    INNER: Generatable<ITEM>,
+    G: Generatable<INNER>,
+{
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM, INNER, G> Generatable<ITEM> for Flatten<ITEM, INNER, G>
+where
+    INNER: Generatable<ITEM>,
+    G: Generatable<INNER>,
+{
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        if let Some(inner) = self.current.as_mut() {
+            match inner.try_next() {
+                Some(Err(Incomplete::Exhausted)) | None => self.current = None,
+                result => return result,
+            }
+        }
+        match self.generator.try_next()? {
+            Ok(inner) => {
+                self.current = Some(inner);
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Generator, GeneratorStep, test_fixtures::Items};
+
+    /// Produces the [`Items`] generators; an empty list stands for a suspension.
+    struct GroupsStep;
+
+    impl GeneratorStep<Vec<Vec<i32>>, usize, Items> for GroupsStep {
+        fn step(groups: &Vec<Vec<i32>>, index: &mut usize) -> Completable<Option<Items>> {
+            let group = groups.get(*index).cloned();
+            *index += 1;
+            match group {
+                Some(group) if group.is_empty() => Err(Incomplete::Suspended),
+                group => Ok(group.map(|it| Items::from_parts(it, 0))),
+            }
+        }
+    }
+
+    type Groups = Generator<Vec<Vec<i32>>, usize, Items, GroupsStep>;
+
+    #[test]
+    fn test_flatten_with_suspensions() {
+        let groups = Groups::from_parts(vec![vec![1, 0, 2], vec![], vec![3]], 0);
+        let mut flatten = Flatten::new(groups);
+        assert!(flatten.current().is_none());
+        assert_eq!(flatten.try_next(), Some(Err(Incomplete::Suspended)));
+        assert!(flatten.current().is_some());
+        assert_eq!(flatten.try_next(), Some(Ok(1)));
+        // Suspension of the inner generator.
+        assert_eq!(flatten.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(flatten.try_next(), Some(Ok(2)));
+        // Suspension of the outer generator.
+        assert_eq!(flatten.try_next(), Some(Err(Incomplete::Suspended)));
+        assert!(flatten.current().is_none());
+        assert_eq!(flatten.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(flatten.try_next(), Some(Ok(3)));
+        assert_eq!(flatten.try_next(), None);
+        assert_eq!(flatten.try_next(), None);
+    }
+
+    #[test]
+    fn test_empty_inner_generators() {
+        let groups = Groups::from_parts(vec![vec![1, 2], vec![], vec![3, 4]], 0);
+        let items = Flatten::new(groups).collect::<Cancellable<Vec<_>>>();
+        assert_eq!(items, Ok(vec![1, 2, 3, 4]));
+        let groups = Groups::from_parts(vec![], 0);
+        assert_eq!(Flatten::new(groups).try_next(), None);
+    }
+
+    #[test]
+    fn test_cancelled() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut flatten = Flatten::new(Groups::from_parts(vec![vec![1]], 0));
+        let result = on_trigger(trigger, || {
+            flatten.by_ref().collect::<Cancellable<Vec<_>>>()
+        });
+        assert!(result.is_err());
+    }
+}
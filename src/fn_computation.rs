@@ -0,0 +1,178 @@
+use crate::{Completable, Computable, StatefulMut, StatefulRef};
+use cancel_this::is_cancelled;
+use std::marker::PhantomData;
+
+/// A stateful computation whose step is given by a closure instead of a [`crate::ComputationStep`].
+///
+/// This is convenient for quick computations, prototypes, and tests, where declaring a dedicated
+/// step type would be unnecessarily verbose. Otherwise, `FnComputation` behaves the same as
+/// [`crate::Computation`] (including cancellation checks before every step).
+///
+/// Since the closure cannot be reconstructed from `CONTEXT` and `STATE` alone,
+/// `FnComputation` does not implement [`crate::FromParts`] (and hence neither
+/// [`crate::Algorithm`]). It implements [`StatefulRef`] and [`StatefulMut`], so it can still
+/// be used as a [`crate::StatefulAlgorithm`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{FnComputation, Incomplete};
+///
+/// let mut computation = FnComputation::new(vec![1, 2, 3], (0, 0), |items: &Vec<i32>, (index, sum): &mut (usize, i32)| {
+///     match items.get(*index) {
+///         Some(item) => {
+///             *index += 1;
+///             *sum += item;
+///             Err(Incomplete::Suspended)
+///         }
+///         None => Ok(*sum),
+///     }
+/// });
+/// assert_eq!(computation.compute().unwrap(), 6);
+/// ```
+pub struct FnComputation<CONTEXT, STATE, OUTPUT, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<OUTPUT>,
+{
+    context: CONTEXT,
+    state: STATE,
+    step: F,
+    _phantom: PhantomData<OUTPUT>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, F> FnComputation<CONTEXT, STATE, OUTPUT, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<OUTPUT>,
+{
+    /// Create a new computation from `context`, initial `state`, and a `step` closure.
+    pub fn new(context: CONTEXT, state: STATE, step: F) -> Self {
+        FnComputation {
+            context,
+            state,
+            step,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Destruct the computation into `CONTEXT` and `STATE` objects, dropping the closure.
+    pub fn into_parts(self) -> (CONTEXT, STATE) {
+        (self.context, self.state)
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, F> Computable<OUTPUT> for FnComputation<CONTEXT, STATE, OUTPUT, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<OUTPUT>,
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        is_cancelled!()?;
+        (self.step)(&self.context, &mut self.state)
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, F> StatefulRef<CONTEXT, STATE>
+    for FnComputation<CONTEXT, STATE, OUTPUT, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<OUTPUT>,
+{
+    fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        &mut self.state
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, F> StatefulMut<CONTEXT, STATE>
+    for FnComputation<CONTEXT, STATE, OUTPUT, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<OUTPUT>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DynStatefulAlgorithm, Incomplete};
+
+    fn countdown(
+        start: u32,
+    ) -> FnComputation<
+        &'static str,
+        u32,
+        String,
+        impl FnMut(&&'static str, &mut u32) -> Completable<String>,
+    > {
+        FnComputation::new("done", start, |message: &&'static str, count: &mut u32| {
+            if *count == 0 {
+                Ok(message.to_string())
+            } else {
+                *count -= 1;
+                Err(Incomplete::Suspended)
+            }
+        })
+    }
+
+    #[test]
+    fn test_fn_computation_steps() {
+        let mut computation = countdown(2);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*computation.state(), 1);
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute(), Ok("done".to_string()));
+        assert_eq!(computation.into_parts(), ("done", 0));
+    }
+
+    #[test]
+    fn test_fn_computation_captures_environment() {
+        let mut steps = 0;
+        let mut computation = FnComputation::new(3, 0, |target: &u32, state: &mut u32| {
+            steps += 1;
+            *state += 1;
+            if *state == *target {
+                Ok(*state * 10)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        });
+        assert_eq!(computation.compute(), Ok(30));
+        assert_eq!(steps, 3);
+    }
+
+    #[test]
+    fn test_fn_computation_dyn_stateful_algorithm() {
+        let mut computation: DynStatefulAlgorithm<&'static str, u32, String> =
+            Box::new(countdown(1));
+        assert_eq!(*computation.context(), "done");
+        assert_eq!(computation.compute().unwrap(), "done");
+    }
+
+    #[test]
+    fn test_fn_computation_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut computation = countdown(3);
+        let result = on_trigger(trigger, || computation.try_compute());
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(*computation.state(), 3);
+    }
+
+    #[test]
+    fn test_fn_computation_update_context() {
+        let mut computation = countdown(1);
+        computation.replace_context("changed");
+        assert_eq!(computation.compute(), Ok("changed".to_string()));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{Completable, Generatable, Incomplete, StatefulMut, StatefulRef};
+use cancel_this::{Cancellable, is_cancelled};
+use std::marker::PhantomData;
+
+/// A stateful generator whose step is given by a closure instead of a [`crate::GeneratorStep`].
+///
+/// This is the generator counterpart of [`crate::FnComputation`]: the closure returns
+/// `Ok(Some(item))` to yield an item, `Ok(None)` once the generator is exhausted, or
+/// `Err(Incomplete::Suspended)` to yield control without producing an item.
+///
+/// As with [`crate::FnComputation`], the closure cannot be reconstructed from `CONTEXT` and
+/// `STATE` alone, so `FnGenerator` does not implement [`crate::FromParts`] (and hence neither
+/// [`crate::GenAlgorithm`]). It implements [`StatefulRef`] and [`StatefulMut`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::FnGenerator;
+///
+/// let generator = FnGenerator::new(3u32, 0u32, |max: &u32, current: &mut u32| {
+///     *current += 1;
+///     Ok((*current <= *max).then_some(*current * 10))
+/// });
+/// let items: Vec<u32> = generator.map(|item| item.unwrap()).collect();
+/// assert_eq!(items, vec![10, 20, 30]);
+/// ```
+pub struct FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    context: CONTEXT,
+    state: STATE,
+    exhausted: bool,
+    step: F,
+    _phantom: PhantomData<ITEM>,
+}
+
+impl<CONTEXT, STATE, ITEM, F> FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    /// Create a new generator from `context`, initial `state`, and a `step` closure.
+    pub fn new(context: CONTEXT, state: STATE, step: F) -> Self {
+        FnGenerator {
+            context,
+            state,
+            exhausted: false,
+            step,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Destruct the generator into `CONTEXT` and `STATE` objects, dropping the closure.
+    pub fn into_parts(self) -> (CONTEXT, STATE) {
+        (self.context, self.state)
+    }
+}
+
+impl<CONTEXT, STATE, ITEM, F> Iterator for FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<CONTEXT, STATE, ITEM, F> Generatable<ITEM> for FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        if self.exhausted {
+            return None;
+        }
+        if let Err(e) = is_cancelled!() {
+            return Some(Err(Incomplete::Cancelled(e)));
+        }
+        match (self.step)(&self.context, &mut self.state) {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) | Err(Incomplete::Exhausted) => {
+                self.exhausted = true;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<CONTEXT, STATE, ITEM, F> StatefulRef<CONTEXT, STATE> for FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        &mut self.state
+    }
+}
+
+impl<CONTEXT, STATE, ITEM, F> StatefulMut<CONTEXT, STATE> for FnGenerator<CONTEXT, STATE, ITEM, F>
+where
+    F: FnMut(&CONTEXT, &mut STATE) -> Completable<Option<ITEM>>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collector, Computable};
+
+    type ItemsFn = fn(&Vec<i32>, &mut (usize, bool)) -> Completable<Option<i32>>;
+
+    /// Yields the items of the context, suspending before every odd item.
+    fn items_generator(items: Vec<i32>) -> FnGenerator<Vec<i32>, (usize, bool), i32, ItemsFn> {
+        FnGenerator::new(
+            items,
+            (0, false),
+            |items: &Vec<i32>, (index, resumed): &mut (usize, bool)| {
+                let Some(item) = items.get(*index).copied() else {
+                    return Ok(None);
+                };
+                if item % 2 != 0 && !*resumed {
+                    *resumed = true;
+                    return Err(Incomplete::Suspended);
+                }
+                *resumed = false;
+                *index += 1;
+                Ok(Some(item))
+            },
+        )
+    }
+
+    #[test]
+    fn test_fn_generator_try_next() {
+        let mut generator = items_generator(vec![2, 3]);
+        assert_eq!(generator.try_next(), Some(Ok(2)));
+        assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(generator.try_next(), Some(Ok(3)));
+        assert_eq!(generator.try_next(), None);
+        assert_eq!(generator.try_next(), None);
+        assert_eq!(generator.into_parts().1, (2, false));
+    }
+
+    #[test]
+    fn test_fn_generator_iterator_skips_suspensions() {
+        let generator = items_generator(vec![1, 2, 3, 4]);
+        let items: Vec<i32> = generator.map(|item| item.unwrap()).collect();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fn_generator_computation() {
+        let generator = items_generator(vec![5, 6, 7]);
+        assert_eq!(*generator.context(), vec![5, 6, 7]);
+        let mut collector = Collector::<i32, Vec<i32>, _>::new(generator);
+        assert_eq!(collector.compute().unwrap(), vec![5, 6, 7]);
+    }
+
+    #[test]
+    fn test_fn_generator_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut generator = items_generator(vec![1]);
+        let result = on_trigger(trigger, || generator.try_next().unwrap());
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(*generator.state(), (0, false));
+    }
+    #[test]
+    fn test_fn_generator_update_context() {
+        let mut generator = items_generator(vec![2, 4]);
+        assert_eq!(generator.try_next(), Some(Ok(2)));
+        generator.replace_context(vec![2, 6, 8]);
+        let items: Vec<i32> = generator.map(|item| item.unwrap()).collect();
+        assert_eq!(items, vec![6, 8]);
+    }
+}
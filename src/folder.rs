@@ -0,0 +1,139 @@
+use crate::{Completable, Computable, DynGeneratable, Generatable, Incomplete};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that reduces all items from a [`Generatable`] into a single accumulator
+/// value using a closure.
+///
+/// Unlike [`crate::Collector`], the items are never stored, so this is suitable for computing
+/// aggregates (sum, running maximum, custom merge) over very large generators. The folder
+/// suspends after every processed item.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Generator, GeneratorStep, Completable, Computable, Folder, FromParts};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(4, 0);
+/// let mut folder = Folder::new(generator, 0, |acc, item| acc + item);
+/// assert_eq!(folder.compute().unwrap(), 10);
+/// ```
+pub struct Folder<ITEM, ACC, F, G = DynGeneratable<ITEM>>
+where
+    F: FnMut(ACC, ITEM) -> ACC,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    accumulator: Option<ACC>,
+    function: F,
+    _phantom: PhantomData<ITEM>,
+}
+
+impl<ITEM, ACC, F, G> Folder<ITEM, ACC, F, G>
+where
+    F: FnMut(ACC, ITEM) -> ACC,
+    G: Generatable<ITEM>,
+{
+    /// Create a new folder that reduces the items of `generator` into `init` using `function`.
+    pub fn new(generator: G, init: ACC, function: F) -> Self {
+        Folder {
+            generator,
+            accumulator: Some(init),
+            function,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// A reference to the current value of the accumulator, assuming the folder
+    /// has not completed yet.
+    pub fn accumulator(&self) -> Option<&ACC> {
+        self.accumulator.as_ref()
+    }
+}
+
+impl<ITEM, ACC, F, G> Computable<ACC> for Folder<ITEM, ACC, F, G>
+where
+    F: FnMut(ACC, ITEM) -> ACC,
+    G: Generatable<ITEM>,
+{
+    fn try_compute(&mut self) -> Completable<ACC> {
+        match self.generator.try_next() {
+            None => self.accumulator.take().ok_or(Incomplete::Exhausted),
+            Some(Ok(item)) => {
+                let accumulator = self.accumulator.take().ok_or(Incomplete::Exhausted)?;
+                self.accumulator = Some((self.function)(accumulator, item));
+                Err(Incomplete::Suspended)
+            }
+            Some(Err(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, test_fixtures::Items};
+
+    #[test]
+    fn test_folder_sum() {
+        let generator = Items::from_parts(vec![1, 2, 3], 0);
+        let mut folder = Folder::new(generator, 0, |acc, item| acc + item);
+
+        assert_eq!(folder.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(folder.accumulator(), Some(&1));
+        assert_eq!(folder.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(folder.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(folder.try_compute(), Ok(6));
+        assert_eq!(folder.accumulator(), None);
+        assert_eq!(folder.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_folder_running_max() {
+        let generator = Items::from_parts(vec![3, 9, -2, 7], 0);
+        let mut folder = Folder::new(
+            generator.dyn_generatable(),
+            None,
+            |acc: Option<i32>, item| Some(acc.map_or(item, |max| max.max(item))),
+        );
+        assert_eq!(folder.compute().unwrap(), Some(9));
+    }
+
+    #[test]
+    fn test_generatable_ext_folder() {
+        use crate::GeneratableExt;
+
+        let generator = Items::from_parts(vec![1, 2, 3], 0);
+        let mut folder = generator.folder(String::new(), |acc, item| format!("{acc}{item}"));
+        assert_eq!(folder.compute().unwrap(), "123");
+    }
+
+    #[test]
+    fn test_folder_empty() {
+        let generator = Items::from_parts(vec![], 0);
+        let mut folder = Folder::new(generator, 42, |acc, item| acc + item);
+        assert_eq!(folder.try_compute(), Ok(42));
+    }
+
+    #[test]
+    fn test_folder_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let generator = Items::from_parts(vec![1, 2, 3], 0);
+        let mut folder = Folder::new(generator, 0, |acc, item| acc + item);
+        let result = on_trigger(trigger, || folder.try_compute());
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(folder.accumulator(), Some(&0));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Computation, ComputationStep, Generator, GeneratorStep};
+
+/// Objects (typically [`crate::Algorithm`] or [`crate::GenAlgorithm`] instances) that can be
+/// forked into independent copies at a suspend point.
+///
+/// Compared to [`Clone`], forking makes an explicit promise: the copy is only taken when the
+/// computation is *quiescent*, i.e., it is not in the middle of a step. Both copies can then be
+/// advanced independently (e.g., to explore alternative branches of a search, or to compare
+/// two heuristics on the same partial state).
+///
+/// For the default [`Computation`] and [`Generator`], a step can never be observed "in
+/// progress", since it requires exclusive access to the state. However, computations that
+/// share parts of their state (e.g., through reference counting) can override
+/// [`Forkable::is_quiescent`], in which case [`Forkable::fork`] checks this condition
+/// using a debug assertion.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let mut computation = Computation::<u32, u32, u32, CountStep>::from_parts(5, 0);
+/// assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+///
+/// let mut branch = computation.fork();
+/// *branch.state_mut() = 10;
+/// assert_eq!(branch.compute().unwrap(), 11);
+/// assert_eq!(computation.compute().unwrap(), 5);
+/// ```
+pub trait Forkable: Sized {
+    /// Returns `true` if this object is at a suspend point and can be safely forked.
+    fn is_quiescent(&self) -> bool {
+        true
+    }
+
+    /// Create an independent copy of this object without checking [`Forkable::is_quiescent`].
+    ///
+    /// This is the method implementors should provide; users should call [`Forkable::fork`].
+    fn fork_unchecked(&self) -> Self;
+
+    /// Create an independent copy of this object.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the object is not [`Forkable::is_quiescent`].
+    fn fork(&self) -> Self {
+        debug_assert!(
+            self.is_quiescent(),
+            "Cannot fork a computation that is in the middle of a step."
+        );
+        self.fork_unchecked()
+    }
+}
+
+impl<CONTEXT: Clone, STATE: Clone, OUTPUT, STEP: ComputationStep<CONTEXT, STATE, OUTPUT>> Forkable
+    for Computation<CONTEXT, STATE, OUTPUT, STEP>
+{
+    fn fork_unchecked(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<CONTEXT: Clone, STATE: Clone, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Forkable
+    for Generator<CONTEXT, STATE, ITEM, STEP>
+{
+    fn fork_unchecked(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Completable, Computable, FromParts, Generatable, Incomplete, test_fixtures::ItemsStep,
+    };
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_fork_generator() {
+        let mut generator = Generator::<Vec<i32>, usize, i32, ItemsStep>::from_parts(vec![1, 2], 0);
+        assert_eq!(generator.try_next(), Some(Ok(1)));
+        let mut fork = generator.fork();
+        assert_eq!(generator.try_next(), Some(Ok(2)));
+        assert_eq!(generator.try_next(), None);
+        assert_eq!(fork.try_next(), Some(Ok(2)));
+    }
+
+    /// A computation that shares a "step in progress" flag with its environment.
+    struct SharedFlagComputation {
+        in_step: Rc<Cell<bool>>,
+        remaining: u32,
+    }
+
+    impl Computable<u32> for SharedFlagComputation {
+        fn try_compute(&mut self) -> Completable<u32> {
+            if self.remaining == 0 {
+                return Ok(0);
+            }
+            self.remaining -= 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    impl Forkable for SharedFlagComputation {
+        fn is_quiescent(&self) -> bool {
+            !self.in_step.get()
+        }
+
+        fn fork_unchecked(&self) -> Self {
+            SharedFlagComputation {
+                in_step: Rc::new(Cell::new(false)),
+                remaining: self.remaining,
+            }
+        }
+    }
+
+    #[test]
+    fn test_fork_quiescent() {
+        let computation = SharedFlagComputation {
+            in_step: Rc::new(Cell::new(false)),
+            remaining: 2,
+        };
+        let mut fork = computation.fork();
+        assert_eq!(fork.compute(), Ok(0));
+        assert_eq!(computation.remaining, 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "middle of a step")]
+    fn test_fork_mid_step_panics() {
+        let computation = SharedFlagComputation {
+            in_step: Rc::new(Cell::new(true)),
+            remaining: 2,
+        };
+        let _ = computation.fork();
+    }
+}
@@ -0,0 +1,62 @@
+/// The constructor part of [`crate::Stateful`]: objects that can be created from
+/// (and destructed into) a `CONTEXT` and a `STATE`.
+///
+/// See [`crate::StatefulRef`] for the accessor part.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count < *target { Err(Incomplete::Suspended) } else { Ok(*count) }
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// let mut count = Count::configure(5u8, 1u8);
+/// assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+/// let (target, state) = count.into_parts();
+/// assert_eq!((target, state), (5, 2));
+/// assert_eq!(Count::from_parts(target, state).compute(), Ok(5));
+/// ```
+pub trait FromParts<CONTEXT, STATE> {
+    /// Create new instance using values that can be converted to `CONTEXT` and `STATE`.
+    fn configure<I1: Into<CONTEXT>, I2: Into<STATE>>(context: I1, initial_state: I2) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        Self::from_parts(context.into(), initial_state.into())
+    }
+
+    /// Create new instance from `CONTEXT` and `STATE`.
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static;
+
+    /// Destruct the instance into `CONTEXT` and `STATE` objects.
+    fn into_parts(self) -> (CONTEXT, STATE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Count;
+    use crate::{Computable, StatefulRef};
+
+    #[test]
+    fn test_round_trip() {
+        let mut count = Count::configure(3u8, 0u8);
+        assert!(count.try_compute().is_err());
+        let (target, state) = count.into_parts();
+        assert_eq!((target, state), (3, 1));
+        let count = Count::from_parts(target, state);
+        assert_eq!(count.state(), &1);
+    }
+}
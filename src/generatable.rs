@@ -1,4 +1,8 @@
-use crate::{Completable, DynGeneratable};
+use crate::blocking_iter::next_skip_suspended;
+use crate::{
+    BlockingIter, BudgetIter, CancelPolicy, Completable, DynGeneratable, DynGeneratableSend,
+    StepIter,
+};
 use cancel_this::Cancellable;
 
 /// An alternative to [`crate::Computable`] which is intended for generators.
@@ -14,6 +18,108 @@ pub trait Generatable<T>: Iterator<Item = Cancellable<T>> {
     /// - `None` when the generator is exhausted
     fn try_next(&mut self) -> Option<Completable<T>>;
 
+    /// Create an [`Iterator`] view of this [`Generatable`] that yields plain `T` items,
+    /// skipping over all suspended states and handling cancellation according to `policy`.
+    fn blocking_iter(&mut self, policy: CancelPolicy) -> BlockingIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        BlockingIter::new(self, policy)
+    }
+
+    /// Create an [`Iterator`] view of this [`Generatable`] that yields every step,
+    /// including suspended states (one step per call to [`Iterator::next`]).
+    fn iter_steps(&mut self) -> StepIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        StepIter::new(self)
+    }
+
+    /// Create an [`Iterator`] view of this [`Generatable`] that skips over suspended states,
+    /// but stops after `suspensions` suspended states are encountered.
+    fn iter_with_budget(&mut self, suspensions: usize) -> BudgetIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        BudgetIter::new(self, suspensions)
+    }
+
+    /// Call `f` for every remaining item of this [`Generatable`], skipping over suspended
+    /// states. Returns an error if the generator is cancelled.
+    ///
+    /// This is the generator counterpart of [`Iterator::for_each`] (the name avoids
+    /// a clash with it).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use computation_process::prelude::*;
+    /// use computation_process::{Completable, Generator, GeneratorStep, Incomplete};
+    ///
+    /// struct CountStep;
+    ///
+    /// impl GeneratorStep<u32, u32, u32> for CountStep {
+    ///     fn step(max: &u32, count: &mut u32) -> Completable<Option<u32>> {
+    ///         *count += 1;
+    ///         if *count % 2 == 0 {
+    ///             return Err(Incomplete::Suspended);
+    ///         }
+    ///         Ok((*count <= *max).then_some(*count))
+    ///     }
+    /// }
+    ///
+    /// let mut generator = Generator::<u32, u32, u32, CountStep>::from_parts(7, 0);
+    /// let mut odd = Vec::new();
+    /// generator.for_each_item(|item| odd.push(item)).unwrap();
+    /// assert_eq!(odd, vec![1, 3, 5, 7]);
+    /// ```
+    fn for_each_item<F: FnMut(T)>(&mut self, mut f: F) -> Cancellable<()>
+    where
+        Self: Sized,
+    {
+        while let Some(item) = next_skip_suspended(self) {
+            f(item?);
+        }
+        Ok(())
+    }
+
+    /// Fold all remaining items of this [`Generatable`] into an accumulator, skipping over
+    /// suspended states. Returns an error if the generator is cancelled.
+    ///
+    /// This is the generator counterpart of [`Iterator::fold`] (the name avoids
+    /// a clash with it).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use computation_process::prelude::*;
+    /// use computation_process::{Completable, Generator, GeneratorStep};
+    ///
+    /// struct CountStep;
+    ///
+    /// impl GeneratorStep<u32, u32, u32> for CountStep {
+    ///     fn step(max: &u32, count: &mut u32) -> Completable<Option<u32>> {
+    ///         *count += 1;
+    ///         Ok((*count <= *max).then_some(*count))
+    ///     }
+    /// }
+    ///
+    /// let mut generator = Generator::<u32, u32, u32, CountStep>::from_parts(4, 0);
+    /// let sum = generator.fold_items(0, |acc, item| acc + item).unwrap();
+    /// assert_eq!(sum, 10);
+    /// ```
+    fn fold_items<ACC, F: FnMut(ACC, T) -> ACC>(&mut self, init: ACC, mut f: F) -> Cancellable<ACC>
+    where
+        Self: Sized,
+    {
+        let mut acc = init;
+        while let Some(item) = next_skip_suspended(self) {
+            acc = f(acc, item?);
+        }
+        Ok(acc)
+    }
+
     /// Utility method to convert this [`Generatable`] to a dynamic type.
     fn dyn_generatable(self) -> DynGeneratable<T>
     where
@@ -21,4 +127,48 @@ pub trait Generatable<T>: Iterator<Item = Cancellable<T>> {
     {
         Box::new(self)
     }
+
+    /// Utility method to convert this [`Generatable`] to a dynamic type that can be
+    /// sent to other threads.
+    fn dyn_generatable_send(self) -> DynGeneratableSend<T>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GenAlgorithm, test_fixtures::Items};
+    use cancel_this::{CancelAtomic, on_trigger};
+
+    #[test]
+    fn test_for_each_item() {
+        let mut generator = Items::from_parts(vec![1, 0, 2, 0, 0, 3], 0);
+        let mut items = Vec::new();
+        assert_eq!(generator.for_each_item(|it| items.push(it)), Ok(()));
+        assert_eq!(items, vec![1, 2, 3]);
+        // The generator is exhausted.
+        assert_eq!(generator.for_each_item(|_| unreachable!()), Ok(()));
+    }
+
+    #[test]
+    fn test_fold_items_on_algorithm() {
+        fn total<A: GenAlgorithm<Vec<i32>, usize, i32>>(mut algorithm: A) -> Cancellable<i32> {
+            algorithm.fold_items(0, |acc, it| acc + it)
+        }
+        assert_eq!(total(Items::from_parts(vec![4, 0, 5], 0)), Ok(9));
+        assert_eq!(total(Items::from_parts(vec![], 0)), Ok(0));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut generator = Items::from_parts(vec![1, 2], 0);
+        let result = on_trigger(trigger, || generator.fold_items(0, |acc, it| acc + it));
+        assert!(result.is_err());
+    }
 }
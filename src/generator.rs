@@ -1,7 +1,8 @@
 use crate::generatable::Generatable;
-use crate::{Completable, GenAlgorithm, Incomplete, Stateful};
+use crate::{Completable, FromParts, GenAlgorithm, Incomplete, StatefulMut, StatefulRef};
 use cancel_this::{Cancellable, is_cancelled};
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 
 /// Defines a single step of a [`Generator`].
 ///
@@ -43,7 +44,7 @@ pub trait GeneratorStep<CONTEXT, STATE, ITEM> {
 /// # Example
 ///
 /// ```rust
-/// use computation_process::{Generator, GeneratorStep, Completable, Generatable, Stateful};
+/// use computation_process::{Generator, GeneratorStep, Completable, Generatable, FromParts};
 ///
 /// struct CountStep;
 ///
@@ -76,10 +77,95 @@ pub struct Generator<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, I
     context: CONTEXT,
     state: STATE,
     exhausted: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "crate::computation::default_cancel_check_interval")
+    )]
+    cancel_check_interval: NonZeroUsize,
+    /// The number of steps since the last cancellation check.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    since_check: usize,
     #[cfg_attr(feature = "serde", serde(skip))]
     _phantom: PhantomData<(ITEM, STEP)>,
 }
 
+// Clone/PartialEq/Eq are implemented manually, because derive would also require
+// `ITEM` and `STEP` to implement the same traits.
+
+impl<CONTEXT: Clone, STATE: Clone, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Clone
+    for Generator<CONTEXT, STATE, ITEM, STEP>
+{
+    /// Create an independent copy of this generator. When cloned at a suspend point,
+    /// both copies can be advanced separately (e.g., to explore different branches).
+    fn clone(&self) -> Self {
+        Generator {
+            context: self.context.clone(),
+            state: self.state.clone(),
+            exhausted: self.exhausted,
+            cancel_check_interval: self.cancel_check_interval,
+            since_check: self.since_check,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<CONTEXT: PartialEq, STATE: PartialEq, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>>
+    PartialEq for Generator<CONTEXT, STATE, ITEM, STEP>
+{
+    /// The cancellation check intervals are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.context == other.context
+            && self.state == other.state
+            && self.exhausted == other.exhausted
+    }
+}
+
+impl<CONTEXT: Eq, STATE: Eq, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Eq
+    for Generator<CONTEXT, STATE, ITEM, STEP>
+{
+}
+
+impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>>
+    Generator<CONTEXT, STATE, ITEM, STEP>
+{
+    /// Check cancellation only before every `interval`-th step (the default is `1`, i.e.,
+    /// before every step). This way, the cancellation checks do not dominate cheap generators,
+    /// but a cancellation can be observed up to `interval - 1` steps later.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn with_cancel_check_interval(mut self, interval: usize) -> Self {
+        self.set_cancel_check_interval(interval);
+        self
+    }
+
+    /// Change the cancellation check interval. See [`Generator::with_cancel_check_interval`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn set_cancel_check_interval(&mut self, interval: usize) {
+        self.cancel_check_interval =
+            NonZeroUsize::new(interval).expect("Cancel check interval must be positive.");
+        self.since_check = 0;
+    }
+
+    /// The number of steps per cancellation check.
+    pub fn cancel_check_interval(&self) -> usize {
+        self.cancel_check_interval.get()
+    }
+
+    /// Check cancellation if the interval since the last check elapsed.
+    fn check_cancelled(&mut self) -> Cancellable<()> {
+        if self.since_check == 0 {
+            is_cancelled!()?;
+        }
+        self.since_check = (self.since_check + 1) % self.cancel_check_interval.get();
+        Ok(())
+    }
+}
+
 impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Iterator
     for Generator<CONTEXT, STATE, ITEM, STEP>
 {
@@ -90,7 +176,7 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Iterator
             return None;
         }
         loop {
-            if let Err(e) = is_cancelled!() {
+            if let Err(e) = self.check_cancelled() {
                 return Some(Err(e));
             }
 
@@ -102,6 +188,7 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Iterator
                 Ok(Some(item)) => return Some(Ok(item)),
                 Err(Incomplete::Suspended) => continue,
                 Err(Incomplete::Cancelled(c)) => return Some(Err(c)),
+                Err(Incomplete::ResourceExceeded(e)) => return Some(Err(e.into())),
                 Err(Incomplete::Exhausted) => {
                     self.exhausted = true;
                     return None;
@@ -118,7 +205,7 @@ impl<CONTEXT, STATE, OUTPUT, STEP: GeneratorStep<CONTEXT, STATE, OUTPUT>> Genera
         if self.exhausted {
             return None;
         }
-        if let Err(e) = is_cancelled!() {
+        if let Err(e) = self.check_cancelled() {
             return Some(Err(Incomplete::Cancelled(e)));
         }
         match STEP::step(&self.context, &mut self.state) {
@@ -136,7 +223,7 @@ impl<CONTEXT, STATE, OUTPUT, STEP: GeneratorStep<CONTEXT, STATE, OUTPUT>> Genera
     }
 }
 
-impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Stateful<CONTEXT, STATE>
+impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> FromParts<CONTEXT, STATE>
     for Generator<CONTEXT, STATE, ITEM, STEP>
 {
     fn from_parts(context: CONTEXT, state: STATE) -> Self
@@ -147,6 +234,8 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Stateful<C
             context,
             state,
             exhausted: false,
+            cancel_check_interval: NonZeroUsize::MIN,
+            since_check: 0,
             _phantom: Default::default(),
         }
     }
@@ -154,7 +243,11 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Stateful<C
     fn into_parts(self) -> (CONTEXT, STATE) {
         (self.context, self.state)
     }
+}
 
+impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> StatefulRef<CONTEXT, STATE>
+    for Generator<CONTEXT, STATE, ITEM, STEP>
+{
     fn context(&self) -> &CONTEXT {
         &self.context
     }
@@ -168,6 +261,14 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> Stateful<C
     }
 }
 
+impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>> StatefulMut<CONTEXT, STATE>
+    for Generator<CONTEXT, STATE, ITEM, STEP>
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
 impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>>
     GenAlgorithm<CONTEXT, STATE, ITEM> for Generator<CONTEXT, STATE, ITEM, STEP>
 {
@@ -176,8 +277,8 @@ impl<CONTEXT, STATE, ITEM, STEP: GeneratorStep<CONTEXT, STATE, ITEM>>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{GenAlgorithm, Generatable, Incomplete, Stateful};
-    use cancel_this::Cancellable;
+    use crate::{GenAlgorithm, Generatable, Incomplete};
+    use cancel_this::{Cancellable, Cancelled};
 
     struct SimpleGeneratorStep;
 
@@ -194,6 +295,25 @@ mod tests {
 
     type SimpleTestGenerator = Generator<i32, u32, String, SimpleGeneratorStep>;
 
+    #[test]
+    fn test_generator_clone() {
+        let mut generator = SimpleTestGenerator::from_parts(7, 0);
+        assert_eq!(generator.try_next(), Some(Ok("item-7-1".to_string())));
+
+        let mut fork = generator.clone();
+        assert!(fork == generator);
+        assert_eq!(fork.try_next(), Some(Ok("item-7-2".to_string())));
+        assert!(fork != generator);
+
+        let rest: Vec<String> = generator.map(|item| item.unwrap()).collect();
+        assert_eq!(rest, vec!["item-7-2", "item-7-3"]);
+        assert_eq!(fork.try_next(), Some(Ok("item-7-3".to_string())));
+        assert_eq!(fork.try_next(), None);
+
+        // The exhausted flag is preserved by cloning.
+        assert_eq!(fork.clone().try_next(), None);
+    }
+
     #[test]
     fn test_generator_from_parts() {
         let generator = SimpleTestGenerator::from_parts(42, 0);
@@ -379,4 +499,31 @@ mod tests {
         assert_eq!(generator.next(), None);
         assert_eq!(generator.next(), None);
     }
+
+    #[test]
+    fn test_generator_cancel_check_interval() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut generator = SimpleTestGenerator::from_parts(7, 0).with_cancel_check_interval(2);
+        assert_eq!(generator.cancel_check_interval(), 2);
+        assert_eq!(generator.try_next(), Some(Ok("item-7-1".to_string())));
+        // The second step is not checked; a cancellation does not advance the interval.
+        let items = on_trigger(trigger, || {
+            let items = [
+                generator.try_next(),
+                generator.try_next(),
+                generator.try_next(),
+            ];
+            Ok::<_, Cancelled>(items)
+        })
+        .unwrap();
+        assert_eq!(items[0], Some(Ok("item-7-2".to_string())));
+        assert!(matches!(items[1], Some(Err(Incomplete::Cancelled(_)))));
+        assert!(matches!(items[2], Some(Err(Incomplete::Cancelled(_)))));
+        let mut fork = generator.clone();
+        fork.set_cancel_check_interval(1);
+        assert!(fork == generator);
+    }
 }
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::mem::size_of;
+
+/// Types that can (approximately) report the amount of heap memory they own.
+///
+/// The reported size excludes the size of the value itself (i.e., `size_of::<Self>()`),
+/// but includes the full size of all heap allocations owned by the value (including
+/// unused capacity). For collections whose allocation strategy is not known
+/// (e.g., [`HashMap`] or [`BTreeMap`]), the size is a lower-bound estimate.
+///
+/// The trait is used by [`crate::MemoryLimited`] to enforce memory budgets of
+/// computation states.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::HeapSize;
+///
+/// let mut items: Vec<String> = Vec::with_capacity(4);
+/// items.push("abc".to_string());
+/// let expected = 4 * size_of::<String>() + items[0].capacity();
+/// assert_eq!(items.heap_size(), expected);
+/// assert_eq!(42u64.heap_size(), 0);
+/// ```
+pub trait HeapSize {
+    /// The number of heap bytes owned by this value.
+    fn heap_size(&self) -> usize;
+
+    /// The total number of bytes used by this value (inline and heap).
+    fn total_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.heap_size()
+    }
+}
+
+macro_rules! impl_no_heap {
+    ($($t:ty),*) => {
+        $(impl HeapSize for $t {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })*
+    };
+}
+
+impl_no_heap!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    std::time::Duration
+);
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map(HeapSize::heap_size).unwrap_or(0)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Box<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().total_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for VecDeque<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::total_size).sum()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.iter()
+            .map(|(k, v)| k.total_size() + v.total_size())
+            .sum()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: HeapSize),+> HeapSize for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.heap_size())+
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(1u8.heap_size(), 0);
+        assert_eq!(().heap_size(), 0);
+        assert_eq!(7u64.total_size(), 8);
+        assert_eq!(None::<String>.heap_size(), 0);
+    }
+
+    #[test]
+    fn test_strings_and_boxes() {
+        let text = String::with_capacity(10);
+        assert_eq!(text.heap_size(), 10);
+        assert_eq!(Some(text).heap_size(), 10);
+        let boxed = Box::new(String::with_capacity(5));
+        assert_eq!(boxed.heap_size(), size_of::<String>() + 5);
+    }
+
+    #[test]
+    fn test_collections() {
+        let vec: Vec<u32> = Vec::with_capacity(8);
+        assert_eq!(vec.heap_size(), 32);
+
+        let mut deque = VecDeque::with_capacity(2);
+        deque.push_back(String::with_capacity(3));
+        assert_eq!(
+            deque.heap_size(),
+            deque.capacity() * size_of::<String>() + 3
+        );
+
+        let set: BTreeSet<u16> = [1, 2, 3].into_iter().collect();
+        assert_eq!(set.heap_size(), 6);
+        let map: BTreeMap<u8, String> = [(1, String::with_capacity(4))].into_iter().collect();
+        assert_eq!(map.heap_size(), 1 + size_of::<String>() + 4);
+
+        let hash: HashMap<u64, u64> = HashMap::with_capacity(4);
+        assert_eq!(hash.heap_size(), hash.capacity() * 16);
+        let hash: HashSet<u8> = HashSet::new();
+        assert_eq!(hash.heap_size(), 0);
+    }
+
+    #[test]
+    fn test_tuples() {
+        let value = (1u32, String::with_capacity(3), vec![1u8; 4]);
+        assert_eq!(value.heap_size(), 3 + value.2.capacity());
+    }
+}
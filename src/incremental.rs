@@ -0,0 +1,259 @@
+use crate::{
+    Completable, Computable, Computation, ComputationStep, FromParts, StatefulMut, StatefulRef,
+};
+
+/// A [`ComputationStep`] that can react to changes of the `CONTEXT`, such that
+/// the computation does not have to be restarted when its input changes.
+///
+/// See [`Incremental`].
+pub trait IncrementalStep<CONTEXT, STATE, OUTPUT>: ComputationStep<CONTEXT, STATE, OUTPUT> {
+    /// Update the `state` after the context was changed to `context`.
+    ///
+    /// The method is called (at a suspend point) before the next step that follows one
+    /// or more changes of the context. It should invalidate (or recompute) the parts of
+    /// the state that depend on the changed parts of the context.
+    fn on_context_changed(state: &mut STATE, context: &CONTEXT);
+}
+
+/// A [`Computable`] wrapper of a [`Computation`] whose `CONTEXT` can be changed while
+/// the computation is running (or after it completed).
+///
+/// Changes of the context (see [`crate::StatefulMut::update_context`] and
+/// [`crate::StatefulMut::replace_context`]) mark the computation as dirty. Before the next step,
+/// [`IncrementalStep::on_context_changed`] is called once (regardless of the number of
+/// changes), which allows the step implementation to invalidate only the affected parts
+/// of its state. Since a [`Computation`] can be advanced after it completed, calling
+/// [`Computable::try_compute`] after a change recomputes the output.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+/// use computation_process::{Incremental, IncrementalStep};
+///
+/// /// Sums the items one per step; `state` is (index, partial sum).
+/// struct SumStep;
+///
+/// impl ComputationStep<Vec<u64>, (usize, u64), u64> for SumStep {
+///     fn step(items: &Vec<u64>, (index, sum): &mut (usize, u64)) -> Completable<u64> {
+///         match items.get(*index) {
+///             None => Ok(*sum),
+///             Some(item) => {
+///                 *index += 1;
+///                 *sum += item;
+///                 Err(Incomplete::Suspended)
+///             }
+///         }
+///     }
+/// }
+///
+/// impl IncrementalStep<Vec<u64>, (usize, u64), u64> for SumStep {
+///     fn on_context_changed((index, _): &mut (usize, u64), items: &Vec<u64>) {
+///         // Items are only appended, hence the partial sum remains valid.
+///         *index = (*index).min(items.len());
+///     }
+/// }
+///
+/// let computation = Computation::<Vec<u64>, (usize, u64), u64, SumStep>::from_parts(vec![1, 2], (0, 0));
+/// let mut incremental = Incremental::new(computation);
+/// assert_eq!(incremental.compute().unwrap(), 3);
+/// incremental.update_context(|items| items.push(10));
+/// assert_eq!(incremental.compute().unwrap(), 13);
+/// assert_eq!(incremental.computation().state(), &(3, 13));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct Incremental<C> {
+    computation: C,
+    dirty: bool,
+    changes: u64,
+}
+
+impl<C> From<C> for Incremental<C> {
+    fn from(value: C) -> Self {
+        Incremental::new(value)
+    }
+}
+
+impl<C> Incremental<C> {
+    /// Wrap the given `computation`.
+    pub fn new(computation: C) -> Self {
+        Incremental {
+            computation,
+            dirty: false,
+            changes: 0,
+        }
+    }
+
+    /// Access to the wrapped computation.
+    pub fn computation(&self) -> &C {
+        &self.computation
+    }
+
+    /// Unwrap the computation. Pending context changes are not propagated to the state.
+    pub fn into_inner(self) -> C {
+        self.computation
+    }
+
+    /// Returns `true` if the context changed since the last step.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// The number of context changes so far.
+    pub fn changes(&self) -> u64 {
+        self.changes
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: IncrementalStep<CONTEXT, STATE, OUTPUT>>
+    Incremental<Computation<CONTEXT, STATE, OUTPUT, STEP>>
+{
+    /// Propagate pending context changes to the state (this is also done automatically
+    /// before the next step).
+    pub fn refresh(&mut self) {
+        if self.dirty {
+            self.dirty = false;
+            let (context, state) = self.computation.parts_mut();
+            STEP::on_context_changed(state, context);
+        }
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: IncrementalStep<CONTEXT, STATE, OUTPUT>> Computable<OUTPUT>
+    for Incremental<Computation<CONTEXT, STATE, OUTPUT, STEP>>
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        self.refresh();
+        self.computation.try_compute()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: IncrementalStep<CONTEXT, STATE, OUTPUT>>
+    FromParts<CONTEXT, STATE> for Incremental<Computation<CONTEXT, STATE, OUTPUT, STEP>>
+{
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        Incremental::new(Computation::from_parts(context, state))
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        self.computation.into_parts()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: IncrementalStep<CONTEXT, STATE, OUTPUT>>
+    StatefulRef<CONTEXT, STATE> for Incremental<Computation<CONTEXT, STATE, OUTPUT, STEP>>
+{
+    fn context(&self) -> &CONTEXT {
+        self.computation.context()
+    }
+
+    fn state(&self) -> &STATE {
+        self.computation.state()
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        self.computation.state_mut()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP: IncrementalStep<CONTEXT, STATE, OUTPUT>>
+    StatefulMut<CONTEXT, STATE> for Incremental<Computation<CONTEXT, STATE, OUTPUT, STEP>>
+{
+    /// Mutable access to the context, which marks the computation as dirty.
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        self.dirty = true;
+        self.changes += 1;
+        self.computation.context_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Incomplete;
+
+    /// Counts the occurrences of the target in the items; `state` is (index, count, refreshes).
+    struct CountStep;
+
+    impl ComputationStep<(Vec<u32>, u32), (usize, u32, u32), u32> for CountStep {
+        fn step(
+            (items, target): &(Vec<u32>, u32),
+            (index, count, _): &mut (usize, u32, u32),
+        ) -> Completable<u32> {
+            match items.get(*index) {
+                None => Ok(*count),
+                Some(item) => {
+                    *index += 1;
+                    if item == target {
+                        *count += 1;
+                    }
+                    Err(Incomplete::Suspended)
+                }
+            }
+        }
+    }
+
+    impl IncrementalStep<(Vec<u32>, u32), (usize, u32, u32), u32> for CountStep {
+        fn on_context_changed(
+            (index, count, refreshes): &mut (usize, u32, u32),
+            (items, target): &(Vec<u32>, u32),
+        ) {
+            // Recount the already processed prefix (e.g., because the target changed).
+            *index = (*index).min(items.len());
+            *count = items[..*index].iter().filter(|it| *it == target).count() as u32;
+            *refreshes += 1;
+        }
+    }
+
+    type Count = Computation<(Vec<u32>, u32), (usize, u32, u32), u32, CountStep>;
+
+    #[test]
+    fn test_change_while_running() {
+        let mut incremental =
+            Incremental::from(Count::from_parts((vec![1, 2, 1, 2], 1), (0, 0, 0)));
+        assert_eq!(incremental.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(incremental.try_compute(), Err(Incomplete::Suspended));
+        assert!(!incremental.is_dirty());
+
+        incremental.update_context(|(_, target)| *target = 2);
+        incremental.update_context(|(items, _)| items.push(2));
+        assert!(incremental.is_dirty());
+        assert_eq!(incremental.changes(), 2);
+        assert_eq!(incremental.compute(), Ok(3));
+        // The hook is called once for both changes.
+        assert_eq!(incremental.state().2, 1);
+    }
+
+    #[test]
+    fn test_change_after_completion() {
+        let mut incremental = Incremental::<Count>::from_parts((vec![3, 3], 3), (0, 0, 0));
+        assert_eq!(incremental.compute(), Ok(2));
+        let previous = incremental.replace_context((vec![3, 1], 1));
+        assert_eq!(previous, (vec![3, 3], 3));
+        incremental.refresh();
+        assert!(!incremental.is_dirty());
+        assert_eq!(incremental.state(), &(2, 1, 1));
+        assert_eq!(incremental.compute(), Ok(1));
+        assert_eq!(incremental.context().1, 1);
+        assert_eq!(incremental.into_parts().1, (2, 1, 1));
+    }
+
+    #[test]
+    fn test_into_inner_keeps_pending_changes() {
+        let mut incremental = Incremental::new(Count::from_parts((vec![5], 5), (0, 0, 0)));
+        assert_eq!(incremental.compute(), Ok(1));
+        incremental.update_context(|(items, _)| items.clear());
+        let computation = incremental.into_inner();
+        assert_eq!(computation.state(), &(1, 1, 0));
+        assert!(computation.context().0.is_empty());
+    }
+}
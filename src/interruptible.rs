@@ -0,0 +1,83 @@
+use crate::{Completable, Computable};
+use cancel_this::{CancelCtrlc, on_trigger};
+
+/// Drive the `computation` to completion, cancelling it once `SIGINT` (Ctrl+C) is received.
+///
+/// While the computation runs, the first Ctrl+C does not terminate the process. Instead,
+/// the computation is cancelled at its next cancellation check and
+/// [`crate::Incomplete::Cancelled`] is returned (the trigger is reported as `CancelCtrlc`).
+/// Since the computation is only borrowed, its partial state remains available afterwards,
+/// e.g., to report the best result found so far or to save a checkpoint and resume later.
+/// Other outcomes (completion, exhaustion, exceeded resources) are returned as they are
+/// (see [`Computable::compute_completable`]).
+///
+/// Requires the `ctrlc` feature.
+///
+/// # Panics
+///
+/// Panics if the `SIGINT` handler cannot be installed (e.g., because another handler
+/// was already registered through the `ctrlc` crate).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+/// use computation_process::{FromParts, StatefulRef, run_interruptible};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let mut computation = Computation::<u32, u32, u32, CountStep>::from_parts(1000, 0);
+/// match run_interruptible(&mut computation) {
+///     Ok(count) => assert_eq!(count, 1000),
+///     Err(Incomplete::Cancelled(_)) => println!("Interrupted at {}.", computation.state()),
+///     Err(e) => panic!("Unexpected outcome: {e:?}"),
+/// }
+/// ```
+pub fn run_interruptible<T, C: Computable<T> + ?Sized>(computation: &mut C) -> Completable<T> {
+    let trigger = CancelCtrlc::try_new().expect("Cannot install the SIGINT handler.");
+    on_trigger(trigger, || computation.compute_completable())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Incomplete, StatefulRef, test_fixtures::Count};
+
+    #[test]
+    fn test_completes() {
+        let mut computation = Count::from_parts(10, 0);
+        assert_eq!(run_interruptible(&mut computation), Ok(10));
+        assert_eq!(*computation.state(), 10);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_interrupted() {
+        // Make sure the handler is installed before the signal is sent.
+        CancelCtrlc::try_new().expect("The handler is installed.");
+        // Does not complete before the signal arrives.
+        let mut computation = Count::from_parts(u32::MAX, 0);
+        let signal = std::thread::spawn(|| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            std::process::Command::new("kill")
+                .args(["-INT", &std::process::id().to_string()])
+                .status()
+                .expect("The signal is sent.")
+        });
+        let result = run_interruptible(&mut computation);
+        assert!(signal.join().unwrap().success());
+        match result {
+            Err(Incomplete::Cancelled(e)) => assert_eq!(e.cause(), "CancelCtrlc"),
+            other => panic!("Unexpected outcome: {other:?}"),
+        }
+        // The partial state survives the interruption.
+        assert!(*computation.state() > 0);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, Computable, DynComputable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that produces the result of a [`Computable`] as its only item.
+///
+/// Suspensions and cancellation of the computation are passed through. Once the result
+/// is produced (or the computation reports [`Incomplete::Exhausted`]), the generator
+/// is exhausted. This makes it possible to use a [`Computable`] wherever
+/// a [`Generatable`] is expected (see also [`crate::LastItem`] for the reverse direction).
+///
+/// See also [`crate::ComputableExt::into_generator`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// struct DoubleStep;
+///
+/// impl ComputationStep<u32, bool, u32> for DoubleStep {
+///     fn step(value: &u32, started: &mut bool) -> Completable<u32> {
+///         if !*started {
+///             *started = true;
+///             return Err(Incomplete::Suspended);
+///         }
+///         Ok(2 * value)
+///     }
+/// }
+///
+/// let computation = Computation::<u32, bool, u32, DoubleStep>::from_parts(21, false);
+/// let mut generator = computation.into_generator();
+/// assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+/// assert_eq!(generator.try_next(), Some(Ok(42)));
+/// assert_eq!(generator.try_next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct IntoGenerator<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    computable: C,
+    finished: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> IntoGenerator<T, C> {
+    /// Create a new [`IntoGenerator`] producing the result of `computable`.
+    pub fn new(computable: C) -> Self {
+        IntoGenerator {
+            computable,
+            finished: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the result was already produced.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Drop the adapter and return the underlying computation.
+    pub fn into_inner(self) -> C {
+        self.computable
+    }
+}
+
+impl<T, C: Computable<T>> From<C> for IntoGenerator<T, C> {
+    fn from(value: C) -> Self {
+        IntoGenerator::new(value)
+    }
+}
+
+impl<T, C: Computable<T>> Iterator for IntoGenerator<T, C> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, C: Computable<T>> Generatable<T> for IntoGenerator<T, C> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        if self.finished {
+            return None;
+        }
+        match self.computable.try_compute() {
+            Ok(value) => {
+                self.finished = true;
+                Some(Ok(value))
+            }
+            Err(Incomplete::Exhausted) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableExt, FnComputation};
+
+    #[test]
+    fn test_single_item() {
+        let computation = FnComputation::new((), 0, |_: &(), count: &mut u32| {
+            *count += 1;
+            if *count < 3 {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(*count)
+            }
+        });
+        let mut generator = computation.into_generator();
+        assert!(!generator.is_finished());
+        assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(
+            generator.by_ref().collect::<Cancellable<Vec<_>>>(),
+            Ok(vec![3])
+        );
+        assert!(generator.is_finished());
+        assert_eq!(generator.try_next(), None);
+    }
+
+    #[test]
+    fn test_exhausted_computation() {
+        let computation = FnComputation::new((), (), |_: &(), _: &mut ()| {
+            Err::<u32, _>(Incomplete::Exhausted)
+        });
+        let mut generator = IntoGenerator::from(computation);
+        assert_eq!(generator.try_next(), None);
+        assert!(generator.is_finished());
+    }
+
+    #[test]
+    fn test_cancelled() {
+        use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut generator = FnComputation::new((), (), |_: &(), _: &mut ()| Ok(1)).into_generator();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(generator.try_next()));
+        assert!(matches!(result, Ok(Some(Err(Incomplete::Cancelled(_))))));
+        assert!(!generator.is_finished());
+        assert_eq!(generator.into_inner().compute(), Ok(1));
+    }
+}
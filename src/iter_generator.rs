@@ -0,0 +1,159 @@
+use crate::{Completable, Generatable, Incomplete};
+use cancel_this::{Cancellable, is_cancelled};
+
+/// A [`Generatable`] that wraps a standard [`Iterator`].
+///
+/// This allows existing iterator-based code to participate in interleaving and cancellation:
+/// cancellation is checked before every item, and optionally, the generator suspends after
+/// every `N` items (see [`IterGenerator::suspend_every`]).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Generatable, Incomplete, IterGenerator};
+///
+/// let mut generator = IterGenerator::new(1..=3).suspend_every(2);
+/// assert_eq!(generator.try_next(), Some(Ok(1)));
+/// assert_eq!(generator.try_next(), Some(Ok(2)));
+/// assert_eq!(generator.try_next(), Some(Err(Incomplete::Suspended)));
+/// assert_eq!(generator.try_next(), Some(Ok(3)));
+/// assert_eq!(generator.try_next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IterGenerator<I: Iterator> {
+    iterator: I,
+    suspend_every: Option<usize>,
+    since_suspend: usize,
+}
+
+impl<I: Iterator> From<I> for IterGenerator<I> {
+    fn from(iterator: I) -> Self {
+        IterGenerator::new(iterator)
+    }
+}
+
+impl<I: Iterator> IterGenerator<I> {
+    /// Create a new [`IterGenerator`] which never suspends.
+    pub fn new(iterator: I) -> Self {
+        IterGenerator {
+            iterator,
+            suspend_every: None,
+            since_suspend: 0,
+        }
+    }
+
+    /// Suspend the generator after every `items` produced items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` is zero.
+    pub fn suspend_every(mut self, items: usize) -> Self {
+        assert!(items > 0, "`items` must be positive.");
+        self.suspend_every = Some(items);
+        self
+    }
+
+    /// A reference to the underlying iterator.
+    pub fn iterator_ref(&self) -> &I {
+        &self.iterator
+    }
+
+    /// Consume this generator and return the underlying iterator.
+    pub fn into_iterator(self) -> I {
+        self.iterator
+    }
+}
+
+impl<I: Iterator> Iterator for IterGenerator<I> {
+    type Item = Cancellable<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<I: Iterator> Generatable<I::Item> for IterGenerator<I> {
+    fn try_next(&mut self) -> Option<Completable<I::Item>> {
+        if let Err(e) = is_cancelled!() {
+            return Some(Err(Incomplete::Cancelled(e)));
+        }
+        if let Some(limit) = self.suspend_every
+            && self.since_suspend >= limit
+        {
+            self.since_suspend = 0;
+            return Some(Err(Incomplete::Suspended));
+        }
+        let item = self.iterator.next()?;
+        self.since_suspend += 1;
+        Some(Ok(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collector, Computable, GeneratableExt};
+
+    #[test]
+    fn test_iter_generator_without_suspension() {
+        let mut generator: IterGenerator<_> = vec!["a", "b"].into_iter().into();
+        assert_eq!(generator.try_next(), Some(Ok("a")));
+        assert_eq!(generator.try_next(), Some(Ok("b")));
+        assert_eq!(generator.try_next(), None);
+    }
+
+    #[test]
+    fn test_iter_generator_suspend_every() {
+        let generator = IterGenerator::new(0..5).suspend_every(1);
+        let steps: Vec<Completable<i32>> = std::iter::from_fn({
+            let mut generator = generator;
+            move || generator.try_next()
+        })
+        .collect();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps[0], Ok(0));
+        assert_eq!(steps[1], Err(Incomplete::Suspended));
+        assert_eq!(steps[8], Ok(4));
+    }
+
+    #[test]
+    fn test_iter_generator_with_adapters() {
+        let mut sum = IterGenerator::new(1..=10)
+            .suspend_every(3)
+            .folder(0, |acc, item| acc + item);
+        assert_eq!(sum.compute(), Ok(55));
+
+        let generator = IterGenerator::new("hello".chars());
+        let mut collector = Collector::<char, String, _>::new(generator);
+        assert_eq!(collector.compute(), Ok("hello".to_string()));
+    }
+
+    #[test]
+    fn test_iter_generator_iterator_skips_suspensions() {
+        let items: Vec<u32> = IterGenerator::new(0..4)
+            .suspend_every(2)
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(items, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_iter_generator_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut generator = IterGenerator::new(0..3);
+        let result = on_trigger(trigger, || generator.try_next().unwrap());
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        // No item is lost due to cancellation.
+        assert_eq!(generator.try_next(), Some(Ok(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_iter_generator_zero_suspend() {
+        let _ = IterGenerator::new(0..3).suspend_every(0);
+    }
+}
@@ -0,0 +1,468 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{
+    Completable, Computable, DynPersistentComputable, Generatable, Incomplete,
+    PersistentComputable, Registry, RegistryError, Tagged,
+};
+use cancel_this::Cancellable;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// The error type of [`JobQueue`] persistence operations.
+#[derive(Debug)]
+pub enum JobQueueError {
+    /// A job type was not registered in the [`JobRegistry`].
+    UnregisteredType(&'static str),
+    /// A persisted job has a tag which is not known to the [`JobRegistry`].
+    UnknownTag(String),
+    /// A job could not be (de)serialized.
+    Serialization(serde_json::Error),
+    /// The queue could not be written to or read from a file.
+    Io(std::io::Error),
+}
+
+impl Display for JobQueueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobQueueError::UnregisteredType(name) => write!(f, "Unregistered job type `{name}`"),
+            JobQueueError::UnknownTag(tag) => write!(f, "Unknown job tag `{tag}`"),
+            JobQueueError::Serialization(e) => write!(f, "Job serialization failed: {e}"),
+            JobQueueError::Io(e) => write!(f, "Job queue I/O failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for JobQueueError {}
+
+impl From<serde_json::Error> for JobQueueError {
+    fn from(value: serde_json::Error) -> Self {
+        JobQueueError::Serialization(value)
+    }
+}
+
+impl From<RegistryError> for JobQueueError {
+    fn from(value: RegistryError) -> Self {
+        match value {
+            RegistryError::UnregisteredType(name) => JobQueueError::UnregisteredType(name),
+            RegistryError::UnknownTag(tag) => JobQueueError::UnknownTag(tag),
+            RegistryError::Serialization(e) => JobQueueError::Serialization(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for JobQueueError {
+    fn from(value: std::io::Error) -> Self {
+        JobQueueError::Io(value)
+    }
+}
+
+/// A [`Registry`] of job types that can be stored in a [`JobQueue`].
+///
+/// Every job type that is pushed into a [`JobQueue`] must be registered first.
+pub type JobRegistry<OUTPUT> = Registry<dyn PersistentComputable<OUTPUT>>;
+
+struct QueuedJob<OUTPUT> {
+    tag: String,
+    name: Option<Cow<'static, str>>,
+    job: DynPersistentComputable<OUTPUT>,
+}
+
+/// A job saved by [`JobQueue::save`], together with its name (if any).
+#[derive(Serialize, Deserialize)]
+struct SavedJob {
+    #[serde(flatten)]
+    job: Tagged,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+/// A persistent first-in-first-out queue of serializable jobs (requires the `persistence`
+/// feature).
+///
+/// A job is any `Computable<OUTPUT>` that implements [`Serialize`] and
+/// [`serde::de::DeserializeOwned`] and is registered in a [`JobRegistry`]. The queue executes
+/// the jobs one after another as a [`Generatable`] that produces the output of each completed
+/// job. At every suspend point, the queue (including the partial progress of the running job)
+/// can be saved ([`JobQueue::save`]) and later restored ([`JobQueue::load`]), e.g., after
+/// a process restart.
+///
+/// [`JobQueue::run_with_checkpoints`] implements the typical "execute and periodically
+/// checkpoint into a file" loop. Note that a job whose output was produced, but that was not
+/// yet removed from a saved checkpoint, is executed again after a restart (i.e., the queue
+/// provides at-least-once semantics).
+///
+/// Jobs can be given a human-readable name ([`JobQueue::push_named`]), which is preserved
+/// by the checkpoints and can be used to look the job up ([`JobQueue::find`]).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete, JobQueue, JobRegistry};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// let mut registry = JobRegistry::new();
+/// registry.register::<Count>("count");
+///
+/// let mut queue = JobQueue::new(registry);
+/// queue.push(Count::from_parts(3, 0)).unwrap();
+/// queue.push(Count::from_parts(5, 0)).unwrap();
+/// assert_eq!(queue.try_next(), Some(Err(Incomplete::Suspended)));
+///
+/// // Persist the queue and restore it using a new registry.
+/// let saved = queue.save().unwrap();
+/// let mut registry = JobRegistry::new();
+/// registry.register::<Count>("count");
+/// let restored = JobQueue::load(registry, saved).unwrap();
+/// let outputs: Vec<u32> = restored.map(|it| it.unwrap()).collect();
+/// assert_eq!(outputs, vec![3, 5]);
+/// ```
+pub struct JobQueue<OUTPUT> {
+    registry: JobRegistry<OUTPUT>,
+    jobs: VecDeque<QueuedJob<OUTPUT>>,
+}
+
+impl<OUTPUT: 'static> JobQueue<OUTPUT> {
+    /// Create a new empty queue using the given `registry`.
+    pub fn new(registry: JobRegistry<OUTPUT>) -> Self {
+        JobQueue {
+            registry,
+            jobs: VecDeque::new(),
+        }
+    }
+
+    /// Append a `job` to the end of the queue.
+    ///
+    /// Fails if the type of the job is not registered.
+    pub fn push<C>(&mut self, job: C) -> Result<(), JobQueueError>
+    where
+        C: Computable<OUTPUT> + Serialize + 'static,
+    {
+        let tag = self
+            .registry
+            .tag_of::<C>()
+            .ok_or(JobQueueError::UnregisteredType(std::any::type_name::<C>()))?;
+        self.jobs.push_back(QueuedJob {
+            tag: tag.to_string(),
+            name: None,
+            job: Box::new(job),
+        });
+        Ok(())
+    }
+
+    /// Append a `job` with a human-readable `name` (see [`JobQueue::name`]) to the end
+    /// of the queue.
+    ///
+    /// Fails if the type of the job is not registered.
+    pub fn push_named<N, C>(&mut self, name: N, job: C) -> Result<(), JobQueueError>
+    where
+        N: Into<Cow<'static, str>>,
+        C: Computable<OUTPUT> + Serialize + 'static,
+    {
+        self.push(job)?;
+        if let Some(queued) = self.jobs.back_mut() {
+            queued.name = Some(name.into());
+        }
+        Ok(())
+    }
+
+    /// The name of the job at the given position of the queue (zero is the running job),
+    /// assuming it was pushed using [`JobQueue::push_named`].
+    pub fn name(&self, position: usize) -> Option<&str> {
+        self.jobs.get(position).and_then(|it| it.name.as_deref())
+    }
+
+    /// The position (in execution order) of the first queued job with the given `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.jobs
+            .iter()
+            .position(|it| it.name.as_deref() == Some(name))
+    }
+
+    /// The number of jobs in the queue (including the running job).
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns `true` if there are no jobs in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// The tags of the queued jobs, in execution order.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.jobs.iter().map(|it| it.tag.as_str())
+    }
+
+    /// Serialize all queued jobs (including the progress of the running job).
+    pub fn save(&self) -> Result<Value, JobQueueError> {
+        let records = self
+            .jobs
+            .iter()
+            .map(|it| {
+                Ok(SavedJob {
+                    job: Tagged {
+                        tag: it.tag.clone(),
+                        data: it.job.save()?,
+                    },
+                    name: it.name.as_deref().map(str::to_string),
+                })
+            })
+            .collect::<Result<Vec<_>, JobQueueError>>()?;
+        Ok(serde_json::to_value(records)?)
+    }
+
+    /// Restore a queue saved using [`JobQueue::save`].
+    pub fn load(registry: JobRegistry<OUTPUT>, saved: Value) -> Result<Self, JobQueueError> {
+        let records: Vec<SavedJob> = serde_json::from_value(saved)?;
+        let mut jobs = VecDeque::with_capacity(records.len());
+        for record in records {
+            let tag = record.job.tag.clone();
+            jobs.push_back(QueuedJob {
+                job: registry.load(record.job)?,
+                name: record.name.map(Cow::Owned),
+                tag,
+            });
+        }
+        Ok(JobQueue { registry, jobs })
+    }
+
+    /// Save the queue into a file (the file is replaced atomically).
+    pub fn save_to(&self, path: &Path) -> Result<(), JobQueueError> {
+        let data = serde_json::to_vec(&self.save()?)?;
+        let temporary = path.with_extension("tmp");
+        std::fs::write(&temporary, data)?;
+        std::fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    /// Restore a queue from a file created by [`JobQueue::save_to`].
+    pub fn load_from(registry: JobRegistry<OUTPUT>, path: &Path) -> Result<Self, JobQueueError> {
+        let data = std::fs::read(path)?;
+        Self::load(registry, serde_json::from_slice(&data)?)
+    }
+
+    /// Execute all jobs in the queue, saving the queue into `path` every `checkpoint_every`
+    /// steps and after every completed job. The `on_output` callback receives the output
+    /// of each completed job.
+    ///
+    /// Returns `Ok(Err(Cancelled))` if the execution was canceled (the queue is saved
+    /// before returning, so the execution can be resumed later).
+    pub fn run_with_checkpoints<F: FnMut(OUTPUT)>(
+        &mut self,
+        path: &Path,
+        checkpoint_every: usize,
+        mut on_output: F,
+    ) -> Result<Cancellable<()>, JobQueueError> {
+        let mut steps = 0usize;
+        while let Some(result) = self.try_next() {
+            match result {
+                Ok(output) => {
+                    on_output(output);
+                    self.save_to(path)?;
+                    steps = 0;
+                }
+                Err(Incomplete::Suspended) => {
+                    steps += 1;
+                    if checkpoint_every > 0 && steps >= checkpoint_every {
+                        self.save_to(path)?;
+                        steps = 0;
+                    }
+                }
+                Err(Incomplete::Cancelled(c)) => {
+                    self.save_to(path)?;
+                    return Ok(Err(c));
+                }
+                Err(Incomplete::ResourceExceeded(e)) => {
+                    self.save_to(path)?;
+                    return Ok(Err(e.into()));
+                }
+                Err(Incomplete::Exhausted) => unreachable!("Exhausted jobs are removed."),
+            }
+        }
+        self.save_to(path)?;
+        Ok(Ok(()))
+    }
+}
+
+impl<OUTPUT: 'static> Iterator for JobQueue<OUTPUT> {
+    type Item = Cancellable<OUTPUT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<OUTPUT: 'static> Generatable<OUTPUT> for JobQueue<OUTPUT> {
+    fn try_next(&mut self) -> Option<Completable<OUTPUT>> {
+        let front = self.jobs.front_mut()?;
+        match front.job.try_compute() {
+            Ok(output) => {
+                self.jobs.pop_front();
+                Some(Ok(output))
+            }
+            Err(Incomplete::Exhausted) => {
+                // A job that is already finished is simply skipped.
+                self.jobs.pop_front();
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, FromParts};
+
+    struct CountStep;
+
+    impl ComputationStep<u32, u32, String> for CountStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<String> {
+            *count += 1;
+            if *count >= *target {
+                Ok(format!("count-{count}"))
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    struct ConcatStep;
+
+    impl ComputationStep<Vec<String>, usize, String> for ConcatStep {
+        fn step(items: &Vec<String>, index: &mut usize) -> Completable<String> {
+            if *index >= items.len() {
+                Ok(items.concat())
+            } else {
+                *index += 1;
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type Count = Computation<u32, u32, String, CountStep>;
+    type Concat = Computation<Vec<String>, usize, String, ConcatStep>;
+
+    fn registry() -> JobRegistry<String> {
+        let mut registry = JobRegistry::new();
+        registry
+            .register::<Count>("count")
+            .register::<Concat>("concat");
+        registry
+    }
+
+    #[test]
+    fn test_heterogeneous_jobs_survive_restart() {
+        let mut queue = JobQueue::new(registry());
+        queue.push(Count::from_parts(3, 0)).unwrap();
+        queue
+            .push(Concat::from_parts(vec!["a".into(), "b".into()], 0))
+            .unwrap();
+        assert_eq!(queue.tags().collect::<Vec<_>>(), vec!["count", "concat"]);
+
+        assert_eq!(queue.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(queue.try_next(), Some(Err(Incomplete::Suspended)));
+        let saved = queue.save().unwrap();
+
+        let mut restored = JobQueue::load(registry(), saved).unwrap();
+        assert_eq!(restored.len(), 2);
+        // The progress of the first job is preserved.
+        assert_eq!(restored.try_next(), Some(Ok("count-3".to_string())));
+        let rest: Vec<String> = restored.map(|it| it.unwrap()).collect();
+        assert_eq!(rest, vec!["ab".to_string()]);
+    }
+
+    #[test]
+    fn test_named_jobs() {
+        let mut queue = JobQueue::new(registry());
+        queue.push_named("first", Count::from_parts(2, 0)).unwrap();
+        queue.push(Count::from_parts(1, 0)).unwrap();
+        queue
+            .push_named(format!("concat-{}", 3), Concat::from_parts(vec![], 0))
+            .unwrap();
+        assert_eq!(queue.name(0), Some("first"));
+        assert_eq!(queue.name(1), None);
+        assert_eq!(queue.find("concat-3"), Some(2));
+        assert_eq!(queue.find("missing"), None);
+
+        // The names survive a restart.
+        let mut restored = JobQueue::load(registry(), queue.save().unwrap()).unwrap();
+        assert_eq!(restored.name(0), Some("first"));
+        assert_eq!(restored.find("concat-3"), Some(2));
+        assert_eq!(restored.next(), Some(Ok("count-2".to_string())));
+        assert_eq!(restored.find("first"), None);
+        assert_eq!(restored.find("concat-3"), Some(1));
+    }
+
+    #[test]
+    fn test_unregistered_job() {
+        let mut queue = JobQueue::new(JobRegistry::<String>::new());
+        let error = queue.push(Count::from_parts(3, 0)).unwrap_err();
+        assert!(matches!(error, JobQueueError::UnregisteredType(_)));
+
+        let mut queue = JobQueue::new(registry());
+        queue.push(Count::from_parts(3, 0)).unwrap();
+        let saved = queue.save().unwrap();
+        let mut partial = JobRegistry::new();
+        partial.register::<Concat>("concat");
+        let error = JobQueue::load(partial, saved).err().unwrap();
+        assert!(matches!(error, JobQueueError::UnknownTag(tag) if tag == "count"));
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered")]
+    fn test_duplicate_tag() {
+        let mut registry = JobRegistry::<String>::new();
+        registry.register::<Count>("job").register::<Concat>("job");
+    }
+
+    #[test]
+    fn test_run_with_checkpoints() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let path = std::env::temp_dir().join(format!(
+            "computation-process-job-queue-{}.json",
+            std::process::id()
+        ));
+        let mut queue = JobQueue::new(registry());
+        queue.push(Count::from_parts(4, 0)).unwrap();
+        queue.push(Count::from_parts(2, 0)).unwrap();
+
+        // The first run is canceled immediately, but the queue is saved.
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(queue.run_with_checkpoints(&path, 1, |_| ()))
+        })
+        .unwrap();
+        assert!(result.unwrap().is_err());
+
+        // "Restart" the process and finish the work.
+        let mut restored = JobQueue::load_from(registry(), &path).unwrap();
+        let mut outputs = Vec::new();
+        let result = restored
+            .run_with_checkpoints(&path, 1, |it| outputs.push(it))
+            .unwrap();
+        assert!(result.is_ok());
+        assert_eq!(outputs, vec!["count-4", "count-2"]);
+
+        let finished = JobQueue::load_from(registry(), &path).unwrap();
+        assert!(finished.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
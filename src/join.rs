@@ -0,0 +1,231 @@
+use crate::{Completable, Computable, Incomplete};
+
+/// A [`Computable`] that runs a tuple of heterogeneous computations interleaved at their
+/// suspend points and completes with a tuple of their outputs.
+///
+/// This is a statically dispatched alternative to [`crate::JoinAll`]: the computations are
+/// stored inline (no boxing), and each one can have a different output type. Tuples with
+/// 2 to 12 computations are supported. The `C` parameter is the tuple of computations and
+/// `O` is the matching tuple of (optional) outputs, which is inferred automatically. Use
+/// [`Join::new`] or the [`crate::join!`] macro to create the join.
+///
+/// Every call to [`Computable::try_compute`] advances one pending computation by a single
+/// step, cycling through the pending computations in a round-robin fashion. Cancellation
+/// interrupts the current computation, which is then resumed first. If any computation is
+/// exhausted before producing its output, the whole [`Join`] is exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, Join};
+///
+/// let number = ComputableIdentity::from(1);
+/// let text = ComputableIdentity::from("two").map(|it| it.to_uppercase());
+/// let mut join = Join::new((number, text));
+/// assert_eq!(join.compute().unwrap(), (1, "TWO".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>, O: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Join<C, O> {
+    tasks: C,
+    outputs: O,
+    cursor: usize,
+    finished: bool,
+}
+
+impl<C, O: Default> Join<C, O> {
+    /// Create a new [`Join`] of the given tuple of computations.
+    pub fn new(tasks: C) -> Self {
+        Join {
+            tasks,
+            outputs: O::default(),
+            cursor: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<C, O> Join<C, O> {
+    /// Access to the tuple of computations.
+    pub fn tasks(&self) -> &C {
+        &self.tasks
+    }
+
+    /// Access to the tuple of outputs of the computations that already completed.
+    pub fn outputs(&self) -> &O {
+        &self.outputs
+    }
+
+    /// Unwrap the tuple of computations.
+    pub fn into_tasks(self) -> C {
+        self.tasks
+    }
+}
+
+macro_rules! impl_join {
+    ($count:literal; $($index:tt $output:ident $task:ident),+) => {
+        impl<$($output, $task: Computable<$output>),+> Computable<($($output,)+)>
+            for Join<($($task,)+), ($(Option<$output>,)+)>
+        {
+            fn try_compute(&mut self) -> Completable<($($output,)+)> {
+                if self.finished {
+                    return Err(Incomplete::Exhausted);
+                }
+                let pending = [$(self.outputs.$index.is_none()),+];
+                let index = (0..$count)
+                    .map(|i| (self.cursor + i) % $count)
+                    .find(|i| pending[*i])
+                    .expect("There is at least one pending computation.");
+                let result = match index {
+                    $($index => self.tasks.$index.try_compute().map(|it| {
+                        self.outputs.$index = Some(it);
+                    }),)+
+                    _ => unreachable!("The index is within the arity of the tuple."),
+                };
+                match result {
+                    Ok(()) => self.cursor = index + 1,
+                    Err(Incomplete::Suspended) => {
+                        self.cursor = index + 1;
+                        return Err(Incomplete::Suspended);
+                    }
+                    Err(Incomplete::Exhausted) => {
+                        self.finished = true;
+                        return Err(Incomplete::Exhausted);
+                    }
+                    Err(e) => {
+                        self.cursor = index;
+                        return Err(e);
+                    }
+                }
+                if $(self.outputs.$index.is_none())||+ {
+                    return Err(Incomplete::Suspended);
+                }
+                self.finished = true;
+                Ok(($(self.outputs.$index.take().expect("All computations completed."),)+))
+            }
+        }
+    };
+}
+
+impl_join!(2; 0 A0 C0, 1 A1 C1);
+impl_join!(3; 0 A0 C0, 1 A1 C1, 2 A2 C2);
+impl_join!(4; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3);
+impl_join!(5; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4);
+impl_join!(6; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5);
+impl_join!(7; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6);
+impl_join!(8; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6, 7 A7 C7);
+impl_join!(9; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6, 7 A7 C7, 8 A8 C8);
+impl_join!(10; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6, 7 A7 C7, 8 A8 C8,
+    9 A9 C9);
+impl_join!(11; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6, 7 A7 C7, 8 A8 C8,
+    9 A9 C9, 10 A10 C10);
+impl_join!(12; 0 A0 C0, 1 A1 C1, 2 A2 C2, 3 A3 C3, 4 A4 C4, 5 A5 C5, 6 A6 C6, 7 A7 C7, 8 A8 C8,
+    9 A9 C9, 10 A10 C10, 11 A11 C11);
+
+/// Create a [`Join`] of 2 to 12 heterogeneous computations.
+///
+/// `join!(a, b, c)` is equivalent to `Join::new((a, b, c))`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, join};
+///
+/// let mut both = join!(ComputableIdentity::from(1), ComputableIdentity::from('x'));
+/// assert_eq!(both.compute().unwrap(), (1, 'x'));
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($($task:expr),+ $(,)?) => {
+        $crate::Join::new(($($task,)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableIdentity, Computation, ComputationStep, FromParts, StatefulRef};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Counts to the target, suspending after every step.
+    struct CountStep;
+
+    impl ComputationStep<u32, u32, u32> for CountStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+            if *count == *target {
+                return Ok(*count);
+            }
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Count = Computation<u32, u32, u32, CountStep>;
+
+    #[test]
+    fn test_round_robin() {
+        let text = ComputableIdentity::from("done");
+        let mut join = join!(Count::from_parts(2, 0), text, Count::from_parts(1, 0));
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.tasks().0.state(), &1);
+        // The identity completes immediately.
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.outputs().1, Some("done"));
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.tasks().2.state(), &1);
+        assert_eq!(join.compute(), Ok((2, "done", 1)));
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let mut done = ComputableIdentity::from(1);
+        assert_eq!(done.try_compute(), Ok(1));
+        let mut join = Join::new((Count::from_parts(3, 0), done));
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_cancellation_resumes_same_task() {
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut join = join!(Count::from_parts(1, 0), Count::from_parts(1, 0));
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(join.try_compute())).unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.tasks().0.state(), &1);
+        assert_eq!(join.tasks().1.state(), &0);
+        assert_eq!(join.compute(), Ok((1, 1)));
+    }
+
+    #[test]
+    fn test_max_arity() {
+        let c = |it: u32| Count::from_parts(it, 0);
+        let mut join = join!(
+            c(0),
+            c(1),
+            c(2),
+            c(3),
+            c(4),
+            c(5),
+            c(6),
+            c(7),
+            c(8),
+            c(9),
+            c(10),
+            c(11)
+        );
+        let outputs = join.compute().unwrap();
+        assert_eq!(outputs, (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11));
+    }
+}
@@ -0,0 +1,369 @@
+use crate::{Completable, Computable, DynComputable, Incomplete};
+use std::marker::PhantomData;
+
+/// Determines how a [`TryJoinAll`] reacts to a failed computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoinPolicy {
+    /// Complete with the error as soon as any computation fails. The remaining
+    /// computations are dropped.
+    #[default]
+    FirstError,
+    /// Wait until all computations complete, then return the error of the first
+    /// failed computation (in the order of computations), if any.
+    WaitAll,
+}
+
+/// A [`Computable`] that runs a collection of computations interleaved at their suspend points
+/// and completes with the outputs of all computations (in the original order).
+///
+/// Every call to [`Computable::try_compute`] advances one pending computation by a single step,
+/// cycling through the pending computations in a round-robin fashion. Cancellation interrupts
+/// the current computation, which is then resumed first. If any computation is exhausted before
+/// producing its output, the whole [`JoinAll`] is exhausted.
+///
+/// For computations that can fail, see [`TryJoinAll`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, JoinAll};
+///
+/// let mut join = JoinAll::new(vec![
+///     ComputableIdentity::from(1).dyn_computable(),
+///     ComputableIdentity::from(2).map(|x| x * 10).dyn_computable(),
+/// ]);
+/// assert_eq!(join.compute().unwrap(), vec![1, 20]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct JoinAll<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    tasks: Vec<Option<C>>,
+    outputs: Vec<Option<T>>,
+    remaining: usize,
+    cursor: usize,
+    finished: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> JoinAll<T, C> {
+    /// Create a new [`JoinAll`] of the given computations.
+    pub fn new(tasks: Vec<C>) -> Self {
+        let mut join = JoinAll {
+            tasks: Vec::with_capacity(tasks.len()),
+            outputs: Vec::with_capacity(tasks.len()),
+            remaining: 0,
+            cursor: 0,
+            finished: false,
+            _phantom: Default::default(),
+        };
+        for task in tasks {
+            join.push(task);
+        }
+        join
+    }
+
+    /// Add another computation to this [`JoinAll`].
+    pub fn push(&mut self, task: C) {
+        self.tasks.push(Some(task));
+        self.outputs.push(None);
+        self.remaining += 1;
+    }
+
+    /// The total number of computations (including the completed ones).
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there are no computations.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// The number of computations that did not complete yet.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Access to the output of the computation at `index` (if it completed and the
+    /// outputs were not returned yet).
+    pub fn output(&self, index: usize) -> Option<&T> {
+        self.outputs.get(index).and_then(|it| it.as_ref())
+    }
+
+    /// Advance the next pending computation by one step. Returns the index of the
+    /// computation if it just completed.
+    fn advance(&mut self) -> Result<Option<usize>, Incomplete> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let count = self.tasks.len();
+        let index = (0..count)
+            .map(|i| (self.cursor + i) % count)
+            .find(|i| self.tasks[*i].is_some())
+            .expect("There is at least one pending computation.");
+        let task = self.tasks[index].as_mut().expect("The task is pending.");
+        match task.try_compute() {
+            Ok(output) => {
+                self.tasks[index] = None;
+                self.outputs[index] = Some(output);
+                self.remaining -= 1;
+                self.cursor = index + 1;
+                Ok(Some(index))
+            }
+            Err(Incomplete::Suspended) => {
+                self.cursor = index + 1;
+                Err(Incomplete::Suspended)
+            }
+            Err(Incomplete::Exhausted) => {
+                self.finish();
+                Err(Incomplete::Exhausted)
+            }
+            Err(e) => {
+                self.cursor = index;
+                Err(e)
+            }
+        }
+    }
+
+    /// Drop all computations and stored outputs.
+    fn finish(&mut self) {
+        self.finished = true;
+        self.tasks.clear();
+        self.outputs.clear();
+        self.remaining = 0;
+    }
+
+    /// Take all outputs once all computations completed.
+    fn take_outputs(&mut self) -> Vec<T> {
+        self.finished = true;
+        self.tasks.clear();
+        self.outputs
+            .drain(..)
+            .map(|it| it.expect("All computations completed."))
+            .collect()
+    }
+}
+
+impl<T, C: Computable<T>> Computable<Vec<T>> for JoinAll<T, C> {
+    fn try_compute(&mut self) -> Completable<Vec<T>> {
+        self.advance()?;
+        if self.remaining == 0 {
+            Ok(self.take_outputs())
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+/// A [`Computable`] that runs a collection of fallible computations interleaved at their
+/// suspend points and completes with the outputs of all computations, or with an error
+/// according to the [`JoinPolicy`].
+///
+/// See [`JoinAll`] for details about the interleaving.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, JoinPolicy, TryJoinAll};
+///
+/// let tasks = vec![
+///     ComputableIdentity::from(Ok::<i32, String>(1)).dyn_computable(),
+///     ComputableIdentity::from(Err::<i32, String>("failed".to_string())).dyn_computable(),
+/// ];
+/// let mut join = TryJoinAll::new(tasks, JoinPolicy::FirstError);
+/// assert_eq!(join.compute().unwrap(), Err("failed".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, E: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct TryJoinAll<T, E, C = DynComputable<Result<T, E>>>
+where
+    C: Computable<Result<T, E>>,
+{
+    join: JoinAll<Result<T, E>, C>,
+    policy: JoinPolicy,
+}
+
+impl<T, E, C: Computable<Result<T, E>>> TryJoinAll<T, E, C> {
+    /// Create a new [`TryJoinAll`] of the given computations using the given `policy`.
+    pub fn new(tasks: Vec<C>, policy: JoinPolicy) -> Self {
+        TryJoinAll {
+            join: JoinAll::new(tasks),
+            policy,
+        }
+    }
+
+    /// Add another computation to this [`TryJoinAll`].
+    pub fn push(&mut self, task: C) {
+        self.join.push(task);
+    }
+
+    /// The policy used by this [`TryJoinAll`].
+    pub fn policy(&self) -> JoinPolicy {
+        self.policy
+    }
+
+    /// The number of computations that did not complete yet.
+    pub fn remaining(&self) -> usize {
+        self.join.remaining()
+    }
+}
+
+impl<T, E, C: Computable<Result<T, E>>> Computable<Result<Vec<T>, E>> for TryJoinAll<T, E, C> {
+    fn try_compute(&mut self) -> Completable<Result<Vec<T>, E>> {
+        let completed = self.join.advance()?;
+        if let Some(index) = completed
+            && self.policy == JoinPolicy::FirstError
+            && matches!(self.join.outputs[index], Some(Err(_)))
+        {
+            let error = self.join.outputs[index].take();
+            self.join.finish();
+            return match error {
+                Some(Err(e)) => Ok(Err(e)),
+                _ => unreachable!("The computation failed."),
+            };
+        }
+        if self.join.remaining == 0 {
+            Ok(self.join.take_outputs().into_iter().collect())
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableIdentity, Computation, ComputationStep, FromParts};
+
+    /// Counts to the target; fails if the target is odd.
+    struct CountStep;
+
+    impl ComputationStep<u32, u32, Result<u32, String>> for CountStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<Result<u32, String>> {
+            if *count < *target {
+                *count += 1;
+                return Err(Incomplete::Suspended);
+            }
+            if target.is_multiple_of(2) {
+                Ok(Ok(*count))
+            } else {
+                Ok(Err(format!("odd {target}")))
+            }
+        }
+    }
+
+    type Count = Computation<u32, u32, Result<u32, String>, CountStep>;
+
+    #[test]
+    fn test_join_all_order_and_interleaving() {
+        let mut join = JoinAll::new(vec![Count::from_parts(4, 0), Count::from_parts(0, 0)]);
+        assert_eq!(join.len(), 2);
+        // Round-robin: the first task suspends, the second completes.
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.remaining(), 1);
+        assert_eq!(join.output(1), Some(&Ok(0)));
+        assert_eq!(join.compute().unwrap(), vec![Ok(4), Ok(0)]);
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_join_all_empty() {
+        let mut join = JoinAll::<Result<u32, String>, Count>::new(Vec::new());
+        assert!(join.is_empty());
+        assert_eq!(join.try_compute(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_join_all_exhausted_task() {
+        let mut consumed = ComputableIdentity::from(1);
+        assert_eq!(consumed.try_compute(), Ok(1));
+        let mut join = JoinAll::new(vec![ComputableIdentity::from(2), consumed]);
+        assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_join_all_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut join = JoinAll::new(vec![Count::from_parts(2, 0), Count::from_parts(2, 0)]);
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(join.try_compute())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(join.compute().unwrap(), vec![Ok(2), Ok(2)]);
+    }
+
+    #[test]
+    fn test_try_join_all_success() {
+        let mut join = TryJoinAll::new(
+            vec![Count::from_parts(2, 0), Count::from_parts(4, 0)],
+            JoinPolicy::FirstError,
+        );
+        assert_eq!(join.compute().unwrap(), Ok(vec![2, 4]));
+    }
+
+    #[test]
+    fn test_try_join_all_first_error() {
+        let mut join = TryJoinAll::new(
+            vec![
+                Count::from_parts(100, 0),
+                Count::from_parts(3, 0),
+                Count::from_parts(5, 0),
+            ],
+            JoinPolicy::FirstError,
+        );
+        assert_eq!(join.policy(), JoinPolicy::FirstError);
+        let mut steps = 1;
+        let result = loop {
+            match join.try_compute() {
+                Ok(result) => break result,
+                Err(Incomplete::Suspended) => steps += 1,
+                Err(e) => panic!("Unexpected {e:?}"),
+            }
+        };
+        assert_eq!(result, Err("odd 3".to_string()));
+        // The long-running first task did not have to finish.
+        assert!(steps < 20);
+        assert_eq!(join.remaining(), 0);
+        assert_eq!(join.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_try_join_all_wait_all() {
+        let mut join = TryJoinAll::new(
+            vec![Count::from_parts(6, 0), Count::from_parts(5, 0)],
+            JoinPolicy::WaitAll,
+        );
+        join.push(Count::from_parts(1, 0));
+        // The first error in the order of computations is reported.
+        assert_eq!(join.compute().unwrap(), Err("odd 5".to_string()));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Completable, Computable, DynGeneratable, Generatable, Incomplete};
+
+/// A [`Computable`] that consumes a [`Generatable`] and returns its last item
+/// (or `None` if the generator produced no items).
+///
+/// Every call to [`Computable::try_compute`] advances the generator at most once,
+/// such that long generators do not block. Suspensions and cancellation of the generator
+/// are passed through. Once the result is returned, the computation is
+/// [`Incomplete::Exhausted`]. This is the reverse of [`crate::IntoGenerator`].
+///
+/// See also [`crate::GeneratableExt::last_item`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(5, 0);
+/// assert_eq!(generator.last_item().compute().unwrap(), Some(5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct LastItem<ITEM, G = DynGeneratable<ITEM>>
+where
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    last: Option<ITEM>,
+    finished: bool,
+}
+
+impl<ITEM, G: Generatable<ITEM>> LastItem<ITEM, G> {
+    /// Create a new [`LastItem`] computation for the given generator.
+    pub fn new(generator: G) -> Self {
+        LastItem {
+            generator,
+            last: None,
+            finished: false,
+        }
+    }
+
+    /// The most recent item produced by the generator so far.
+    pub fn last(&self) -> Option<&ITEM> {
+        self.last.as_ref()
+    }
+}
+
+impl<ITEM, G: Generatable<ITEM>> Computable<Option<ITEM>> for LastItem<ITEM, G> {
+    fn try_compute(&mut self) -> Completable<Option<ITEM>> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        match self.generator.try_next() {
+            Some(Ok(item)) => {
+                self.last = Some(item);
+                Err(Incomplete::Suspended)
+            }
+            Some(Err(Incomplete::Exhausted)) | None => {
+                self.finished = true;
+                Ok(self.last.take())
+            }
+            Some(Err(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableExt, FnComputation, FromParts, GeneratableExt, test_fixtures::Items};
+
+    #[test]
+    fn test_last_item_with_suspension() {
+        let mut last = LastItem::new(Items::from_parts(vec![1, 0, 2], 0));
+        assert_eq!(last.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(last.last(), Some(&1));
+        assert_eq!(last.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(last.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(last.try_compute(), Ok(Some(2)));
+        assert_eq!(last.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_empty_generator() {
+        let mut last = Items::from_parts(vec![], 0).last_item();
+        assert_eq!(last.compute(), Ok(None));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let computation = FnComputation::new(7, (), |value: &i32, _: &mut ()| Ok(*value));
+        let mut last = computation.into_generator().last_item();
+        assert_eq!(last.compute(), Ok(Some(7)));
+    }
+}
@@ -29,17 +29,66 @@
 //!
 //! ## Core Concepts
 //!
-//! - [`Completable<T>`]: A result type that can be incomplete (`Suspended`, `Cancelled`, or `Exhausted`).
+//! - [`Completable<T>`]: A result type that can be incomplete (`Suspended`, `Cancelled`, `Exhausted`, or `ResourceExceeded`).
 //! - [`Computable<T>`]: A trait for objects that can be driven to completion by calling [`Computable::try_compute`].
 //! - [`Algorithm<CTX, STATE, T>`]: Extends [`Computable`] with access to context and state.
 //! - [`Generatable<T>`]: Like [`Computable`], but produces a stream of values.
 //! - [`GenAlgorithm<CTX, STATE, T>`]: Extends [`Generatable`] with context and state.
 //! - [`Computation`] and [`Generator`]: Default implementations using step functions.
+//! - [`ResumableWith<INPUT, T>`]: Like [`Computable`], but every resume carries an `INPUT` value.
+//!
+//! With the `derive` feature, the `ComputationState` derive macro generates a [`ComputationStep`]
+//! from an enum of computation phases (see [`Transition`]), and the `computation!` and
+//! `generator!` macros compile straight-line code with `suspend!()` and `emit!(item)`
+//! markers into a [`ComputationStep`] or [`GeneratorStep`].
+//!
+//! With the `rayon` feature, `ParallelComputation` allows individual steps to use
+//! data-parallelism while keeping cancellation observable on the worker threads.
+//!
+//! With the `persistence` feature, `JobQueue` executes a queue of registered serializable
+//! jobs and can save (and later restore) the queue, including the progress of the running job.
+//! The underlying `Registry` maps type tags to concrete types, such that type-erased
+//! algorithms (e.g., `DynPersistentAlgorithm`) can be stored in heterogeneous checkpoints.
+//!
+//! With the `testing` feature, the `testing` module provides helpers which assert that
+//! a computation has the same outcome when it is serialized and restored at every suspend point.
+//!
+//! With the `ctrlc` feature, `run_interruptible` drives a computation until it completes
+//! or until Ctrl+C is pressed, in which case the computation is cancelled.
+//!
+//! With the `wasm` feature, `SliceDriver` advances a computation in time slices that can be
+//! scheduled from a browser event loop, and can save the computation into a JSON string
+//! between the slices (e.g., to move it between a web worker and the main thread).
+//!
+//! With the `indicatif` feature, `WithProgressBar` displays the [`Progress`] of a computation
+//! using a progress bar that is updated at every suspend point.
+//!
+//! In debug builds (or with the `debug-invariants` feature), [`CheckedComputation`] verifies
+//! a user-provided invariant of the computation state after every step.
+//!
+//! The [`algorithms`] module provides ready-made suspendable skeletons of common algorithm
+//! shapes (e.g., worklist-based graph exploration).
+//!
+//! Long-running step functions can call [`yield_point!`] inside their loops to suspend
+//! according to a [`YieldPolicy`] configured by the driver of the computation.
+//!
+//! Most functionality is provided through traits. To bring all of them into scope
+//! (including the [`ComputableExt`] and [`GeneratableExt`] combinators), use
+//! `use computation_process::prelude::*;`.
+//!
+//! ## `no_std`
+//!
+//! The crate requires `std` and there is no `no_std` mode. Cancellation is built
+//! on [`cancel-this`](https://crates.io/crates/cancel-this). Its `Cancelled` type is part
+//! of the core API (e.g., [`Incomplete::Cancelled`] and [`Computable::compute`]), and its
+//! thread-local triggers are checked by every [`Computation`] and [`Generator`] step.
+//! `cancel-this` itself depends on `std`. A `no_std + alloc` core would therefore need its
+//! own cancellation mechanism, which would be a breaking change for all users.
 //!
 //! ## Quick Example
 //!
 //! ```rust
-//! use computation_process::{Computation, ComputationStep, Completable, Incomplete, Computable, Stateful};
+//! use computation_process::{Computation, ComputationStep, Completable, Incomplete, Computable, FromParts};
 //!
 //! struct CountingStep;
 //!
@@ -62,26 +111,220 @@
 // All traits/structs have dedicated modules for encapsulation, and we then re-export
 // these types here for easier public usage.
 
+mod adaptive_budget;
 mod algorithm;
+mod batch;
+mod best_so_far;
+mod blackboard;
+mod blocking_iter;
+mod bounded_buffer;
+mod broadcast;
+mod buffered;
+mod cancel_token;
+mod channel_generatable;
+mod channel_sink;
+mod checked_computation;
+mod chunking_collector;
 mod collector;
 mod completable;
+mod composite;
 mod computable;
 mod computable_identity;
 mod computation;
+mod computation_id;
+mod dag_runner;
+mod dedup;
+mod downcast;
+mod drive_blocking;
+mod event_flag;
+mod ext;
+mod fallible;
+mod flat_map;
+mod flatten;
+mod fn_computation;
+mod fn_generator;
+mod folder;
+mod forkable;
+mod from_parts;
 mod generatable;
 mod generator;
-
+mod heap_size;
+mod incremental;
+#[cfg(feature = "ctrlc")]
+mod interruptible;
+mod into_generator;
+mod iter_generator;
+#[cfg(feature = "persistence")]
+mod job_queue;
+mod join;
+mod join_all;
+mod last_item;
+mod map;
+mod memoized;
+mod memory_limited;
+mod merge;
+mod named;
+mod ordered_merge;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod peekable;
+mod phased;
+mod progress;
+mod pump;
+#[cfg(feature = "persistence")]
+mod registry;
+mod resource_pool;
+mod resumable;
+mod retry;
+mod retry_hint;
+mod scan;
+mod scheduler;
+mod select_all;
+mod sequence;
+mod serializable_deadline;
+mod shared_context;
+mod shared_result;
+mod sink;
+#[cfg(feature = "wasm")]
+mod slice_driver;
+mod speculate;
+mod stateful_mut;
+mod stateful_ref;
+mod step_iter;
+mod step_middleware;
+mod sweep;
+mod tee;
+mod throttled;
+mod timeline;
+mod transition;
+mod wait_until;
+mod weighted_merge;
+mod windows;
+#[cfg(feature = "indicatif")]
+mod with_progress_bar;
+mod worker;
+mod yield_policy;
+
+pub mod algorithms;
+pub mod bench;
+pub mod pipeline;
+pub mod prelude;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(all(feature = "derive", test))]
+mod test_derive;
+#[cfg(test)]
+mod test_fixtures;
 #[cfg(all(feature = "serde", test))]
 mod test_serialization;
 
+pub use adaptive_budget::AdaptiveBudget;
 pub use algorithm::{Algorithm, GenAlgorithm, Stateful};
-pub use collector::Collector;
-pub use completable::{Completable, Incomplete};
+pub use batch::Batch;
+pub use best_so_far::BestSoFar;
+pub use blackboard::{Blackboard, BlackboardKey};
+pub use blocking_iter::{BlockingIter, CancelPolicy};
+pub use bounded_buffer::{BoundedBuffer, ProducerConsumer};
+pub use broadcast::{Broadcast, BroadcastReceiver};
+pub use buffered::Buffered;
+pub use cancel_token::CancelToken;
+pub use channel_generatable::ChannelGeneratable;
+pub use channel_sink::ChannelSink;
+pub use checked_computation::CheckedComputation;
+pub use chunking_collector::ChunkingCollector;
+pub use collector::{Collector, OverflowPolicy};
+pub use completable::{Completable, Incomplete, RESOURCE_EXCEEDED, Resource, ResourceExceeded};
+pub use composite::{Child, SubComputation, drive_sub};
 pub use computable::{Computable, ComputableResult};
 pub use computable_identity::ComputableIdentity;
 pub use computation::{Computation, ComputationStep};
+pub use computation_id::ComputationId;
+pub use dag_runner::DagRunner;
+pub use dedup::{Dedup, Unique};
+pub use downcast::{AnyComputable, AnyGeneratable};
+pub use drive_blocking::drive_blocking;
+pub use event_flag::EventFlag;
+pub use ext::{ComputableExt, GeneratableExt};
+pub use fallible::{AndThen, FallibleComputation, FallibleStep, MapErr, TryComputable};
+pub use flat_map::FlatMap;
+pub use flatten::Flatten;
+pub use fn_computation::FnComputation;
+pub use fn_generator::FnGenerator;
+pub use folder::Folder;
+pub use forkable::Forkable;
+pub use from_parts::FromParts;
 pub use generatable::Generatable;
 pub use generator::{Generator, GeneratorStep};
+pub use heap_size::HeapSize;
+pub use incremental::{Incremental, IncrementalStep};
+#[cfg(feature = "ctrlc")]
+pub use interruptible::run_interruptible;
+pub use into_generator::IntoGenerator;
+pub use iter_generator::IterGenerator;
+#[cfg(feature = "persistence")]
+pub use job_queue::{JobQueue, JobQueueError, JobRegistry};
+pub use join::Join;
+pub use join_all::{JoinAll, JoinPolicy, TryJoinAll};
+pub use last_item::LastItem;
+pub use map::Map;
+pub use memoized::{LruCache, MemoCache, Memoized, ResultCache};
+pub use memory_limited::MemoryLimited;
+pub use merge::Merge;
+pub use named::Named;
+pub use ordered_merge::OrderedMerge;
+#[cfg(feature = "rayon")]
+pub use parallel::{CancelScope, ParallelComputation, ParallelStep};
+pub use peekable::Peekable;
+pub use phased::Phased;
+pub use progress::Progress;
+pub use pump::Pump;
+#[cfg(feature = "persistence")]
+pub use registry::{
+    DynPersistentAlgorithm, DynPersistentComputable, DynPersistentGenAlgorithm, Erase, Persistent,
+    PersistentAlgorithm, PersistentComputable, PersistentGenAlgorithm, Registry, RegistryError,
+    Tagged,
+};
+pub use resource_pool::{Pooled, ResourcePool};
+pub use resumable::{DynResumableWith, ResumableComputation, ResumableStep, ResumableWith};
+pub use retry::{Backoff, Retry};
+pub use retry_hint::{suspend_for, take_retry_hint};
+pub use scan::Scan;
+pub use scheduler::{Scheduler, TaskGuard, TaskStatus};
+pub use select_all::SelectAll;
+pub use sequence::Sequence;
+pub use serializable_deadline::SerializableDeadline;
+pub use shared_context::SharedContext;
+#[cfg(feature = "serde")]
+pub use shared_context::shared_context_scope;
+pub use shared_result::{ResultHandle, SharedResult};
+pub use sink::Sink;
+#[cfg(feature = "wasm")]
+pub use slice_driver::SliceDriver;
+pub use speculate::Speculate;
+pub use stateful_mut::StatefulMut;
+pub use stateful_ref::{StatefulAlgorithm, StatefulRef};
+pub use step_iter::{BudgetIter, StepIter};
+pub use step_middleware::{Layered, StepMiddleware};
+pub use sweep::Sweep;
+pub use tee::{Tee, tee};
+pub use throttled::Throttled;
+pub use timeline::{StepOutcome, Timeline, TimelineEvent};
+pub use transition::Transition;
+pub use wait_until::{WaitUntil, suspend_until, take_wake_condition};
+pub use weighted_merge::WeightedMerge;
+pub use windows::Windows;
+#[cfg(feature = "indicatif")]
+pub use with_progress_bar::WithProgressBar;
+pub use worker::{WorkerHandle, WorkerStatus, spawn_worker, spawn_worker_with_snapshots};
+pub use yield_policy::{YieldPolicy, Yielding, yield_point};
+
+#[cfg(feature = "derive")]
+pub use computation_process_derive::{ComputationState, computation, generator};
+
+// Allows the derive macros to refer to `::computation_process` within this crate (e.g., in tests).
+#[cfg(feature = "derive")]
+extern crate self as computation_process;
 
 /// A type alias for `Box<dyn Computable<T>>`.
 pub type DynComputable<T> = Box<dyn Computable<T>>;
@@ -95,6 +338,36 @@ pub type DynAlgorithm<CONTEXT, STATE, OUTPUT> = Box<dyn Algorithm<CONTEXT, STATE
 /// A type alias for `Box<dyn GenAlgorithm<CONTEXT, STATE, OUTPUT>>`.
 pub type DynGenAlgorithm<CONTEXT, STATE, ITEM> = Box<dyn GenAlgorithm<CONTEXT, STATE, ITEM>>;
 
+/// A type alias for `Box<dyn Computable<T> + Send>`.
+///
+/// Unlike [`DynComputable`], this type can be moved across threads (e.g., into an executor).
+pub type DynComputableSend<T> = Box<dyn Computable<T> + Send>;
+
+/// A type alias for `Box<dyn Generatable<T> + Send>`.
+pub type DynGeneratableSend<T> = Box<dyn Generatable<T> + Send>;
+
+/// A type alias for `Box<dyn Algorithm<CONTEXT, STATE, OUTPUT> + Send>`.
+pub type DynAlgorithmSend<CONTEXT, STATE, OUTPUT> =
+    Box<dyn Algorithm<CONTEXT, STATE, OUTPUT> + Send>;
+
+/// A type alias for `Box<dyn GenAlgorithm<CONTEXT, STATE, OUTPUT> + Send>`.
+pub type DynGenAlgorithmSend<CONTEXT, STATE, ITEM> =
+    Box<dyn GenAlgorithm<CONTEXT, STATE, ITEM> + Send>;
+
+/// A type alias for `Box<dyn AnyComputable<T>>` (a [`DynComputable`] that can be downcast).
+pub type DynAnyComputable<T> = Box<dyn AnyComputable<T>>;
+
+/// A type alias for `Box<dyn AnyGeneratable<T>>` (a [`DynGeneratable`] that can be downcast).
+pub type DynAnyGeneratable<T> = Box<dyn AnyGeneratable<T>>;
+
+/// A type alias for `Box<dyn StatefulAlgorithm<CONTEXT, STATE, OUTPUT>>`.
+pub type DynStatefulAlgorithm<CONTEXT, STATE, OUTPUT> =
+    Box<dyn StatefulAlgorithm<CONTEXT, STATE, OUTPUT>>;
+
+/// A type alias for `Box<dyn StatefulAlgorithm<CONTEXT, STATE, OUTPUT> + Send>`.
+pub type DynStatefulAlgorithmSend<CONTEXT, STATE, OUTPUT> =
+    Box<dyn StatefulAlgorithm<CONTEXT, STATE, OUTPUT> + Send>;
+
 // Dummy implementations of Computable / Generatable for dynamic objects, because these
 // are not implemented automatically.
 
@@ -110,6 +383,18 @@ impl<CONTEXT, STATE, OUTPUT> Computable<OUTPUT> for DynAlgorithm<CONTEXT, STATE,
     }
 }
 
+impl<CONTEXT, STATE, OUTPUT> Computable<OUTPUT> for DynStatefulAlgorithm<CONTEXT, STATE, OUTPUT> {
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        (**self).try_compute()
+    }
+}
+
+impl<T> Computable<T> for DynAnyComputable<T> {
+    fn try_compute(&mut self) -> Completable<T> {
+        (**self).try_compute()
+    }
+}
+
 impl<T> Generatable<T> for DynGeneratable<T> {
     fn try_next(&mut self) -> Option<Completable<T>> {
         (**self).try_next()
@@ -122,6 +407,44 @@ impl<CONTEXT, STATE, OUTPUT> Generatable<OUTPUT> for DynGenAlgorithm<CONTEXT, ST
     }
 }
 
+impl<T> Generatable<T> for DynAnyGeneratable<T> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        (**self).try_next()
+    }
+}
+
+impl<T> Computable<T> for DynComputableSend<T> {
+    fn try_compute(&mut self) -> Completable<T> {
+        (**self).try_compute()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT> Computable<OUTPUT> for DynAlgorithmSend<CONTEXT, STATE, OUTPUT> {
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        (**self).try_compute()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT> Computable<OUTPUT>
+    for DynStatefulAlgorithmSend<CONTEXT, STATE, OUTPUT>
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        (**self).try_compute()
+    }
+}
+
+impl<T> Generatable<T> for DynGeneratableSend<T> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        (**self).try_next()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT> Generatable<OUTPUT> for DynGenAlgorithmSend<CONTEXT, STATE, OUTPUT> {
+    fn try_next(&mut self) -> Option<Completable<OUTPUT>> {
+        (**self).try_next()
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use super::*;
@@ -221,6 +544,46 @@ mod integration_tests {
         assert_eq!(item, 1);
     }
 
+    #[test]
+    fn test_dyn_computable_send_across_threads() {
+        let computation =
+            Computation::<Vec<i32>, i32, i32, SumComputationStep>::from_parts(vec![1, 2, 3], 0);
+        let mut dyn_computable: DynComputableSend<i32> = computation.dyn_computable_send();
+        let handle = std::thread::spawn(move || dyn_computable.compute().unwrap());
+        assert_eq!(handle.join().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_dyn_algorithm_send_across_threads() {
+        let computation =
+            Computation::<Vec<i32>, i32, i32, SumComputationStep>::from_parts(vec![4, 5], 0);
+        let mut dyn_algorithm: DynAlgorithmSend<Vec<i32>, i32, i32> =
+            computation.dyn_algorithm_send();
+        let handle = std::thread::spawn(move || {
+            let result = dyn_algorithm.compute().unwrap();
+            (result, *dyn_algorithm.state())
+        });
+        assert_eq!(handle.join().unwrap(), (9, 2));
+    }
+
+    #[test]
+    fn test_dyn_generatable_send_across_threads() {
+        let generator = Generator::<i32, i32, i32, RangeGeneratorStep>::from_parts(3, 0);
+        let dyn_generatable: DynGeneratableSend<i32> = generator.dyn_generatable_send();
+        let handle = std::thread::spawn(move || {
+            let mut collector: Collector<i32, Vec<i32>, DynGeneratableSend<i32>> =
+                Collector::new(dyn_generatable);
+            collector.compute().unwrap()
+        });
+        assert_eq!(handle.join().unwrap(), vec![1, 2, 3]);
+
+        let generator = Generator::<i32, i32, i32, RangeGeneratorStep>::from_parts(2, 0);
+        let mut dyn_gen_algorithm: DynGenAlgorithmSend<i32, i32, i32> =
+            generator.dyn_algorithm_send();
+        let handle = std::thread::spawn(move || dyn_gen_algorithm.try_next());
+        assert_eq!(handle.join().unwrap(), Some(Ok(1)));
+    }
+
     #[test]
     fn test_end_to_end_computation_with_suspensions() {
         let computation =
@@ -0,0 +1,71 @@
+use crate::{Completable, Computable};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that transforms the result of another [`Computable`] using a function.
+///
+/// See [`crate::ComputableExt::map`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::ComputableIdentity;
+///
+/// let identity: ComputableIdentity<i32> = 21.into();
+/// let mut doubled = identity.map(|x| x * 2);
+/// assert_eq!(doubled.compute().unwrap(), 42);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Map<T, C: Computable<T>, F> {
+    computable: C,
+    function: Option<F>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>, F> Map<T, C, F> {
+    /// Create a new [`Map`] adapter transforming the result of `computable`.
+    pub fn new(computable: C, function: F) -> Self {
+        Map {
+            computable,
+            function: Some(function),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// A reference to the underlying computation.
+    pub fn computable_ref(&self) -> &C {
+        &self.computable
+    }
+}
+
+impl<T, R, C: Computable<T>, F: FnOnce(T) -> R> Computable<R> for Map<T, C, F> {
+    fn try_compute(&mut self) -> Completable<R> {
+        let value = self.computable.try_compute()?;
+        match self.function.take() {
+            Some(function) => Ok(function(value)),
+            None => Err(crate::Incomplete::Exhausted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{ComputableIdentity, Computation, Incomplete, test_fixtures::CountStep};
+
+    #[test]
+    fn test_map_passes_suspensions() {
+        let computation = Computation::<u32, u32, u32, CountStep>::from_parts(2, 0);
+        let mut mapped = computation.map(|x| format!("count={}", x));
+        assert_eq!(mapped.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(mapped.try_compute(), Ok("count=2".to_string()));
+    }
+
+    #[test]
+    fn test_map_exhausted() {
+        let identity: ComputableIdentity<i32> = 1.into();
+        let mut mapped = identity.map(|x| x + 1);
+        assert_eq!(mapped.try_compute(), Ok(2));
+        assert_eq!(mapped.try_compute(), Err(Incomplete::Exhausted));
+    }
+}
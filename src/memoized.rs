@@ -0,0 +1,381 @@
+use crate::{Algorithm, Completable, Computable, Incomplete};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// A storage of computed results used by [`MemoCache`].
+///
+/// The crate provides implementations for [`HashMap`] (unbounded) and [`LruCache`]
+/// (bounded, evicting the least recently used entries).
+pub trait ResultCache<K, V> {
+    /// Retrieve a cached value (the cache may update its internal bookkeeping).
+    fn lookup(&mut self, key: &K) -> Option<&V>;
+
+    /// Returns `true` if a value is cached for `key` (without updating any bookkeeping).
+    fn contains(&self, key: &K) -> bool;
+
+    /// Store a new value in the cache.
+    fn store(&mut self, key: K, value: V);
+}
+
+impl<K: Eq + Hash, V> ResultCache<K, V> for HashMap<K, V> {
+    fn lookup(&mut self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+/// A simple bounded [`ResultCache`] which evicts the least recently used entry once
+/// its capacity is exceeded.
+///
+/// Eviction is linear in the number of entries, which is acceptable for the intended
+/// use case where the cached values are results of (expensive) computations.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    clock: u64,
+    entries: HashMap<K, (V, u64)>,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    /// Create a new cache with the given `capacity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "`capacity` must be positive.");
+        LruCache {
+            capacity,
+            clock: 0,
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// The number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the cache contains the given `key` (without updating its usage).
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> ResultCache<K, V> for LruCache<K, V> {
+    fn lookup(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(value)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        LruCache::contains(self, key)
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.clock += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (value, self.clock));
+    }
+}
+
+/// A shared cache of computation results, used to create [`Memoized`] computations.
+///
+/// The cache is reference-counted: cloning a [`MemoCache`] creates a new handle to the
+/// same underlying storage. All [`Memoized`] computations created from the same cache
+/// share their results.
+pub struct MemoCache<K, T, CACHE = HashMap<K, T>>
+where
+    CACHE: ResultCache<K, T>,
+{
+    cache: Rc<RefCell<CACHE>>,
+    _phantom: PhantomData<(K, T)>,
+}
+
+impl<K, T, CACHE: ResultCache<K, T>> Clone for MemoCache<K, T, CACHE> {
+    fn clone(&self) -> Self {
+        MemoCache {
+            cache: self.cache.clone(),
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, T: Clone> Default for MemoCache<K, T> {
+    fn default() -> Self {
+        MemoCache::new(HashMap::new())
+    }
+}
+
+impl<K, T: Clone, CACHE: ResultCache<K, T>> MemoCache<K, T, CACHE> {
+    /// Create a new [`MemoCache`] backed by the given [`ResultCache`].
+    pub fn new(cache: CACHE) -> Self {
+        MemoCache {
+            cache: Rc::new(RefCell::new(cache)),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Wrap `computable` such that its result is stored under `key`, or, if a result
+    /// for `key` is already known, the computation is skipped entirely.
+    pub fn memoize<C: Computable<T>>(&self, key: K, computable: C) -> Memoized<K, T, C, CACHE> {
+        Memoized {
+            cache: self.clone(),
+            key: Some(key),
+            computable,
+        }
+    }
+
+    /// Same as [`MemoCache::memoize`], but the key is derived from the `CONTEXT`
+    /// of the given [`Algorithm`].
+    pub fn memoize_algorithm<CONTEXT, STATE, C, F>(
+        &self,
+        algorithm: C,
+        key: F,
+    ) -> Memoized<K, T, C, CACHE>
+    where
+        C: Algorithm<CONTEXT, STATE, T>,
+        F: FnOnce(&CONTEXT) -> K,
+    {
+        let key = key(algorithm.context());
+        self.memoize(key, algorithm)
+    }
+
+    /// Retrieve a copy of the result stored under `key`.
+    pub fn get(&self, key: &K) -> Option<T> {
+        self.cache.borrow_mut().lookup(key).cloned()
+    }
+}
+
+/// A [`Computable`] whose result is stored in (and if possible retrieved from) a [`MemoCache`].
+///
+/// See [`MemoCache::memoize`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, MemoCache};
+///
+/// struct SquareStep;
+///
+/// impl ComputationStep<u64, (), u64> for SquareStep {
+///     fn step(x: &u64, _: &mut ()) -> Completable<u64> {
+///         Ok(x * x)
+///     }
+/// }
+///
+/// type Square = Computation<u64, (), u64, SquareStep>;
+///
+/// let cache = MemoCache::default();
+/// let mut first = cache.memoize_algorithm(Square::from_parts(7, ()), |x| *x);
+/// assert_eq!(first.compute().unwrap(), 49);
+/// // The second computation is never executed.
+/// let mut second = cache.memoize_algorithm(Square::from_parts(7, ()), |x| *x);
+/// assert!(second.is_cached());
+/// assert_eq!(second.compute().unwrap(), 49);
+/// ```
+pub struct Memoized<K, T, C, CACHE = HashMap<K, T>>
+where
+    C: Computable<T>,
+    CACHE: ResultCache<K, T>,
+{
+    cache: MemoCache<K, T, CACHE>,
+    key: Option<K>,
+    computable: C,
+}
+
+impl<K, T: Clone, C: Computable<T>, CACHE: ResultCache<K, T>> Memoized<K, T, C, CACHE> {
+    /// Returns `true` if the result of this computation is already stored in the cache.
+    pub fn is_cached(&self) -> bool {
+        match self.key.as_ref() {
+            Some(key) => self.cache.cache.borrow().contains(key),
+            None => false,
+        }
+    }
+
+    /// A reference to the underlying computation.
+    pub fn computable_ref(&self) -> &C {
+        &self.computable
+    }
+}
+
+impl<K, T: Clone, C: Computable<T>, CACHE: ResultCache<K, T>> Computable<T>
+    for Memoized<K, T, C, CACHE>
+{
+    fn try_compute(&mut self) -> Completable<T> {
+        let key = self.key.as_ref().ok_or(Incomplete::Exhausted)?;
+        if let Some(value) = self.cache.get(key) {
+            self.key = None;
+            return Ok(value);
+        }
+        let value = self.computable.try_compute()?;
+        if let Some(key) = self.key.take() {
+            self.cache.cache.borrow_mut().store(key, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Counts the number of actual executions using a shared counter.
+    struct CountingComputable {
+        value: i32,
+        steps: u32,
+        executions: Rc<Cell<u32>>,
+    }
+
+    impl Computable<i32> for CountingComputable {
+        fn try_compute(&mut self) -> Completable<i32> {
+            self.executions.set(self.executions.get() + 1);
+            if self.steps > 0 {
+                self.steps -= 1;
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(self.value)
+            }
+        }
+    }
+
+    fn counting(value: i32, executions: &Rc<Cell<u32>>) -> CountingComputable {
+        CountingComputable {
+            value,
+            steps: 1,
+            executions: executions.clone(),
+        }
+    }
+
+    #[test]
+    fn test_memoized_hash_map() {
+        let executions = Rc::new(Cell::new(0));
+        let cache = MemoCache::default();
+
+        let mut first = cache.memoize("a", counting(1, &executions));
+        assert!(!first.is_cached());
+        assert_eq!(first.compute().unwrap(), 1);
+        assert_eq!(executions.get(), 2);
+        assert_eq!(first.try_compute(), Err(Incomplete::Exhausted));
+
+        let mut second = cache.memoize("a", counting(2, &executions));
+        assert!(second.is_cached());
+        assert_eq!(second.try_compute(), Ok(1));
+        assert_eq!(executions.get(), 2);
+
+        let mut third = cache.memoize("b", counting(3, &executions));
+        assert_eq!(third.compute().unwrap(), 3);
+        assert_eq!(cache.get(&"b"), Some(3));
+    }
+
+    #[test]
+    fn test_lru_cache_eviction() {
+        let mut cache = LruCache::new(2);
+        cache.store(1, "one");
+        cache.store(2, "two");
+        assert_eq!(cache.lookup(&1), Some(&"one"));
+        // Key 2 is now the least recently used entry.
+        cache.store(3, "three");
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&1));
+        assert!(!cache.contains(&2));
+        assert!(cache.contains(&3));
+    }
+
+    #[test]
+    fn test_memoized_lru() {
+        let executions = Rc::new(Cell::new(0));
+        let cache = MemoCache::new(LruCache::new(1));
+
+        assert_eq!(
+            cache.memoize(1, counting(10, &executions)).compute(),
+            Ok(10)
+        );
+        assert_eq!(
+            cache.memoize(2, counting(20, &executions)).compute(),
+            Ok(20)
+        );
+        // Key 1 was evicted, so the computation runs again.
+        assert_eq!(
+            cache.memoize(1, counting(11, &executions)).compute(),
+            Ok(11)
+        );
+        assert_eq!(executions.get(), 6);
+    }
+
+    #[test]
+    fn test_is_cached_keeps_lru_order() {
+        let executions = Rc::new(Cell::new(0));
+        let cache = MemoCache::new(LruCache::new(2));
+        assert_eq!(
+            cache.memoize(1, counting(10, &executions)).compute(),
+            Ok(10)
+        );
+        assert_eq!(
+            cache.memoize(2, counting(20, &executions)).compute(),
+            Ok(20)
+        );
+        // Checking the cache does not count as a use of key 1.
+        assert!(cache.memoize(1, counting(11, &executions)).is_cached());
+        assert_eq!(
+            cache.memoize(3, counting(30, &executions)).compute(),
+            Ok(30)
+        );
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(20));
+    }
+
+    #[test]
+    fn test_memoized_cancellation_keeps_key() {
+        use crate::{Computation, ComputationStep, FromParts};
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        struct DoubleStep;
+
+        impl ComputationStep<i32, (), i32> for DoubleStep {
+            fn step(x: &i32, _: &mut ()) -> Completable<i32> {
+                Ok(2 * x)
+            }
+        }
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let cache = MemoCache::default();
+        let computation = Computation::<i32, (), i32, DoubleStep>::from_parts(4, ());
+        let mut memoized = cache.memoize_algorithm(computation, |x| *x);
+        let result = on_trigger(trigger, || memoized.compute());
+        assert!(result.is_err());
+        assert_eq!(cache.get(&4), None);
+        assert_eq!(memoized.compute(), Ok(8));
+        assert_eq!(cache.get(&4), Some(8));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    Completable, Computable, HeapSize, Incomplete, Resource, ResourceExceeded, StatefulRef,
+};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that tracks the number of steps and the (approximate) memory used by
+/// another computation, and stops it once a memory budget is exceeded.
+///
+/// The memory of the inner computation is measured after every step that suspends,
+/// using a size function (for computations whose `STATE` implements [`HeapSize`],
+/// [`MemoryLimited::new`] measures the heap size of the state). If the measured size exceeds
+/// the budget, the wrapper returns [`Incomplete::ResourceExceeded`] instead of
+/// [`Incomplete::Suspended`]. The inner computation is kept intact, so after raising the
+/// budget (see [`MemoryLimited::set_budget`]), the computation can be resumed. Until then,
+/// every call returns [`Incomplete::ResourceExceeded`] without advancing the computation.
+///
+/// Blocking APIs (e.g., [`Computable::compute`]) report the outcome as
+/// [`cancel_this::Cancelled`] with [`crate::RESOURCE_EXCEEDED`] as the cause.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, Incomplete, MemoryLimited, Resource,
+/// };
+///
+/// /// Collects numbers up to the target.
+/// struct CollectStep;
+///
+/// impl ComputationStep<u64, Vec<u64>, usize> for CollectStep {
+///     fn step(target: &u64, items: &mut Vec<u64>) -> Completable<usize> {
+///         items.push(items.len() as u64);
+///         if items.len() as u64 >= *target { Ok(items.len()) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Collect = Computation<u64, Vec<u64>, usize, CollectStep>;
+///
+/// let mut limited = MemoryLimited::new(Collect::from_parts(1000, Vec::new()), 1024);
+/// let Err(Incomplete::ResourceExceeded(exceeded)) = limited.compute_completable() else {
+///     unreachable!()
+/// };
+/// assert_eq!(exceeded.resource, Resource::Memory);
+/// assert!(exceeded.used > 1024);
+///
+/// // Raising the budget allows the computation to finish.
+/// limited.set_budget(1 << 20);
+/// assert_eq!(limited.compute().unwrap(), 1000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryLimited<T, C, F = fn(&C) -> usize> {
+    inner: C,
+    size: F,
+    budget: usize,
+    steps: u64,
+    last_size: usize,
+    peak_size: usize,
+    _phantom: PhantomData<T>,
+}
+
+/// Measures the heap size of the state of a [`StatefulRef`] computation.
+fn state_heap_size<CONTEXT, STATE: HeapSize, C: StatefulRef<CONTEXT, STATE>>(
+    computation: &C,
+) -> usize {
+    computation.state().heap_size()
+}
+
+impl<T, C> MemoryLimited<T, C> {
+    /// Wrap the `inner` computation such that the heap size of its `STATE` never exceeds
+    /// `budget` bytes (at suspend points).
+    pub fn new<CONTEXT, STATE: HeapSize>(inner: C, budget: usize) -> Self
+    where
+        C: StatefulRef<CONTEXT, STATE>,
+    {
+        MemoryLimited::with_size_fn(inner, budget, state_heap_size::<CONTEXT, STATE, C>)
+    }
+}
+
+impl<T, C, F: Fn(&C) -> usize> MemoryLimited<T, C, F> {
+    /// Wrap the `inner` computation such that the (approximate) size returned by `size`
+    /// never exceeds `budget` bytes (at suspend points).
+    pub fn with_size_fn(inner: C, budget: usize, size: F) -> Self {
+        MemoryLimited {
+            inner,
+            size,
+            budget,
+            steps: 0,
+            last_size: 0,
+            peak_size: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The current memory budget (in bytes).
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Change the memory budget (in bytes).
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+    }
+
+    /// The number of steps of the inner computation performed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The size of the inner computation measured after the last step.
+    pub fn last_size(&self) -> usize {
+        self.last_size
+    }
+
+    /// The largest size of the inner computation measured so far.
+    pub fn peak_size(&self) -> usize {
+        self.peak_size
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn check_budget(&self) -> Result<(), Incomplete> {
+        if self.last_size > self.budget {
+            Err(Incomplete::ResourceExceeded(ResourceExceeded {
+                resource: Resource::Memory,
+                limit: self.budget as u64,
+                used: self.last_size as u64,
+            }))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T, C: Computable<T>, F: Fn(&C) -> usize> Computable<T> for MemoryLimited<T, C, F> {
+    fn try_compute(&mut self) -> Completable<T> {
+        self.check_budget()?;
+        let result = self.inner.try_compute();
+        self.steps += 1;
+        if let Err(Incomplete::Suspended) = result {
+            self.last_size = (self.size)(&self.inner);
+            self.peak_size = self.peak_size.max(self.last_size);
+            self.check_budget()?;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, FromParts, RESOURCE_EXCEEDED, StatefulRef};
+
+    /// Pushes one string per step, finishes once the target count is reached.
+    struct GrowStep;
+
+    impl ComputationStep<usize, Vec<String>, usize> for GrowStep {
+        fn step(target: &usize, items: &mut Vec<String>) -> Completable<usize> {
+            if items.len() >= *target {
+                return Ok(items.len());
+            }
+            items.push("x".repeat(100));
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Grow = Computation<usize, Vec<String>, usize, GrowStep>;
+
+    #[test]
+    fn test_within_budget() {
+        let mut limited = MemoryLimited::new(Grow::from_parts(3, Vec::new()), 10_000);
+        assert_eq!(limited.compute(), Ok(3));
+        assert_eq!(limited.steps(), 4);
+        assert!(limited.peak_size() >= 300);
+        assert_eq!(limited.inner().state().len(), 3);
+    }
+
+    #[test]
+    fn test_exceeded_and_resumed() {
+        let mut limited = MemoryLimited::with_size_fn(Grow::from_parts(5, Vec::new()), 250, |c| {
+            c.state().iter().map(|it| it.len()).sum()
+        });
+        assert_eq!(limited.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(limited.try_compute(), Err(Incomplete::Suspended));
+        let expected = Incomplete::ResourceExceeded(ResourceExceeded {
+            resource: Resource::Memory,
+            limit: 250,
+            used: 300,
+        });
+        assert_eq!(limited.try_compute(), Err(expected.clone()));
+        // The computation is not advanced while the budget is exceeded.
+        assert_eq!(limited.try_compute(), Err(expected));
+        assert_eq!(limited.steps(), 3);
+        assert_eq!(limited.last_size(), 300);
+
+        limited.set_budget(1000);
+        assert_eq!(limited.budget(), 1000);
+        assert_eq!(limited.compute(), Ok(5));
+        assert_eq!(limited.peak_size(), 500);
+        assert_eq!(limited.into_inner().state().len(), 5);
+    }
+
+    #[test]
+    fn test_blocking_compute_reports_cause() {
+        let mut limited = MemoryLimited::new(Grow::from_parts(100, Vec::new()), 1000);
+        assert_eq!(limited.compute().unwrap_err().cause(), RESOURCE_EXCEEDED);
+    }
+}
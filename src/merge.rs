@@ -0,0 +1,188 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that interleaves the items of multiple generators in a round-robin fashion.
+///
+/// Every call to [`Generatable::try_next`] polls the sub-generators (starting after the one
+/// that produced the last item) until one of them produces an item. If all sub-generators
+/// are suspended, the merge returns [`Incomplete::Suspended`]. Exhausted sub-generators are
+/// removed, and the merge is exhausted once all sub-generators are exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, Merge};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let merge = Merge::new(vec![
+///     Generator::<u32, u32, u32, RangeStep>::from_parts(2, 0).dyn_generatable(),
+///     Generator::<u32, u32, u32, RangeStep>::from_parts(13, 10).dyn_generatable(),
+/// ]);
+/// let items: Vec<u32> = merge.map(|it| it.unwrap()).collect();
+/// assert_eq!(items, vec![1, 11, 2, 12, 13]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct Merge<T, G = DynGeneratable<T>>
+where
+    G: Generatable<T>,
+{
+    generators: Vec<G>,
+    cursor: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, G: Generatable<T>> Merge<T, G> {
+    /// Create a new [`Merge`] of the given generators.
+    pub fn new(generators: Vec<G>) -> Self {
+        Merge {
+            generators,
+            cursor: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Add another generator to this [`Merge`].
+    pub fn push(&mut self, generator: G) {
+        self.generators.push(generator);
+    }
+
+    /// The number of sub-generators that are not exhausted yet.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Returns `true` if all sub-generators are exhausted.
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for Merge<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for Merge<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let mut remaining = self.generators.len();
+        while remaining > 0 {
+            if self.cursor >= self.generators.len() {
+                self.cursor = 0;
+            }
+            match self.generators[self.cursor].try_next() {
+                Some(Ok(item)) => {
+                    self.cursor += 1;
+                    return Some(Ok(item));
+                }
+                Some(Err(Incomplete::Suspended)) => self.cursor += 1,
+                Some(Err(Incomplete::Exhausted)) | None => {
+                    // The removed generator is replaced by its successor at the same index.
+                    self.generators.remove(self.cursor);
+                }
+                Some(Err(e)) => return Some(Err(e)),
+            }
+            remaining -= 1;
+        }
+
+        if self.generators.is_empty() {
+            None
+        } else {
+            Some(Err(Incomplete::Suspended))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Generator, GeneratorStep, test_fixtures::ItemsStep};
+
+    /// Produces the context items, suspending once before each item.
+    struct SlowItemsStep;
+
+    impl GeneratorStep<Vec<i32>, usize, i32> for SlowItemsStep {
+        fn step(items: &Vec<i32>, index: &mut usize) -> Completable<Option<i32>> {
+            *index += 1;
+            if *index % 2 == 1 {
+                if *index / 2 < items.len() {
+                    Err(Incomplete::Suspended)
+                } else {
+                    Ok(None)
+                }
+            } else {
+                Ok(Some(items[*index / 2 - 1]))
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_round_robin() {
+        let mut merge = Merge::new(vec![
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![1, 2, 3], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![10], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![], 0).dyn_generatable(),
+        ]);
+        assert_eq!(merge.try_next(), Some(Ok(1)));
+        assert_eq!(merge.try_next(), Some(Ok(10)));
+        assert_eq!(merge.try_next(), Some(Ok(2)));
+        assert_eq!(merge.len(), 2);
+        assert_eq!(merge.try_next(), Some(Ok(3)));
+        assert_eq!(merge.try_next(), None);
+        assert!(merge.is_empty());
+    }
+
+    #[test]
+    fn test_merge_skips_suspended() {
+        let mut merge = Merge::new(vec![
+            Generator::<_, _, _, SlowItemsStep>::from_parts(vec![1, 2], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![10, 20], 0).dyn_generatable(),
+        ]);
+        // The first generator suspends, so the second one is used.
+        assert_eq!(merge.try_next(), Some(Ok(10)));
+        assert_eq!(merge.try_next(), Some(Ok(1)));
+        assert_eq!(merge.try_next(), Some(Ok(20)));
+        // Now the second generator is exhausted and the first one suspends.
+        assert_eq!(merge.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(merge.try_next(), Some(Ok(2)));
+        assert_eq!(merge.try_next(), None);
+    }
+
+    #[test]
+    fn test_merge_empty() {
+        let mut merge: Merge<i32> = Merge::new(Vec::new());
+        assert_eq!(merge.try_next(), None);
+    }
+
+    #[test]
+    fn test_merge_push_and_iterate() {
+        let mut merge = Merge::new(Vec::new());
+        merge.push(Generator::<_, _, _, SlowItemsStep>::from_parts(
+            vec![1, 2],
+            0,
+        ));
+        merge.push(Generator::<_, _, _, SlowItemsStep>::from_parts(vec![3], 0));
+        let mut items: Vec<i32> = merge.map(|it| it.unwrap()).collect();
+        items.sort();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+}
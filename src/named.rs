@@ -0,0 +1,160 @@
+use crate::{Completable, Computable, FromParts, Generatable, StatefulMut, StatefulRef};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+/// A wrapper which attaches a human-readable name to a [`Computable`] or [`Generatable`].
+///
+/// The wrapper behaves exactly like the inner object. The name is intended for logs,
+/// checkpoints, and progress reports; it is also displayed by the [`Display`]
+/// implementation. Use [`crate::Scheduler::spawn_named`] to attach a name to a task
+/// of a scheduler.
+///
+/// See also [`crate::ComputableExt::named`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::ComputableIdentity;
+///
+/// let mut task = ComputableIdentity::from(5).named("five");
+/// assert_eq!(task.name(), "five");
+/// assert_eq!(task.to_string(), "five");
+/// assert_eq!(task.compute().unwrap(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Named<C> {
+    name: Cow<'static, str>,
+    inner: C,
+}
+
+impl<C> Named<C> {
+    /// Attach the given `name` to `inner`.
+    pub fn new<N: Into<Cow<'static, str>>>(name: N, inner: C) -> Self {
+        Named {
+            name: name.into(),
+            inner,
+        }
+    }
+
+    /// The name of the wrapped object.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Change the name of the wrapped object.
+    pub fn rename<N: Into<Cow<'static, str>>>(&mut self, name: N) {
+        self.name = name.into();
+    }
+
+    /// A reference to the wrapped object.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// A mutable reference to the wrapped object.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Remove the name and return the wrapped object.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> Display for Named<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<T, C: Computable<T>> Computable<T> for Named<C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        self.inner.try_compute()
+    }
+}
+
+impl<C: Iterator> Iterator for Named<C> {
+    type Item = C::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for Named<G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        self.inner.try_next()
+    }
+}
+
+impl<CONTEXT, STATE, C: FromParts<CONTEXT, STATE>> FromParts<CONTEXT, STATE> for Named<C> {
+    /// Create the inner object from parts, named after its type.
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        Named::new(std::any::type_name::<C>(), C::from_parts(context, state))
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        self.inner.into_parts()
+    }
+}
+
+impl<CONTEXT, STATE, C: StatefulRef<CONTEXT, STATE>> StatefulRef<CONTEXT, STATE> for Named<C> {
+    fn context(&self) -> &CONTEXT {
+        self.inner.context()
+    }
+
+    fn state(&self) -> &STATE {
+        self.inner.state()
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        self.inner.state_mut()
+    }
+}
+
+impl<CONTEXT, STATE, C: StatefulMut<CONTEXT, STATE>> StatefulMut<CONTEXT, STATE> for Named<C> {
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        self.inner.context_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{Count, CountGenerator};
+    use crate::{ComputableExt, Incomplete};
+    use cancel_this::Cancellable;
+
+    #[test]
+    fn test_named_computation() {
+        let computation = Count::from_parts(2, 0);
+        let mut named = computation.named(format!("count-to-{}", 2));
+        assert_eq!(named.name(), "count-to-2");
+        assert_eq!(named.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(named.state(), &1);
+        named.rename("renamed");
+        assert_eq!(named.to_string(), "renamed");
+        assert_eq!(named.compute(), Ok(2));
+        assert_eq!(named.into_inner().into_parts(), (2, 2));
+    }
+
+    #[test]
+    fn test_named_generator() {
+        let mut named = Named::new("gen", CountGenerator::from_parts(3, 0));
+        assert_eq!(named.try_next(), Some(Ok(1)));
+        assert_eq!(named.inner().state(), &1);
+        assert_eq!(named.collect::<Cancellable<Vec<_>>>(), Ok(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_default_name() {
+        let named = Named::<Count>::from_parts(1, 0);
+        assert!(named.name().contains("Computation"));
+    }
+}
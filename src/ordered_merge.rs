@@ -0,0 +1,174 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A [`Generatable`] that performs a k-way merge of multiple sorted generators.
+///
+/// Assuming every sub-generator produces items in ascending order, the merged generator
+/// also produces all items in ascending order (items that compare equal are produced in
+/// the order of the sub-generators). The merge keeps the next item of every sub-generator
+/// in a binary heap, hence each item costs `O(log k)` operations.
+///
+/// Before an item can be produced, the merge needs to know the next item of every
+/// non-exhausted sub-generator. If some of them are suspended, the merge returns
+/// [`Incomplete::Suspended`] as well.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, OrderedMerge};
+///
+/// struct StepBy;
+///
+/// impl GeneratorStep<(u32, u32), u32, u32> for StepBy {
+///     fn step(&(step, max): &(u32, u32), current: &mut u32) -> Completable<Option<u32>> {
+///         *current += step;
+///         if *current <= max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let merge = OrderedMerge::new(vec![
+///     Generator::<(u32, u32), u32, u32, StepBy>::from_parts((2, 8), 0),
+///     Generator::<(u32, u32), u32, u32, StepBy>::from_parts((3, 9), 0),
+/// ]);
+/// let items: Vec<u32> = merge.map(|it| it.unwrap()).collect();
+/// assert_eq!(items, vec![2, 3, 4, 6, 6, 8, 9]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, T: Ord + serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct OrderedMerge<T: Ord, G = DynGeneratable<T>>
+where
+    G: Generatable<T>,
+{
+    generators: Vec<G>,
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+    /// Indices of generators whose next item is not in the heap yet.
+    pending: Vec<usize>,
+}
+
+impl<T: Ord, G: Generatable<T>> OrderedMerge<T, G> {
+    /// Create a new [`OrderedMerge`] of the given (sorted) generators.
+    pub fn new(generators: Vec<G>) -> Self {
+        OrderedMerge {
+            pending: (0..generators.len()).collect(),
+            heap: BinaryHeap::with_capacity(generators.len()),
+            generators,
+        }
+    }
+
+    /// Peek at the smallest item that is currently known to the merge.
+    ///
+    /// Note that this is not necessarily the next produced item if some sub-generators
+    /// are still suspended.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse((item, _))| item)
+    }
+}
+
+impl<T: Ord, G: Generatable<T>> Iterator for OrderedMerge<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T: Ord, G: Generatable<T>> Generatable<T> for OrderedMerge<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let mut i = 0;
+        while i < self.pending.len() {
+            let index = self.pending[i];
+            match self.generators[index].try_next() {
+                Some(Ok(item)) => {
+                    self.heap.push(Reverse((item, index)));
+                    self.pending.swap_remove(i);
+                }
+                Some(Err(Incomplete::Suspended)) => i += 1,
+                Some(Err(Incomplete::Exhausted)) | None => {
+                    self.pending.swap_remove(i);
+                }
+                Some(Err(e)) => return Some(Err(e)),
+            }
+        }
+
+        if !self.pending.is_empty() {
+            return Some(Err(Incomplete::Suspended));
+        }
+
+        let Reverse((item, index)) = self.heap.pop()?;
+        self.pending.push(index);
+        Some(Ok(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Generator, GeneratorStep};
+
+    struct ItemsStep;
+
+    impl GeneratorStep<Vec<i32>, usize, i32> for ItemsStep {
+        fn step(items: &Vec<i32>, index: &mut usize) -> Completable<Option<i32>> {
+            let item = items.get(*index).copied();
+            *index += 1;
+            Ok(item)
+        }
+    }
+
+    /// Suspends before every item.
+    struct SlowItemsStep;
+
+    impl GeneratorStep<Vec<i32>, usize, i32> for SlowItemsStep {
+        fn step(items: &Vec<i32>, index: &mut usize) -> Completable<Option<i32>> {
+            *index += 1;
+            if *index % 2 == 1 {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(items.get(*index / 2 - 1).copied())
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordered_merge() {
+        let merge = OrderedMerge::new(vec![
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![1, 4, 7], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![2, 5], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![], 0).dyn_generatable(),
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![0, 3, 9], 0).dyn_generatable(),
+        ]);
+        let items: Vec<i32> = merge.map(|it| it.unwrap()).collect();
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_ordered_merge_waits_for_suspended() {
+        let mut merge = OrderedMerge::new(vec![
+            Generator::<_, _, _, ItemsStep>::from_parts(vec![5], 0).dyn_generatable(),
+            Generator::<_, _, _, SlowItemsStep>::from_parts(vec![1], 0).dyn_generatable(),
+        ]);
+        // The first item cannot be decided until the slow generator produces its item.
+        assert_eq!(merge.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(merge.peek_min(), Some(&5));
+        assert_eq!(merge.try_next(), Some(Ok(1)));
+        assert_eq!(merge.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(merge.try_next(), Some(Ok(5)));
+        assert_eq!(merge.try_next(), None);
+    }
+
+    #[test]
+    fn test_ordered_merge_empty() {
+        let mut merge: OrderedMerge<i32> = OrderedMerge::new(vec![]);
+        assert_eq!(merge.try_next(), None);
+    }
+}
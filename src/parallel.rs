@@ -0,0 +1,312 @@
+use crate::{Algorithm, Completable, Computable, FromParts, StatefulMut, StatefulRef};
+use cancel_this::{
+    Cancellable, CancellationTrigger, Cancelled, DynamicCancellationTrigger, active_triggers,
+    is_cancelled, on_trigger,
+};
+use rayon::ThreadPool;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// A snapshot of the cancellation triggers of the thread that started a [`ParallelStep`].
+///
+/// The triggers of `cancel-this` are thread-local, which means that the worker threads
+/// of a rayon pool do not observe the cancellation triggers of the thread that executes the
+/// computation. A `CancelScope` captures these triggers and makes them available to the
+/// worker threads, either directly ([`CancelScope::check`]), or by re-installing them for the
+/// duration of a closure ([`CancelScope::run`]), in which case `is_cancelled!()` works as usual.
+#[derive(Clone)]
+pub struct CancelScope {
+    trigger: DynamicCancellationTrigger,
+}
+
+impl CancelScope {
+    /// Capture the cancellation triggers that are active on the current thread.
+    pub fn capture() -> Self {
+        CancelScope {
+            trigger: active_triggers(),
+        }
+    }
+
+    /// Returns [`Cancelled`] if the captured triggers are canceled.
+    pub fn check(&self) -> Cancellable<()> {
+        if self.trigger.is_cancelled() {
+            Err(Cancelled::new(self.trigger.type_name()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run `action` (typically on a worker thread) with the captured triggers installed,
+    /// such that `is_cancelled!()` within `action` observes them.
+    pub fn run<R, E: From<Cancelled>, F: FnOnce() -> Result<R, E>>(
+        &self,
+        action: F,
+    ) -> Result<R, E> {
+        on_trigger(self.trigger.clone(), action)
+    }
+}
+
+/// Defines a single step of a [`ParallelComputation`].
+///
+/// Compared to [`crate::ComputationStep`], the step can use rayon to parallelize its work and
+/// receives a [`CancelScope`] that makes cancellation observable on the worker threads.
+pub trait ParallelStep<CONTEXT, STATE, OUTPUT> {
+    /// Execute one step of the computation.
+    fn step(context: &CONTEXT, state: &mut STATE, cancel: &CancelScope) -> Completable<OUTPUT>;
+}
+
+/// A stateful computation whose steps can internally use data-parallelism through rayon
+/// (requires the `rayon` feature).
+///
+/// By default, the steps use the global rayon thread pool. A dedicated pool can be assigned
+/// using [`ParallelComputation::with_pool`], in which case every step is executed within
+/// [`ThreadPool::install`].
+///
+/// ## Suspend points and parallelism
+///
+/// Suspend points only exist *between* steps. All parallel work started by a step must be
+/// finished (which is what rayon's parallel iterators and `join` guarantee) before the step
+/// returns. As a consequence, a suspended computation never has any work running in the pool
+/// and can be safely serialized or moved. Conversely, a step is never interrupted by a
+/// suspension, so long parallel sections delay the next suspend point.
+///
+/// ## Cancellation
+///
+/// Cancellation is checked before every step, like in [`crate::Computation`]. Within the step,
+/// the work executed by the pool should use the provided [`CancelScope`] to observe
+/// cancellation. To keep the computation restartable, a step should only commit the results of
+/// a parallel section into its `STATE` once the whole section succeeded.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{CancelScope, Completable, Incomplete, ParallelComputation, ParallelStep};
+/// use rayon::prelude::*;
+///
+/// /// Sums the squares of the context, processing one chunk of 100 items per step.
+/// struct SquaresStep;
+///
+/// impl ParallelStep<Vec<u64>, (usize, u64), u64> for SquaresStep {
+///     fn step(items: &Vec<u64>, state: &mut (usize, u64), cancel: &CancelScope) -> Completable<u64> {
+///         let (offset, total) = state;
+///         if *offset >= items.len() {
+///             return Ok(*total);
+///         }
+///         let end = (*offset + 100).min(items.len());
+///         let chunk: u64 = items[*offset..end]
+///             .par_iter()
+///             .map(|x| cancel.check().map(|_| x * x))
+///             .sum::<Result<u64, _>>()?;
+///         *total += chunk;
+///         *offset = end;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// let items: Vec<u64> = (1..=1000).collect();
+/// let mut computation = ParallelComputation::<_, _, _, SquaresStep>::from_parts(items, (0, 0));
+/// assert_eq!(computation.compute().unwrap(), 333_833_500);
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "CONTEXT: serde::Serialize + for<'a> serde::Deserialize<'a>, STATE: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    context: CONTEXT,
+    state: STATE,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pool: Option<Arc<ThreadPool>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<(OUTPUT, STEP)>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    /// Execute the steps of this computation in the given rayon `pool`
+    /// instead of the global pool.
+    pub fn with_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// The dedicated thread pool of this computation (if any).
+    pub fn pool(&self) -> Option<&Arc<ThreadPool>> {
+        self.pool.as_ref()
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> Computable<OUTPUT>
+    for ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    CONTEXT: Sync,
+    STATE: Send,
+    OUTPUT: Send,
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    fn try_compute(&mut self) -> Completable<OUTPUT> {
+        is_cancelled!()?;
+        let scope = CancelScope::capture();
+        let (context, state) = (&self.context, &mut self.state);
+        match &self.pool {
+            None => STEP::step(context, state, &scope),
+            Some(pool) => pool.install(|| STEP::step(context, state, &scope)),
+        }
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> FromParts<CONTEXT, STATE>
+    for ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        ParallelComputation {
+            context,
+            state,
+            pool: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        (self.context, self.state)
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> StatefulRef<CONTEXT, STATE>
+    for ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        &mut self.state
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> StatefulMut<CONTEXT, STATE>
+    for ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP> Algorithm<CONTEXT, STATE, OUTPUT>
+    for ParallelComputation<CONTEXT, STATE, OUTPUT, STEP>
+where
+    CONTEXT: Sync,
+    STATE: Send,
+    OUTPUT: Send,
+    STEP: ParallelStep<CONTEXT, STATE, OUTPUT>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Incomplete;
+    use cancel_this::CancelAtomic;
+    use rayon::prelude::*;
+
+    /// Counts the items that are divisible by 3, one batch of 1000 items per step.
+    /// The context also carries a trigger which is canceled when the item `cancel_at` is found.
+    struct CountStep;
+
+    type CountContext = (u64, Option<u64>, CancelAtomic);
+
+    impl ParallelStep<CountContext, (u64, usize), usize> for CountStep {
+        fn step(
+            (limit, cancel_at, trigger): &CountContext,
+            (offset, count): &mut (u64, usize),
+            cancel: &CancelScope,
+        ) -> Completable<usize> {
+            if *offset >= *limit {
+                return Ok(*count);
+            }
+            let end = (*offset + 1000).min(*limit);
+            let batch = (*offset..end)
+                .into_par_iter()
+                .map(|x| {
+                    cancel.run(|| {
+                        if Some(x) == *cancel_at {
+                            trigger.cancel();
+                        }
+                        is_cancelled!()?;
+                        Ok::<usize, Cancelled>(usize::from(x.is_multiple_of(3)))
+                    })
+                })
+                .sum::<Cancellable<usize>>()?;
+            *offset = end;
+            *count += batch;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Count = ParallelComputation<CountContext, (u64, usize), usize, CountStep>;
+
+    #[test]
+    fn test_parallel_computation_global_pool() {
+        let mut computation = Count::from_parts((3000, None, CancelAtomic::new()), (0, 0));
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*computation.state(), (1000, 334));
+        assert_eq!(computation.compute(), Ok(1000));
+    }
+
+    #[test]
+    fn test_parallel_computation_dedicated_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let mut computation =
+            Count::from_parts((2500, None, CancelAtomic::new()), (0, 0)).with_pool(Arc::new(pool));
+        assert!(computation.pool().is_some());
+        assert_eq!(computation.compute(), Ok(834));
+    }
+
+    #[test]
+    fn test_parallel_cancellation_on_worker_threads() {
+        let trigger = CancelAtomic::new();
+        let mut computation = Count::from_parts((5000, Some(2500), trigger.clone()), (0, 0));
+        let result = on_trigger(trigger, || computation.compute());
+        assert!(result.is_err());
+        // The canceled batch is not committed into the state.
+        assert_eq!(*computation.state(), (2000, 667));
+    }
+
+    #[test]
+    fn test_cancel_scope_check() {
+        let trigger = CancelAtomic::new();
+        let scope = on_trigger(trigger.clone(), || {
+            Ok::<_, Cancelled>(CancelScope::capture())
+        })
+        .unwrap();
+        assert!(scope.check().is_ok());
+        trigger.cancel();
+        let results: Vec<Cancellable<()>> = (0..4).into_par_iter().map(|_| scope.check()).collect();
+        assert!(results.iter().all(|r| r.is_err()));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+
+/// A [`Generatable`] adapter that can look at the next item of another [`Generatable`]
+/// without consuming it (see [`Peekable::peek`]).
+///
+/// A peeked item is buffered and returned by the next call to [`Generatable::try_next`].
+/// If the inner generator suspends (or is cancelled) while peeking, nothing is buffered and
+/// the outcome is returned by [`Peekable::peek`], so it can be called again later.
+///
+/// Note that [`Iterator::peekable`] can also be used with generators, but it cannot
+/// observe suspensions.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, Incomplete, Peekable};
+///
+/// /// Produces the given items, suspending before each of them.
+/// struct ItemsStep;
+///
+/// impl GeneratorStep<Vec<u32>, usize, u32> for ItemsStep {
+///     fn step(items: &Vec<u32>, index: &mut usize) -> Completable<Option<u32>> {
+///         *index += 1;
+///         if *index % 2 == 1 {
+///             return Err(Incomplete::Suspended);
+///         }
+///         Ok(items.get(*index / 2 - 1).copied())
+///     }
+/// }
+///
+/// let generator = Generator::<Vec<u32>, usize, u32, ItemsStep>::from_parts(vec![1, 1, 2], 0);
+/// let mut peekable = Peekable::new(generator);
+/// assert_eq!(peekable.peek(), Some(Err(Incomplete::Suspended)));
+/// assert_eq!(peekable.peek(), Some(Ok(&1)));
+/// // Group equal consecutive items using look-ahead.
+/// let mut groups = Vec::new();
+/// while let Some(item) = peekable.next() {
+///     let item = item.unwrap();
+///     let mut count = 1;
+///     loop {
+///         match peekable.next_if_eq(&item) {
+///             Ok(Some(_)) => count += 1,
+///             Ok(None) => break,
+///             Err(Incomplete::Suspended) => continue,
+///             Err(e) => panic!("Unexpected: {e:?}"),
+///         }
+///     }
+///     groups.push((item, count));
+/// }
+/// assert_eq!(groups, vec![(1, 2), (2, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Peekable<ITEM, G = DynGeneratable<ITEM>>
+where
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    /// The buffered item, or `Some(None)` if the generator is known to be exhausted.
+    peeked: Option<Option<ITEM>>,
+}
+
+impl<ITEM, G: Generatable<ITEM>> Peekable<ITEM, G> {
+    /// Create a new [`Peekable`] adapter for the given generator.
+    pub fn new(generator: G) -> Self {
+        Peekable {
+            generator,
+            peeked: None,
+        }
+    }
+
+    /// Look at the next item without consuming it.
+    ///
+    /// Returns `None` if the generator is exhausted. If the generator suspends (or is
+    /// cancelled), the error is returned and nothing is buffered.
+    pub fn peek(&mut self) -> Option<Completable<&ITEM>> {
+        if let Err(e) = self.fill() {
+            return Some(Err(e));
+        }
+        self.peeked.as_ref().and_then(Option::as_ref).map(Ok)
+    }
+
+    /// Like [`Peekable::peek`], but returns a mutable reference to the buffered item.
+    pub fn peek_mut(&mut self) -> Option<Completable<&mut ITEM>> {
+        if let Err(e) = self.fill() {
+            return Some(Err(e));
+        }
+        self.peeked.as_mut().and_then(Option::as_mut).map(Ok)
+    }
+
+    /// Consume and return the next item if it satisfies `predicate`.
+    ///
+    /// Returns `Ok(None)` if the next item does not satisfy the predicate (the item stays
+    /// buffered) or if the generator is exhausted.
+    pub fn next_if<F: FnOnce(&ITEM) -> bool>(&mut self, predicate: F) -> Completable<Option<ITEM>> {
+        match self.peek() {
+            Some(Ok(item)) if predicate(item) => Ok(self.peeked.take().flatten()),
+            Some(Ok(_)) | None => Ok(None),
+            Some(Err(e)) => Err(e),
+        }
+    }
+
+    /// Consume and return the next item if it is equal to `expected`.
+    pub fn next_if_eq(&mut self, expected: &ITEM) -> Completable<Option<ITEM>>
+    where
+        ITEM: PartialEq,
+    {
+        self.next_if(|it| it == expected)
+    }
+
+    /// Returns `true` if an item is currently buffered.
+    pub fn is_peeked(&self) -> bool {
+        matches!(self.peeked, Some(Some(_)))
+    }
+
+    /// Make sure the next item (or the end of the generator) is buffered.
+    fn fill(&mut self) -> Result<(), Incomplete> {
+        if self.peeked.is_none() {
+            self.peeked = match self.generator.try_next() {
+                None => Some(None),
+                Some(Ok(item)) => Some(Some(item)),
+                Some(Err(e)) => return Err(e),
+            };
+        }
+        Ok(())
+    }
+}
+
+impl<ITEM, G: Generatable<ITEM>> Iterator for Peekable<ITEM, G> {
+    type Item = Cancellable<ITEM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM, G: Generatable<ITEM>> Generatable<ITEM> for Peekable<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<ITEM>> {
+        match self.peeked.take() {
+            Some(Some(item)) => Some(Ok(item)),
+            Some(None) => {
+                self.peeked = Some(None);
+                None
+            }
+            None => self.generator.try_next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, test_fixtures::Items};
+
+    #[test]
+    fn test_peek_does_not_consume() {
+        let mut peekable = Peekable::new(Items::from_parts(vec![1, 2], 0));
+        assert!(!peekable.is_peeked());
+        assert_eq!(peekable.peek(), Some(Ok(&1)));
+        assert_eq!(peekable.peek(), Some(Ok(&1)));
+        assert!(peekable.is_peeked());
+        assert_eq!(peekable.try_next(), Some(Ok(1)));
+        assert_eq!(peekable.try_next(), Some(Ok(2)));
+        assert_eq!(peekable.peek(), None);
+        assert_eq!(peekable.try_next(), None);
+        assert_eq!(peekable.try_next(), None);
+    }
+
+    #[test]
+    fn test_peek_through_suspension() {
+        let mut peekable = Peekable::new(Items::from_parts(vec![0, 0, 3], 0));
+        assert_eq!(peekable.peek(), Some(Err(Incomplete::Suspended)));
+        assert!(!peekable.is_peeked());
+        assert_eq!(peekable.try_next(), Some(Err(Incomplete::Suspended)));
+        if let Some(Ok(item)) = peekable.peek_mut() {
+            *item *= 10;
+        }
+        assert_eq!(peekable.collect::<Cancellable<Vec<_>>>(), Ok(vec![30]));
+    }
+
+    #[test]
+    fn test_next_if() {
+        let mut peekable = Peekable::new(Items::from_parts(vec![1, 3, 4, 0, 5], 0));
+        let mut odd = Vec::new();
+        while let Some(item) = peekable.next_if(|it| it % 2 == 1).unwrap() {
+            odd.push(item);
+        }
+        assert_eq!(odd, vec![1, 3]);
+        assert_eq!(peekable.next_if_eq(&4), Ok(Some(4)));
+        assert_eq!(peekable.next_if_eq(&5), Err(Incomplete::Suspended));
+        assert_eq!(peekable.next_if_eq(&6), Ok(None));
+        assert_eq!(peekable.next_if_eq(&5), Ok(Some(5)));
+        assert_eq!(peekable.next_if(|_| true), Ok(None));
+    }
+}
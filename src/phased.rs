@@ -0,0 +1,198 @@
+use crate::{Completable, Incomplete, Transition};
+
+/// A reusable `STATE` of multi-phase algorithms: the current phase `P` (typically
+/// a field-less enum) together with the data `D` shared by all phases.
+///
+/// The common shape of a phase-based [`crate::ComputationStep`] is a `match` over
+/// [`Phased::phase`], where every branch updates [`Phased::data_mut`] and either stays in
+/// the current phase using [`Phased::stay`], moves to another phase using
+/// [`Phased::advance_to`], or completes. [`Phased::apply`] performs the same actions for
+/// a [`Transition`]. Since [`Phased`] also counts the suspend points in the current phase,
+/// phases can be easily bounded or reported.
+///
+/// If the phases carry different data, consider the `ComputationState` derive macro
+/// (feature `derive`) instead.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{
+///     Completable, Computable, Computation, ComputationStep, FromParts, Incomplete, Phased,
+///     StatefulRef,
+/// };
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Phase {
+///     Sum,
+///     Scale,
+/// }
+///
+/// /// Sums the items one per step, then multiplies the sum by the number of items.
+/// struct SumScaleStep;
+///
+/// impl ComputationStep<Vec<u32>, Phased<Phase, (usize, u32)>, u32> for SumScaleStep {
+///     fn step(items: &Vec<u32>, state: &mut Phased<Phase, (usize, u32)>) -> Completable<u32> {
+///         match state.phase() {
+///             Phase::Sum => {
+///                 let (index, sum) = state.data_mut();
+///                 match items.get(*index) {
+///                     Some(item) => {
+///                         *sum += item;
+///                         *index += 1;
+///                         Err(state.stay())
+///                     }
+///                     None => Err(state.advance_to(Phase::Scale)),
+///                 }
+///             }
+///             Phase::Scale => Ok(state.data().1 * items.len() as u32),
+///         }
+///     }
+/// }
+///
+/// let state = Phased::new(Phase::Sum, (0, 0));
+/// let mut computation =
+///     Computation::<_, _, u32, SumScaleStep>::from_parts(vec![1, 2, 3], state);
+/// assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(computation.state().steps_in_phase(), 1);
+/// assert_eq!(computation.compute(), Ok(18));
+/// assert_eq!(computation.state().phase(), &Phase::Scale);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "P: serde::Serialize + for<'a> serde::Deserialize<'a>, D: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Phased<P, D> {
+    phase: P,
+    data: D,
+    steps_in_phase: u64,
+}
+
+impl<P, D> Phased<P, D> {
+    /// Create a new state in the initial `phase` with the given `data`.
+    pub fn new(phase: P, data: D) -> Self {
+        Phased {
+            phase,
+            data,
+            steps_in_phase: 0,
+        }
+    }
+
+    /// The current phase.
+    pub fn phase(&self) -> &P {
+        &self.phase
+    }
+
+    /// The data shared by all phases.
+    pub fn data(&self) -> &D {
+        &self.data
+    }
+
+    /// Mutable access to the data shared by all phases.
+    pub fn data_mut(&mut self) -> &mut D {
+        &mut self.data
+    }
+
+    /// The number of steps spent in the current phase, i.e., the number of calls
+    /// to [`Phased::stay`] since the phase was entered.
+    pub fn steps_in_phase(&self) -> u64 {
+        self.steps_in_phase
+    }
+
+    /// Stay in the current phase, counting one more step in it.
+    ///
+    /// Returns [`Incomplete::Suspended`], such that the step ends with a suspend point:
+    /// `return Err(state.stay())`.
+    pub fn stay(&mut self) -> Incomplete {
+        self.steps_in_phase += 1;
+        Incomplete::Suspended
+    }
+
+    /// Move to the given `phase`, resetting [`Phased::steps_in_phase`].
+    ///
+    /// Returns [`Incomplete::Suspended`], such that the phase change is also a suspend
+    /// point: `return Err(state.advance_to(next))`.
+    pub fn advance_to(&mut self, phase: P) -> Incomplete {
+        self.phase = phase;
+        self.steps_in_phase = 0;
+        Incomplete::Suspended
+    }
+
+    /// Apply the `transition` returned by a phase handler: [`Transition::Next`] moves to
+    /// the next phase (see [`Phased::advance_to`]) and [`Transition::Done`] completes with
+    /// the output.
+    pub fn apply<OUTPUT>(&mut self, transition: Transition<P, OUTPUT>) -> Completable<OUTPUT> {
+        match transition {
+            Transition::Next(phase) => Err(self.advance_to(phase)),
+            Transition::Done(output) => Ok(output),
+        }
+    }
+
+    /// Returns `true` if the current phase is equal to `phase`.
+    pub fn is_in(&self, phase: &P) -> bool
+    where
+        P: PartialEq,
+    {
+        &self.phase == phase
+    }
+
+    /// Destruct the state into the current phase and data.
+    pub fn into_parts(self) -> (P, D) {
+        (self.phase, self.data)
+    }
+}
+
+impl<P, D> From<(P, D)> for Phased<P, D> {
+    fn from((phase, data): (P, D)) -> Self {
+        Phased::new(phase, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    enum Phase {
+        #[default]
+        Load,
+        Solve,
+    }
+
+    #[test]
+    fn test_advance_to() {
+        let mut state = Phased::new(Phase::Load, vec![1]);
+        state.data_mut().push(2);
+        assert_eq!(state.stay(), Incomplete::Suspended);
+        state.data_mut().push(3);
+        assert_eq!(state.stay(), Incomplete::Suspended);
+        assert_eq!(state.steps_in_phase(), 2);
+        assert!(state.is_in(&Phase::Load));
+        assert_eq!(state.advance_to(Phase::Solve), Incomplete::Suspended);
+        assert!(state.is_in(&Phase::Solve));
+        assert_eq!(state.steps_in_phase(), 0);
+        assert_eq!(state.into_parts(), (Phase::Solve, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut state: Phased<Phase, u32> = Phased::default();
+        assert_eq!(state.phase(), &Phase::Load);
+        let next = state.apply(Transition::<_, ()>::Next(Phase::Solve));
+        assert_eq!(next, Err(Incomplete::Suspended));
+        assert_eq!(state.phase(), &Phase::Solve);
+        assert_eq!(state.apply(Transition::Done(7)), Ok(7));
+        // Completion does not change the phase.
+        assert_eq!(state.phase(), &Phase::Solve);
+    }
+
+    #[test]
+    fn test_from_tuple() {
+        let state = Phased::from((Phase::Solve, "data"));
+        assert_eq!(state, Phased::new(Phase::Solve, "data"));
+        assert_eq!(state.data(), &"data");
+    }
+}
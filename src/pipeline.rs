@@ -0,0 +1,404 @@
+//! A builder API for connecting a generator to a chain of transformations and a collector.
+//!
+//! A [`Pipeline`] starts with a [`Generatable`] and adds stages using [`Pipeline::map`]
+//! and [`Pipeline::filter`]. The pipeline is finished using [`Pipeline::collect`] (or
+//! [`Pipeline::fold`]), which produces a single [`crate::Computable`]. Every call to
+//! [`crate::Computable::try_compute`] pulls at most one item through all stages, hence
+//! all stages honor suspension and cancellation of the source generator.
+//!
+//! Stages created using [`Pipeline::map`] and [`Pipeline::filter`] are based on closures
+//! and thus cannot be serialized. For checkpointable pipelines, implement [`PipelineStage`]
+//! for a (serializable) type and add it using [`Pipeline::stage`]. Each such stage owns a
+//! bounded buffer of processed items. The buffers are part of the pipeline state, hence the
+//! whole pipeline (source, stages and buffers) can be serialized at any suspend point.
+//!
+//! # Example
+//!
+//! ```rust
+//! use computation_process::prelude::*;
+//! use computation_process::pipeline::Pipeline;
+//! use computation_process::{Completable, Generator, GeneratorStep};
+//!
+//! struct RangeStep;
+//!
+//! impl GeneratorStep<u32, u32, u32> for RangeStep {
+//!     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+//!         *current += 1;
+//!         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+//!     }
+//! }
+//!
+//! let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(10, 0);
+//! let mut computation = Pipeline::from(generator)
+//!     .filter(|x| x % 2 == 0)
+//!     .map(|x| x * x)
+//!     .collect::<Vec<_>>();
+//! assert_eq!(computation.compute().unwrap(), vec![4, 16, 36, 64, 100]);
+//! ```
+
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Collector, Completable, Folder, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// A serializable transformation step used by [`BufferedStage`].
+///
+/// Unlike closure-based stages, a [`PipelineStage`] is a standalone type which can carry its
+/// own (serializable) state, e.g., counters or running statistics.
+pub trait PipelineStage<IN, OUT> {
+    /// Process a single input item, producing an optional output item
+    /// (`None` means the item is filtered out).
+    fn process(&mut self, item: IN) -> Option<OUT>;
+}
+
+/// A builder connecting a [`Generatable`] source to a chain of stages.
+///
+/// See the [module documentation](crate::pipeline) for details.
+#[derive(Debug, Clone)]
+pub struct Pipeline<T, G: Generatable<T>> {
+    generator: G,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, G: Generatable<T>> From<G> for Pipeline<T, G> {
+    fn from(generator: G) -> Self {
+        Pipeline {
+            generator,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<T, G: Generatable<T>> Pipeline<T, G> {
+    /// Add a stage that transforms every item using `function`.
+    pub fn map<R, F: FnMut(T) -> R>(self, function: F) -> Pipeline<R, MapStage<T, G, F>> {
+        Pipeline::from(MapStage {
+            generator: self.generator,
+            function,
+            _phantom: Default::default(),
+        })
+    }
+
+    /// Add a stage that only retains items matching the `predicate`.
+    pub fn filter<F: FnMut(&T) -> bool>(self, predicate: F) -> Pipeline<T, FilterStage<T, G, F>> {
+        Pipeline::from(FilterStage {
+            generator: self.generator,
+            predicate,
+            _phantom: Default::default(),
+        })
+    }
+
+    /// Add a [`PipelineStage`] with a buffer of (at most) `capacity` processed items.
+    ///
+    /// See [`BufferedStage`].
+    pub fn stage<OUT, S: PipelineStage<T, OUT>>(
+        self,
+        capacity: usize,
+        stage: S,
+    ) -> Pipeline<OUT, BufferedStage<T, OUT, S, G>> {
+        Pipeline::from(BufferedStage::new(self.generator, capacity, stage))
+    }
+
+    /// Finish the pipeline by collecting all items into a `COLLECTION`.
+    pub fn collect<COLLECTION: Default + Extend<T>>(self) -> Collector<T, COLLECTION, G> {
+        Collector::new(self.generator)
+    }
+
+    /// Finish the pipeline by reducing all items into `init` using `function`.
+    pub fn fold<ACC, F: FnMut(ACC, T) -> ACC>(
+        self,
+        init: ACC,
+        function: F,
+    ) -> Folder<T, ACC, F, G> {
+        Folder::new(self.generator, init, function)
+    }
+
+    /// Finish the pipeline, returning the last stage as a [`Generatable`].
+    pub fn into_generator(self) -> G {
+        self.generator
+    }
+}
+
+/// A [`Pipeline`] stage that transforms every item using a function.
+#[derive(Debug, Clone)]
+pub struct MapStage<T, G: Generatable<T>, F> {
+    generator: G,
+    function: F,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, R, G: Generatable<T>, F: FnMut(T) -> R> Iterator for MapStage<T, G, F> {
+    type Item = Cancellable<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, R, G: Generatable<T>, F: FnMut(T) -> R> Generatable<R> for MapStage<T, G, F> {
+    fn try_next(&mut self) -> Option<Completable<R>> {
+        Some(self.generator.try_next()?.map(&mut self.function))
+    }
+}
+
+/// A [`Pipeline`] stage that only retains items matching a predicate.
+///
+/// Items that do not match are replaced by [`Incomplete::Suspended`].
+#[derive(Debug, Clone)]
+pub struct FilterStage<T, G: Generatable<T>, F> {
+    generator: G,
+    predicate: F,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, G: Generatable<T>, F: FnMut(&T) -> bool> Iterator for FilterStage<T, G, F> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>, F: FnMut(&T) -> bool> Generatable<T> for FilterStage<T, G, F> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        match self.generator.try_next()? {
+            Ok(item) if (self.predicate)(&item) => Some(Ok(item)),
+            Ok(_) => Some(Err(Incomplete::Suspended)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A [`Pipeline`] stage with a bounded buffer of processed items.
+///
+/// The stage alternates between two phases: First, it pulls items from the upstream
+/// generator (one per [`Generatable::try_next`] call) and stores the processed results
+/// in its buffer until the buffer is full or the upstream is exhausted. Then, it releases
+/// the buffered items one by one until the buffer is empty again.
+///
+/// The buffer is part of the stage state, hence a [`BufferedStage`] can be serialized
+/// (assuming the upstream generator and the [`PipelineStage`] are serializable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, S: serde::Serialize + for<'a> serde::Deserialize<'a>, OUT: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct BufferedStage<IN, OUT, S, G>
+where
+    S: PipelineStage<IN, OUT>,
+    G: Generatable<IN>,
+{
+    generator: G,
+    stage: S,
+    capacity: usize,
+    buffer: VecDeque<OUT>,
+    draining: bool,
+    upstream_exhausted: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<IN>,
+}
+
+impl<IN, OUT, S: PipelineStage<IN, OUT>, G: Generatable<IN>> BufferedStage<IN, OUT, S, G> {
+    /// Create a new buffered stage that processes the items of `generator` using `stage`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(generator: G, capacity: usize, stage: S) -> Self {
+        assert!(capacity > 0, "`capacity` must be positive.");
+        BufferedStage {
+            generator,
+            stage,
+            capacity,
+            buffer: VecDeque::with_capacity(capacity),
+            draining: false,
+            upstream_exhausted: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The processed items that are waiting in the buffer of this stage.
+    pub fn buffer(&self) -> &VecDeque<OUT> {
+        &self.buffer
+    }
+
+    /// A reference to the underlying [`PipelineStage`].
+    pub fn stage_ref(&self) -> &S {
+        &self.stage
+    }
+}
+
+impl<IN, OUT, S: PipelineStage<IN, OUT>, G: Generatable<IN>> Iterator
+    for BufferedStage<IN, OUT, S, G>
+{
+    type Item = Cancellable<OUT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<IN, OUT, S: PipelineStage<IN, OUT>, G: Generatable<IN>> Generatable<OUT>
+    for BufferedStage<IN, OUT, S, G>
+{
+    fn try_next(&mut self) -> Option<Completable<OUT>> {
+        if self.draining {
+            if let Some(item) = self.buffer.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.upstream_exhausted {
+                return None;
+            }
+            self.draining = false;
+        }
+
+        match self.generator.try_next() {
+            Some(Ok(item)) => {
+                if let Some(output) = self.stage.process(item) {
+                    self.buffer.push_back(output);
+                }
+                self.draining = self.buffer.len() >= self.capacity;
+                Some(Err(Incomplete::Suspended))
+            }
+            Some(Err(Incomplete::Exhausted)) | None => {
+                self.upstream_exhausted = true;
+                self.draining = true;
+                self.buffer.pop_front().map(Ok)
+            }
+            Some(Err(e)) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, FromParts, Generator, GeneratorStep};
+
+    struct RangeStep;
+
+    impl GeneratorStep<u32, u32, u32> for RangeStep {
+        fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+            *current += 1;
+            if *current <= *max {
+                Ok(Some(*current))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    type RangeGenerator = Generator<u32, u32, u32, RangeStep>;
+
+    #[test]
+    fn test_pipeline_steps() {
+        let generator = RangeGenerator::from_parts(3, 0);
+        let mut computation = Pipeline::from(generator)
+            .filter(|x| *x != 2)
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        // Item 1 passes, item 2 is filtered out, item 3 passes, then completion.
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(
+            computation.try_compute(),
+            Ok(vec!["1".to_string(), "3".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_pipeline_fold() {
+        let generator = RangeGenerator::from_parts(4, 0);
+        let mut computation = Pipeline::from(generator)
+            .map(|x| x * 10)
+            .fold(0, |acc, x| acc + x);
+        assert_eq!(computation.compute().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_pipeline_into_generator() {
+        let generator = RangeGenerator::from_parts(4, 0);
+        let stage = Pipeline::from(generator)
+            .filter(|x| x % 2 == 1)
+            .into_generator();
+        let items: Vec<u32> = stage.map(|it| it.unwrap()).collect();
+        assert_eq!(items, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_pipeline_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let generator = RangeGenerator::from_parts(4, 0);
+        let mut computation = Pipeline::from(generator).map(|x| x + 1).collect::<Vec<_>>();
+        let result = on_trigger(trigger, || computation.compute());
+        assert!(result.is_err());
+    }
+
+    /// Multiplies items by a factor and counts the processed items.
+    struct ScaleStage {
+        factor: u32,
+        processed: usize,
+    }
+
+    impl PipelineStage<u32, u32> for ScaleStage {
+        fn process(&mut self, item: u32) -> Option<u32> {
+            self.processed += 1;
+            Some(item * self.factor)
+        }
+    }
+
+    #[test]
+    fn test_buffered_stage_phases() {
+        let generator = RangeGenerator::from_parts(3, 0);
+        let stage = ScaleStage {
+            factor: 2,
+            processed: 0,
+        };
+        let mut stage = Pipeline::from(generator).stage(2, stage).into_generator();
+        // Fill the buffer.
+        assert_eq!(stage.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(stage.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(stage.buffer().len(), 2);
+        // Drain the buffer.
+        assert_eq!(stage.try_next(), Some(Ok(2)));
+        assert_eq!(stage.try_next(), Some(Ok(4)));
+        // Fill again until the upstream is exhausted.
+        assert_eq!(stage.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(stage.try_next(), Some(Ok(6)));
+        assert_eq!(stage.try_next(), None);
+        assert_eq!(stage.try_next(), None);
+        assert_eq!(stage.stage_ref().processed, 3);
+    }
+
+    #[test]
+    fn test_buffered_stage_chain() {
+        let generator = RangeGenerator::from_parts(5, 0);
+        let mut computation = Pipeline::from(generator)
+            .stage(
+                2,
+                ScaleStage {
+                    factor: 2,
+                    processed: 0,
+                },
+            )
+            .filter(|x| *x > 4)
+            .stage(
+                3,
+                ScaleStage {
+                    factor: 10,
+                    processed: 0,
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(computation.compute().unwrap(), vec![60, 80, 100]);
+    }
+}
@@ -0,0 +1,15 @@
+//! A "prelude" module re-exporting all traits of this crate that provide methods.
+//!
+//! Most methods of this crate are provided through traits. Use
+//! `use computation_process::prelude::*;` to bring all of them into scope at once.
+//! The traits which are only implemented by users (e.g., [`crate::ComputationStep`]
+//! or [`crate::GeneratorStep`]) are not included.
+
+pub use crate::{
+    Algorithm, AnyComputable, AnyGeneratable, Computable, ComputableExt, Forkable, FromParts,
+    GenAlgorithm, Generatable, GeneratableExt, HeapSize, Progress, ResumableWith, Sink, Stateful,
+    StatefulAlgorithm, StatefulMut, StatefulRef, TryComputable,
+};
+
+#[cfg(feature = "persistence")]
+pub use crate::Persistent;
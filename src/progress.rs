@@ -0,0 +1,80 @@
+use crate::{Generatable, Pump, Sink};
+
+/// A computation (or generator) that can report how much work it has already done.
+///
+/// The values are only informative (e.g., for progress bars, see `WithProgressBar` with
+/// the `indicatif` feature): `completed` should not decrease between two steps, and
+/// `total` (if known) is an upper bound on `completed`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Incomplete, IterGenerator, Progress, Pump};
+///
+/// let mut pump = Pump::new(IterGenerator::new(1..=3), Vec::new());
+/// assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(pump.completed(), 1);
+/// assert_eq!(pump.total(), None);
+/// assert_eq!(pump.compute(), Ok(3));
+/// assert_eq!(pump.completed(), 3);
+/// ```
+pub trait Progress {
+    /// The number of completed units of work.
+    fn completed(&self) -> u64;
+
+    /// The total number of units of work (if known).
+    fn total(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Completed units are the items moved into the sink.
+impl<ITEM, S: Sink<ITEM>, G: Generatable<ITEM>> Progress for Pump<ITEM, S, G> {
+    fn completed(&self) -> u64 {
+        self.moved() as u64
+    }
+}
+
+impl<P: Progress + ?Sized> Progress for Box<P> {
+    fn completed(&self) -> u64 {
+        (**self).completed()
+    }
+
+    fn total(&self) -> Option<u64> {
+        (**self).total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, Incomplete, IterGenerator};
+
+    /// Reports a fixed total on top of the progress of a pump.
+    struct Bounded(Pump<i32, Vec<i32>>);
+
+    impl Progress for Bounded {
+        fn completed(&self) -> u64 {
+            self.0.completed()
+        }
+
+        fn total(&self) -> Option<u64> {
+            Some(3)
+        }
+    }
+
+    #[test]
+    fn test_pump_progress() {
+        let generator = IterGenerator::new(1..=3).suspend_every(1);
+        let mut pump: Pump<i32, Vec<i32>> = Pump::new(Box::new(generator), Vec::new());
+        assert_eq!(pump.completed(), 0);
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(pump.completed(), 1);
+        let mut boxed: Box<dyn Progress> = Box::new(Bounded(pump));
+        assert_eq!(boxed.completed(), 1);
+        assert_eq!(boxed.total(), Some(3));
+        boxed = Box::new(boxed);
+        assert_eq!(boxed.total(), Some(3));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Completable, Computable, DynGeneratable, Generatable, Incomplete, Sink};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that moves all items from a [`Generatable`] into a [`Sink`],
+/// producing the number of moved items.
+///
+/// Every step moves at most one item. If the sink signals backpressure, the pump suspends
+/// and keeps flushing the sink in the following steps before the generator is advanced
+/// again. The pump completes once the generator is exhausted and all items are delivered.
+/// If the sink is closed (returns [`Incomplete::Exhausted`]), the pump is exhausted as well.
+///
+/// Together with [`crate::ChannelSink`] and [`crate::ChannelGeneratable`], this enables
+/// producer/consumer topologies across threads.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     ChannelGeneratable, ChannelSink, Completable, Generator, GeneratorStep, Pump,
+/// };
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         Ok((*current <= *max).then_some(*current))
+///     }
+/// }
+///
+/// let (sink, receiver) = ChannelSink::channel(2);
+/// let consumer = std::thread::spawn(move || {
+///     let items = ChannelGeneratable::new(receiver);
+///     items.map(|it| it.unwrap()).sum::<u32>()
+/// });
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(100, 0);
+/// let mut pump = Pump::new(generator, sink);
+/// assert_eq!(pump.compute().unwrap(), 100);
+/// drop(pump);
+/// assert_eq!(consumer.join().unwrap(), 5050);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, S: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Pump<ITEM, S, G = DynGeneratable<ITEM>>
+where
+    S: Sink<ITEM>,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    sink: S,
+    moved: usize,
+    /// Set when the sink signalled backpressure.
+    flushing: bool,
+    finished: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<ITEM>,
+}
+
+impl<ITEM, S: Sink<ITEM>, G: Generatable<ITEM>> Pump<ITEM, S, G> {
+    /// Create a new [`Pump`] moving the items of `generator` into `sink`.
+    pub fn new(generator: G, sink: S) -> Self {
+        Pump {
+            generator,
+            sink,
+            moved: 0,
+            flushing: false,
+            finished: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The number of items sent into the sink so far.
+    pub fn moved(&self) -> usize {
+        self.moved
+    }
+
+    /// Returns `true` if the pump waits for the sink to accept the buffered items.
+    pub fn is_blocked(&self) -> bool {
+        self.flushing
+    }
+
+    /// Access to the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable access to the sink.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Unwrap the generator and the sink.
+    pub fn into_parts(self) -> (G, S) {
+        (self.generator, self.sink)
+    }
+
+    /// Record the outcome of a sink operation.
+    fn sent(&mut self, result: Completable<()>) -> Completable<()> {
+        match result {
+            Ok(()) => {
+                self.flushing = false;
+                Ok(())
+            }
+            Err(Incomplete::Suspended) => {
+                self.flushing = true;
+                Err(Incomplete::Suspended)
+            }
+            Err(Incomplete::Exhausted) => {
+                self.finished = true;
+                Err(Incomplete::Exhausted)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<ITEM, S: Sink<ITEM>, G: Generatable<ITEM>> Computable<usize> for Pump<ITEM, S, G> {
+    fn try_compute(&mut self) -> Completable<usize> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        if self.flushing {
+            let result = self.sink.try_flush();
+            self.sent(result)?;
+        }
+        match self.generator.try_next() {
+            None => {
+                let result = self.sink.try_flush();
+                self.sent(result)?;
+                self.finished = true;
+                Ok(self.moved)
+            }
+            Some(Ok(item)) => {
+                self.moved += 1;
+                let result = self.sink.try_send(item);
+                self.sent(result)?;
+                Err(Incomplete::Suspended)
+            }
+            Some(Err(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChannelSink, FromParts, test_fixtures::Items};
+
+    #[test]
+    fn test_pump_into_vec() {
+        let mut pump = Pump::new(Items::from_parts(vec![1, 0, 2], 0), Vec::new());
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(pump.moved(), 1);
+        assert_eq!(pump.compute(), Ok(2));
+        assert_eq!(pump.try_compute(), Err(Incomplete::Exhausted));
+        let (_, items) = pump.into_parts();
+        assert_eq!(items, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_backpressure() {
+        let (sink, receiver) = ChannelSink::channel(1);
+        let mut pump = Pump::new(Items::from_parts(vec![1, 2, 3], 0), sink);
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert!(pump.is_blocked());
+        // The generator is not advanced while the sink is full.
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(pump.moved(), 2);
+        assert_eq!(pump.sink().pending(), 1);
+        assert_eq!(receiver.recv(), Ok(1));
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        assert!(pump.is_blocked());
+        assert_eq!(receiver.recv(), Ok(2));
+        assert_eq!(pump.try_compute(), Ok(3));
+        assert_eq!(receiver.recv(), Ok(3));
+    }
+
+    #[test]
+    fn test_closed_sink() {
+        let (sink, receiver) = ChannelSink::channel(1);
+        let mut pump = Pump::new(Items::from_parts(vec![1, 2, 3], 0), sink);
+        assert_eq!(pump.try_compute(), Err(Incomplete::Suspended));
+        drop(receiver);
+        assert_eq!(pump.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(pump.try_compute(), Err(Incomplete::Exhausted));
+    }
+}
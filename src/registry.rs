@@ -0,0 +1,473 @@
+use crate::{Algorithm, Computable, GenAlgorithm};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Object-safe serialization of a (possibly type-erased) value.
+///
+/// Implemented automatically for every [`Serialize`] type. Together with a [`Registry`],
+/// this allows saving and restoring trait objects like [`DynPersistentAlgorithm`].
+pub trait Persistent {
+    /// Serialize this value into a JSON [`Value`].
+    fn save(&self) -> serde_json::Result<Value>;
+
+    /// The [`TypeId`] of the concrete (not type-erased) type of this value.
+    fn persistent_type_id(&self) -> TypeId;
+
+    /// The name of the concrete (not type-erased) type of this value.
+    fn persistent_type_name(&self) -> &'static str;
+}
+
+impl<T: Serialize + 'static> Persistent for T {
+    fn save(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+
+    fn persistent_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn persistent_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+/// A [`Computable`] that is also [`Persistent`].
+pub trait PersistentComputable<OUTPUT>: Computable<OUTPUT> + Persistent {}
+
+impl<OUTPUT, C: Computable<OUTPUT> + Persistent> PersistentComputable<OUTPUT> for C {}
+
+/// An [`Algorithm`] that is also [`Persistent`].
+pub trait PersistentAlgorithm<CONTEXT, STATE, OUTPUT>:
+    Algorithm<CONTEXT, STATE, OUTPUT> + Persistent
+{
+}
+
+impl<CONTEXT, STATE, OUTPUT, A> PersistentAlgorithm<CONTEXT, STATE, OUTPUT> for A where
+    A: Algorithm<CONTEXT, STATE, OUTPUT> + Persistent
+{
+}
+
+/// A [`GenAlgorithm`] that is also [`Persistent`].
+pub trait PersistentGenAlgorithm<CONTEXT, STATE, ITEM>:
+    GenAlgorithm<CONTEXT, STATE, ITEM> + Persistent
+{
+}
+
+impl<CONTEXT, STATE, ITEM, A> PersistentGenAlgorithm<CONTEXT, STATE, ITEM> for A where
+    A: GenAlgorithm<CONTEXT, STATE, ITEM> + Persistent
+{
+}
+
+/// A dynamic [`PersistentComputable`] type.
+pub type DynPersistentComputable<OUTPUT> = Box<dyn PersistentComputable<OUTPUT>>;
+
+/// A dynamic [`PersistentAlgorithm`] type.
+pub type DynPersistentAlgorithm<CONTEXT, STATE, OUTPUT> =
+    Box<dyn PersistentAlgorithm<CONTEXT, STATE, OUTPUT>>;
+
+/// A dynamic [`PersistentGenAlgorithm`] type.
+pub type DynPersistentGenAlgorithm<CONTEXT, STATE, ITEM> =
+    Box<dyn PersistentGenAlgorithm<CONTEXT, STATE, ITEM>>;
+
+/// Conversion of a concrete value `V` into a boxed trait object `Self`.
+///
+/// Implemented for the `dyn Persistent*` trait objects of this module. It is used by
+/// [`Registry::register`] to restore concrete types as trait objects.
+pub trait Erase<V>: Persistent {
+    /// Box the `value` as a trait object.
+    fn erase(value: V) -> Box<Self>;
+}
+
+impl<OUTPUT: 'static, C> Erase<C> for dyn PersistentComputable<OUTPUT>
+where
+    C: Computable<OUTPUT> + Serialize + 'static,
+{
+    fn erase(value: C) -> Box<Self> {
+        Box::new(value)
+    }
+}
+
+impl<CONTEXT: 'static, STATE: 'static, OUTPUT: 'static, A> Erase<A> for dyn PersistentAlgorithm<CONTEXT, STATE, OUTPUT>
+where
+    A: Algorithm<CONTEXT, STATE, OUTPUT> + Serialize + 'static,
+{
+    fn erase(value: A) -> Box<Self> {
+        Box::new(value)
+    }
+}
+
+impl<CONTEXT: 'static, STATE: 'static, ITEM: 'static, A> Erase<A> for dyn PersistentGenAlgorithm<CONTEXT, STATE, ITEM>
+where
+    A: GenAlgorithm<CONTEXT, STATE, ITEM> + Serialize + 'static,
+{
+    fn erase(value: A) -> Box<Self> {
+        Box::new(value)
+    }
+}
+
+/// The error type of [`Registry`] operations.
+#[derive(Debug)]
+pub enum RegistryError {
+    /// The type of the saved value was not registered.
+    UnregisteredType(&'static str),
+    /// The tag of the loaded value is not known to the registry.
+    UnknownTag(String),
+    /// The value could not be (de)serialized.
+    Serialization(serde_json::Error),
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::UnregisteredType(name) => write!(f, "Unregistered type `{name}`"),
+            RegistryError::UnknownTag(tag) => write!(f, "Unknown type tag `{tag}`"),
+            RegistryError::Serialization(e) => write!(f, "Serialization failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl From<serde_json::Error> for RegistryError {
+    fn from(value: serde_json::Error) -> Self {
+        RegistryError::Serialization(value)
+    }
+}
+
+/// A serialized value together with the tag of its type.
+///
+/// Produced by [`Registry::save`] and consumed by [`Registry::load`]. Since `Tagged` is
+/// itself serializable, values of different types can be stored in a single checkpoint
+/// (e.g., as a `Vec<Tagged>`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tagged {
+    /// The tag under which the type of the value is registered.
+    pub tag: String,
+    /// The serialized value.
+    pub data: Value,
+}
+
+type Loader<T> = fn(Value) -> serde_json::Result<Box<T>>;
+
+fn load_erased<T: ?Sized + Erase<V>, V: DeserializeOwned>(
+    value: Value,
+) -> serde_json::Result<Box<T>> {
+    Ok(T::erase(serde_json::from_value::<V>(value)?))
+}
+
+/// Maps concrete types to unique string tags, such that type-erased values
+/// (e.g., [`DynPersistentAlgorithm`]) can be serialized and later restored
+/// (requires the `persistence` feature).
+///
+/// `T` is the trait object type of the restored values, e.g.,
+/// `dyn PersistentAlgorithm<CONTEXT, STATE, OUTPUT>`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, DynPersistentAlgorithm, Incomplete,
+///     PersistentAlgorithm, Registry, Tagged,
+/// };
+///
+/// struct UpStep;
+/// struct DownStep;
+///
+/// impl ComputationStep<u32, u32, u32> for UpStep {
+///     fn step(target: &u32, value: &mut u32) -> Completable<u32> {
+///         *value += 1;
+///         if *value >= *target { Ok(*value) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// impl ComputationStep<u32, u32, u32> for DownStep {
+///     fn step(target: &u32, value: &mut u32) -> Completable<u32> {
+///         *value -= 1;
+///         if *value <= *target { Ok(*value) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Up = Computation<u32, u32, u32, UpStep>;
+/// type Down = Computation<u32, u32, u32, DownStep>;
+///
+/// let mut registry = Registry::<dyn PersistentAlgorithm<u32, u32, u32>>::new();
+/// registry.register::<Up>("up").register::<Down>("down");
+///
+/// let mut tasks: Vec<DynPersistentAlgorithm<u32, u32, u32>> =
+///     vec![Box::new(Up::from_parts(5, 0)), Box::new(Down::from_parts(5, 10))];
+/// for task in tasks.iter_mut() {
+///     assert_eq!(task.try_compute(), Err(Incomplete::Suspended));
+/// }
+///
+/// // A heterogeneous checkpoint.
+/// let checkpoint = tasks.iter().map(|it| registry.save(it.as_ref())).collect::<Result<Vec<Tagged>, _>>().unwrap();
+/// let json = serde_json::to_string(&checkpoint).unwrap();
+///
+/// let checkpoint: Vec<Tagged> = serde_json::from_str(&json).unwrap();
+/// let mut restored = checkpoint.into_iter().map(|it| registry.load(it).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(*restored[0].state(), 1);
+/// assert_eq!(*restored[1].state(), 9);
+/// assert_eq!(restored[0].compute(), Ok(5));
+/// assert_eq!(restored[1].compute(), Ok(5));
+/// ```
+pub struct Registry<T: ?Sized> {
+    loaders: HashMap<String, Loader<T>>,
+    tags: HashMap<TypeId, String>,
+}
+
+impl<T: ?Sized> Default for Registry<T> {
+    fn default() -> Self {
+        Registry {
+            loaders: HashMap::new(),
+            tags: HashMap::new(),
+        }
+    }
+}
+
+impl<T: ?Sized + Persistent> Registry<T> {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the type `V` under the given `tag`.
+    ///
+    /// Registering the same type under the same tag repeatedly has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `tag` is already used by a different type, or if `V` is already
+    /// registered under a different tag.
+    pub fn register<V>(&mut self, tag: &str) -> &mut Self
+    where
+        V: DeserializeOwned + 'static,
+        T: Erase<V>,
+    {
+        let type_id = TypeId::of::<V>();
+        if let Some(existing) = self.tags.get(&type_id) {
+            assert_eq!(
+                existing, tag,
+                "Type is already registered under a different tag."
+            );
+            return self;
+        }
+        assert!(
+            !self.loaders.contains_key(tag),
+            "Tag `{tag}` is already registered."
+        );
+        self.loaders.insert(tag.to_string(), load_erased::<T, V>);
+        self.tags.insert(type_id, tag.to_string());
+        self
+    }
+
+    /// Returns the tag of the type `V` (if registered).
+    pub fn tag_of<V: 'static>(&self) -> Option<&str> {
+        self.tags.get(&TypeId::of::<V>()).map(|it| it.as_str())
+    }
+
+    /// Returns the tag of the concrete type of `value` (if registered).
+    pub fn tag_of_value(&self, value: &T) -> Option<&str> {
+        self.tags
+            .get(&value.persistent_type_id())
+            .map(|it| it.as_str())
+    }
+
+    /// Returns `true` if a type is registered under the given `tag`.
+    pub fn contains_tag(&self, tag: &str) -> bool {
+        self.loaders.contains_key(tag)
+    }
+
+    /// Serialize `value` together with the tag of its concrete type.
+    pub fn save(&self, value: &T) -> Result<Tagged, RegistryError> {
+        let tag = self
+            .tag_of_value(value)
+            .ok_or(RegistryError::UnregisteredType(
+                value.persistent_type_name(),
+            ))?;
+        Ok(Tagged {
+            tag: tag.to_string(),
+            data: value.save()?,
+        })
+    }
+
+    /// Restore a value saved using [`Registry::save`].
+    pub fn load(&self, tagged: Tagged) -> Result<Box<T>, RegistryError> {
+        let loader = self
+            .loaders
+            .get(&tagged.tag)
+            .ok_or(RegistryError::UnknownTag(tagged.tag))?;
+        Ok(loader(tagged.data)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Completable, Computation, ComputationStep, FromParts, Generator, GeneratorStep, Incomplete,
+    };
+
+    struct SquareStep;
+
+    impl ComputationStep<u64, u64, u64> for SquareStep {
+        fn step(context: &u64, state: &mut u64) -> Completable<u64> {
+            if *state == 0 {
+                *state = *context;
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(*state * *state)
+            }
+        }
+    }
+
+    struct DoubleStep;
+
+    impl ComputationStep<u64, u64, u64> for DoubleStep {
+        fn step(context: &u64, state: &mut u64) -> Completable<u64> {
+            *state += *context;
+            if *state >= 2 * *context {
+                Ok(*state)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    struct RangeStep;
+
+    impl GeneratorStep<u64, u64, u64> for RangeStep {
+        fn step(context: &u64, state: &mut u64) -> Completable<Option<u64>> {
+            if *state >= *context {
+                return Ok(None);
+            }
+            *state += 1;
+            Ok(Some(*state))
+        }
+    }
+
+    type Square = Computation<u64, u64, u64, SquareStep>;
+    type Double = Computation<u64, u64, u64, DoubleStep>;
+    type Range = Generator<u64, u64, u64, RangeStep>;
+
+    fn registry() -> Registry<dyn PersistentAlgorithm<u64, u64, u64>> {
+        let mut registry = Registry::new();
+        registry
+            .register::<Square>("square")
+            .register::<Double>("double");
+        registry
+    }
+
+    #[test]
+    fn test_algorithm_round_trip() {
+        let registry = registry();
+        assert_eq!(registry.tag_of::<Square>(), Some("square"));
+        assert_eq!(registry.tag_of::<Range>(), None);
+        assert!(registry.contains_tag("double"));
+
+        let mut square: DynPersistentAlgorithm<u64, u64, u64> = Box::new(Square::from_parts(7, 0));
+        assert_eq!(square.try_compute(), Err(Incomplete::Suspended));
+        let tagged = registry.save(square.as_ref()).unwrap();
+        assert_eq!(tagged.tag, "square");
+
+        let json = serde_json::to_string(&tagged).unwrap();
+        let tagged: Tagged = serde_json::from_str(&json).unwrap();
+        let mut restored = registry.load(tagged).unwrap();
+        assert_eq!(*restored.context(), 7);
+        assert_eq!(*restored.state(), 7);
+        assert_eq!(restored.compute(), Ok(49));
+    }
+
+    #[test]
+    fn test_heterogeneous_checkpoint() {
+        let registry = registry();
+        let tasks: Vec<DynPersistentAlgorithm<u64, u64, u64>> = vec![
+            Box::new(Double::from_parts(3, 0)),
+            Box::new(Square::from_parts(3, 0)),
+        ];
+        let checkpoint = tasks
+            .iter()
+            .map(|it| registry.save(it.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let tags = checkpoint
+            .iter()
+            .map(|it| it.tag.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["double", "square"]);
+
+        let results = checkpoint
+            .into_iter()
+            .map(|it| registry.load(it).unwrap().compute().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![6, 9]);
+    }
+
+    #[test]
+    fn test_gen_algorithm_round_trip() {
+        let mut registry = Registry::<dyn PersistentGenAlgorithm<u64, u64, u64>>::new();
+        registry.register::<Range>("range");
+
+        let mut range: DynPersistentGenAlgorithm<u64, u64, u64> = Box::new(Range::from_parts(4, 0));
+        assert_eq!(range.try_next(), Some(Ok(1)));
+        let tagged = registry.save(range.as_ref()).unwrap();
+        let restored = registry.load(tagged).unwrap();
+        assert_eq!(
+            restored.map(|it| it.unwrap()).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_computable_round_trip() {
+        let mut registry = Registry::<dyn PersistentComputable<u64>>::new();
+        registry.register::<Square>("square");
+        let square: DynPersistentComputable<u64> = Box::new(Square::from_parts(5, 0));
+        let mut restored = registry
+            .load(registry.save(square.as_ref()).unwrap())
+            .unwrap();
+        assert_eq!(restored.compute(), Ok(25));
+    }
+
+    #[test]
+    fn test_errors() {
+        let registry = registry();
+        let range = Range::from_parts(1, 0);
+        let mut generators = Registry::<dyn PersistentGenAlgorithm<u64, u64, u64>>::new();
+        let error = generators.save(&range).unwrap_err();
+        assert!(matches!(error, RegistryError::UnregisteredType(_)));
+        generators.register::<Range>("range");
+
+        let tagged = generators.save(&range).unwrap();
+        let error = registry.load(tagged).err().unwrap();
+        assert!(matches!(error, RegistryError::UnknownTag(tag) if tag == "range"));
+
+        let invalid = Tagged {
+            tag: "square".to_string(),
+            data: Value::Bool(true),
+        };
+        let error = registry.load(invalid).err().unwrap();
+        assert!(matches!(error, RegistryError::Serialization(_)));
+        assert!(error.to_string().starts_with("Serialization failed"));
+    }
+
+    #[test]
+    fn test_repeated_registration() {
+        let mut registry = registry();
+        registry.register::<Square>("square");
+        assert_eq!(registry.tag_of::<Square>(), Some("square"));
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered")]
+    fn test_duplicate_tag() {
+        let mut registry = Registry::<dyn PersistentComputable<u64>>::new();
+        registry
+            .register::<Square>("task")
+            .register::<Double>("task");
+    }
+}
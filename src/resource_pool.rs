@@ -0,0 +1,298 @@
+use crate::{Completable, Computable, Generatable, Incomplete, Resource, ResourceExceeded};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct PoolState {
+    /// The budget in nanoseconds.
+    budget: AtomicU64,
+    /// The time spent in steps in nanoseconds.
+    used: AtomicU64,
+}
+
+/// A budget of step time shared by a set of computations (possibly running on different
+/// threads).
+///
+/// Every computation wrapped using [`ResourcePool::limit`] measures the (wall-clock) duration
+/// of each of its steps and adds it to the pool. Once the total time spent in steps reaches
+/// the budget of the pool, all wrapped computations stop with
+/// [`Incomplete::ResourceExceeded`] (with [`Resource::StepTime`]) instead of performing
+/// another step. The computations are kept intact, so they can be resumed once the budget
+/// is raised (see [`ResourcePool::extend`]).
+///
+/// Cloning a [`ResourcePool`] creates another handle to the same pool. Since the budget is
+/// only checked between steps, the total time can exceed the budget by at most one step
+/// of each computation.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete, ResourcePool};
+/// use std::time::Duration;
+///
+/// /// Sleeps for one millisecond per step, never completes.
+/// struct SleepStep;
+///
+/// impl ComputationStep<(), (), ()> for SleepStep {
+///     fn step(_: &(), _: &mut ()) -> Completable<()> {
+///         std::thread::sleep(Duration::from_millis(1));
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// type Sleep = Computation<(), (), (), SleepStep>;
+///
+/// // Both heuristics together get 20 milliseconds.
+/// let pool = ResourcePool::new(Duration::from_millis(20));
+/// let mut first = pool.limit(Sleep::from_parts((), ()));
+/// let mut second = pool.limit(Sleep::from_parts((), ()));
+/// let result = loop {
+///     match first.try_compute().and_then(|_| second.try_compute()) {
+///         Err(Incomplete::Suspended) => continue,
+///         result => break result,
+///     }
+/// };
+/// assert!(matches!(result, Err(Incomplete::ResourceExceeded(_))));
+/// assert!(pool.is_exhausted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResourcePool {
+    state: Arc<PoolState>,
+}
+
+impl ResourcePool {
+    /// Create a new pool with the given total `budget` of step time.
+    pub fn new(budget: Duration) -> Self {
+        ResourcePool {
+            state: Arc::new(PoolState {
+                budget: AtomicU64::new(as_nanos(budget)),
+                used: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Wrap a computation (or generator), such that its steps consume the budget of this pool.
+    pub fn limit<T, C>(&self, inner: C) -> Pooled<T, C> {
+        Pooled::new(inner, self.clone())
+    }
+
+    /// The total budget of this pool.
+    pub fn budget(&self) -> Duration {
+        Duration::from_nanos(self.state.budget.load(Ordering::Relaxed))
+    }
+
+    /// The time spent in the steps of the wrapped computations so far.
+    pub fn used(&self) -> Duration {
+        Duration::from_nanos(self.state.used.load(Ordering::Relaxed))
+    }
+
+    /// The remaining budget (zero if the budget is exhausted).
+    pub fn remaining(&self) -> Duration {
+        self.budget().saturating_sub(self.used())
+    }
+
+    /// Returns `true` if the budget is exhausted.
+    pub fn is_exhausted(&self) -> bool {
+        self.used() >= self.budget()
+    }
+
+    /// Increase the total budget of this pool by `additional` time.
+    pub fn extend(&self, additional: Duration) {
+        self.state
+            .budget
+            .fetch_add(as_nanos(additional), Ordering::Relaxed);
+    }
+
+    /// Returns `true` if both handles refer to the same pool.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.state, &b.state)
+    }
+
+    /// Check that the budget is not exhausted.
+    fn check(&self) -> Result<(), Incomplete> {
+        let budget = self.state.budget.load(Ordering::Relaxed);
+        let used = self.state.used.load(Ordering::Relaxed);
+        if used >= budget {
+            Err(Incomplete::ResourceExceeded(ResourceExceeded {
+                resource: Resource::StepTime,
+                limit: budget,
+                used,
+            }))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Add the duration of one step to the used budget.
+    fn consume(&self, elapsed: Duration) {
+        self.state
+            .used
+            .fetch_add(as_nanos(elapsed), Ordering::Relaxed);
+    }
+}
+
+fn as_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// A [`Computable`] (or [`Generatable`]) whose steps consume the budget of a [`ResourcePool`].
+///
+/// See [`ResourcePool::limit`].
+#[derive(Debug, Clone)]
+pub struct Pooled<T, C> {
+    inner: C,
+    pool: ResourcePool,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C> Pooled<T, C> {
+    /// Wrap the `inner` computation (or generator), such that its steps consume
+    /// the budget of `pool`.
+    pub fn new(inner: C, pool: ResourcePool) -> Self {
+        Pooled {
+            inner,
+            pool,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The pool used by this computation.
+    pub fn pool(&self) -> &ResourcePool {
+        &self.pool
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn measure<R, F: FnOnce(&mut C) -> R>(&mut self, step: F) -> R {
+        let started = Instant::now();
+        let result = step(&mut self.inner);
+        self.pool.consume(started.elapsed());
+        result
+    }
+}
+
+impl<T, C: Computable<T>> Computable<T> for Pooled<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        self.pool.check()?;
+        self.measure(|inner| inner.try_compute())
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for Pooled<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for Pooled<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        if let Err(e) = self.pool.check() {
+            return Some(Err(e));
+        }
+        self.measure(|inner| inner.try_next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Computation, ComputationStep, FromParts, RESOURCE_EXCEEDED, StatefulRef,
+        test_fixtures::CountGenerator,
+    };
+
+    struct SleepStep;
+
+    impl ComputationStep<u32, u32, u32> for SleepStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+            std::thread::sleep(Duration::from_millis(2));
+            *count += 1;
+            if *count >= *target {
+                Ok(*count)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type Sleep = Computation<u32, u32, u32, SleepStep>;
+
+    #[test]
+    fn test_shared_budget() {
+        let pool = ResourcePool::new(Duration::from_millis(10));
+        let mut first = pool.limit(Sleep::from_parts(100, 0));
+        let mut second = pool.limit(Sleep::from_parts(100, 0));
+        assert!(ResourcePool::ptr_eq(first.pool(), second.pool()));
+        let mut steps = 0;
+        loop {
+            let result = if steps % 2 == 0 {
+                first.try_compute()
+            } else {
+                second.try_compute()
+            };
+            match result {
+                Err(Incomplete::Suspended) => steps += 1,
+                Err(Incomplete::ResourceExceeded(e)) => {
+                    assert_eq!(e.resource, Resource::StepTime);
+                    assert!(e.used >= e.limit);
+                    break;
+                }
+                result => panic!("Unexpected result: {result:?}"),
+            }
+        }
+        // Each step takes at least two milliseconds.
+        assert!(steps <= 5);
+        assert!(pool.is_exhausted());
+        assert_eq!(pool.remaining(), Duration::ZERO);
+        // Both computations are stopped.
+        assert!(matches!(
+            first.try_compute(),
+            Err(Incomplete::ResourceExceeded(_))
+        ));
+        assert_eq!(first.inner().state() + second.inner().state(), steps as u32);
+    }
+
+    #[test]
+    fn test_extend_and_resume() {
+        let pool = ResourcePool::new(Duration::from_millis(1));
+        let mut limited = pool.limit(Sleep::from_parts(3, 0));
+        assert_eq!(limited.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(limited.compute().unwrap_err().cause(), RESOURCE_EXCEEDED);
+        assert_eq!(limited.inner().state(), &1);
+
+        pool.extend(Duration::from_secs(10));
+        assert_eq!(pool.budget(), Duration::from_millis(10_001));
+        assert_eq!(limited.compute(), Ok(3));
+        assert!(pool.used() >= Duration::from_millis(6));
+        assert_eq!(limited.into_inner().state(), &3);
+    }
+
+    #[test]
+    fn test_generator() {
+        let pool = ResourcePool::new(Duration::from_secs(10));
+        let generator = pool.limit(CountGenerator::from_parts(3, 0));
+        let items = generator.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+
+        let pool = ResourcePool::new(Duration::ZERO);
+        let mut generator = pool.limit(CountGenerator::from_parts(3, 0));
+        assert!(matches!(
+            generator.try_next(),
+            Some(Err(Incomplete::ResourceExceeded(_)))
+        ));
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Completable, FromParts, Incomplete, StatefulMut, StatefulRef};
+use cancel_this::{Cancellable, is_cancelled};
+use std::marker::PhantomData;
+
+/// A variant of [`crate::Computable`] where every resume carries an `INPUT` value.
+///
+/// This enables request/response-style computations: the computation suspends whenever it needs
+/// additional information from the driver (e.g., an interactive solver asking a question), and
+/// the driver supplies the answer as the `input` of the next [`ResumableWith::try_compute_with`]
+/// call. Note that the very first call also carries an input value.
+pub trait ResumableWith<INPUT, OUTPUT> {
+    /// Try to advance this computation using the given `input`, returning a value once
+    /// the computation is done.
+    fn try_compute_with(&mut self, input: INPUT) -> Completable<OUTPUT>;
+
+    /// Advance this computation until completion, skipping over all suspended states.
+    ///
+    /// The `respond` function is called to obtain the input value before each resume.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an exhausted computation.
+    fn compute_with<F: FnMut() -> INPUT>(&mut self, mut respond: F) -> Cancellable<OUTPUT>
+    where
+        Self: Sized,
+    {
+        loop {
+            match self.try_compute_with(respond()) {
+                Ok(value) => return Ok(value),
+                Err(Incomplete::Suspended) => continue,
+                Err(Incomplete::Cancelled(c)) => return Err(c),
+                Err(Incomplete::ResourceExceeded(e)) => return Err(e.into()),
+                Err(Incomplete::Exhausted) => {
+                    panic!("Called `compute_with` on an exhausted `ResumableWith`.")
+                }
+            }
+        }
+    }
+
+    /// Utility method to convert this [`ResumableWith`] to a dynamic type.
+    fn dyn_resumable(self) -> DynResumableWith<INPUT, OUTPUT>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+/// A type alias for `Box<dyn ResumableWith<INPUT, OUTPUT>>`.
+pub type DynResumableWith<INPUT, OUTPUT> = Box<dyn ResumableWith<INPUT, OUTPUT>>;
+
+impl<INPUT, OUTPUT> ResumableWith<INPUT, OUTPUT> for DynResumableWith<INPUT, OUTPUT> {
+    fn try_compute_with(&mut self, input: INPUT) -> Completable<OUTPUT> {
+        (**self).try_compute_with(input)
+    }
+}
+
+/// Defines a single step of a [`ResumableComputation`].
+///
+/// This is the [`crate::ComputationStep`] equivalent for computations that receive
+/// an `INPUT` value on every resume.
+pub trait ResumableStep<CONTEXT, STATE, INPUT, OUTPUT> {
+    /// Execute one step of the computation using the `input` supplied by the driver.
+    fn step(context: &CONTEXT, state: &mut STATE, input: INPUT) -> Completable<OUTPUT>;
+}
+
+/// A stateful computation that receives an `INPUT` value every time it is resumed.
+///
+/// `ResumableComputation` is the default implementation of [`ResumableWith`], analogous
+/// to [`crate::Computation`]. The computation typically stores the "question" for the driver
+/// in its `STATE`, which the driver can inspect through [`StatefulRef::state`] while the
+/// computation is suspended.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{
+///     Completable, FromParts, Incomplete, ResumableComputation, ResumableStep, ResumableWith,
+///     StatefulRef,
+/// };
+///
+/// /// Guess a number in `0..=CONTEXT` by asking "is it less than x?" questions.
+/// struct GuessStep;
+///
+/// impl ResumableStep<u32, (u32, u32), Option<bool>, u32> for GuessStep {
+///     fn step(_: &u32, state: &mut (u32, u32), answer: Option<bool>) -> Completable<u32> {
+///         let (low, high) = state;
+///         let mid = (*low + *high).div_ceil(2);
+///         match answer {
+///             Some(true) => *high = mid - 1,
+///             Some(false) => *low = mid,
+///             None => (),
+///         }
+///         if low == high { Ok(*low) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let secret = 7;
+/// let mut guess = ResumableComputation::<u32, (u32, u32), Option<bool>, u32, GuessStep>::from_parts(10, (0, 10));
+/// let mut answer = None;
+/// let result = loop {
+///     match guess.try_compute_with(answer) {
+///         Ok(value) => break value,
+///         Err(_) => {
+///             let (low, high) = *guess.state();
+///             answer = Some(secret < (low + high).div_ceil(2));
+///         }
+///     }
+/// };
+/// assert_eq!(result, secret);
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "CONTEXT: serde::Serialize + for<'a> serde::Deserialize<'a>, STATE: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct ResumableComputation<CONTEXT, STATE, INPUT, OUTPUT, STEP>
+where
+    STEP: ResumableStep<CONTEXT, STATE, INPUT, OUTPUT>,
+{
+    context: CONTEXT,
+    state: STATE,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<(INPUT, OUTPUT, STEP)>,
+}
+
+impl<CONTEXT, STATE, INPUT, OUTPUT, STEP> ResumableWith<INPUT, OUTPUT>
+    for ResumableComputation<CONTEXT, STATE, INPUT, OUTPUT, STEP>
+where
+    STEP: ResumableStep<CONTEXT, STATE, INPUT, OUTPUT>,
+{
+    fn try_compute_with(&mut self, input: INPUT) -> Completable<OUTPUT> {
+        is_cancelled!()?;
+        STEP::step(&self.context, &mut self.state, input)
+    }
+}
+
+impl<CONTEXT, STATE, INPUT, OUTPUT, STEP> FromParts<CONTEXT, STATE>
+    for ResumableComputation<CONTEXT, STATE, INPUT, OUTPUT, STEP>
+where
+    STEP: ResumableStep<CONTEXT, STATE, INPUT, OUTPUT>,
+{
+    fn from_parts(context: CONTEXT, state: STATE) -> Self
+    where
+        Self: Sized + 'static,
+    {
+        ResumableComputation {
+            context,
+            state,
+            _phantom: Default::default(),
+        }
+    }
+
+    fn into_parts(self) -> (CONTEXT, STATE) {
+        (self.context, self.state)
+    }
+}
+
+impl<CONTEXT, STATE, INPUT, OUTPUT, STEP> StatefulRef<CONTEXT, STATE>
+    for ResumableComputation<CONTEXT, STATE, INPUT, OUTPUT, STEP>
+where
+    STEP: ResumableStep<CONTEXT, STATE, INPUT, OUTPUT>,
+{
+    fn context(&self) -> &CONTEXT {
+        &self.context
+    }
+
+    fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut STATE {
+        &mut self.state
+    }
+}
+
+impl<CONTEXT, STATE, INPUT, OUTPUT, STEP> StatefulMut<CONTEXT, STATE>
+    for ResumableComputation<CONTEXT, STATE, INPUT, OUTPUT, STEP>
+where
+    STEP: ResumableStep<CONTEXT, STATE, INPUT, OUTPUT>,
+{
+    fn context_mut(&mut self) -> &mut CONTEXT {
+        &mut self.context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the inputs until the running total reaches the context value.
+    struct AccumulateStep;
+
+    impl ResumableStep<i32, i32, i32, i32> for AccumulateStep {
+        fn step(target: &i32, total: &mut i32, input: i32) -> Completable<i32> {
+            *total += input;
+            if *total >= *target {
+                Ok(*total)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type TestResumable = ResumableComputation<i32, i32, i32, i32, AccumulateStep>;
+
+    #[test]
+    fn test_try_compute_with() {
+        let mut computation = TestResumable::from_parts(10, 0);
+        assert_eq!(computation.try_compute_with(3), Err(Incomplete::Suspended));
+        assert_eq!(*computation.state(), 3);
+        assert_eq!(computation.try_compute_with(4), Err(Incomplete::Suspended));
+        assert_eq!(computation.try_compute_with(5), Ok(12));
+    }
+
+    #[test]
+    fn test_compute_with() {
+        let mut computation = TestResumable::from_parts(10, 0);
+        let mut calls = 0;
+        let result = computation
+            .compute_with(|| {
+                calls += 1;
+                2
+            })
+            .unwrap();
+        assert_eq!(result, 10);
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn test_dyn_resumable() {
+        let computation = TestResumable::from_parts(5, 0);
+        let mut dyn_resumable = computation.dyn_resumable();
+        assert_eq!(
+            dyn_resumable.try_compute_with(2),
+            Err(Incomplete::Suspended)
+        );
+        assert_eq!(dyn_resumable.compute_with(|| 3), Ok(5));
+    }
+
+    #[test]
+    fn test_resumable_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut computation = TestResumable::from_parts(5, 0);
+        let result = on_trigger(trigger, || computation.try_compute_with(1));
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(*computation.state(), 0);
+    }
+}
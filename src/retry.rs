@@ -0,0 +1,253 @@
+use crate::{Completable, Computable, Incomplete, suspend_for};
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// The waiting policy used by [`Retry`] between two attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backoff {
+    /// Start the next attempt immediately (in the next step).
+    #[default]
+    Immediate,
+    /// Suspend the given number of times before starting the next attempt.
+    ///
+    /// The suspension that reports the failed attempt counts as the first one, i.e.,
+    /// `Steps(0)` and `Steps(1)` behave the same as [`Backoff::Immediate`].
+    Steps(usize),
+    /// Suspend until the given amount of wall-clock time has elapsed.
+    Time(Duration),
+    /// Suspend until the given amount of wall-clock time has elapsed, doubling the
+    /// waiting time after every failed attempt (up to `max`).
+    Exponential {
+        /// The waiting time after the first failed attempt.
+        initial: Duration,
+        /// The maximal waiting time.
+        max: Duration,
+    },
+}
+
+/// The current waiting state of a [`Retry`].
+#[derive(Debug, Clone, Copy)]
+enum Waiting {
+    Steps(usize),
+    Until(Instant),
+}
+
+/// A [`Computable`] that restarts a fallible computation until it succeeds or the maximal
+/// number of retries is reached.
+///
+/// The computation is created by a `factory` function, which is called again for every
+/// attempt. Between two attempts, the [`Retry`] waits according to its [`Backoff`] policy,
+/// returning [`Incomplete::Suspended`] while waiting (it never blocks the thread). When
+/// waiting for a time-based backoff, the suspension carries a retry hint
+/// (see [`crate::suspend_for`]).
+/// Once all retries are used up, the last error is returned. Cancellation of the inner
+/// computation is never retried.
+///
+/// See also [`crate::TryComputable`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Backoff, ComputableIdentity, Retry};
+///
+/// let mut attempt = 0;
+/// let mut retry = Retry::new(3, Backoff::Steps(2), move || {
+///     attempt += 1;
+///     ComputableIdentity::from(if attempt < 3 { Err("failed") } else { Ok(attempt) })
+/// });
+/// assert_eq!(retry.compute().unwrap(), Ok(3));
+/// assert_eq!(retry.attempts(), 3);
+/// ```
+pub struct Retry<T, E, C, F>
+where
+    C: Computable<Result<T, E>>,
+    F: FnMut() -> C,
+{
+    factory: F,
+    computable: Option<C>,
+    max_retries: usize,
+    backoff: Backoff,
+    attempts: usize,
+    waiting: Option<Waiting>,
+    finished: bool,
+    _phantom: PhantomData<(T, E)>,
+}
+
+impl<T, E, C, F> Retry<T, E, C, F>
+where
+    C: Computable<Result<T, E>>,
+    F: FnMut() -> C,
+{
+    /// Create a new [`Retry`] that makes at most `1 + max_retries` attempts to run
+    /// the computation created by `factory`.
+    pub fn new(max_retries: usize, backoff: Backoff, factory: F) -> Self {
+        Retry {
+            factory,
+            computable: None,
+            max_retries,
+            backoff,
+            attempts: 0,
+            waiting: None,
+            finished: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The number of attempts that were started so far.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Compute the waiting state after the given number of failed attempts.
+    fn wait_after(&self, failed: usize) -> Option<Waiting> {
+        match self.backoff {
+            Backoff::Immediate => None,
+            // The failed attempt itself already returns one suspension.
+            Backoff::Steps(steps) => Some(Waiting::Steps(steps.saturating_sub(1))),
+            Backoff::Time(duration) => Some(Waiting::Until(Instant::now() + duration)),
+            Backoff::Exponential { initial, max } => {
+                let factor = 2u32.saturating_pow(u32::try_from(failed - 1).unwrap_or(u32::MAX));
+                let duration = initial.saturating_mul(factor).min(max);
+                Some(Waiting::Until(Instant::now() + duration))
+            }
+        }
+    }
+}
+
+impl<T, E, C, F> Computable<Result<T, E>> for Retry<T, E, C, F>
+where
+    C: Computable<Result<T, E>>,
+    F: FnMut() -> C,
+{
+    fn try_compute(&mut self) -> Completable<Result<T, E>> {
+        if self.finished {
+            return Err(Incomplete::Exhausted);
+        }
+        match self.waiting {
+            Some(Waiting::Steps(0)) | None => self.waiting = None,
+            Some(Waiting::Steps(remaining)) => {
+                self.waiting = Some(Waiting::Steps(remaining - 1));
+                return Err(Incomplete::Suspended);
+            }
+            Some(Waiting::Until(deadline)) => {
+                let now = Instant::now();
+                if now < deadline {
+                    return Err(suspend_for(deadline - now));
+                }
+                self.waiting = None;
+            }
+        }
+
+        if self.computable.is_none() {
+            self.attempts += 1;
+            self.computable = Some((self.factory)());
+        }
+
+        let Some(computable) = self.computable.as_mut() else {
+            unreachable!("The computation was created above.");
+        };
+        match computable.try_compute()? {
+            Ok(value) => {
+                self.computable = None;
+                self.finished = true;
+                Ok(Ok(value))
+            }
+            Err(e) => {
+                self.computable = None;
+                if self.attempts > self.max_retries {
+                    self.finished = true;
+                    Ok(Err(e))
+                } else {
+                    self.waiting = self.wait_after(self.attempts);
+                    match self.waiting {
+                        Some(Waiting::Until(deadline)) => Err(suspend_for(
+                            deadline.saturating_duration_since(Instant::now()),
+                        )),
+                        _ => Err(Incomplete::Suspended),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComputableIdentity;
+
+    fn failing_until(
+        success_at: usize,
+    ) -> impl FnMut() -> ComputableIdentity<Result<usize, usize>> {
+        let mut attempt = 0;
+        move || {
+            attempt += 1;
+            ComputableIdentity::from(if attempt < success_at {
+                Err(attempt)
+            } else {
+                Ok(attempt)
+            })
+        }
+    }
+
+    #[test]
+    fn test_retry_immediate_success() {
+        let mut retry = Retry::new(3, Backoff::Immediate, failing_until(1));
+        assert_eq!(retry.try_compute(), Ok(Ok(1)));
+        assert_eq!(retry.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_retry_steps_backoff() {
+        let mut retry = Retry::new(3, Backoff::Steps(2), failing_until(2));
+        // First attempt fails.
+        assert_eq!(retry.try_compute(), Err(Incomplete::Suspended));
+        // Backoff step.
+        assert_eq!(retry.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(retry.attempts(), 1);
+        // Second attempt succeeds.
+        assert_eq!(retry.try_compute(), Ok(Ok(2)));
+        assert_eq!(retry.attempts(), 2);
+    }
+
+    #[test]
+    fn test_retry_steps_backoff_count() {
+        for steps in 0..5 {
+            let mut retry = Retry::new(2, Backoff::Steps(steps), failing_until(3));
+            // The number of suspensions observed while the given attempt was the last one.
+            let mut suspended = vec![0; 4];
+            let result = loop {
+                match retry.try_compute() {
+                    Ok(result) => break result,
+                    Err(Incomplete::Suspended) => suspended[retry.attempts()] += 1,
+                    Err(e) => panic!("Unexpected: {e:?}"),
+                }
+            };
+            assert_eq!(result, Ok(3));
+            let expected = steps.max(1);
+            assert_eq!(suspended, vec![0, expected, expected, 0]);
+        }
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let mut retry = Retry::new(2, Backoff::Immediate, failing_until(10));
+        assert_eq!(retry.compute(), Ok(Err(3)));
+        assert_eq!(retry.attempts(), 3);
+        assert_eq!(retry.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_retry_time_backoff() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(4),
+        };
+        let start = Instant::now();
+        let mut retry = Retry::new(3, backoff, failing_until(4));
+        assert_eq!(retry.compute(), Ok(Ok(4)));
+        // Waiting times are 1ms, 2ms and 4ms.
+        assert!(start.elapsed() >= Duration::from_millis(7));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::Incomplete;
+use std::cell::Cell;
+use std::time::Duration;
+
+thread_local! {
+    static RETRY_HINT: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+/// Suspend the current step with a hint that the computation should not be polled again
+/// before `delay` elapses.
+///
+/// The hint is recorded on the current thread and [`Incomplete::Suspended`] is returned,
+/// so the function can be used wherever a suspension is expected:
+/// `return Err(suspend_for(delay))`. Drivers that understand the hint (e.g.,
+/// [`crate::Scheduler`]) obtain it using [`take_retry_hint`] and avoid re-polling the
+/// computation (and busy-spinning) while it waits for a time-based condition. Other drivers
+/// simply treat the outcome as a normal suspension.
+///
+/// If several hints are recorded before the driver takes them (e.g., by nested
+/// computations), the shortest delay is kept.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Incomplete, suspend_for, take_retry_hint};
+/// use std::time::Duration;
+///
+/// assert_eq!(suspend_for(Duration::from_millis(10)), Incomplete::Suspended);
+/// let _ = suspend_for(Duration::from_millis(5));
+/// assert_eq!(take_retry_hint(), Some(Duration::from_millis(5)));
+/// assert_eq!(take_retry_hint(), None);
+/// ```
+pub fn suspend_for(delay: Duration) -> Incomplete {
+    RETRY_HINT.with(|hint| {
+        let delay = hint.get().map_or(delay, |it| it.min(delay));
+        hint.set(Some(delay));
+    });
+    Incomplete::Suspended
+}
+
+/// Take (and clear) the retry hint recorded on the current thread by [`suspend_for`].
+///
+/// Drivers should call this function before a step (to discard stale hints) and after
+/// the step returns [`Incomplete::Suspended`].
+pub fn take_retry_hint() -> Option<Duration> {
+    RETRY_HINT.with(Cell::take)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_hint_wins() {
+        assert_eq!(take_retry_hint(), None);
+        let _ = suspend_for(Duration::from_secs(3));
+        let _ = suspend_for(Duration::from_secs(1));
+        let _ = suspend_for(Duration::from_secs(2));
+        assert_eq!(take_retry_hint(), Some(Duration::from_secs(1)));
+        assert_eq!(take_retry_hint(), None);
+    }
+
+    #[test]
+    fn test_hints_are_thread_local() {
+        let _ = suspend_for(Duration::from_secs(1));
+        let other = std::thread::spawn(take_retry_hint).join().unwrap();
+        assert_eq!(other, None);
+        assert_eq!(take_retry_hint(), Some(Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that threads a state through the items of another [`Generatable`]
+/// and produces the values returned by a closure (analogous to [`Iterator::scan`]).
+///
+/// For every item, the closure receives a mutable reference to the state and the item.
+/// It returns the value that should be produced, or `None` to stop the generator.
+/// Suspensions and cancellation of the inner generator are passed through, so this is
+/// suitable for computing running statistics over generated data.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, Scan};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(5, 0);
+/// // Running average of the produced items.
+/// let mut averages = Scan::new(generator, (0, 0), |(sum, count), item| {
+///     *sum += item;
+///     *count += 1;
+///     Some(*sum as f64 / *count as f64)
+/// });
+/// let items = averages.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(items, vec![1.0, 1.5, 2.0, 2.5, 3.0]);
+/// assert_eq!(averages.state(), &(15, 5));
+/// ```
+pub struct Scan<ITEM, S, OUT, F, G = DynGeneratable<ITEM>>
+where
+    F: FnMut(&mut S, ITEM) -> Option<OUT>,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    state: S,
+    function: F,
+    stopped: bool,
+    _phantom: PhantomData<(ITEM, OUT)>,
+}
+
+impl<ITEM, S, OUT, F, G> Scan<ITEM, S, OUT, F, G>
+where
+    F: FnMut(&mut S, ITEM) -> Option<OUT>,
+    G: Generatable<ITEM>,
+{
+    /// Create a new [`Scan`] over the items of `generator`, starting with the `initial` state.
+    pub fn new(generator: G, initial: S, function: F) -> Self {
+        Scan {
+            generator,
+            state: initial,
+            function,
+            stopped: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// A reference to the current state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Drop the generator and return the current state.
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
+
+impl<ITEM, S, OUT, F, G> Iterator for Scan<ITEM, S, OUT, F, G>
+where
+    F: FnMut(&mut S, ITEM) -> Option<OUT>,
+    G: Generatable<ITEM>,
+{
+    type Item = Cancellable<OUT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM, S, OUT, F, G> Generatable<OUT> for Scan<ITEM, S, OUT, F, G>
+where
+    F: FnMut(&mut S, ITEM) -> Option<OUT>,
+    G: Generatable<ITEM>,
+{
+    fn try_next(&mut self) -> Option<Completable<OUT>> {
+        if self.stopped {
+            return None;
+        }
+        match self.generator.try_next()? {
+            Ok(item) => match (self.function)(&mut self.state, item) {
+                Some(value) => Some(Ok(value)),
+                None => {
+                    self.stopped = true;
+                    None
+                }
+            },
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Incomplete, test_fixtures::Items};
+
+    #[test]
+    fn test_running_maximum_with_suspension() {
+        let generator = Items::from_parts(vec![3, 0, 1, 5, 0, 2], 0);
+        let mut scan = Scan::new(generator, i32::MIN, |max, item| {
+            *max = (*max).max(item);
+            Some(*max)
+        });
+        assert_eq!(scan.try_next(), Some(Ok(3)));
+        assert_eq!(scan.try_next(), Some(Err(Incomplete::Suspended)));
+        let rest = scan.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(rest, vec![3, 5, 5]);
+        assert_eq!(scan.into_state(), 5);
+    }
+
+    #[test]
+    fn test_stop_early() {
+        let generator = Items::from_parts(vec![1, 2, 3, 4], 0);
+        let mut scan = Scan::new(generator, 0, |sum, item| {
+            *sum += item;
+            (*sum < 5).then_some(*sum)
+        });
+        assert_eq!(scan.try_next(), Some(Ok(1)));
+        assert_eq!(scan.try_next(), Some(Ok(3)));
+        assert_eq!(scan.try_next(), None);
+        // The inner generator is not advanced after the scan stopped.
+        assert_eq!(scan.try_next(), None);
+        assert_eq!(scan.state(), &6);
+    }
+}
@@ -0,0 +1,1187 @@
+use crate::{
+    Blackboard, CancelToken, Completable, Computable, ComputableResult, ComputationId,
+    DynComputable, Generatable, Incomplete, StepOutcome, Timeline, WaitUntil, take_retry_hint,
+    take_wake_condition,
+};
+use cancel_this::{Cancellable, is_cancelled};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+/// The initial sleep of the blocking [`Scheduler`] iterator while all pending computations
+/// are waiting for a wake condition.
+const MIN_IDLE_BACKOFF: Duration = Duration::from_micros(50);
+
+/// The longest sleep of the blocking [`Scheduler`] iterator while all pending computations
+/// are waiting for a wake condition.
+const MAX_IDLE_BACKOFF: Duration = Duration::from_millis(10);
+
+/// A single computation managed by a [`Scheduler`].
+#[derive(Debug)]
+struct Task<T, C: Computable<T>> {
+    computation: ComputableResult<T, C>,
+    name: Option<Cow<'static, str>>,
+    priority: u32,
+    steps: u64,
+    /// The value of the scheduler clock when the task was last advanced (or spawned).
+    advanced_at: u64,
+    /// The time when the task was last advanced (or spawned).
+    progress_at: Instant,
+    /// Set once the starvation of the task was reported (until it is advanced again).
+    reported: bool,
+    /// The task is not advanced before this time (see [`crate::suspend_for`]).
+    ready_at: Option<Instant>,
+    /// The task is not advanced until this condition is satisfied (see [`crate::suspend_until`]).
+    waiting: Option<WaitUntil>,
+}
+
+/// The status of a computation spawned in a [`Scheduler`] (see [`Scheduler::status`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskStatus {
+    /// The computation did not complete yet.
+    Pending,
+    /// The computation completed and its output was produced by the scheduler.
+    Completed,
+    /// The computation was exhausted without producing an output.
+    Exhausted,
+    /// The computation was dropped using [`Scheduler::cancel`], or it was cancelled
+    /// by its own cancellation token (see [`crate::Computation::with_cancel_token`]).
+    Cancelled,
+    /// The computation was removed using [`Scheduler::take_result`] before it completed.
+    Detached,
+}
+
+/// The callback invoked for starved tasks (see [`Scheduler::on_starvation`]).
+struct StarvationHook {
+    threshold: Duration,
+    callback: Box<dyn FnMut(usize, Duration) + Send>,
+}
+
+impl Debug for StarvationHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StarvationHook")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Generatable`] that interleaves a dynamic set of computations on a single thread,
+/// producing the index and output of each computation as soon as it completes.
+///
+/// Every call to [`Generatable::try_next`] advances one pending computation by a single
+/// step. The computation is chosen from the pending computations with the highest priority
+/// (see [`Scheduler::spawn_with_priority`]); computations with equal priority are advanced
+/// in a round-robin fashion. Computations that are exhausted are removed. Cancellation
+/// (using the thread-local `cancel-this` triggers) stops the scheduler early, but the
+/// interrupted computation is kept, so the scheduler can be resumed later. A computation
+/// that is cancelled while the thread-local triggers are not (e.g., using a per-task token,
+/// see [`crate::Computation::with_cancel_token`]) is removed instead.
+///
+/// The scheduler owns a [`Blackboard`] that the computations can access while they are
+/// performing a step (using [`Blackboard::access`]). This allows cooperative algorithms
+/// to share information (e.g., the best solution found so far) without locking.
+///
+/// A computation that suspends with a retry hint (see [`crate::suspend_for`]) is not advanced
+/// again until the hinted delay elapses. If all pending computations are waiting,
+/// [`Generatable::try_next`] returns [`Incomplete::Suspended`] without advancing any of them
+/// (see [`Scheduler::time_until_ready`]) and the blocking iterator sleeps instead.
+///
+/// Similarly, a computation that suspends with a wake condition (see [`crate::suspend_until`])
+/// is only advanced again once the condition is satisfied. This allows event-driven
+/// scheduling, where computations wait for an external [`crate::EventFlag`], or for another
+/// computation to update the [`Blackboard`]. While all pending computations are waiting
+/// for a wake condition, the blocking iterator backs off instead of busy-waiting, and it
+/// ends if none of the conditions can be satisfied (see [`Iterator::next`]).
+///
+/// Every computation is kept in a [`ComputableResult`] handle. Existing handles can be
+/// spawned using [`Scheduler::spawn_result`], and pending computations can be taken out
+/// of the scheduler as handles using [`Scheduler::take_result`].
+///
+/// Optionally, the scheduler records a [`Timeline`] of all performed steps
+/// (see [`Scheduler::enable_timeline`]).
+///
+/// Strict priorities can starve computations with a low priority. The scheduler can report
+/// computations that did not make progress for a long time (see [`Scheduler::starved`] and
+/// [`Scheduler::on_starvation`]) and can gradually raise the priority of waiting
+/// computations (see [`Scheduler::with_aging`]), such that every computation eventually runs.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Blackboard, BlackboardKey, Completable, Computation, ComputationStep, Incomplete, Scheduler,
+/// };
+///
+/// const BEST: BlackboardKey<u32> = BlackboardKey::new("best");
+///
+/// /// Searches the given range downwards for the largest multiple of seven, stopping early
+/// /// once the best value found by any computation cannot be improved (returning that value).
+/// struct SearchStep;
+///
+/// impl ComputationStep<(u32, u32), u32, u32> for SearchStep {
+///     fn step(&(low, _): &(u32, u32), next: &mut u32) -> Completable<u32> {
+///         let best = Blackboard::access(|board| *board.get_or_insert_with(BEST, || 0)).unwrap();
+///         if *next <= low || *next <= best {
+///             return Ok(best);
+///         }
+///         *next -= 1;
+///         if *next % 7 == 0 {
+///             Blackboard::access(|board| board.insert(BEST, (*next).max(best)));
+///         }
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// type Search = Computation<(u32, u32), u32, u32, SearchStep>;
+///
+/// let mut scheduler = Scheduler::new();
+/// scheduler.spawn(Search::from_parts((0, 50), 50).dyn_computable());
+/// scheduler.spawn(Search::from_parts((50, 100), 100).dyn_computable());
+/// let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+/// // The first search stops as soon as it finds 49, since nothing better exists in its range.
+/// assert_eq!(outputs, vec![(0, 49), (1, 98)]);
+/// assert_eq!(scheduler.blackboard().get(BEST), Some(&98));
+/// # use cancel_this::Cancellable;
+/// ```
+#[derive(Debug)]
+pub struct Scheduler<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    /// The pending computations by index (finished computations are removed).
+    tasks: BTreeMap<usize, Task<T, C>>,
+    /// The identifier and status of every spawned computation (including the finished ones).
+    ids: Vec<(ComputationId, TaskStatus)>,
+    lookup: HashMap<ComputationId, usize>,
+    cursor: usize,
+    blackboard: Blackboard,
+    timeline: Option<Timeline>,
+    /// The number of steps performed by the scheduler.
+    clock: u64,
+    aging: Option<u64>,
+    starvation: Option<StarvationHook>,
+    /// The root of the cancellation tokens of the spawned computations.
+    token: CancelToken,
+    /// The source of the current time (replaced by a fake clock in tests).
+    now: fn() -> Instant,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> Default for Scheduler<T, C> {
+    fn default() -> Self {
+        Scheduler {
+            tasks: BTreeMap::new(),
+            ids: Vec::new(),
+            lookup: HashMap::new(),
+            cursor: 0,
+            blackboard: Blackboard::new(),
+            timeline: None,
+            clock: 0,
+            aging: None,
+            starvation: None,
+            token: CancelToken::new(),
+            now: Instant::now,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<T, C: Computable<T>> Scheduler<T, C> {
+    /// Create a new [`Scheduler`] without any computations and with an empty [`Blackboard`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`Scheduler`] without any computations that shares the given [`Blackboard`].
+    pub fn with_blackboard(blackboard: Blackboard) -> Self {
+        Scheduler {
+            blackboard,
+            ..Self::default()
+        }
+    }
+
+    /// Enable aging: the priority of a pending computation is (temporarily) raised by one
+    /// for every `steps` steps of the scheduler during which the computation was not advanced.
+    /// Once advanced, the computation returns to its original priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is zero.
+    pub fn with_aging(mut self, steps: u64) -> Self {
+        assert!(steps > 0, "Aging interval must be positive.");
+        self.aging = Some(steps);
+        self
+    }
+
+    /// Register a `callback` that is invoked with the index of a computation and the time
+    /// since its last progress once a pending computation was not advanced for at least
+    /// `threshold`. The callback is invoked (during [`Generatable::try_next`]) at most once
+    /// until the computation is advanced again. Replaces the previously registered callback.
+    pub fn on_starvation<F: FnMut(usize, Duration) + Send + 'static>(
+        &mut self,
+        threshold: Duration,
+        callback: F,
+    ) {
+        self.starvation = Some(StarvationHook {
+            threshold,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// Add a computation with the default priority (`0`) and return its index.
+    ///
+    /// The index is the number of previously spawned computations and is reported
+    /// together with the output of the computation.
+    pub fn spawn(&mut self, computation: C) -> usize {
+        self.spawn_with_priority(computation, 0)
+    }
+
+    /// Add a computation with the given `priority` and return its index.
+    ///
+    /// Computations with a higher priority are always advanced before computations
+    /// with a lower priority.
+    pub fn spawn_with_priority(&mut self, computation: C, priority: u32) -> usize {
+        self.spawn_result(ComputableResult::new(computation), priority)
+    }
+
+    /// Add a computation wrapped in a [`ComputableResult`] handle with the given `priority`
+    /// and return its index (see also [`Scheduler::take_result`]).
+    ///
+    /// If the handle already holds a result, the result is produced once the computation
+    /// is selected, without advancing the inner computation. A cached cancellation
+    /// (see [`ComputableResult::clear_failure`]) is discarded, such that the computation
+    /// can be resumed.
+    pub fn spawn_result(&mut self, mut result: ComputableResult<T, C>, priority: u32) -> usize {
+        if matches!(result.outcome(), Some(Err(Incomplete::Cancelled(_)))) {
+            result.clear_failure();
+        }
+        let index = self.ids.len();
+        self.tasks.insert(
+            index,
+            Task {
+                computation: result,
+                name: None,
+                priority,
+                steps: 0,
+                advanced_at: self.clock,
+                progress_at: (self.now)(),
+                reported: false,
+                ready_at: None,
+                waiting: None,
+            },
+        );
+        let id = ComputationId::fresh();
+        self.ids.push((id, TaskStatus::Pending));
+        self.lookup.insert(id, index);
+        index
+    }
+
+    /// Add a computation with a human-readable `name` (see [`Scheduler::name`]) and return
+    /// its index.
+    pub fn spawn_named<N: Into<Cow<'static, str>>>(&mut self, name: N, computation: C) -> usize {
+        let index = self.spawn(computation);
+        if let Some(task) = self.tasks.get_mut(&index) {
+            task.name = Some(name.into());
+        }
+        index
+    }
+
+    /// The name of the pending computation at `index`, assuming it was spawned using
+    /// [`Scheduler::spawn_named`].
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.tasks.get(&index).and_then(|it| it.name.as_deref())
+    }
+
+    /// The index of the first pending computation with the given `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.tasks
+            .iter()
+            .find(|(_, it)| it.name.as_deref() == Some(name))
+            .map(|(index, _)| *index)
+    }
+
+    /// Remove the pending computation at `index` from the scheduler and return it
+    /// as a [`ComputableResult`] handle (e.g., to drive it to completion elsewhere, or to
+    /// spawn it in another scheduler using [`Scheduler::spawn_result`]).
+    ///
+    /// Returns `None` if there is no such pending computation.
+    pub fn take_result(&mut self, index: usize) -> Option<ComputableResult<T, C>> {
+        let task = self.tasks.remove(&index)?;
+        self.ids[index].1 = TaskStatus::Detached;
+        Some(task.computation)
+    }
+
+    /// Drop the pending computation at `index`. Returns `false` if there is no such
+    /// pending computation.
+    pub fn cancel(&mut self, index: usize) -> bool {
+        let cancelled = self.tasks.remove(&index).is_some();
+        if cancelled {
+            self.ids[index].1 = TaskStatus::Cancelled;
+        }
+        cancelled
+    }
+
+    /// The identifier of the computation at `index` (including finished computations).
+    pub fn id(&self, index: usize) -> Option<ComputationId> {
+        self.ids.get(index).map(|(id, _)| *id)
+    }
+
+    /// The index of the computation with the given identifier, if it was spawned
+    /// in this scheduler.
+    pub fn index_of(&self, id: ComputationId) -> Option<usize> {
+        self.lookup.get(&id).copied()
+    }
+
+    /// The status of the computation with the given identifier, if it was spawned
+    /// in this scheduler.
+    pub fn status(&self, id: ComputationId) -> Option<TaskStatus> {
+        self.index_of(id).map(|index| self.ids[index].1)
+    }
+
+    /// Drop the pending computation with the given identifier (see [`Scheduler::cancel`]).
+    pub fn cancel_id(&mut self, id: ComputationId) -> bool {
+        self.index_of(id).is_some_and(|index| self.cancel(index))
+    }
+
+    /// Run all pending computations to completion and return their outputs
+    /// by computation identifier.
+    ///
+    /// If the scheduler is cancelled, the outputs produced so far are lost, but the pending
+    /// computations are kept (see [`Scheduler`]).
+    pub fn collect_by_id(&mut self) -> Cancellable<HashMap<ComputationId, T>> {
+        let mut outputs = HashMap::new();
+        while let Some(item) = self.next() {
+            let (index, output) = item?;
+            outputs.insert(self.ids[index].0, output);
+        }
+        Ok(outputs)
+    }
+
+    /// The number of pending computations.
+    pub fn pending(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// The time until at least one pending computation can be advanced, i.e., zero unless
+    /// all pending computations are waiting for their retry hint to elapse (see
+    /// [`crate::suspend_for`]).
+    ///
+    /// Computations waiting for a wake condition (see [`crate::suspend_until`]) are not
+    /// ready until the condition is satisfied, which does not depend on time. Hence, this
+    /// returns `None` if there are no pending computations, or if all of them are waiting
+    /// for a wake condition.
+    pub fn time_until_ready(&self) -> Option<Duration> {
+        let now = (self.now)();
+        self.tasks
+            .values()
+            .filter(|task| task.waiting.is_none())
+            .map(|task| {
+                task.ready_at
+                    .map_or(Duration::ZERO, |at| at.saturating_duration_since(now))
+            })
+            .min()
+    }
+
+    /// Returns `true` if the computation at `index` is waiting for a wake condition
+    /// (see [`crate::suspend_until`]).
+    pub fn is_waiting(&self, index: usize) -> bool {
+        self.tasks
+            .get(&index)
+            .is_some_and(|it| it.waiting.is_some())
+    }
+
+    /// Returns `true` if the computation at `index` is still pending.
+    pub fn is_pending(&self, index: usize) -> bool {
+        self.task(index).is_some()
+    }
+
+    /// Access to the pending computation at `index` (if any).
+    pub fn task(&self, index: usize) -> Option<&C> {
+        self.tasks
+            .get(&index)
+            .map(|it| it.computation.computable_ref())
+    }
+
+    /// Mutable access to the pending computation at `index` (if any), e.g., to change
+    /// its context using [`crate::StatefulMut::update_context`].
+    ///
+    /// The returned [`TaskGuard`] borrows the scheduler, hence no computation can be advanced
+    /// while the guard exists. This guarantees that the computation is modified at
+    /// a suspend point.
+    pub fn task_mut(&mut self, index: usize) -> Option<TaskGuard<'_, C>> {
+        self.tasks.get_mut(&index).map(|it| TaskGuard {
+            index,
+            task: it.computation.computable_mut(),
+        })
+    }
+
+    /// The priority of the pending computation at `index` (if any).
+    pub fn priority(&self, index: usize) -> Option<u32> {
+        self.tasks.get(&index).map(|it| it.priority)
+    }
+
+    /// The time since the pending computation at `index` (if any) was last advanced
+    /// (or spawned).
+    pub fn time_since_progress(&self, index: usize) -> Option<Duration> {
+        let now = (self.now)();
+        self.tasks
+            .get(&index)
+            .map(|it| now.saturating_duration_since(it.progress_at))
+    }
+
+    /// The indices of the pending computations that were not advanced for at least `threshold`.
+    pub fn starved(&self, threshold: Duration) -> Vec<usize> {
+        let now = (self.now)();
+        self.tasks
+            .iter()
+            .filter(|(_, task)| now.saturating_duration_since(task.progress_at) >= threshold)
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    /// The root [`CancelToken`] of this scheduler.
+    ///
+    /// Computations spawned with a child of this token (see [`CancelToken::child`] and
+    /// [`crate::Computation::with_cancel_token`]) can be cancelled all at once by cancelling
+    /// the root token, while nested computations can be cancelled individually using their
+    /// own child tokens. Cancelled computations are removed once they are advanced.
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    /// Access to the [`Blackboard`] shared by the computations.
+    pub fn blackboard(&self) -> &Blackboard {
+        &self.blackboard
+    }
+
+    /// Mutable access to the [`Blackboard`] shared by the computations.
+    pub fn blackboard_mut(&mut self) -> &mut Blackboard {
+        &mut self.blackboard
+    }
+
+    /// Drop the remaining computations and return the shared [`Blackboard`].
+    pub fn into_blackboard(self) -> Blackboard {
+        self.blackboard
+    }
+
+    /// Start recording a [`Timeline`] of the steps performed by the computations
+    /// (the recording continues if it is already enabled).
+    pub fn enable_timeline(&mut self) {
+        self.timeline.get_or_insert_with(Timeline::new);
+    }
+
+    /// The recorded [`Timeline`] (if recording is enabled).
+    pub fn timeline(&self) -> Option<&Timeline> {
+        self.timeline.as_ref()
+    }
+
+    /// Stop recording and return the recorded [`Timeline`] (if recording was enabled).
+    pub fn take_timeline(&mut self) -> Option<Timeline> {
+        self.timeline.take()
+    }
+
+    /// Clear the wake conditions that are satisfied.
+    fn wake(&mut self) {
+        for task in self.tasks.values_mut() {
+            if task
+                .waiting
+                .as_mut()
+                .is_some_and(|it| it.is_ready(&self.blackboard))
+            {
+                task.waiting = None;
+            }
+        }
+    }
+
+    /// Returns `true` if some pending computation is waiting for an [`crate::EventFlag`],
+    /// which can be set externally.
+    fn waits_for_flag(&self) -> bool {
+        self.tasks
+            .values()
+            .any(|task| matches!(task.waiting, Some(WaitUntil::Flag(_))))
+    }
+
+    /// Find the index of the next computation to advance (skipping the computations
+    /// that are waiting for their retry hint to elapse or for their wake condition).
+    fn select(&self) -> Option<usize> {
+        let now = (self.now)();
+        let mut selected: Option<(usize, u64)> = None;
+        // Round-robin order: starting at the cursor and wrapping around.
+        let ordered = self
+            .tasks
+            .range(self.cursor..)
+            .chain(self.tasks.range(..self.cursor));
+        for (index, task) in ordered {
+            if task.waiting.is_some() || task.ready_at.is_some_and(|at| at > now) {
+                continue;
+            }
+            let priority = self.effective_priority(task);
+            if selected.is_none_or(|(_, best)| priority > best) {
+                selected = Some((*index, priority));
+            }
+        }
+        selected.map(|(index, _)| index)
+    }
+
+    /// The priority of the task, including the bonus obtained by aging.
+    fn effective_priority(&self, task: &Task<T, C>) -> u64 {
+        let bonus = self
+            .aging
+            .map(|steps| (self.clock - task.advanced_at) / steps)
+            .unwrap_or(0);
+        u64::from(task.priority).saturating_add(bonus)
+    }
+
+    /// Invoke the starvation callback for all newly starved tasks (except for the task
+    /// that was just `advanced`).
+    fn report_starvation(&mut self, advanced: usize) {
+        let now = (self.now)();
+        let Some(hook) = self.starvation.as_mut() else {
+            return;
+        };
+        for (&index, task) in self.tasks.iter_mut() {
+            if !task.reported && index != advanced {
+                let waiting = now.saturating_duration_since(task.progress_at);
+                if waiting >= hook.threshold {
+                    task.reported = true;
+                    (hook.callback)(index, waiting);
+                }
+            }
+        }
+    }
+}
+
+/// Mutable access to a suspended computation of a [`Scheduler`].
+///
+/// See [`Scheduler::task_mut`].
+#[derive(Debug)]
+pub struct TaskGuard<'a, C> {
+    index: usize,
+    task: &'a mut C,
+}
+
+impl<C> TaskGuard<'_, C> {
+    /// The index of the guarded computation.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<C> Deref for TaskGuard<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        self.task
+    }
+}
+
+impl<C> DerefMut for TaskGuard<'_, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.task
+    }
+}
+
+impl<T, C: Computable<T>> Generatable<(usize, T)> for Scheduler<T, C> {
+    fn try_next(&mut self) -> Option<Completable<(usize, T)>> {
+        self.wake();
+        let Some(index) = self.select() else {
+            // Either all computations finished, or all of them are waiting.
+            return (self.pending() > 0).then_some(Err(Incomplete::Suspended));
+        };
+        let task = self.tasks.get_mut(&index).expect("The task is pending.");
+        let started = self.timeline.is_some().then(Instant::now);
+        // Discard stale hints that were not recorded by this computation.
+        let _ = take_retry_hint();
+        let _ = take_wake_condition();
+        // The output is moved out of the handle once the task is removed (see below).
+        let result = self
+            .blackboard
+            .install(|| task.computation.try_compute().map(|_| ()));
+        let now = (self.now)();
+        task.ready_at = take_retry_hint().map(|delay| now + delay);
+        task.waiting = take_wake_condition();
+        if let (Some(timeline), Some(started)) = (self.timeline.as_mut(), started) {
+            let outcome = StepOutcome::of(&result);
+            timeline.record(index, task.steps, outcome, started, started.elapsed());
+        }
+        task.steps += 1;
+        task.advanced_at = self.clock + 1;
+        task.progress_at = now;
+        task.reported = false;
+        self.clock += 1;
+        self.report_starvation(index);
+        match result {
+            Ok(()) => {
+                let task = self.tasks.remove(&index).expect("The task is pending.");
+                let output = task.computation.result().expect("The result is computed.");
+                self.ids[index].1 = TaskStatus::Completed;
+                self.cursor = index + 1;
+                Some(Ok((index, output)))
+            }
+            Err(Incomplete::Suspended) => {
+                self.cursor = index + 1;
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(Incomplete::Exhausted) => {
+                self.tasks.remove(&index);
+                self.ids[index].1 = TaskStatus::Exhausted;
+                self.cursor = index + 1;
+                Some(Err(Incomplete::Suspended))
+            }
+            // The computation was cancelled by its own token (e.g., see
+            // `Computation::with_cancel_token`), not by the thread-local triggers.
+            Err(Incomplete::Cancelled(_)) if is_cancelled!().is_ok() => {
+                self.tasks.remove(&index);
+                self.ids[index].1 = TaskStatus::Cancelled;
+                self.cursor = index + 1;
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => {
+                if let Some(task) = self.tasks.get_mut(&index) {
+                    // Keep the computation resumable once the cancellation is lifted.
+                    task.computation.clear_failure();
+                }
+                self.cursor = index;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<T, C: Computable<T>> Iterator for Scheduler<T, C> {
+    type Item = Cancellable<(usize, T)>;
+
+    /// Advance the computations until one of them completes. If all pending computations
+    /// are waiting for their retry hint to elapse, the thread sleeps instead of polling them.
+    /// If all of them are waiting for a wake condition, the thread sleeps with an exponential
+    /// backoff (up to 10 ms) between checking the conditions (while checking for
+    /// cancellation). If all of them are waiting only for [`Blackboard`] predicates,
+    /// the conditions cannot be satisfied (no other computation can update the blackboard),
+    /// hence the iterator ends and the computations remain pending.
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut backoff = MIN_IDLE_BACKOFF;
+        loop {
+            match self.try_next()? {
+                Ok(item) => return Some(Ok(item)),
+                Err(Incomplete::Suspended) => {
+                    // Clear the conditions satisfied by the last step.
+                    self.wake();
+                    match self.time_until_ready() {
+                        Some(wait) => {
+                            backoff = MIN_IDLE_BACKOFF;
+                            if !wait.is_zero() {
+                                std::thread::sleep(wait);
+                            }
+                        }
+                        None if !self.waits_for_flag() => return None,
+                        None => {
+                            if let Err(cancelled) = is_cancelled!() {
+                                return Some(Err(cancelled));
+                            }
+                            std::thread::sleep(backoff);
+                            backoff = (backoff * 2).min(MAX_IDLE_BACKOFF);
+                        }
+                    }
+                }
+                Err(Incomplete::Cancelled(c)) => return Some(Err(c)),
+                Err(Incomplete::ResourceExceeded(e)) => return Some(Err(e.into())),
+                Err(Incomplete::Exhausted) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BlackboardKey, ComputableIdentity, Computation, ComputationStep, EventFlag, FromParts,
+        StatefulMut, StatefulRef,
+    };
+    use std::cell::Cell;
+    use std::task::Poll;
+
+    const LOG: BlackboardKey<Vec<u32>> = BlackboardKey::new("log");
+
+    /// Logs its id into the blackboard and finishes after the number of steps given
+    /// by the context.
+    struct LogStep;
+
+    impl ComputationStep<(u32, u32), u32, u32> for LogStep {
+        fn step(&(id, delay): &(u32, u32), steps: &mut u32) -> Completable<u32> {
+            Blackboard::access(|board| board.get_or_insert_with(LOG, Vec::new).push(id));
+            *steps += 1;
+            if *steps < delay {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(id)
+            }
+        }
+    }
+
+    type Log = Computation<(u32, u32), u32, u32, LogStep>;
+
+    #[test]
+    fn test_round_robin() {
+        let mut scheduler = Scheduler::new();
+        assert_eq!(scheduler.spawn(Log::from_parts((1, 3), 0)), 0);
+        assert_eq!(scheduler.spawn(Log::from_parts((2, 1), 0)), 1);
+        assert_eq!(scheduler.spawn(Log::from_parts((3, 2), 0)), 2);
+        assert_eq!(scheduler.pending(), 3);
+
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(1, 2), (2, 3), (0, 1)]);
+        assert_eq!(scheduler.pending(), 0);
+        assert_eq!(
+            scheduler.blackboard().get(LOG),
+            Some(&vec![1, 2, 3, 1, 3, 1])
+        );
+        assert_eq!(scheduler.try_next(), None);
+    }
+
+    #[test]
+    fn test_priorities() {
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Log::from_parts((1, 2), 0));
+        scheduler.spawn_with_priority(Log::from_parts((2, 2), 0), 5);
+        scheduler.spawn_with_priority(Log::from_parts((3, 2), 0), 5);
+        assert_eq!(scheduler.priority(1), Some(5));
+
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(1, 2), (2, 3), (0, 1)]);
+        let log = scheduler.into_blackboard().remove(LOG).unwrap();
+        assert_eq!(log, vec![2, 3, 2, 3, 1, 1]);
+    }
+
+    #[test]
+    fn test_spawn_and_cancel() {
+        let mut scheduler = Scheduler::with_blackboard(Blackboard::new());
+        scheduler.spawn(Log::from_parts((1, 2), 0));
+        scheduler.spawn(Log::from_parts((2, 5), 0));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(*scheduler.task(1).unwrap().state(), 1);
+        assert!(scheduler.cancel(1));
+        assert!(!scheduler.cancel(1));
+        assert!(!scheduler.is_pending(1));
+
+        // New computations can be added while the scheduler is running.
+        scheduler.spawn(Log::from_parts((3, 1), 0));
+        let outputs = scheduler.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(2, 3), (0, 1)]);
+    }
+
+    #[test]
+    fn test_update_context_at_suspend_point() {
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Log::from_parts((1, 3), 0));
+        scheduler.spawn(Log::from_parts((2, 3), 0));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        {
+            let mut task = scheduler.task_mut(0).unwrap();
+            assert_eq!(task.index(), 0);
+            assert_eq!(task.state(), &1);
+            // Change the id and shorten the delay of the running computation.
+            assert_eq!(task.replace_context((5, 2)), (1, 3));
+        }
+        assert!(scheduler.task_mut(7).is_none());
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(0, 5), (1, 2)]);
+    }
+
+    #[test]
+    fn test_timeline() {
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Log::from_parts((1, 2), 0));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert!(scheduler.timeline().is_none());
+        scheduler.enable_timeline();
+        scheduler.spawn(Log::from_parts((2, 1), 0));
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(1, 2), (0, 1)]);
+
+        let timeline = scheduler.take_timeline().unwrap();
+        let events = timeline
+            .events()
+            .iter()
+            .map(|it| (it.task, it.step, it.outcome))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (1, 0, StepOutcome::Completed),
+                (0, 1, StepOutcome::Completed)
+            ]
+        );
+        assert!(timeline.events()[0].start <= timeline.events()[1].start);
+        assert!(scheduler.timeline().is_none());
+    }
+
+    #[test]
+    fn test_aging() {
+        let mut scheduler = Scheduler::new().with_aging(3);
+        scheduler.spawn(Log::from_parts((1, 2), 0));
+        scheduler.spawn_with_priority(Log::from_parts((2, 10), 0), 1);
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(0, 1), (1, 2)]);
+        // After three steps of waiting, the first task has the same priority as the second
+        // one and takes its round-robin turn.
+        let log = scheduler.into_blackboard().remove(LOG).unwrap();
+        assert_eq!(log, vec![2, 2, 2, 1, 2, 2, 2, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_starvation() {
+        use std::sync::{Arc, Mutex};
+
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Log::from_parts((1, 1), 0));
+        scheduler.spawn_with_priority(Log::from_parts((2, 4), 0), 1);
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        scheduler.on_starvation(Duration::ZERO, move |index, _| {
+            sink.lock().unwrap().push(index);
+        });
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.starved(Duration::ZERO), vec![0, 1]);
+        assert!(scheduler.starved(Duration::from_secs(3600)).is_empty());
+        assert!(scheduler.time_since_progress(0).is_some());
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(1, 2), (0, 1)]);
+        // The second task is reported after each of its own steps; the first task only
+        // once, since it was not advanced in between.
+        assert_eq!(reported.lock().unwrap().first(), Some(&0));
+        assert_eq!(
+            reported
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|it| **it == 0)
+                .count(),
+            1
+        );
+        assert_eq!(scheduler.time_since_progress(0), None);
+    }
+
+    #[test]
+    fn test_skips_exhausted() {
+        let mut consumed = ComputableIdentity::from(1);
+        assert_eq!(consumed.try_compute(), Ok(1));
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(consumed);
+        scheduler.spawn(ComputableIdentity::from(2));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.pending(), 1);
+        assert_eq!(scheduler.try_next(), Some(Ok((1, 2))));
+        assert_eq!(scheduler.try_next(), None);
+    }
+
+    #[test]
+    fn test_blackboard_prefilled() {
+        let mut scheduler = Scheduler::new();
+        scheduler.blackboard_mut().insert(LOG, vec![0]);
+        scheduler.spawn(Log::from_parts((7, 1), 0));
+        assert_eq!(scheduler.try_next(), Some(Ok((0, 7))));
+        assert_eq!(scheduler.blackboard().get(LOG), Some(&vec![0, 7]));
+        // Outside the scheduler, the blackboard is not available.
+        assert_eq!(Blackboard::access(|_| ()), None);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Log::from_parts((1, 2), 0));
+        scheduler.spawn(Log::from_parts((2, 1), 0));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(scheduler.try_next())
+        })
+        .unwrap();
+        assert!(matches!(result, Some(Err(Incomplete::Cancelled(_)))));
+        assert_eq!(scheduler.pending(), 2);
+        assert_eq!(scheduler.try_next(), Some(Ok((1, 2))));
+    }
+
+    #[test]
+    fn test_task_cancel_token() {
+        use cancel_this::CancelAtomic;
+
+        let token = CancelAtomic::new();
+        let mut scheduler = Scheduler::new();
+        let first = scheduler.spawn(Log::from_parts((1, 3), 0).with_cancel_token(token.clone()));
+        let second = scheduler.spawn(Log::from_parts((2, 3), 0));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        token.cancel();
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(second, 2)]);
+        let id = scheduler.id(first).unwrap();
+        assert_eq!(scheduler.status(id), Some(TaskStatus::Cancelled));
+    }
+
+    #[test]
+    fn test_token_tree() {
+        let mut scheduler = Scheduler::new();
+        let group = scheduler.cancel_token().child();
+        let first = Log::from_parts((1, 2), 0).with_cancel_token(group.child());
+        let second = Log::from_parts((2, 2), 0).with_cancel_token(group.child());
+        let third = Log::from_parts((3, 2), 0).with_cancel_token(scheduler.cancel_token().child());
+        for computation in [first, second, third] {
+            scheduler.spawn(computation);
+        }
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        group.cancel();
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(2, 3)]);
+
+        scheduler
+            .spawn(Log::from_parts((4, 2), 0).with_cancel_token(scheduler.cancel_token().child()));
+        scheduler.cancel_token().cancel();
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.pending(), 0);
+    }
+
+    /// Waits for the given delay (once), then completes.
+    struct WaitStep;
+
+    impl ComputationStep<Duration, bool, u32> for WaitStep {
+        fn step(delay: &Duration, waited: &mut bool) -> Completable<u32> {
+            if *waited {
+                return Ok(delay.as_millis() as u32);
+            }
+            *waited = true;
+            Err(crate::suspend_for(*delay))
+        }
+    }
+
+    type Wait = Computation<Duration, bool, u32, WaitStep>;
+
+    thread_local! {
+        static START: Instant = Instant::now();
+        static OFFSET: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    }
+
+    /// A clock which only moves forward through [`advance`].
+    fn fake_now() -> Instant {
+        START.with(|start| *start + OFFSET.with(Cell::get))
+    }
+
+    fn advance(delay: Duration) {
+        OFFSET.with(|offset| offset.set(offset.get() + delay));
+    }
+
+    #[test]
+    fn test_retry_hints() {
+        let mut scheduler = Scheduler::new();
+        scheduler.now = fake_now;
+        scheduler.spawn(Wait::from_parts(Duration::from_millis(20), false));
+        scheduler.spawn(Wait::from_parts(Duration::from_millis(10), false));
+        assert_eq!(scheduler.time_until_ready(), Some(Duration::ZERO));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        // Both computations are waiting, hence none of them is advanced.
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(scheduler.task(0).unwrap().state(), &true);
+        assert_eq!(
+            scheduler.time_until_ready(),
+            Some(Duration::from_millis(10))
+        );
+
+        advance(Duration::from_millis(10));
+        assert_eq!(scheduler.try_next(), Some(Ok((1, 10))));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        advance(Duration::from_millis(10));
+        assert_eq!(scheduler.try_next(), Some(Ok((0, 20))));
+        assert_eq!(scheduler.time_until_ready(), None);
+    }
+
+    const READY: BlackboardKey<bool> = BlackboardKey::new("ready");
+
+    /// Waits until the blackboard entry is set.
+    struct ConsumerStep;
+
+    impl ComputationStep<(), u32, u32> for ConsumerStep {
+        fn step(_context: &(), polls: &mut u32) -> Completable<u32> {
+            *polls += 1;
+            let ready = Blackboard::access(|board| board.get(READY).copied()).flatten();
+            if ready == Some(true) {
+                Ok(*polls)
+            } else {
+                Err(crate::suspend_until(WaitUntil::predicate(|board| {
+                    board.get(READY) == Some(&true)
+                })))
+            }
+        }
+    }
+
+    /// Sets the blackboard entry after a few steps.
+    struct ProducerStep;
+
+    impl ComputationStep<(), u32, u32> for ProducerStep {
+        fn step(_context: &(), steps: &mut u32) -> Completable<u32> {
+            *steps += 1;
+            if *steps < 5 {
+                return Err(Incomplete::Suspended);
+            }
+            Blackboard::access(|board| board.insert(READY, true));
+            Ok(*steps)
+        }
+    }
+
+    /// Waits until the flag is set.
+    struct FlagStep;
+
+    impl ComputationStep<EventFlag, u32, u32> for FlagStep {
+        fn step(flag: &EventFlag, polls: &mut u32) -> Completable<u32> {
+            *polls += 1;
+            if flag.is_set() {
+                Ok(*polls)
+            } else {
+                Err(crate::suspend_until(WaitUntil::flag(flag.clone())))
+            }
+        }
+    }
+
+    #[test]
+    fn test_wake_conditions() {
+        let mut scheduler = Scheduler::<u32>::new();
+        let consumer = Computation::<(), u32, u32, ConsumerStep>::from_parts((), 0);
+        let producer = Computation::<(), u32, u32, ProducerStep>::from_parts((), 0);
+        scheduler.spawn(consumer.dyn_computable());
+        scheduler.spawn(producer.dyn_computable());
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert!(scheduler.is_waiting(0));
+        assert!(!scheduler.is_waiting(1));
+        assert_eq!(scheduler.time_until_ready(), Some(Duration::ZERO));
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        // The consumer is polled only twice: once before and once after the producer finished.
+        assert_eq!(outputs, vec![(1, 5), (0, 2)]);
+    }
+
+    #[test]
+    fn test_all_tasks_waiting() {
+        let flag = EventFlag::new();
+        let mut scheduler = Scheduler::<u32>::new();
+        let waiter = Computation::<EventFlag, u32, u32, FlagStep>::from_parts(flag.clone(), 0);
+        scheduler.spawn(waiter.dyn_computable());
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        assert!(scheduler.is_waiting(0));
+        // The only task is not ready, and it does not become ready with time.
+        assert_eq!(scheduler.time_until_ready(), None);
+        let setter = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            flag.set();
+        });
+        assert_eq!(scheduler.next(), Some(Ok((0, 2))));
+        setter.join().unwrap();
+    }
+
+    #[test]
+    fn test_unsatisfiable_wake_condition() {
+        let mut scheduler = Scheduler::<u32>::new();
+        let consumer = Computation::<(), u32, u32, ConsumerStep>::from_parts((), 0);
+        scheduler.spawn(consumer.dyn_computable());
+        // Nothing can set the blackboard entry, hence the iterator ends instead of spinning.
+        assert_eq!(scheduler.next(), None);
+        assert_eq!(scheduler.pending(), 1);
+        assert!(scheduler.is_waiting(0));
+        // The condition is re-checked once the blackboard changes.
+        scheduler.blackboard_mut().insert(READY, true);
+        assert_eq!(scheduler.next(), Some(Ok((0, 2))));
+    }
+
+    #[test]
+    fn test_cancel_while_waiting() {
+        use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+        let mut scheduler = Scheduler::<u32>::new();
+        let waiter = Computation::<EventFlag, u32, u32, FlagStep>::from_parts(EventFlag::new(), 0);
+        scheduler.spawn(waiter.dyn_computable());
+        let trigger = CancelAtomic::new();
+        let canceller = {
+            let trigger = trigger.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(20));
+                trigger.cancel();
+            })
+        };
+        // The flag is never set, but the waiting iterator can still be cancelled.
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(scheduler.next())).unwrap();
+        assert!(matches!(result, Some(Err(_))));
+        canceller.join().unwrap();
+        assert_eq!(scheduler.pending(), 1);
+    }
+
+    #[test]
+    fn test_named_tasks() {
+        let mut scheduler = Scheduler::new();
+        let first = scheduler.spawn_named("first", Log::from_parts((1, 2), 0));
+        let second = scheduler.spawn(Log::from_parts((2, 1), 0));
+        let third = scheduler.spawn_named(format!("task-{}", 3), Log::from_parts((3, 1), 0));
+        assert_eq!(scheduler.name(first), Some("first"));
+        assert_eq!(scheduler.name(second), None);
+        assert_eq!(scheduler.find("task-3"), Some(third));
+        assert_eq!(scheduler.find("missing"), None);
+        assert!(scheduler.cancel(third));
+        assert_eq!(scheduler.name(third), None);
+        assert_eq!(scheduler.find("task-3"), None);
+    }
+
+    #[test]
+    fn test_result_handles() {
+        let mut scheduler = Scheduler::new();
+        let slow = scheduler.spawn(Log::from_parts((1, 3), 0));
+        let mut finished = ComputableResult::new(Log::from_parts((2, 1), 0));
+        assert_eq!(finished.try_compute(), Ok(&2));
+        let finished = scheduler.spawn_result(finished, 0);
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        // The stored result is produced without advancing the computation again.
+        assert_eq!(scheduler.try_next(), Some(Ok((finished, 2))));
+        assert_eq!(scheduler.blackboard().get(LOG), Some(&vec![1]));
+
+        let mut handle = scheduler.take_result(slow).unwrap();
+        let id = scheduler.id(slow).unwrap();
+        assert_eq!(scheduler.status(id), Some(TaskStatus::Detached));
+        assert!(scheduler.take_result(slow).is_none());
+        assert_eq!(scheduler.try_next(), None);
+        assert_eq!(handle.computable_ref().state(), &1);
+        assert_eq!(handle.poll_ref(), Poll::Pending);
+
+        // The handle can be moved to another scheduler and completed there.
+        let mut other = Scheduler::new();
+        let index = other.spawn_result(handle, 0);
+        assert_eq!(other.task(index).map(|it| *it.state()), Some(2));
+        assert_eq!(other.collect::<Cancellable<Vec<_>>>(), Ok(vec![(index, 1)]));
+    }
+
+    #[test]
+    fn test_computation_ids() {
+        let mut scheduler = Scheduler::new();
+        let first = scheduler.spawn(Log::from_parts((1, 2), 0));
+        let second = scheduler.spawn(Log::from_parts((2, 1), 0));
+        let third = scheduler.spawn(Log::from_parts((3, 1), 0));
+        let ids = [first, second, third].map(|it| scheduler.id(it).unwrap());
+        assert_eq!(scheduler.id(3), None);
+        assert_eq!(scheduler.index_of(ids[1]), Some(second));
+        assert_eq!(scheduler.index_of(ComputationId::fresh()), None);
+        assert_eq!(scheduler.status(ids[0]), Some(TaskStatus::Pending));
+        assert!(scheduler.cancel_id(ids[2]));
+        assert!(!scheduler.cancel_id(ids[2]));
+        assert_eq!(scheduler.status(ids[2]), Some(TaskStatus::Cancelled));
+        let outputs = scheduler.collect_by_id().unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[&ids[0]], 1);
+        assert_eq!(outputs[&ids[1]], 2);
+        assert_eq!(scheduler.status(ids[0]), Some(TaskStatus::Completed));
+        assert_eq!(scheduler.id(first), Some(ids[0]));
+    }
+}
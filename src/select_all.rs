@@ -0,0 +1,211 @@
+use crate::{Completable, Computable, DynComputable, Incomplete};
+use std::marker::PhantomData;
+
+/// A [`Computable`] that runs a collection of computations interleaved at their suspend points
+/// and completes with the output (and index) of the first computation that completes.
+///
+/// Every call to [`Computable::try_compute`] advances one pending computation by a single step,
+/// cycling through the computations in a round-robin fashion. Once a computation completes,
+/// all other computations are dropped (i.e., cancelled). Computations that are exhausted are
+/// skipped, and if all computations are exhausted, the [`SelectAll`] is exhausted as well.
+///
+/// This is typically used to run a portfolio of algorithms solving the same problem
+/// and take the first answer.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete, SelectAll};
+///
+/// /// Finishes after the number of steps given by the context.
+/// struct DelayStep;
+///
+/// impl ComputationStep<u32, u32, &'static str> for DelayStep {
+///     fn step(delay: &u32, steps: &mut u32) -> Completable<&'static str> {
+///         *steps += 1;
+///         if *steps < *delay { Err(Incomplete::Suspended) } else { Ok("done") }
+///     }
+/// }
+///
+/// type Delay = Computation<u32, u32, &'static str, DelayStep>;
+///
+/// let mut select = SelectAll::new(vec![
+///     Delay::from_parts(10, 0),
+///     Delay::from_parts(3, 0),
+///     Delay::from_parts(5, 0),
+/// ]);
+/// assert_eq!(select.compute().unwrap(), (1, "done"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct SelectAll<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    tasks: Vec<Option<C>>,
+    cursor: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> SelectAll<T, C> {
+    /// Create a new [`SelectAll`] of the given computations.
+    pub fn new(tasks: Vec<C>) -> Self {
+        SelectAll {
+            tasks: tasks.into_iter().map(Some).collect(),
+            cursor: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Add another computation to this [`SelectAll`]. Its index is the number
+    /// of previously added computations.
+    pub fn push(&mut self, task: C) {
+        self.tasks.push(Some(task));
+    }
+
+    /// The number of computations that are still running.
+    pub fn running(&self) -> usize {
+        self.tasks.iter().filter(|it| it.is_some()).count()
+    }
+
+    /// Access to the computation at `index` (if it is still running).
+    pub fn task(&self, index: usize) -> Option<&C> {
+        self.tasks.get(index).and_then(|it| it.as_ref())
+    }
+}
+
+impl<T, C: Computable<T>> Computable<(usize, T)> for SelectAll<T, C> {
+    fn try_compute(&mut self) -> Completable<(usize, T)> {
+        let count = self.tasks.len();
+        let index = (0..count)
+            .map(|i| (self.cursor + i) % count)
+            .find(|i| self.tasks[*i].is_some())
+            .ok_or(Incomplete::Exhausted)?;
+        let task = self.tasks[index].as_mut().expect("The task is running.");
+        match task.try_compute() {
+            Ok(output) => {
+                // Drop (cancel) all other computations, but keep the indices of the slots.
+                self.tasks.fill_with(|| None);
+                Ok((index, output))
+            }
+            Err(Incomplete::Suspended) => {
+                self.cursor = index + 1;
+                Err(Incomplete::Suspended)
+            }
+            Err(Incomplete::Exhausted) => {
+                self.tasks[index] = None;
+                self.cursor = index + 1;
+                if self.running() == 0 {
+                    Err(Incomplete::Exhausted)
+                } else {
+                    Err(Incomplete::Suspended)
+                }
+            }
+            Err(e) => {
+                self.cursor = index;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ComputableExt, ComputableIdentity, Computation, ComputationStep, FromParts, StatefulRef,
+    };
+
+    struct DelayStep;
+
+    impl ComputationStep<u32, u32, u32> for DelayStep {
+        fn step(delay: &u32, steps: &mut u32) -> Completable<u32> {
+            *steps += 1;
+            if *steps < *delay {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(*delay * 100)
+            }
+        }
+    }
+
+    type Delay = Computation<u32, u32, u32, DelayStep>;
+
+    #[test]
+    fn test_select_first_completed() {
+        let mut select = SelectAll::new(vec![Delay::from_parts(4, 0), Delay::from_parts(2, 0)]);
+        assert_eq!(select.running(), 2);
+        assert_eq!(select.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(select.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(select.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*select.task(0).unwrap().state(), 2);
+        assert_eq!(select.try_compute(), Ok((1, 200)));
+        // The remaining computations are dropped.
+        assert_eq!(select.running(), 0);
+        assert_eq!(select.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_select_push_after_completion() {
+        let mut select = SelectAll::new(vec![Delay::from_parts(1, 0), Delay::from_parts(3, 0)]);
+        assert_eq!(select.try_compute(), Ok((0, 100)));
+        // The index of a new computation is still the number of previously added ones.
+        select.push(Delay::from_parts(1, 0));
+        assert!(select.task(0).is_none());
+        assert!(select.task(2).is_some());
+        assert_eq!(select.try_compute(), Ok((2, 100)));
+    }
+
+    #[test]
+    fn test_select_ties_resolved_by_order() {
+        let mut select = SelectAll::new(vec![Delay::from_parts(2, 0), Delay::from_parts(2, 0)]);
+        assert_eq!(select.compute().unwrap(), (0, 200));
+    }
+
+    #[test]
+    fn test_select_skips_exhausted() {
+        let mut consumed = ComputableIdentity::from(1).map(|x| x + 1);
+        assert_eq!(consumed.try_compute(), Ok(2));
+        let mut select = SelectAll::new(vec![
+            consumed.dyn_computable(),
+            ComputableIdentity::from(7).dyn_computable(),
+        ]);
+        select.push(ComputableIdentity::from(8).dyn_computable());
+        assert_eq!(select.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(select.running(), 2);
+        assert_eq!(select.try_compute(), Ok((1, 7)));
+    }
+
+    #[test]
+    fn test_select_all_exhausted() {
+        let mut select = SelectAll::<u32, Delay>::new(Vec::new());
+        assert_eq!(select.try_compute(), Err(Incomplete::Exhausted));
+
+        let mut consumed = ComputableIdentity::from(1);
+        assert_eq!(consumed.try_compute(), Ok(1));
+        let mut select = SelectAll::new(vec![consumed]);
+        assert_eq!(select.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_select_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut select = SelectAll::new(vec![Delay::from_parts(1, 0), Delay::from_parts(5, 0)]);
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(select.try_compute())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert_eq!(select.running(), 2);
+        assert_eq!(select.compute().unwrap(), (0, 100));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Collector, Completable, Computable, DynComputable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that runs a collection of computations one after another,
+/// producing the output of each computation as soon as it completes.
+///
+/// Every call to [`Generatable::try_next`] advances the current computation by one step,
+/// so the sequence suspends within tasks (whenever the current computation suspends) and
+/// between tasks (each output is a separate item). Cancellation stops the sequence early,
+/// but the interrupted computation is kept, so the sequence can be resumed later.
+/// Computations that are already exhausted are skipped.
+///
+/// To obtain all outputs as a single `Vec<T>`, use [`Sequence::collect_outputs`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{ComputableIdentity, Sequence};
+///
+/// let sequence = Sequence::new(vec![
+///     ComputableIdentity::from(1).dyn_computable(),
+///     ComputableIdentity::from(2).dyn_computable(),
+///     ComputableIdentity::from(3).map(|x| x * 10).dyn_computable(),
+/// ]);
+/// assert_eq!(sequence.collect_outputs().compute().unwrap(), vec![1, 2, 30]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "C: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct Sequence<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    tasks: VecDeque<C>,
+    completed: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> Sequence<T, C> {
+    /// Create a new [`Sequence`] of the given computations.
+    pub fn new(tasks: Vec<C>) -> Self {
+        Sequence {
+            tasks: tasks.into(),
+            completed: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Append another computation to the end of this [`Sequence`].
+    pub fn push(&mut self, task: C) {
+        self.tasks.push_back(task);
+    }
+
+    /// The number of computations that did not complete yet (including the running one).
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if all computations completed.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// The number of computations that completed so far.
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// Access to the currently running computation (if any).
+    pub fn current(&self) -> Option<&C> {
+        self.tasks.front()
+    }
+
+    /// Convert this sequence into a [`Computable`] that collects the outputs of all
+    /// computations into a `Vec<T>`.
+    pub fn collect_outputs(self) -> Collector<T, Vec<T>, Self> {
+        Collector::new(self)
+    }
+}
+
+impl<T, C: Computable<T>> Iterator for Sequence<T, C> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, C: Computable<T>> Generatable<T> for Sequence<T, C> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let task = self.tasks.front_mut()?;
+        match task.try_compute() {
+            Ok(output) => {
+                self.tasks.pop_front();
+                self.completed += 1;
+                Some(Ok(output))
+            }
+            Err(Incomplete::Exhausted) => {
+                self.tasks.pop_front();
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableIdentity, Computation, ComputationStep, FromParts, StatefulRef};
+
+    struct CountStep;
+
+    impl ComputationStep<u32, u32, u32> for CountStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+            if *count >= *target {
+                return Ok(*count);
+            }
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Count = Computation<u32, u32, u32, CountStep>;
+
+    #[test]
+    fn test_sequence_items() {
+        let mut sequence = Sequence::new(vec![Count::from_parts(1, 0), Count::from_parts(0, 0)]);
+        assert_eq!(sequence.len(), 2);
+        assert_eq!(sequence.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(sequence.try_next(), Some(Ok(1)));
+        assert_eq!(sequence.completed(), 1);
+        assert_eq!(sequence.try_next(), Some(Ok(0)));
+        assert_eq!(sequence.try_next(), None);
+        assert!(sequence.is_empty());
+    }
+
+    #[test]
+    fn test_sequence_collect() {
+        let mut sequence = Sequence::new(vec![Count::from_parts(3, 0)]);
+        sequence.push(Count::from_parts(2, 0));
+        let mut collector = sequence.collect_outputs();
+        assert_eq!(collector.compute().unwrap(), vec![3, 2]);
+    }
+
+    #[test]
+    fn test_sequence_skips_exhausted() {
+        let mut consumed = ComputableIdentity::from(1);
+        assert_eq!(consumed.try_compute(), Ok(1));
+        let sequence = Sequence::new(vec![
+            consumed.dyn_computable(),
+            ComputableIdentity::from(2).dyn_computable(),
+        ]);
+        let outputs: Vec<i32> = sequence.map(|it| it.unwrap()).collect();
+        assert_eq!(outputs, vec![2]);
+    }
+
+    #[test]
+    fn test_sequence_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut sequence = Sequence::new(vec![Count::from_parts(2, 0), Count::from_parts(1, 0)]);
+        assert_eq!(sequence.try_next(), Some(Err(Incomplete::Suspended)));
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(sequence.try_next())
+        })
+        .unwrap();
+        assert!(matches!(result, Some(Err(Incomplete::Cancelled(_)))));
+
+        // The interrupted task is resumed.
+        assert_eq!(*sequence.current().unwrap().state(), 1);
+        let outputs: Vec<u32> = sequence.map(|it| it.unwrap()).collect();
+        assert_eq!(outputs, vec![2, 1]);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Completable, Resource, ResourceExceeded};
+use std::time::{Duration, Instant};
+
+/// A time budget that is spent by measured computation steps and can be stored
+/// as part of the computation `STATE`.
+///
+/// Unlike a deadline based on [`Instant`], a [`SerializableDeadline`] only stores
+/// the overall `budget` and the compute time `spent` so far. Hence, a computation that
+/// is checkpointed (serialized together with its state) and resumed in a new process
+/// still respects its overall time budget, and the time during which the computation
+/// was not running is not counted.
+///
+/// Use [`SerializableDeadline::measure`] to perform (and measure) one step of the
+/// computation. Once the budget is spent, the step is not performed and
+/// [`crate::Incomplete::ResourceExceeded`] (with [`Resource::StepTime`]) is returned instead.
+/// The budget can be raised using [`SerializableDeadline::extend`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, Incomplete, Resource, SerializableDeadline,
+/// };
+/// use std::time::Duration;
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u64, (u64, SerializableDeadline), u64> for CountStep {
+///     fn step(target: &u64, (count, deadline): &mut (u64, SerializableDeadline)) -> Completable<u64> {
+///         deadline.measure(|| {
+///             *count += 1;
+///             if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///         })
+///     }
+/// }
+///
+/// type Count = Computation<u64, (u64, SerializableDeadline), u64, CountStep>;
+///
+/// let deadline = SerializableDeadline::new(Duration::ZERO);
+/// let mut computation = Count::from_parts(10, (0, deadline));
+/// let Err(Incomplete::ResourceExceeded(exceeded)) = computation.try_compute() else {
+///     unreachable!()
+/// };
+/// assert_eq!(exceeded.resource, Resource::StepTime);
+/// assert_eq!(computation.state().0, 0);
+///
+/// // The budget is part of the state, so it survives checkpointing.
+/// let (target, (count, mut deadline)) = computation.into_parts();
+/// deadline.extend(Duration::from_secs(60));
+/// let mut computation = Count::from_parts(target, (count, deadline));
+/// assert_eq!(computation.compute().unwrap(), 10);
+/// assert!(computation.state().1.spent() > Duration::ZERO);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerializableDeadline {
+    budget: Duration,
+    spent: Duration,
+}
+
+impl SerializableDeadline {
+    /// Create a new deadline with the given overall time `budget`.
+    pub fn new(budget: Duration) -> Self {
+        SerializableDeadline {
+            budget,
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// The overall time budget.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// The compute time spent so far.
+    pub fn spent(&self) -> Duration {
+        self.spent
+    }
+
+    /// The compute time that remains in the budget.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.spent)
+    }
+
+    /// Returns `true` if the whole budget was spent.
+    pub fn is_expired(&self) -> bool {
+        self.spent >= self.budget
+    }
+
+    /// Raise the overall time budget by `extra`.
+    pub fn extend(&mut self, extra: Duration) {
+        self.budget = self.budget.saturating_add(extra);
+    }
+
+    /// Record `elapsed` compute time (e.g., measured by an external timer).
+    pub fn charge(&mut self, elapsed: Duration) {
+        self.spent = self.spent.saturating_add(elapsed);
+    }
+
+    /// Returns [`ResourceExceeded`] if the whole budget was spent.
+    pub fn check(&self) -> Result<(), ResourceExceeded> {
+        if self.is_expired() {
+            Err(ResourceExceeded {
+                resource: Resource::StepTime,
+                limit: saturating_nanos(self.budget),
+                used: saturating_nanos(self.spent),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Perform one `step` and add its duration to the spent time. If the budget is
+    /// already spent, the step is not performed and [`ResourceExceeded`] is returned.
+    ///
+    /// The step that exceeds the budget is completed normally; the budget is only
+    /// enforced before the following step.
+    pub fn measure<R, F: FnOnce() -> Completable<R>>(&mut self, step: F) -> Completable<R> {
+        self.check()?;
+        let started = Instant::now();
+        let result = step();
+        self.charge(started.elapsed());
+        result
+    }
+}
+
+/// Convert the duration to nanoseconds, saturating at `u64::MAX`.
+fn saturating_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Incomplete;
+
+    #[test]
+    fn test_budget_accounting() {
+        let mut deadline = SerializableDeadline::new(Duration::from_millis(10));
+        assert_eq!(deadline.remaining(), Duration::from_millis(10));
+        deadline.charge(Duration::from_millis(4));
+        assert_eq!(deadline.spent(), Duration::from_millis(4));
+        assert_eq!(deadline.remaining(), Duration::from_millis(6));
+        assert!(!deadline.is_expired());
+        assert_eq!(deadline.check(), Ok(()));
+        deadline.charge(Duration::from_millis(8));
+        assert!(deadline.is_expired());
+        assert_eq!(deadline.remaining(), Duration::ZERO);
+        assert_eq!(
+            deadline.check(),
+            Err(ResourceExceeded {
+                resource: Resource::StepTime,
+                limit: 10_000_000,
+                used: 12_000_000,
+            })
+        );
+        deadline.extend(Duration::from_millis(5));
+        assert_eq!(deadline.budget(), Duration::from_millis(15));
+        assert_eq!(deadline.check(), Ok(()));
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut deadline = SerializableDeadline::new(Duration::from_millis(1));
+        let result = deadline.measure(|| {
+            std::thread::sleep(Duration::from_millis(2));
+            Err::<(), _>(Incomplete::Suspended)
+        });
+        assert_eq!(result, Err(Incomplete::Suspended));
+        assert!(deadline.spent() >= Duration::from_millis(2));
+        let mut performed = false;
+        let result = deadline.measure(|| {
+            performed = true;
+            Ok(())
+        });
+        assert!(matches!(result, Err(Incomplete::ResourceExceeded(_))));
+        assert!(!performed);
+    }
+
+    #[test]
+    fn test_saturating_nanos() {
+        assert_eq!(saturating_nanos(Duration::MAX), u64::MAX);
+        assert_eq!(saturating_nanos(Duration::from_micros(3)), 3_000);
+    }
+}
@@ -0,0 +1,306 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A reference-counted, immutable `CONTEXT` that can be shared by many computations.
+///
+/// Computations often need access to a large read-only input (e.g., a parsed model).
+/// Using `SharedContext<CTX>` as the `CONTEXT` of a [`crate::Computation`] (or any other
+/// [`crate::Stateful`] type) allows all computations to reference the same value without
+/// cloning it. Since `SharedContext` implements `From<CTX>` and `From<Arc<CTX>>`,
+/// it can be passed directly to [`crate::FromParts::configure`].
+///
+/// With the `serde` feature, `SharedContext` serializes the underlying value. To store
+/// a context that is shared by multiple serialized computations only once, run the
+/// (de)serialization inside `shared_context_scope`.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, SharedContext};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<SharedContext<Vec<u32>>, usize, usize> for CountStep {
+///     fn step(items: &SharedContext<Vec<u32>>, threshold: &mut usize) -> Completable<usize> {
+///         Ok(items.iter().filter(|it| **it as usize > *threshold).count())
+///     }
+/// }
+///
+/// type Count = Computation<SharedContext<Vec<u32>>, usize, usize, CountStep>;
+///
+/// let model = SharedContext::new((0..1000).collect::<Vec<u32>>());
+/// let mut tasks: Vec<Count> = (0..4)
+///     .map(|i| Count::from_parts(model.clone(), i * 250))
+///     .collect();
+/// assert!(SharedContext::ptr_eq(tasks[0].context(), tasks[3].context()));
+/// let results: Vec<usize> = tasks.iter_mut().map(|it| it.compute().unwrap()).collect();
+/// assert_eq!(results, vec![999, 749, 499, 249]);
+/// ```
+pub struct SharedContext<CTX> {
+    value: Arc<CTX>,
+}
+
+impl<CTX> SharedContext<CTX> {
+    /// Create a new shared context.
+    pub fn new(value: CTX) -> Self {
+        SharedContext {
+            value: Arc::new(value),
+        }
+    }
+
+    /// Access to the underlying reference-counted pointer.
+    pub fn as_arc(&self) -> &Arc<CTX> {
+        &self.value
+    }
+
+    /// Unwrap the underlying reference-counted pointer.
+    pub fn into_arc(self) -> Arc<CTX> {
+        self.value
+    }
+
+    /// Returns `true` if both contexts reference the same value.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.value, &b.value)
+    }
+
+    /// The number of references to the underlying value.
+    pub fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.value)
+    }
+}
+
+// Clone is implemented manually, because derive would require `CTX: Clone`.
+impl<CTX> Clone for SharedContext<CTX> {
+    fn clone(&self) -> Self {
+        SharedContext {
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<CTX> Deref for SharedContext<CTX> {
+    type Target = CTX;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<CTX> AsRef<CTX> for SharedContext<CTX> {
+    fn as_ref(&self) -> &CTX {
+        &self.value
+    }
+}
+
+impl<CTX> From<CTX> for SharedContext<CTX> {
+    fn from(value: CTX) -> Self {
+        SharedContext::new(value)
+    }
+}
+
+impl<CTX> From<Arc<CTX>> for SharedContext<CTX> {
+    fn from(value: Arc<CTX>) -> Self {
+        SharedContext { value }
+    }
+}
+
+impl<CTX: Debug> Debug for SharedContext<CTX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedContext").field(&self.value).finish()
+    }
+}
+
+impl<CTX: PartialEq> PartialEq for SharedContext<CTX> {
+    fn eq(&self, other: &Self) -> bool {
+        SharedContext::ptr_eq(self, other) || self.value == other.value
+    }
+}
+
+impl<CTX: Eq> Eq for SharedContext<CTX> {}
+
+impl<CTX: Hash> Hash for SharedContext<CTX> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::SharedContext;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::any::Any;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// The tables of shared contexts that were already (de)serialized in the current scope.
+    #[derive(Default)]
+    struct Tables {
+        /// Maps the address of a serialized value to its id. The values are retained,
+        /// such that the addresses cannot be reused during the scope.
+        serialized: HashMap<usize, (u64, Box<dyn Any>)>,
+        /// Maps the id of a deserialized value to an `Arc<CTX>` (as `dyn Any`).
+        deserialized: HashMap<u64, Box<dyn Any>>,
+    }
+
+    thread_local! {
+        static TABLES: RefCell<Option<Tables>> = const { RefCell::new(None) };
+    }
+
+    /// Run `action` such that every [`SharedContext`] that is serialized (or deserialized)
+    /// by the action is stored only once (requires the `serde` feature).
+    ///
+    /// Within the scope, the first occurrence of a shared value is serialized together
+    /// with a numeric id, and all other occurrences only reference this id. The output
+    /// must then be deserialized within a scope as well, which restores a single shared
+    /// value for all occurrences. Scopes are per-thread and can be nested (the inner scope
+    /// is independent of the outer one).
+    pub fn shared_context_scope<R, F: FnOnce() -> R>(action: F) -> R {
+        let previous = TABLES.with(|it| it.replace(Some(Tables::default())));
+        // Restore the previous tables even if the action panics.
+        struct Restore(Option<Option<Tables>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take().unwrap_or_default();
+                TABLES.with(|it| it.replace(previous));
+            }
+        }
+        let _restore = Restore(Some(previous));
+        action()
+    }
+
+    #[derive(Serialize)]
+    struct SharedRef<'a, CTX> {
+        id: Option<u64>,
+        value: Option<&'a CTX>,
+    }
+
+    #[derive(Deserialize)]
+    struct SharedOwned<CTX> {
+        id: Option<u64>,
+        value: Option<CTX>,
+    }
+
+    impl<CTX: Serialize + 'static> Serialize for SharedContext<CTX> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let address = Arc::as_ptr(&self.value) as usize;
+            let (id, first) = TABLES.with(|it| {
+                let mut tables = it.borrow_mut();
+                let Some(tables) = tables.as_mut() else {
+                    return (None, true);
+                };
+                if let Some((id, _)) = tables.serialized.get(&address) {
+                    return (Some(*id), false);
+                }
+                let id = tables.serialized.len() as u64;
+                let retained: Box<dyn Any> = Box::new(self.value.clone());
+                tables.serialized.insert(address, (id, retained));
+                (Some(id), true)
+            });
+            SharedRef {
+                id,
+                value: first.then_some(self.value.as_ref()),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, CTX: Deserialize<'de> + 'static> Deserialize<'de> for SharedContext<CTX> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            use serde::de::Error;
+
+            let owned = SharedOwned::<CTX>::deserialize(deserializer)?;
+            match (owned.id, owned.value) {
+                (None, Some(value)) => Ok(SharedContext::new(value)),
+                (Some(id), Some(value)) => {
+                    let value = Arc::new(value);
+                    TABLES.with(|it| {
+                        if let Some(tables) = it.borrow_mut().as_mut() {
+                            let stored: Box<dyn Any> = Box::new(value.clone());
+                            tables.deserialized.insert(id, stored);
+                        }
+                    });
+                    Ok(SharedContext { value })
+                }
+                (Some(id), None) => TABLES.with(|it| {
+                    let tables = it.borrow();
+                    let Some(tables) = tables.as_ref() else {
+                        return Err(D::Error::custom(format!(
+                            "Shared context `{id}` can be only deserialized in `shared_context_scope`."
+                        )));
+                    };
+                    tables
+                        .deserialized
+                        .get(&id)
+                        .and_then(|it| it.downcast_ref::<Arc<CTX>>())
+                        .map(|value| SharedContext {
+                            value: value.clone(),
+                        })
+                        .ok_or_else(|| D::Error::custom(format!("Unknown shared context `{id}`.")))
+                }),
+                (None, None) => Err(D::Error::custom("Missing shared context value.")),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use serialization::shared_context_scope;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, Computation, ComputationStep, FromParts, Incomplete, StatefulRef};
+
+    struct LenStep;
+
+    impl ComputationStep<SharedContext<String>, u32, usize> for LenStep {
+        fn step(text: &SharedContext<String>, steps: &mut u32) -> crate::Completable<usize> {
+            *steps += 1;
+            if *steps < 2 {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(text.len())
+            }
+        }
+    }
+
+    type Len = Computation<SharedContext<String>, u32, usize, LenStep>;
+
+    #[test]
+    fn test_shared_context_is_not_cloned() {
+        let text = SharedContext::new("hello".to_string());
+        let first = Len::from_parts(text.clone(), 0);
+        let second = Len::from_parts(text.clone(), 0);
+        assert_eq!(text.reference_count(), 3);
+        assert!(SharedContext::ptr_eq(first.context(), second.context()));
+        drop(first);
+        assert_eq!(text.reference_count(), 2);
+        assert_eq!(second.context().as_ref(), "hello");
+    }
+
+    #[test]
+    fn test_configure_with_conversions() {
+        let mut owned = Len::configure("abc".to_string(), 0u32);
+        assert_eq!(owned.compute(), Ok(3));
+
+        let arc = Arc::new("abcd".to_string());
+        let mut shared = Len::configure(arc.clone(), 0u32);
+        assert!(Arc::ptr_eq(shared.context().as_arc(), &arc));
+        assert_eq!(shared.compute(), Ok(4));
+        assert!(Arc::ptr_eq(&shared.into_parts().0.into_arc(), &arc));
+    }
+
+    #[test]
+    fn test_equality() {
+        let a = SharedContext::new(vec![1, 2]);
+        let b = SharedContext::new(vec![1, 2]);
+        assert_eq!(a, b);
+        assert!(!SharedContext::ptr_eq(&a, &b));
+        assert_eq!(a, a.clone());
+        assert_eq!(format!("{a:?}"), "SharedContext([1, 2])");
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{Completable, Computable, DynComputable, Incomplete};
+use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock};
+
+/// The terminal outcome published by a [`SharedResult`]: either the result,
+/// or `None` if the computation was exhausted without producing one.
+type Slot<T> = Arc<OnceLock<Option<T>>>;
+
+/// A [`Computable`] that drives a computation once and publishes its result to any number
+/// of lightweight [`ResultHandle`]s (similar to a promise or a future).
+///
+/// The [`SharedResult`] itself should be driven by a single owner (e.g., a [`crate::Scheduler`]
+/// or a worker thread). It behaves exactly like the inner computation, but once the result
+/// is available, a copy is also stored for all handles created by [`SharedResult::handle`].
+/// This way, several downstream tasks can depend on the output of one upstream computation
+/// without running it repeatedly.
+///
+/// If the inner computation is exhausted before producing a result, the handles
+/// are exhausted as well. Cancellation of the inner computation is not terminal,
+/// since the computation can be resumed later.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{FnComputation, Incomplete, SharedResult};
+///
+/// let upstream = FnComputation::new(10u64, 0u64, |limit, i| {
+///     *i += 1;
+///     if *i < *limit { Err(Incomplete::Suspended) } else { Ok(*i * 2) }
+/// });
+/// let mut shared = SharedResult::new(upstream);
+/// let handle = shared.handle();
+/// let mut downstream = handle.clone().map(|x| x + 1);
+///
+/// assert_eq!(handle.get(), None);
+/// assert_eq!(downstream.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(shared.compute().unwrap(), 20);
+/// assert_eq!(handle.get(), Some(20));
+/// assert_eq!(downstream.compute().unwrap(), 21);
+/// ```
+#[derive(Debug)]
+pub struct SharedResult<T, C = DynComputable<T>>
+where
+    T: Clone,
+    C: Computable<T>,
+{
+    computable: C,
+    slot: Slot<T>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Clone, C: Computable<T>> SharedResult<T, C> {
+    /// Create a new [`SharedResult`] driving the given `computable`.
+    pub fn new(computable: C) -> Self {
+        SharedResult {
+            computable,
+            slot: Arc::new(OnceLock::new()),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Create a new handle to the result of this computation.
+    pub fn handle(&self) -> ResultHandle<T> {
+        ResultHandle {
+            slot: self.slot.clone(),
+        }
+    }
+
+    /// Returns `true` if the result was already published (or the computation was exhausted).
+    pub fn is_ready(&self) -> bool {
+        self.slot.get().is_some()
+    }
+
+    /// A reference to the inner computation.
+    pub fn computable_ref(&self) -> &C {
+        &self.computable
+    }
+}
+
+impl<T: Clone, C: Computable<T>> Computable<T> for SharedResult<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        match self.computable.try_compute() {
+            Ok(value) => {
+                let _ = self.slot.set(Some(value.clone()));
+                Ok(value)
+            }
+            Err(Incomplete::Exhausted) => {
+                let _ = self.slot.set(None);
+                Err(Incomplete::Exhausted)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A cloneable handle to the result of a [`SharedResult`].
+///
+/// The handle can be queried directly ([`ResultHandle::get`]), or used as a [`Computable`]
+/// which is [`Incomplete::Suspended`] until the result is published, and then produces
+/// a copy of the result on every poll. Handles can be sent to other threads if `T` is
+/// [`Send`] and [`Sync`].
+#[derive(Debug)]
+pub struct ResultHandle<T> {
+    slot: Slot<T>,
+}
+
+impl<T> Clone for ResultHandle<T> {
+    fn clone(&self) -> Self {
+        ResultHandle {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<T: Clone> ResultHandle<T> {
+    /// A copy of the result, assuming it is already available.
+    pub fn get(&self) -> Option<T> {
+        self.slot.get().cloned().flatten()
+    }
+
+    /// A reference to the result, assuming it is already available.
+    pub fn get_ref(&self) -> Option<&T> {
+        self.slot.get().and_then(Option::as_ref)
+    }
+
+    /// Returns `true` if the result was already published (or the computation was exhausted).
+    pub fn is_ready(&self) -> bool {
+        self.slot.get().is_some()
+    }
+
+    /// Returns `true` if both handles refer to the same computation.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.slot, &b.slot)
+    }
+}
+
+impl<T: Clone> Computable<T> for ResultHandle<T> {
+    fn try_compute(&mut self) -> Completable<T> {
+        match self.slot.get() {
+            None => Err(Incomplete::Suspended),
+            Some(None) => Err(Incomplete::Exhausted),
+            Some(Some(value)) => Ok(value.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableExt, ComputableIdentity, FnComputation, Scheduler};
+    use cancel_this::Cancellable;
+
+    #[test]
+    fn test_handles_see_published_result() {
+        let mut shared = SharedResult::new(ComputableIdentity::from(7));
+        let mut first = shared.handle();
+        let second = first.clone();
+        assert!(ResultHandle::ptr_eq(&first, &second));
+        assert!(!shared.is_ready());
+        assert_eq!(first.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(shared.try_compute(), Ok(7));
+        assert!(shared.is_ready());
+        assert!(second.is_ready());
+        assert_eq!(second.get_ref(), Some(&7));
+        assert_eq!(first.try_compute(), Ok(7));
+        assert_eq!(first.try_compute(), Ok(7));
+        // The driver itself behaves like the inner computation.
+        assert_eq!(shared.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(second.get(), Some(7));
+    }
+
+    #[test]
+    fn test_exhausted_upstream() {
+        let mut inner = ComputableIdentity::from(1);
+        assert_eq!(inner.try_compute(), Ok(1));
+        let mut shared = SharedResult::new(inner);
+        let mut handle = shared.handle();
+        assert_eq!(shared.try_compute(), Err(Incomplete::Exhausted));
+        assert!(handle.is_ready());
+        assert_eq!(handle.get(), None);
+        assert_eq!(handle.try_compute(), Err(Incomplete::Exhausted));
+    }
+
+    #[test]
+    fn test_scheduler_dependencies() {
+        let upstream = FnComputation::new((), 0, |_: &(), count: &mut u32| {
+            *count += 1;
+            if *count < 3 {
+                Err(Incomplete::Suspended)
+            } else {
+                Ok(*count)
+            }
+        });
+        let shared = SharedResult::new(upstream);
+        let mut scheduler = Scheduler::<u32>::new();
+        // Downstream tasks are spawned first, but they wait for the upstream result.
+        scheduler.spawn(shared.handle().map(|x| x * 10).dyn_computable());
+        scheduler.spawn(shared.handle().map(|x| x + 1).dyn_computable());
+        scheduler.spawn(shared.dyn_computable());
+        let mut results = scheduler.collect::<Cancellable<Vec<_>>>().unwrap();
+        results.sort();
+        assert_eq!(results, vec![(0, 30), (1, 4), (2, 3)]);
+    }
+
+    #[test]
+    fn test_handle_on_other_thread() {
+        let mut shared = SharedResult::new(ComputableIdentity::from(String::from("done")));
+        let mut handle = shared.handle();
+        let waiter = std::thread::spawn(move || handle.compute().unwrap());
+        assert_eq!(shared.compute().unwrap(), "done");
+        assert_eq!(waiter.join().unwrap(), "done");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Completable, Incomplete};
+use std::collections::VecDeque;
+use std::sync::mpsc::Sender;
+
+/// A consumer of items that can signal backpressure (e.g., a bounded channel).
+///
+/// [`Sink::try_send`] always takes ownership of the item. If the item cannot be delivered
+/// immediately, the sink buffers it and returns [`Incomplete::Suspended`] to signal
+/// backpressure: the caller should then call [`Sink::try_flush`] until it succeeds before
+/// sending more items (otherwise, the buffer of the sink keeps growing). A sink that can no
+/// longer accept items (e.g., because the receiver was dropped) returns
+/// [`Incomplete::Exhausted`].
+///
+/// See [`crate::ChannelSink`] and [`crate::Pump`].
+pub trait Sink<T> {
+    /// Send one `item` into this sink.
+    fn try_send(&mut self, item: T) -> Completable<()>;
+
+    /// Try to deliver the items buffered by this sink. Returns [`Incomplete::Suspended`]
+    /// if some items are still buffered.
+    fn try_flush(&mut self) -> Completable<()> {
+        Ok(())
+    }
+}
+
+impl<T> Sink<T> for Vec<T> {
+    fn try_send(&mut self, item: T) -> Completable<()> {
+        self.push(item);
+        Ok(())
+    }
+}
+
+impl<T> Sink<T> for VecDeque<T> {
+    fn try_send(&mut self, item: T) -> Completable<()> {
+        self.push_back(item);
+        Ok(())
+    }
+}
+
+/// An unbounded channel never signals backpressure.
+impl<T> Sink<T> for Sender<T> {
+    fn try_send(&mut self, item: T) -> Completable<()> {
+        self.send(item).map_err(|_| Incomplete::Exhausted)
+    }
+}
+
+impl<T, S: Sink<T> + ?Sized> Sink<T> for Box<S> {
+    fn try_send(&mut self, item: T) -> Completable<()> {
+        (**self).try_send(item)
+    }
+
+    fn try_flush(&mut self) -> Completable<()> {
+        (**self).try_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_collection_sinks() {
+        let mut items = Vec::new();
+        assert_eq!(items.try_send(1), Ok(()));
+        assert_eq!(items.try_flush(), Ok(()));
+        let mut boxed: Box<dyn Sink<i32>> = Box::new(VecDeque::new());
+        assert_eq!(boxed.try_send(2), Ok(()));
+        assert_eq!(boxed.try_flush(), Ok(()));
+        assert_eq!(items, vec![1]);
+    }
+
+    #[test]
+    fn test_unbounded_channel() {
+        let (mut sender, receiver) = channel();
+        assert_eq!(sender.try_send(1), Ok(()));
+        assert_eq!(receiver.recv(), Ok(1));
+        drop(receiver);
+        assert_eq!(sender.try_send(2), Err(Incomplete::Exhausted));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{Completable, Computable, Incomplete, take_retry_hint};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::time::Duration;
+use web_time::Instant;
+
+/// A driver that advances a [`Computable`] in time slices, such that it can run inside
+/// a single-threaded event loop (e.g., in a browser).
+///
+/// Every call to [`SliceDriver::run_slice`] performs as many steps as fit into the `slice`
+/// duration (at least one) and then returns [`Incomplete::Suspended`], at which point
+/// the caller should yield to the event loop and schedule the next slice (e.g., using
+/// `requestAnimationFrame` or `setTimeout` glue). If a step suspended with a retry hint
+/// (see [`crate::suspend_for`]), the slice ends early and [`SliceDriver::suggested_delay`]
+/// returns the delay that should be used for scheduling the next slice.
+///
+/// The driver (including the computation) can be saved into a JSON string at any suspend
+/// point and restored elsewhere (e.g., to move the computation between a web worker and
+/// the main thread). The clock is provided by `web-time`, which works both natively and
+/// on `wasm32-unknown-unknown`.
+///
+/// Requires the `wasm` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, Incomplete, SliceDriver, FromParts,
+/// };
+/// use std::time::Duration;
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// let computation = Count::from_parts(1_000, 0);
+/// let mut driver = SliceDriver::new(computation, Duration::from_millis(5));
+/// // Called from the event loop glue until the computation is done.
+/// let result = loop {
+///     match driver.run_slice() {
+///         Err(Incomplete::Suspended) => {
+///             // Save the state and continue elsewhere (e.g., in a web worker).
+///             let saved = driver.save().unwrap();
+///             driver = SliceDriver::<u32, Count>::load(&saved).unwrap();
+///         }
+///         result => break result,
+///     }
+/// };
+/// assert_eq!(result, Ok(1_000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "C: Serialize + for<'a> Deserialize<'a>")]
+pub struct SliceDriver<T, C> {
+    computation: C,
+    slice: Duration,
+    steps: u64,
+    slices: u64,
+    /// The retry hint of the last slice (a runtime value, hence not serialized).
+    #[serde(skip)]
+    suggested_delay: Option<Duration>,
+    #[serde(skip)]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> SliceDriver<T, C> {
+    /// Create a new driver that advances `computation` for roughly `slice` per invocation.
+    pub fn new(computation: C, slice: Duration) -> Self {
+        SliceDriver {
+            computation,
+            slice,
+            steps: 0,
+            slices: 0,
+            suggested_delay: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The duration of one time slice.
+    pub fn slice(&self) -> Duration {
+        self.slice
+    }
+
+    /// Change the duration of the time slices (applies to the next slice).
+    pub fn set_slice(&mut self, slice: Duration) {
+        self.slice = slice;
+    }
+
+    /// The total number of steps performed by this driver.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The total number of slices performed by this driver.
+    pub fn slices(&self) -> u64 {
+        self.slices
+    }
+
+    /// The delay after which the next slice should be scheduled, if the last slice ended
+    /// because of a retry hint. `None` means that the next slice can run immediately.
+    pub fn suggested_delay(&self) -> Option<Duration> {
+        self.suggested_delay
+    }
+
+    /// Access to the driven computation.
+    pub fn computation(&self) -> &C {
+        &self.computation
+    }
+
+    /// Mutable access to the driven computation.
+    pub fn computation_mut(&mut self) -> &mut C {
+        &mut self.computation
+    }
+
+    /// Unwrap the driven computation.
+    pub fn into_inner(self) -> C {
+        self.computation
+    }
+
+    /// Advance the computation for one time slice.
+    ///
+    /// Returns [`Incomplete::Suspended`] if the computation needs more slices. Any other
+    /// outcome of the computation (completion, cancellation, exhaustion) is returned
+    /// immediately.
+    pub fn run_slice(&mut self) -> Completable<T> {
+        let start = Instant::now();
+        self.slices += 1;
+        self.suggested_delay = None;
+        // Discard a stale hint (e.g., left behind by another computation).
+        let _ = take_retry_hint();
+        loop {
+            self.steps += 1;
+            match self.computation.try_compute() {
+                Err(Incomplete::Suspended) => {
+                    if let Some(delay) = take_retry_hint() {
+                        self.suggested_delay = Some(delay);
+                        return Err(Incomplete::Suspended);
+                    }
+                    if start.elapsed() >= self.slice {
+                        return Err(Incomplete::Suspended);
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<T, C> SliceDriver<T, C>
+where
+    C: Computable<T> + Serialize + for<'a> Deserialize<'a>,
+{
+    /// Save the driver (including the computation) into a JSON string.
+    pub fn save(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Restore a driver saved using [`SliceDriver::save`].
+    pub fn load(saved: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Computation, ComputationStep, FromParts, StatefulRef, suspend_for, test_fixtures::Count,
+    };
+
+    /// Asks to be retried later after every step.
+    struct PollStep;
+
+    impl ComputationStep<(), u32, u32> for PollStep {
+        fn step(_context: &(), polls: &mut u32) -> Completable<u32> {
+            *polls += 1;
+            if *polls == 3 {
+                Ok(*polls)
+            } else {
+                Err(suspend_for(Duration::from_millis(10)))
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_slice() {
+        // At least one step is performed per slice.
+        let mut driver = SliceDriver::new(Count::from_parts(3, 0), Duration::ZERO);
+        assert_eq!(driver.run_slice(), Err(Incomplete::Suspended));
+        assert_eq!(driver.run_slice(), Err(Incomplete::Suspended));
+        assert_eq!(driver.run_slice(), Ok(3));
+        assert_eq!((driver.steps(), driver.slices()), (3, 3));
+    }
+
+    #[test]
+    fn test_long_slice() {
+        let mut driver = SliceDriver::new(Count::from_parts(100, 0), Duration::from_secs(60));
+        assert_eq!(driver.run_slice(), Ok(100));
+        assert_eq!(driver.slices(), 1);
+        assert_eq!(driver.into_inner().state(), &100);
+    }
+
+    #[test]
+    fn test_retry_hint_ends_slice() {
+        let computation = Computation::<(), u32, u32, PollStep>::from_parts((), 0);
+        let mut driver = SliceDriver::new(computation, Duration::from_secs(60));
+        assert_eq!(driver.run_slice(), Err(Incomplete::Suspended));
+        assert_eq!(driver.suggested_delay(), Some(Duration::from_millis(10)));
+        assert_eq!(driver.steps(), 1);
+        assert_eq!(driver.run_slice(), Err(Incomplete::Suspended));
+        assert_eq!(driver.run_slice(), Ok(3));
+        assert_eq!(driver.suggested_delay(), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut driver = SliceDriver::new(Count::from_parts(5, 0), Duration::ZERO);
+        assert_eq!(driver.run_slice(), Err(Incomplete::Suspended));
+        let saved = driver.save().unwrap();
+        let mut restored = SliceDriver::<u32, Count>::load(&saved).unwrap();
+        assert_eq!(restored, driver);
+        restored.set_slice(Duration::from_secs(60));
+        assert_eq!(restored.run_slice(), Ok(5));
+        assert_eq!(restored.steps(), 5);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{Completable, Computable, Forkable, Incomplete};
+use std::marker::PhantomData;
+
+/// A [`Computable`] wrapper that can run a forked copy of a computation ahead speculatively,
+/// and then either adopt ([`Speculate::commit`]) or discard ([`Speculate::abort`]) the result.
+///
+/// A speculation starts with [`Speculate::speculation_mut`] (which forks the computation
+/// using [`Forkable::fork`], so the speculative copy can be modified, e.g., to try a heuristic
+/// decision) or directly with [`Speculate::run_ahead`]. The speculative copy is only advanced
+/// by [`Speculate::run_ahead`], while [`Computable::try_compute`] always advances the original
+/// computation (which is, however, unusual during a speculation).
+///
+/// This is typically used for look-ahead heuristics in search algorithms: run a few steps of
+/// a promising branch, then commit to it if it looks good, or fall back to the original state.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete, Speculate};
+///
+/// /// Counts up to the target, one step at a time.
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// let mut speculate = Speculate::new(Count::from_parts(10, 0));
+/// // Speculatively skip ahead, but the look-ahead is not conclusive.
+/// *speculate.speculation_mut().state_mut() = 5;
+/// assert_eq!(speculate.run_ahead(2), Err(Incomplete::Suspended));
+/// assert_eq!(speculate.speculation().unwrap().state(), &7);
+/// speculate.abort();
+/// assert_eq!(speculate.original().state(), &0);
+///
+/// // Second attempt finishes during the look-ahead and is adopted.
+/// *speculate.speculation_mut().state_mut() = 8;
+/// assert_eq!(speculate.run_ahead(5), Ok(&10));
+/// assert!(speculate.commit());
+/// assert_eq!(speculate.compute().unwrap(), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "T: serde::Serialize + for<'a> serde::Deserialize<'a>, C: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Speculate<T, C>
+where
+    C: Computable<T> + Forkable,
+{
+    original: C,
+    /// The speculative copy and its output (if it already completed).
+    speculation: Option<(C, Option<T>)>,
+    /// The output adopted from a committed speculation.
+    committed: Option<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T> + Forkable> From<C> for Speculate<T, C> {
+    fn from(value: C) -> Self {
+        Speculate::new(value)
+    }
+}
+
+impl<T, C: Computable<T> + Forkable> Speculate<T, C> {
+    /// Create a new [`Speculate`] wrapper of the given computation.
+    pub fn new(computation: C) -> Self {
+        Speculate {
+            original: computation,
+            speculation: None,
+            committed: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Access to the original (non-speculative) computation.
+    pub fn original(&self) -> &C {
+        &self.original
+    }
+
+    /// Returns `true` if there is an active speculation.
+    pub fn is_speculating(&self) -> bool {
+        self.speculation.is_some()
+    }
+
+    /// Access to the speculative copy (if there is an active speculation).
+    pub fn speculation(&self) -> Option<&C> {
+        self.speculation.as_ref().map(|(it, _)| it)
+    }
+
+    /// Mutable access to the speculative copy. If there is no active speculation,
+    /// a new one is started by forking the original computation.
+    pub fn speculation_mut(&mut self) -> &mut C {
+        &mut self.start().0
+    }
+
+    /// Advance the speculative copy by at most `steps` steps (a new speculation is started
+    /// if necessary). Returns the output of the speculative copy once it completes,
+    /// or [`Incomplete::Suspended`] if the speculation is still running after `steps` steps.
+    ///
+    /// Once the speculative copy completes, its output is retained and returned by all
+    /// subsequent calls (until the speculation is committed or aborted).
+    pub fn run_ahead(&mut self, steps: usize) -> Completable<&T> {
+        let (speculation, output) = self.start();
+        if output.is_none() {
+            for _ in 0..steps {
+                match speculation.try_compute() {
+                    Ok(value) => {
+                        *output = Some(value);
+                        break;
+                    }
+                    Err(Incomplete::Suspended) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        output.as_ref().ok_or(Incomplete::Suspended)
+    }
+
+    /// Adopt the speculative copy (including its output, if it completed) as the original
+    /// computation. Returns `false` if there is no active speculation.
+    pub fn commit(&mut self) -> bool {
+        let Some((speculation, output)) = self.speculation.take() else {
+            return false;
+        };
+        self.original = speculation;
+        self.committed = output;
+        true
+    }
+
+    /// Discard the active speculation (if any) and return the speculative copy.
+    pub fn abort(&mut self) -> Option<C> {
+        self.speculation.take().map(|(it, _)| it)
+    }
+
+    /// Discard the active speculation (if any) and return the original computation.
+    pub fn into_inner(self) -> C {
+        self.original
+    }
+
+    fn start(&mut self) -> &mut (C, Option<T>) {
+        let original = &self.original;
+        self.speculation
+            .get_or_insert_with(|| (original.fork(), None))
+    }
+}
+
+impl<T, C: Computable<T> + Forkable> Computable<T> for Speculate<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        if let Some(output) = self.committed.take() {
+            return Ok(output);
+        }
+        self.original.try_compute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computation, ComputationStep, FromParts, StatefulRef};
+
+    /// Counts up to the target, one step at a time.
+    struct CountStep;
+
+    impl ComputationStep<u32, u32, u32> for CountStep {
+        fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+            *count += 1;
+            if *count >= *target {
+                Ok(*count * 10)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type Count = Computation<u32, u32, u32, CountStep>;
+
+    #[test]
+    fn test_abort_restores_original() {
+        let mut speculate = Speculate::from(Count::from_parts(5, 0));
+        assert_eq!(speculate.try_compute(), Err(Incomplete::Suspended));
+        assert!(!speculate.is_speculating());
+        assert_eq!(speculate.run_ahead(2), Err(Incomplete::Suspended));
+        assert!(speculate.is_speculating());
+        assert_eq!(speculate.speculation().unwrap().state(), &3);
+        assert_eq!(speculate.original().state(), &1);
+
+        let aborted = speculate.abort().unwrap();
+        assert_eq!(aborted.state(), &3);
+        assert_eq!(speculate.abort().map(|it| *it.state()), None);
+        assert!(!speculate.commit());
+        assert_eq!(speculate.compute().unwrap(), 50);
+    }
+
+    #[test]
+    fn test_commit_running_speculation() {
+        let mut speculate = Speculate::new(Count::from_parts(5, 0));
+        assert_eq!(speculate.run_ahead(3), Err(Incomplete::Suspended));
+        assert!(speculate.commit());
+        assert!(!speculate.is_speculating());
+        assert_eq!(speculate.original().state(), &3);
+        assert_eq!(speculate.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(speculate.try_compute(), Ok(50));
+    }
+
+    #[test]
+    fn test_commit_completed_speculation() {
+        let mut speculate = Speculate::new(Count::from_parts(3, 0));
+        *speculate.speculation_mut().state_mut() = 1;
+        assert_eq!(speculate.run_ahead(10), Ok(&30));
+        // The output is retained, the speculative copy is not advanced further.
+        assert_eq!(speculate.run_ahead(10), Ok(&30));
+        assert_eq!(speculate.speculation().unwrap().state(), &3);
+        assert!(speculate.commit());
+        assert_eq!(speculate.try_compute(), Ok(30));
+        assert_eq!(speculate.into_inner().state(), &3);
+    }
+
+    #[test]
+    fn test_run_ahead_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let mut speculate = Speculate::new(Count::from_parts(3, 0));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(speculate.run_ahead(5).cloned())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert!(speculate.is_speculating());
+        assert_eq!(speculate.run_ahead(5), Ok(&30));
+    }
+}
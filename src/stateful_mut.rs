@@ -0,0 +1,90 @@
+use crate::StatefulRef;
+
+/// A [`StatefulRef`] object whose `CONTEXT` can be changed between two steps (e.g., to change
+/// a parameter of a running computation).
+///
+/// The context can only be changed at a suspend point, which is guaranteed by the borrow
+/// checker for all objects owned by the caller. Keep in mind that the `STATE` may depend
+/// on the previous context (see [`crate::Incremental`] for computations that can react
+/// to such changes).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count < *target { Err(Incomplete::Suspended) } else { Ok(*count) }
+///     }
+/// }
+///
+/// let mut count = Computation::<u32, u32, u32, CountStep>::from_parts(100, 0);
+/// assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(count.replace_context(5), 100);
+/// count.update_context(|target| *target -= 2);
+/// assert_eq!(count.compute(), Ok(3));
+/// ```
+pub trait StatefulMut<CONTEXT, STATE>: StatefulRef<CONTEXT, STATE> {
+    /// Access to the underlying `CONTEXT` as a mutable reference.
+    ///
+    /// Prefer [`StatefulMut::replace_context`] or [`StatefulMut::update_context`], which make
+    /// the intent explicit.
+    fn context_mut(&mut self) -> &mut CONTEXT;
+
+    /// Replace the underlying `CONTEXT` between two steps, returning the previous context.
+    fn replace_context(&mut self, context: CONTEXT) -> CONTEXT {
+        std::mem::replace(self.context_mut(), context)
+    }
+
+    /// Modify the underlying `CONTEXT` between two steps using `update`.
+    fn update_context<R, F: FnOnce(&mut CONTEXT) -> R>(&mut self, update: F) -> R
+    where
+        Self: Sized,
+    {
+        update(self.context_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Completable, FromParts, Generator, GeneratorStep};
+    use cancel_this::Cancellable;
+
+    /// Generates the numbers below the context.
+    struct BelowStep;
+
+    impl GeneratorStep<u32, u32, u32> for BelowStep {
+        fn step(limit: &u32, next: &mut u32) -> Completable<Option<u32>> {
+            if *next >= *limit {
+                return Ok(None);
+            }
+            *next += 1;
+            Ok(Some(*next - 1))
+        }
+    }
+
+    #[test]
+    fn test_generator_context_change() {
+        let mut generator = Generator::<u32, u32, u32, BelowStep>::from_parts(10, 0);
+        assert_eq!(generator.next(), Some(Ok(0)));
+        assert_eq!(generator.replace_context(3), 10);
+        assert_eq!(generator.update_context(|limit| *limit), 3);
+        let rest = generator.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(rest, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_dyn_context_mut() {
+        let mut generator = Generator::<u32, u32, u32, BelowStep>::from_parts(1, 0);
+        let stateful: &mut dyn StatefulMut<u32, u32> = &mut generator;
+        *stateful.context_mut() = 2;
+        assert_eq!(stateful.replace_context(3), 2);
+        assert_eq!(generator.context(), &3);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Computable;
+
+/// The object-safe accessor part of [`crate::Stateful`]: access to the `CONTEXT` and `STATE`
+/// of an object, without the ability to construct or destruct it (see [`crate::FromParts`]).
+///
+/// Unlike [`crate::Stateful`], the trait can be combined with [`Computable`] in trait
+/// objects (see [`StatefulAlgorithm`]), and it can be implemented by objects that cannot be
+/// constructed from their parts alone (e.g., [`crate::FnComputation`]).
+pub trait StatefulRef<CONTEXT, STATE> {
+    /// Access to the underlying immutable `CONTEXT`.
+    fn context(&self) -> &CONTEXT;
+
+    /// Access to the underlying `STATE`.
+    fn state(&self) -> &STATE;
+
+    /// Access to the underlying `STATE` as a mutable reference.
+    ///
+    /// Keep in mind that having a consistent state is important for the correctness
+    /// of [`crate::Algorithm`] and [`crate::GenAlgorithm`]. You should modify the internal
+    /// state of a [`StatefulRef`] object only in rare, well-defined situations.
+    fn state_mut(&mut self) -> &mut STATE;
+}
+
+/// A [`Computable`] with an accessible `CONTEXT` and `STATE` (see [`StatefulRef`]).
+///
+/// Unlike [`crate::Algorithm`], this trait is implemented automatically for every
+/// [`Computable`] which is also [`StatefulRef`], including wrappers like
+/// [`crate::Incremental`] and computations that cannot be constructed from parts
+/// (like [`crate::FnComputation`]).
+/// See [`crate::DynStatefulAlgorithm`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, DynStatefulAlgorithm, Incomplete, StatefulRef,
+/// };
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count < *target { Err(Incomplete::Suspended) } else { Ok(*count) }
+///     }
+/// }
+///
+/// let mut tasks: Vec<DynStatefulAlgorithm<u32, u32, u32>> = vec![
+///     Box::new(Computation::<u32, u32, u32, CountStep>::from_parts(3, 0)),
+///     Box::new(Computation::<u32, u32, u32, CountStep>::from_parts(5, 2)),
+/// ];
+/// for task in tasks.iter_mut() {
+///     assert_eq!(task.try_compute(), Err(Incomplete::Suspended));
+/// }
+/// let states = tasks.iter().map(|it| *it.state()).collect::<Vec<_>>();
+/// assert_eq!(states, vec![1, 3]);
+/// ```
+pub trait StatefulAlgorithm<CONTEXT, STATE, OUTPUT>:
+    Computable<OUTPUT> + StatefulRef<CONTEXT, STATE>
+{
+}
+
+impl<CONTEXT, STATE, OUTPUT, C> StatefulAlgorithm<CONTEXT, STATE, OUTPUT> for C where
+    C: Computable<OUTPUT> + StatefulRef<CONTEXT, STATE> + ?Sized
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Completable, Computation, ComputationStep, DynStatefulAlgorithm, DynStatefulAlgorithmSend,
+        FromParts, Generatable, Incomplete, Incremental, IncrementalStep, Scheduler,
+    };
+
+    struct SumStep;
+
+    impl ComputationStep<Vec<u32>, (usize, u32), u32> for SumStep {
+        fn step(items: &Vec<u32>, (index, sum): &mut (usize, u32)) -> Completable<u32> {
+            match items.get(*index) {
+                Some(item) => {
+                    *index += 1;
+                    *sum += item;
+                    Err(Incomplete::Suspended)
+                }
+                None => Ok(*sum),
+            }
+        }
+    }
+
+    impl IncrementalStep<Vec<u32>, (usize, u32), u32> for SumStep {
+        fn on_context_changed(state: &mut (usize, u32), _context: &Vec<u32>) {
+            *state = (0, 0);
+        }
+    }
+
+    type Sum = Computation<Vec<u32>, (usize, u32), u32, SumStep>;
+
+    #[test]
+    fn test_scheduler_inspects_boxed_tasks() {
+        let mut scheduler =
+            Scheduler::<u32, DynStatefulAlgorithm<Vec<u32>, (usize, u32), u32>>::new();
+        scheduler.spawn(Box::new(Sum::from_parts(vec![1, 2, 3], (0, 0))));
+        scheduler.spawn(Box::new(Incremental::new(Sum::from_parts(vec![4], (0, 0)))));
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+        let task = scheduler.task(0).unwrap();
+        assert_eq!(task.context(), &vec![1, 2, 3]);
+        assert_eq!(task.state(), &(1, 1));
+        // Skip the second item of the first task.
+        scheduler.task_mut(0).unwrap().state_mut().0 = 2;
+        let mut results = scheduler
+            .collect::<cancel_this::Cancellable<Vec<_>>>()
+            .unwrap();
+        results.sort();
+        assert_eq!(results, vec![(0, 4), (1, 4)]);
+    }
+
+    #[test]
+    fn test_send_variant() {
+        let mut task: DynStatefulAlgorithmSend<Vec<u32>, (usize, u32), u32> =
+            Box::new(Sum::from_parts(vec![5, 6], (0, 0)));
+        let handle = std::thread::spawn(move || {
+            let result = task.compute();
+            (result, *task.state())
+        });
+        assert_eq!(handle.join().unwrap(), (Ok(11), (2, 11)));
+    }
+}
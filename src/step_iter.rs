@@ -0,0 +1,171 @@
+use crate::{Completable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// An [`Iterator`] view of a [`Generatable`] that yields every step of the generator,
+/// including [`Incomplete::Suspended`].
+///
+/// Unlike the default [`Iterator`] implementation of generators, this iterator never loops
+/// internally: every call to [`Iterator::next`] performs exactly one step.
+///
+/// See [`Generatable::iter_steps`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Generatable, Incomplete, IterGenerator};
+///
+/// let mut generator = IterGenerator::new(1..=2).suspend_every(1);
+/// let steps: Vec<_> = generator.iter_steps().collect();
+/// assert_eq!(steps, vec![Ok(1), Err(Incomplete::Suspended), Ok(2), Err(Incomplete::Suspended)]);
+/// ```
+#[derive(Debug)]
+pub struct StepIter<'a, T, G: Generatable<T> + ?Sized> {
+    generator: &'a mut G,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T, G: Generatable<T> + ?Sized> StepIter<'a, T, G> {
+    /// Create a new [`StepIter`] view of the given `generator`.
+    pub fn new(generator: &'a mut G) -> Self {
+        StepIter {
+            generator,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<T, G: Generatable<T> + ?Sized> Iterator for StepIter<'_, T, G> {
+    type Item = Completable<T>;
+
+    fn next(&mut self) -> Option<Completable<T>> {
+        self.generator.try_next()
+    }
+}
+
+/// An [`Iterator`] view of a [`Generatable`] that skips over suspended states, but stops
+/// once the generator suspended a given number of times.
+///
+/// This prevents callers that embed generators in iterator pipelines from busy-looping on
+/// a generator that keeps suspending. Once the budget is spent, the iterator returns `None`
+/// and [`BudgetIter::budget_spent`] becomes `true`; the generator itself can be resumed later.
+///
+/// See [`Generatable::iter_with_budget`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Generatable, IterGenerator};
+///
+/// let mut generator = IterGenerator::new(1..=5).suspend_every(2);
+/// let mut iter = generator.iter_with_budget(1);
+/// let items: Vec<u32> = iter.by_ref().map(|item| item.unwrap()).collect();
+/// assert_eq!(items, vec![1, 2, 3, 4]);
+/// assert!(iter.budget_spent());
+/// ```
+#[derive(Debug)]
+pub struct BudgetIter<'a, T, G: Generatable<T> + ?Sized> {
+    generator: &'a mut G,
+    remaining: usize,
+    budget_spent: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T, G: Generatable<T> + ?Sized> BudgetIter<'a, T, G> {
+    /// Create a new [`BudgetIter`] view of `generator` which tolerates `suspensions`
+    /// suspended states before it stops.
+    pub fn new(generator: &'a mut G, suspensions: usize) -> Self {
+        BudgetIter {
+            generator,
+            remaining: suspensions,
+            budget_spent: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the iteration stopped because the suspension budget was spent
+    /// (as opposed to the generator being exhausted).
+    pub fn budget_spent(&self) -> bool {
+        self.budget_spent
+    }
+
+    /// The number of suspensions that are still tolerated.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<T, G: Generatable<T> + ?Sized> Iterator for BudgetIter<'_, T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Cancellable<T>> {
+        if self.budget_spent {
+            return None;
+        }
+        loop {
+            match self.generator.try_next()? {
+                Ok(item) => return Some(Ok(item)),
+                Err(Incomplete::Suspended) if self.remaining == 0 => {
+                    self.budget_spent = true;
+                    return None;
+                }
+                Err(Incomplete::Suspended) => self.remaining -= 1,
+                Err(Incomplete::Cancelled(c)) => return Some(Err(c)),
+                Err(Incomplete::ResourceExceeded(e)) => return Some(Err(e.into())),
+                Err(Incomplete::Exhausted) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IterGenerator;
+
+    #[test]
+    fn test_iter_steps_partial() {
+        let mut generator = IterGenerator::new(0..3).suspend_every(2);
+        let first: Vec<_> = generator.iter_steps().take(3).collect();
+        assert_eq!(first, vec![Ok(0), Ok(1), Err(Incomplete::Suspended)]);
+        let rest: Vec<_> = generator.iter_steps().collect();
+        assert_eq!(rest, vec![Ok(2)]);
+    }
+
+    #[test]
+    fn test_iter_with_budget_resumes() {
+        let mut generator = IterGenerator::new(0..6).suspend_every(1);
+        let mut iter = generator.iter_with_budget(2);
+        let items: Vec<u32> = iter.by_ref().map(|item| item.unwrap()).collect();
+        assert_eq!(items, vec![0, 1, 2]);
+        assert!(iter.budget_spent());
+        assert_eq!(iter.remaining(), 0);
+        assert_eq!(iter.next(), None);
+
+        let items: Vec<u32> = generator
+            .iter_with_budget(usize::MAX)
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(items, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_iter_with_budget_exhausted() {
+        let mut generator = IterGenerator::new(0..2);
+        let mut iter = generator.iter_with_budget(0);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert!(!iter.budget_spent());
+    }
+
+    #[test]
+    fn test_iter_with_budget_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+
+        let mut generator = IterGenerator::new(0..2);
+        let result = on_trigger(trigger, || generator.iter_with_budget(1).next().unwrap());
+        assert!(result.is_err());
+    }
+}
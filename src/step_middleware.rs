@@ -0,0 +1,216 @@
+use crate::{Completable, ComputationStep, GeneratorStep};
+use std::marker::PhantomData;
+
+/// Hooks that are executed around every step of a [`ComputationStep`]
+/// (or [`GeneratorStep`]) when composed using [`Layered`].
+///
+/// Middleware implements cross-cutting concerns (validation, logging, invariant checks, ...)
+/// that can be reused across many step implementations. For generators, the `OUTPUT`
+/// of the middleware is `Option<ITEM>`.
+pub trait StepMiddleware<CONTEXT, STATE, OUTPUT> {
+    /// Called before every step. Returning an error skips the step and returns
+    /// the error instead (the [`StepMiddleware::after_step`] hook is still called).
+    fn before_step(context: &CONTEXT, state: &mut STATE) -> Completable<()> {
+        let _ = (context, state);
+        Ok(())
+    }
+
+    /// Called after every step with the `result` of the step, which can be modified
+    /// (e.g., replaced with an error).
+    fn after_step(context: &CONTEXT, state: &mut STATE, result: &mut Completable<OUTPUT>) {
+        let _ = (context, state, result);
+    }
+}
+
+/// A [`ComputationStep`] (or [`GeneratorStep`]) that runs `STEP` wrapped
+/// in the hooks of the `MIDDLEWARE` (see [`StepMiddleware`]).
+///
+/// Multiple middleware layers are composed by nesting, i.e., in
+/// `Layered<Layered<STEP, A>, B>`, the hooks of `A` are the innermost ones.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, Incomplete, Layered, StepMiddleware,
+/// };
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct SumStep;
+///
+/// impl ComputationStep<Vec<u32>, (usize, u32), u32> for SumStep {
+///     fn step(items: &Vec<u32>, (index, sum): &mut (usize, u32)) -> Completable<u32> {
+///         let Some(item) = items.get(*index) else {
+///             return Ok(*sum);
+///         };
+///         *index += 1;
+///         *sum += item;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// static STEPS: AtomicUsize = AtomicUsize::new(0);
+///
+/// /// Counts the steps and checks that the index stays in bounds.
+/// struct CheckIndex;
+///
+/// impl StepMiddleware<Vec<u32>, (usize, u32), u32> for CheckIndex {
+///     fn after_step(items: &Vec<u32>, (index, _): &mut (usize, u32), _: &mut Completable<u32>) {
+///         STEPS.fetch_add(1, Ordering::Relaxed);
+///         assert!(*index <= items.len());
+///     }
+/// }
+///
+/// type Sum = Computation<Vec<u32>, (usize, u32), u32, Layered<SumStep, CheckIndex>>;
+///
+/// let mut sum = Sum::from_parts(vec![1, 2, 3], (0, 0));
+/// assert_eq!(sum.compute().unwrap(), 6);
+/// assert_eq!(STEPS.load(Ordering::Relaxed), 4);
+/// ```
+pub struct Layered<STEP, MIDDLEWARE> {
+    _phantom: PhantomData<(STEP, MIDDLEWARE)>,
+}
+
+impl<CONTEXT, STATE, OUTPUT, STEP, MIDDLEWARE> ComputationStep<CONTEXT, STATE, OUTPUT>
+    for Layered<STEP, MIDDLEWARE>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+    MIDDLEWARE: StepMiddleware<CONTEXT, STATE, OUTPUT>,
+{
+    fn step(context: &CONTEXT, state: &mut STATE) -> Completable<OUTPUT> {
+        let mut result = match MIDDLEWARE::before_step(context, state) {
+            Ok(()) => STEP::step(context, state),
+            Err(e) => Err(e),
+        };
+        MIDDLEWARE::after_step(context, state, &mut result);
+        result
+    }
+}
+
+impl<CONTEXT, STATE, ITEM, STEP, MIDDLEWARE> GeneratorStep<CONTEXT, STATE, ITEM>
+    for Layered<STEP, MIDDLEWARE>
+where
+    STEP: GeneratorStep<CONTEXT, STATE, ITEM>,
+    MIDDLEWARE: StepMiddleware<CONTEXT, STATE, Option<ITEM>>,
+{
+    fn step(context: &CONTEXT, state: &mut STATE) -> Completable<Option<ITEM>> {
+        let mut result = match MIDDLEWARE::before_step(context, state) {
+            Ok(()) => STEP::step(context, state),
+            Err(e) => Err(e),
+        };
+        MIDDLEWARE::after_step(context, state, &mut result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computable, Computation, FromParts, Generator, Incomplete, StatefulRef};
+    use cancel_this::{Cancellable, Cancelled};
+
+    /// Counts up to the target; `state` is (count, log).
+    struct CountStep;
+
+    impl ComputationStep<u32, (u32, Vec<&'static str>), u32> for CountStep {
+        fn step(target: &u32, (count, log): &mut (u32, Vec<&'static str>)) -> Completable<u32> {
+            log.push("step");
+            *count += 1;
+            if *count >= *target {
+                Ok(*count)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    impl GeneratorStep<u32, (u32, Vec<&'static str>), u32> for CountStep {
+        fn step(
+            target: &u32,
+            (count, log): &mut (u32, Vec<&'static str>),
+        ) -> Completable<Option<u32>> {
+            log.push("step");
+            *count += 1;
+            Ok((*count <= *target).then_some(*count))
+        }
+    }
+
+    struct Outer;
+
+    impl<T> StepMiddleware<u32, (u32, Vec<&'static str>), T> for Outer {
+        fn before_step(_: &u32, (_, log): &mut (u32, Vec<&'static str>)) -> Completable<()> {
+            log.push("outer-before");
+            Ok(())
+        }
+
+        fn after_step(_: &u32, (_, log): &mut (u32, Vec<&'static str>), _: &mut Completable<T>) {
+            log.push("outer-after");
+        }
+    }
+
+    struct Inner;
+
+    impl<T> StepMiddleware<u32, (u32, Vec<&'static str>), T> for Inner {
+        fn before_step(_: &u32, (_, log): &mut (u32, Vec<&'static str>)) -> Completable<()> {
+            log.push("inner-before");
+            Ok(())
+        }
+    }
+
+    /// Refuses to step past the limit of 2.
+    struct Limit;
+
+    impl StepMiddleware<u32, (u32, Vec<&'static str>), u32> for Limit {
+        fn before_step(_: &u32, (count, _): &mut (u32, Vec<&'static str>)) -> Completable<()> {
+            if *count >= 2 {
+                Err(Incomplete::Cancelled(Cancelled::new("Limit")))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn after_step(_: &u32, _: &mut (u32, Vec<&'static str>), result: &mut Completable<u32>) {
+            if let Ok(value) = result {
+                *value *= 10;
+            }
+        }
+    }
+
+    #[test]
+    fn test_layer_order() {
+        type Count = Computation<
+            u32,
+            (u32, Vec<&'static str>),
+            u32,
+            Layered<Layered<CountStep, Inner>, Outer>,
+        >;
+        let mut computation = Count::from_parts(1, (0, Vec::new()));
+        assert_eq!(computation.compute(), Ok(1));
+        assert_eq!(
+            computation.state().1,
+            vec!["outer-before", "inner-before", "step", "outer-after"]
+        );
+    }
+
+    #[test]
+    fn test_skip_and_modify() {
+        type Count = Computation<u32, (u32, Vec<&'static str>), u32, Layered<CountStep, Limit>>;
+        let mut computation = Count::from_parts(2, (0, Vec::new()));
+        assert_eq!(computation.compute(), Ok(20));
+
+        let mut computation = Count::from_parts(5, (0, Vec::new()));
+        assert_eq!(computation.compute().unwrap_err().cause(), "Limit");
+        // The third step was skipped.
+        assert_eq!(computation.state().1.len(), 2);
+    }
+
+    #[test]
+    fn test_generator() {
+        type Count = Generator<u32, (u32, Vec<&'static str>), u32, Layered<CountStep, Outer>>;
+        let mut generator = Count::from_parts(2, (0, Vec::new()));
+        let items = generator.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(generator.state().1.len(), 9);
+    }
+}
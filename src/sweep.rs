@@ -0,0 +1,186 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, ComputationStep, Generatable, Incomplete};
+use cancel_this::{Cancellable, is_cancelled};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that runs one [`ComputationStep`] for every configuration of a parameter
+/// grid and produces `(params, output)` pairs as the individual computations complete.
+///
+/// For every parameter value, the `factory` closure creates the `CONTEXT` and the initial
+/// `STATE` of the computation. The configurations are evaluated one after another, such that
+/// every call to [`Generatable::try_next`] performs a single step of the current computation.
+/// Cancellation is checked before every step, and the interrupted configuration is resumed
+/// afterwards. Configurations whose computation is [`Incomplete::Exhausted`] are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{Completable, ComputationStep, Incomplete, Sweep};
+///
+/// /// Computes `base^exponent` using one multiplication per step.
+/// struct PowerStep;
+///
+/// impl ComputationStep<u64, (u32, u64), u64> for PowerStep {
+///     fn step(base: &u64, (exponent, acc): &mut (u32, u64)) -> Completable<u64> {
+///         if *exponent == 0 {
+///             return Ok(*acc);
+///         }
+///         *exponent -= 1;
+///         *acc *= base;
+///         Err(Incomplete::Suspended)
+///     }
+/// }
+///
+/// let grid = [(2, 3), (3, 2), (10, 0)];
+/// let sweep = Sweep::<_, _, _, _, PowerStep, _>::new(grid, |&(base, exp)| (base, (exp, 1)));
+/// let results = sweep.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(results, vec![((2, 3), 8), ((3, 2), 9), ((10, 0), 1)]);
+/// ```
+pub struct Sweep<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+    F: FnMut(&PARAMS) -> (CONTEXT, STATE),
+{
+    grid: VecDeque<PARAMS>,
+    factory: F,
+    /// The configuration that is currently evaluated.
+    current: Option<(PARAMS, CONTEXT, STATE)>,
+    _phantom: PhantomData<(OUTPUT, STEP)>,
+}
+
+impl<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F> Sweep<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+    F: FnMut(&PARAMS) -> (CONTEXT, STATE),
+{
+    /// Create a new [`Sweep`] over the given parameter `grid`, using `factory` to create
+    /// the context and the initial state for every configuration.
+    pub fn new<I: IntoIterator<Item = PARAMS>>(grid: I, factory: F) -> Self {
+        Sweep {
+            grid: grid.into_iter().collect(),
+            factory,
+            current: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The parameters of the configuration that is currently evaluated, if any.
+    pub fn current(&self) -> Option<&PARAMS> {
+        self.current.as_ref().map(|(params, _, _)| params)
+    }
+
+    /// The number of configurations that were not started yet.
+    pub fn remaining(&self) -> usize {
+        self.grid.len()
+    }
+}
+
+impl<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F> Iterator
+    for Sweep<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+    F: FnMut(&PARAMS) -> (CONTEXT, STATE),
+{
+    type Item = Cancellable<(PARAMS, OUTPUT)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F> Generatable<(PARAMS, OUTPUT)>
+    for Sweep<PARAMS, CONTEXT, STATE, OUTPUT, STEP, F>
+where
+    STEP: ComputationStep<CONTEXT, STATE, OUTPUT>,
+    F: FnMut(&PARAMS) -> (CONTEXT, STATE),
+{
+    fn try_next(&mut self) -> Option<Completable<(PARAMS, OUTPUT)>> {
+        if self.current.is_none() {
+            let params = self.grid.pop_front()?;
+            let (context, state) = (self.factory)(&params);
+            self.current = Some((params, context, state));
+        }
+        if let Err(e) = is_cancelled!() {
+            return Some(Err(e.into()));
+        }
+        let (_, context, state) = self.current.as_mut().expect("Configuration is present.");
+        match STEP::step(context, state) {
+            Ok(output) => {
+                let (params, _, _) = self.current.take().expect("Configuration is present.");
+                Some(Ok((params, output)))
+            }
+            Err(Incomplete::Exhausted) => {
+                self.current = None;
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Counts down to zero and returns the product of the context and the number of steps.
+    /// Negative states are exhausted.
+    struct CountdownStep;
+
+    impl ComputationStep<i32, (i32, i32), i32> for CountdownStep {
+        fn step(scale: &i32, (remaining, steps): &mut (i32, i32)) -> Completable<i32> {
+            if *remaining < 0 {
+                return Err(Incomplete::Exhausted);
+            }
+            if *remaining == 0 {
+                return Ok(*steps * scale);
+            }
+            *remaining -= 1;
+            *steps += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Countdowns<F> = Sweep<(i32, i32), i32, (i32, i32), i32, CountdownStep, F>;
+
+    fn factory(&(scale, count): &(i32, i32)) -> (i32, (i32, i32)) {
+        (scale, (count, 0))
+    }
+
+    #[test]
+    fn test_sweep_steps() {
+        let mut sweep = Countdowns::new([(10, 1), (5, 0)], factory);
+        assert_eq!(sweep.remaining(), 2);
+        assert_eq!(sweep.current(), None);
+        assert_eq!(sweep.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(sweep.current(), Some(&(10, 1)));
+        assert_eq!(sweep.remaining(), 1);
+        assert_eq!(sweep.try_next(), Some(Ok(((10, 1), 10))));
+        assert_eq!(sweep.current(), None);
+        assert_eq!(sweep.try_next(), Some(Ok(((5, 0), 0))));
+        assert_eq!(sweep.try_next(), None);
+    }
+
+    #[test]
+    fn test_exhausted_configurations_are_skipped() {
+        let grid = (-1..3).map(|count| (2, count));
+        let results = Countdowns::new(grid, factory).collect::<Cancellable<Vec<_>>>();
+        assert_eq!(results, Ok(vec![((2, 0), 0), ((2, 1), 2), ((2, 2), 4)]));
+    }
+
+    #[test]
+    fn test_cancellation_resumes_configuration() {
+        let mut sweep = Countdowns::new([(3, 2)], factory);
+        assert_eq!(sweep.try_next(), Some(Err(Incomplete::Suspended)));
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(sweep.try_next()));
+        assert!(matches!(result, Ok(Some(Err(Incomplete::Cancelled(_))))));
+        assert_eq!(sweep.current(), Some(&(3, 2)));
+        assert_eq!(
+            sweep.collect::<Cancellable<Vec<_>>>(),
+            Ok(vec![((3, 2), 6)])
+        );
+    }
+}
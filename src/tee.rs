@@ -0,0 +1,178 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Internal state shared between the two [`Tee`] handles.
+#[derive(Debug)]
+struct TeeShared<T, G> {
+    generator: G,
+    /// Items produced by the generator that were not yet consumed by each handle.
+    queues: [VecDeque<T>; 2],
+    /// Handles that were dropped.
+    closed: [bool; 2],
+    max_lag: usize,
+}
+
+/// One of the two handles created by [`tee`] (or [`crate::GeneratableExt::tee`]).
+///
+/// Both handles produce all items of the source generator and can be consumed independently
+/// (on a single thread). Items produced by the source generator are buffered until both
+/// handles consume them, but a handle never gets ahead of the other one by more than
+/// `max_lag` items: in such case, it returns [`Incomplete::Suspended`] until the other handle
+/// catches up (or is dropped).
+///
+/// Keep in mind that blocking iteration over the leading handle loops forever once it
+/// reaches the maximal lag, since it cannot make progress without the other handle.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for CountStep {
+///     fn step(max: &u32, count: &mut u32) -> Completable<Option<u32>> {
+///         *count += 1;
+///         Ok((*count <= *max).then_some(*count))
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, CountStep>::from_parts(4, 0);
+/// let (mut left, mut right) = generator.tee(2);
+/// assert_eq!(left.try_next(), Some(Ok(1)));
+/// assert_eq!(left.try_next(), Some(Ok(2)));
+/// // The left handle is two items ahead and has to wait.
+/// assert_eq!(left.try_next(), Some(Err(Incomplete::Suspended)));
+/// assert_eq!(right.try_next(), Some(Ok(1)));
+/// assert_eq!(left.try_next(), Some(Ok(3)));
+/// drop(left);
+/// // Once the left handle is dropped, the right handle is no longer constrained.
+/// let rest = right.map(|it| it.unwrap()).collect::<Vec<_>>();
+/// assert_eq!(rest, vec![2, 3, 4]);
+/// ```
+#[derive(Debug)]
+pub struct Tee<T, G = DynGeneratable<T>>
+where
+    T: Clone,
+    G: Generatable<T>,
+{
+    shared: Rc<RefCell<TeeShared<T, G>>>,
+    index: usize,
+}
+
+/// Split the items of `generator` into two independent [`Tee`] handles, such that
+/// one handle is at most `max_lag` items ahead of the other.
+pub fn tee<T: Clone, G: Generatable<T>>(generator: G, max_lag: usize) -> (Tee<T, G>, Tee<T, G>) {
+    let shared = Rc::new(RefCell::new(TeeShared {
+        generator,
+        queues: [VecDeque::new(), VecDeque::new()],
+        closed: [false, false],
+        max_lag,
+    }));
+    let first = Tee {
+        shared: shared.clone(),
+        index: 0,
+    };
+    let second = Tee { shared, index: 1 };
+    (first, second)
+}
+
+impl<T: Clone, G: Generatable<T>> Tee<T, G> {
+    /// The number of items that were produced by the other handle, but not yet by this one.
+    pub fn lag(&self) -> usize {
+        self.shared.borrow().queues[self.index].len()
+    }
+
+    /// The maximal number of items by which one handle can get ahead of the other.
+    pub fn max_lag(&self) -> usize {
+        self.shared.borrow().max_lag
+    }
+}
+
+impl<T: Clone, G: Generatable<T>> Drop for Tee<T, G> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.closed[self.index] = true;
+        shared.queues[self.index].clear();
+    }
+}
+
+impl<T: Clone, G: Generatable<T>> Iterator for Tee<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T: Clone, G: Generatable<T>> Generatable<T> for Tee<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let mut shared = self.shared.borrow_mut();
+        if let Some(item) = shared.queues[self.index].pop_front() {
+            return Some(Ok(item));
+        }
+        let other = 1 - self.index;
+        if !shared.closed[other] && shared.queues[other].len() >= shared.max_lag {
+            return Some(Err(Incomplete::Suspended));
+        }
+        match shared.generator.try_next()? {
+            Ok(item) => {
+                if !shared.closed[other] {
+                    shared.queues[other].push_back(item.clone());
+                }
+                Some(Ok(item))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GeneratableExt, test_fixtures::Items};
+
+    #[test]
+    fn test_alternating_consumers() {
+        let (mut left, mut right) = tee(Items::from_parts(vec![1, 0, 2, 3], 0), 1);
+        assert_eq!(left.max_lag(), 1);
+        assert_eq!(left.try_next(), Some(Ok(1)));
+        assert_eq!(right.lag(), 1);
+        assert_eq!(left.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(right.try_next(), Some(Ok(1)));
+        // The suspension is observed by whichever handle advances the source.
+        assert_eq!(right.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(right.try_next(), Some(Ok(2)));
+        assert_eq!(left.lag(), 1);
+        assert_eq!(left.try_next(), Some(Ok(2)));
+        assert_eq!(left.try_next(), Some(Ok(3)));
+        assert_eq!(left.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(right.try_next(), Some(Ok(3)));
+        assert_eq!(right.try_next(), None);
+        assert_eq!(left.try_next(), None);
+    }
+
+    #[test]
+    fn test_unbounded_after_drop() {
+        let (left, right) = Items::from_parts(vec![1, 2, 3], 0).tee(0);
+        drop(right);
+        let items = left.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_lagging_consumer_keeps_items_after_exhaustion() {
+        let (mut left, right) = Items::from_parts(vec![1, 2], 0).tee(5);
+        assert_eq!(
+            left.by_ref().collect::<Cancellable<Vec<_>>>(),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(right.lag(), 2);
+        assert_eq!(right.collect::<Cancellable<Vec<_>>>(), Ok(vec![1, 2]));
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    Completable, Computable, Computation, ComputationState, FromParts, Incomplete, StatefulRef,
+    Transition,
+};
+
+/// Finds the first item of the context that is divisible by `divisor`,
+/// reporting `None` when no such item exists.
+#[derive(ComputationState, Debug, Clone, PartialEq)]
+#[computation(context = (Vec<u32>, u32), output = Option<u32>)]
+enum SearchPhase {
+    Validate,
+    Scan(usize),
+    #[phase(handler = report)]
+    Found {
+        item: u32,
+    },
+}
+
+impl SearchPhase {
+    fn validate(context: &(Vec<u32>, u32)) -> Completable<Transition<Self, Option<u32>>> {
+        if context.1 == 0 {
+            Ok(Transition::Done(None))
+        } else {
+            Ok(Transition::Next(SearchPhase::Scan(0)))
+        }
+    }
+
+    fn scan(
+        (items, divisor): &(Vec<u32>, u32),
+        index: &mut usize,
+    ) -> Completable<Transition<Self, Option<u32>>> {
+        match items.get(*index) {
+            None => Ok(Transition::Done(None)),
+            Some(item) if item.is_multiple_of(*divisor) => {
+                Ok(Transition::Next(SearchPhase::Found { item: *item }))
+            }
+            Some(_) => {
+                *index += 1;
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    fn report(_: &(Vec<u32>, u32), item: &mut u32) -> Completable<Transition<Self, Option<u32>>> {
+        Ok(Transition::Done(Some(*item)))
+    }
+}
+
+type Search = Computation<(Vec<u32>, u32), SearchPhase, Option<u32>, SearchPhaseStep>;
+
+#[test]
+fn test_derived_phases() {
+    let mut search = Search::from_parts((vec![3, 5, 8, 10], 4), SearchPhase::Validate);
+    assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(*search.state(), SearchPhase::Scan(0));
+    assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(*search.state(), SearchPhase::Scan(1));
+    assert_eq!(search.compute(), Ok(Some(8)));
+    assert_eq!(*search.state(), SearchPhase::Found { item: 8 });
+}
+
+#[test]
+fn test_derived_phases_early_exit() {
+    let mut search = Search::from_parts((vec![3, 5], 0), SearchPhase::Validate);
+    assert_eq!(search.try_compute(), Ok(None));
+
+    let mut search = Search::from_parts((vec![3, 5], 2), SearchPhase::Validate);
+    assert_eq!(search.compute(), Ok(None));
+}
+
+#[test]
+fn test_derived_phases_cancellation() {
+    use cancel_this::{CancelAtomic, on_trigger};
+
+    let trigger = CancelAtomic::new();
+    trigger.cancel();
+
+    let mut search = Search::from_parts((vec![3], 3), SearchPhase::Validate);
+    let result = on_trigger(trigger, || search.compute());
+    assert!(result.is_err());
+    assert_eq!(*search.state(), SearchPhase::Validate);
+}
+
+crate::computation! {
+    /// Computes the Collatz stopping time of the context, suspending after every step.
+    struct CollatzSteps;
+    fn(start: &u64) -> u32 {
+        let mut value: u64 = *start;
+        let mut steps: u32 = 0;
+        while value != 1 {
+            if value.is_multiple_of(2) {
+                value /= 2;
+            } else {
+                value = 3 * value + 1;
+                suspend!();
+            }
+            steps += 1;
+            suspend!();
+        }
+        steps
+    }
+}
+
+#[test]
+fn test_computation_macro() {
+    let mut collatz = CollatzSteps::start(6);
+    assert_eq!(collatz.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(collatz.compute(), Ok(8));
+    assert_eq!(collatz.try_compute(), Err(Incomplete::Exhausted));
+    assert_eq!(CollatzSteps::start(1).try_compute(), Ok(0));
+}
+
+#[test]
+fn test_computation_macro_suspend_count() {
+    // 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1: two odd steps with extra suspend points.
+    let mut collatz = CollatzSteps::start(3);
+    let mut suspended = 0;
+    let result = loop {
+        match collatz.try_compute() {
+            Ok(value) => break value,
+            Err(Incomplete::Suspended) => suspended += 1,
+            Err(e) => panic!("Unexpected: {e:?}"),
+        }
+    };
+    assert_eq!(result, 7);
+    assert_eq!(suspended, 9);
+}
+
+#[test]
+fn test_computation_macro_cancellation() {
+    use cancel_this::{CancelAtomic, on_trigger};
+
+    let mut collatz = CollatzSteps::start(7);
+    assert_eq!(collatz.try_compute(), Err(Incomplete::Suspended));
+
+    let trigger = CancelAtomic::new();
+    trigger.cancel();
+    let result = on_trigger(trigger, || collatz.compute());
+    assert!(result.is_err());
+    // The computation can continue after cancellation.
+    assert_eq!(collatz.compute(), Ok(16));
+}
+
+/// Fails with [`Incomplete::Suspended`] on every odd attempt.
+fn every_other(attempts: &mut u32) -> Completable<()> {
+    *attempts += 1;
+    if attempts.is_multiple_of(2) {
+        Ok(())
+    } else {
+        Err(Incomplete::Suspended)
+    }
+}
+
+crate::computation! {
+    /// Sums the items of the context up to the first zero, checking every item using `?`.
+    struct CheckedSum;
+    fn(items: &Vec<u32>) -> u32 {
+        let mut attempts: u32 = 0;
+        let mut index: usize = 0;
+        let mut total: u32 = 0;
+        suspend!();
+        while index < items.len() {
+            every_other(&mut attempts)?;
+            if items[index] == 0 {
+                return Ok(total);
+            }
+            total += items[index];
+            index += 1;
+            suspend!();
+        }
+        total
+    }
+}
+
+#[test]
+fn test_computation_macro_early_exit() {
+    let mut sum = CheckedSum::start(vec![1, 2, 3]);
+    let mut suspended = 0;
+    let result = loop {
+        match sum.try_compute() {
+            Ok(value) => break value,
+            Err(Incomplete::Suspended) => suspended += 1,
+            Err(e) => panic!("Unexpected: {e:?}"),
+        }
+    };
+    assert_eq!(result, 6);
+    // One `suspend!()` before the loop, and one `suspend!()` and one `?` per item.
+    assert_eq!(suspended, 7);
+    assert_eq!(sum.try_compute(), Err(Incomplete::Exhausted));
+}
+
+#[test]
+fn test_computation_macro_early_return() {
+    let mut sum = CheckedSum::start(vec![1, 2, 0, 4]);
+    assert_eq!(sum.compute(), Ok(3));
+    assert_eq!(sum.try_compute(), Err(Incomplete::Exhausted));
+}
+
+crate::generator! {
+    /// Emits all divisors of the context, suspending after every tested candidate.
+    struct Divisors;
+    fn(number: &u32) -> u32 {
+        let mut candidate: u32 = 1;
+        while candidate <= *number {
+            if number.is_multiple_of(candidate) {
+                emit!(candidate);
+            }
+            candidate += 1;
+            suspend!();
+        }
+    }
+}
+
+#[test]
+fn test_generator_macro() {
+    use crate::Generatable;
+
+    let mut divisors = Divisors::start(4);
+    assert_eq!(divisors.try_next(), Some(Ok(1)));
+    assert_eq!(divisors.try_next(), Some(Err(Incomplete::Suspended)));
+    assert_eq!(divisors.try_next(), Some(Ok(2)));
+    let rest: Vec<u32> = divisors.map(|item| item.unwrap()).collect();
+    assert_eq!(rest, vec![4]);
+
+    let all: Vec<u32> = Divisors::start(12).map(|item| item.unwrap()).collect();
+    assert_eq!(all, vec![1, 2, 3, 4, 6, 12]);
+}
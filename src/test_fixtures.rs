@@ -0,0 +1,48 @@
+//! Computations and generators shared by the unit tests of this crate.
+
+use crate::{Completable, Computation, ComputationStep, Generator, GeneratorStep, Incomplete};
+
+/// Counts to the target (the context), suspending after every step except the last one.
+pub struct CountStep;
+
+impl ComputationStep<u32, u32, u32> for CountStep {
+    fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+        *count += 1;
+        if *count >= *target {
+            Ok(*count)
+        } else {
+            Err(Incomplete::Suspended)
+        }
+    }
+}
+
+/// Generates the numbers up to the target (the context).
+impl GeneratorStep<u32, u32, u32> for CountStep {
+    fn step(target: &u32, count: &mut u32) -> Completable<Option<u32>> {
+        *count += 1;
+        Ok((*count <= *target).then_some(*count))
+    }
+}
+
+/// A [`Computation`] driven by [`CountStep`].
+pub type Count = Computation<u32, u32, u32, CountStep>;
+
+/// A [`Generator`] driven by [`CountStep`].
+pub type CountGenerator = Generator<u32, u32, u32, CountStep>;
+
+/// Produces the items (the context); zero stands for a suspension.
+pub struct ItemsStep;
+
+impl GeneratorStep<Vec<i32>, usize, i32> for ItemsStep {
+    fn step(items: &Vec<i32>, index: &mut usize) -> Completable<Option<i32>> {
+        let item = items.get(*index).copied();
+        *index += 1;
+        match item {
+            Some(0) => Err(Incomplete::Suspended),
+            item => Ok(item),
+        }
+    }
+}
+
+/// A [`Generator`] driven by [`ItemsStep`].
+pub type Items = Generator<Vec<i32>, usize, i32, ItemsStep>;
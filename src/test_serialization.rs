@@ -1,6 +1,8 @@
 use crate::{
-    Collector, Completable, Computable, ComputableResult, Computation, ComputationStep, Generator,
-    GeneratorStep, Incomplete, Stateful,
+    BestSoFar, Blackboard, BlackboardKey, Child, Collector, Completable, Computable,
+    ComputableResult, Computation, ComputationStep, DagRunner, FromParts, Generatable, Generator,
+    GeneratorStep, Incomplete, Join, Phased, Scheduler, SharedContext, StatefulRef, SubComputation,
+    shared_context_scope,
 };
 use serde::{Deserialize, Serialize};
 
@@ -91,6 +93,27 @@ fn test_generator_serialization() {
     assert_eq!(generator.state(), deserialized.state());
 }
 
+#[test]
+fn test_zero_cancel_check_interval_is_rejected() {
+    type TestComputation = Computation<TestContext, TestState, i32, TestComputationStep>;
+    type TestGenerator = Generator<TestContext, TestState, i32, TestGeneratorStep>;
+
+    let computation =
+        TestComputation::from_parts(TestContext(10), TestState(5)).with_cancel_check_interval(3);
+    let serialized = serde_json::to_string(&computation).unwrap();
+    let deserialized: TestComputation = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.cancel_check_interval(), 3);
+    let zero = serialized.replace("\"cancel_check_interval\":3", "\"cancel_check_interval\":0");
+    assert!(serde_json::from_str::<TestComputation>(&zero).is_err());
+
+    let generator =
+        TestGenerator::from_parts(TestContext(10), TestState(5)).with_cancel_check_interval(3);
+    let serialized = serde_json::to_string(&generator).unwrap();
+    let zero = serialized.replace("\"cancel_check_interval\":3", "\"cancel_check_interval\":0");
+    assert_ne!(zero, serialized);
+    assert!(serde_json::from_str::<TestGenerator>(&zero).is_err());
+}
+
 #[test]
 fn test_collector_serialization() {
     let generator = Generator::<TestContext, TestState, i32, TestGeneratorStep>::from_parts(
@@ -117,3 +140,599 @@ fn test_collector_serialization() {
     let result = deserialized.compute().unwrap();
     assert_eq!(result, vec![6, 7, 8, 9]);
 }
+
+#[test]
+fn test_bounded_collector_serialization() {
+    use crate::OverflowPolicy;
+
+    let generator = Generator::<TestContext, TestState, i32, TestGeneratorStep>::from_parts(
+        TestContext(10),
+        TestState(0),
+    );
+    let mut collector =
+        Collector::<i32, Vec<i32>, _>::bounded(generator, 3, OverflowPolicy::DropOldest);
+    // Collect a few items before taking a snapshot.
+    for _ in 0..5 {
+        assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    let serialized = serde_json::to_string(&collector).unwrap();
+    let mut deserialized: Collector<
+        i32,
+        Vec<i32>,
+        Generator<TestContext, TestState, i32, TestGeneratorStep>,
+    > = serde_json::from_str(&serialized).unwrap();
+
+    assert_eq!(deserialized.compute().unwrap(), vec![7, 8, 9]);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct OffsetStage(i32);
+
+impl crate::pipeline::PipelineStage<i32, i32> for OffsetStage {
+    fn process(&mut self, item: i32) -> Option<i32> {
+        Some(item + self.0)
+    }
+}
+
+#[test]
+fn test_pipeline_serialization() {
+    use crate::pipeline::{BufferedStage, Pipeline};
+
+    type Source = Generator<TestContext, TestState, i32, TestGeneratorStep>;
+    type Stage1 = BufferedStage<i32, i32, OffsetStage, Source>;
+    type Stage2 = BufferedStage<i32, i32, OffsetStage, Stage1>;
+
+    let generator = Source::from_parts(TestContext(10), TestState(0));
+    let mut collector = Pipeline::from(generator)
+        .stage(3, OffsetStage(100))
+        .stage(2, OffsetStage(1000))
+        .collect::<Vec<i32>>();
+
+    // Run a few steps, so that the buffers contain some in-flight items.
+    for _ in 0..5 {
+        assert_eq!(collector.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    let serialized = serde_json::to_string(&collector).unwrap();
+    let mut deserialized: Collector<i32, Vec<i32>, Stage2> =
+        serde_json::from_str(&serialized).unwrap();
+
+    let expected: Vec<i32> = (1..10).map(|x| x + 1100).collect();
+    assert_eq!(deserialized.compute().unwrap(), expected);
+    assert_eq!(collector.compute().unwrap(), expected);
+}
+
+struct ParentStep;
+
+type ParentState = (
+    Vec<i32>,
+    Child<i32, Computation<TestContext, TestState, i32, TestComputationStep>>,
+);
+
+impl ComputationStep<Vec<i32>, ParentState, i32> for ParentStep {
+    fn step(targets: &Vec<i32>, (outputs, child): &mut ParentState) -> Completable<i32> {
+        while outputs.len() < targets.len() {
+            let target = targets[outputs.len()];
+            outputs.push(
+                child.step_child(|| Computation::from_parts(TestContext(target), TestState(0)))?,
+            );
+        }
+        Ok(outputs.iter().sum())
+    }
+}
+
+#[test]
+fn test_composite_serialization() {
+    type Parent = Computation<Vec<i32>, ParentState, i32, ParentStep>;
+    let mut parent = Parent::from_parts(vec![2, 3], (Vec::new(), Child::empty()));
+    assert_eq!(parent.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(parent.try_compute(), Err(Incomplete::Suspended));
+
+    // The running child is saved as part of the parent state.
+    let serialized = serde_json::to_string(&parent).unwrap();
+    let mut deserialized: Parent = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.state().0, vec![2]);
+    let child = deserialized.state().1.computation().unwrap();
+    assert_eq!(child.state(), &TestState(1));
+    assert_eq!(deserialized.compute().unwrap(), 5);
+}
+
+struct SharedSumStep;
+
+impl ComputationStep<SharedContext<Vec<i32>>, usize, i32> for SharedSumStep {
+    fn step(items: &SharedContext<Vec<i32>>, skip: &mut usize) -> Completable<i32> {
+        Ok(items.iter().skip(*skip).sum())
+    }
+}
+
+#[test]
+fn test_shared_context_serialization() {
+    type SharedSum = Computation<SharedContext<Vec<i32>>, usize, i32, SharedSumStep>;
+    let items = SharedContext::new((1..=100).collect::<Vec<i32>>());
+    let tasks: Vec<SharedSum> = (0..3)
+        .map(|i| SharedSum::from_parts(items.clone(), i))
+        .collect();
+
+    // Without a scope, every computation contains a copy of the context.
+    let plain = serde_json::to_string(&tasks).unwrap();
+    let restored: Vec<SharedSum> = serde_json::from_str(&plain).unwrap();
+    assert!(!SharedContext::ptr_eq(
+        restored[0].context(),
+        restored[1].context()
+    ));
+    assert_eq!(restored[2].context(), &items);
+
+    // Within a scope, the context is stored only once and remains shared.
+    let deduplicated = shared_context_scope(|| serde_json::to_string(&tasks).unwrap());
+    assert!(deduplicated.len() * 2 < plain.len());
+    let mut restored: Vec<SharedSum> =
+        shared_context_scope(|| serde_json::from_str(&deduplicated).unwrap());
+    assert!(SharedContext::ptr_eq(
+        restored[0].context(),
+        restored[2].context()
+    ));
+    assert_eq!(restored[1].compute().unwrap(), 5049);
+
+    // References cannot be resolved outside a scope.
+    assert!(serde_json::from_str::<Vec<SharedSum>>(&deduplicated).is_err());
+}
+
+const INCUMBENT: BlackboardKey<BestSoFar<i32>> = BlackboardKey::new("incumbent");
+
+struct PruneStep;
+
+impl ComputationStep<Vec<i32>, usize, Option<i32>> for PruneStep {
+    fn step(values: &Vec<i32>, position: &mut usize) -> Completable<Option<i32>> {
+        let Some(value) = values.get(*position) else {
+            return Ok(BestSoFar::peek_shared(INCUMBENT));
+        };
+        *position += 1;
+        BestSoFar::offer_shared(INCUMBENT, *value);
+        Err(Incomplete::Suspended)
+    }
+}
+
+#[test]
+fn test_best_so_far_checkpoint() {
+    type Prune = Computation<Vec<i32>, usize, Option<i32>, PruneStep>;
+    let mut scheduler = Scheduler::new();
+    scheduler.spawn(Prune::from_parts(vec![5, 1, 9], 0));
+    scheduler.spawn(Prune::from_parts(vec![7, 3], 0));
+    for _ in 0..3 {
+        assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+    }
+
+    // Save the computations together with the incumbent.
+    let tasks = [0, 1].map(|i| scheduler.task(i).unwrap());
+    let checkpoint =
+        serde_json::to_string(&(tasks, scheduler.blackboard().get(INCUMBENT))).unwrap();
+    let (tasks, incumbent): ([Prune; 2], BestSoFar<i32>) =
+        serde_json::from_str(&checkpoint).unwrap();
+    assert_eq!(incumbent.peek(), Some(&7));
+
+    let mut board = Blackboard::new();
+    board.insert(INCUMBENT, incumbent);
+    let mut restored = Scheduler::with_blackboard(board);
+    for task in tasks {
+        restored.spawn(task);
+    }
+    let outputs = restored
+        .collect::<cancel_this::Cancellable<Vec<_>>>()
+        .unwrap();
+    assert_eq!(outputs, vec![(0, Some(9)), (1, Some(9))]);
+}
+
+struct ProductStep;
+
+impl ComputationStep<Vec<i32>, (i32, u32), i32> for ProductStep {
+    fn step(inputs: &Vec<i32>, (factor, delay): &mut (i32, u32)) -> Completable<i32> {
+        if *delay > 0 {
+            *delay -= 1;
+            return Err(Incomplete::Suspended);
+        }
+        Ok(inputs.iter().product::<i32>() * *factor)
+    }
+}
+
+#[test]
+fn test_dag_runner_serialization() {
+    type Product = Computation<Vec<i32>, (i32, u32), i32, ProductStep>;
+    let mut dag = DagRunner::<i32, (i32, u32), Product>::new();
+    let a = dag.add_task((2, 0), &[]);
+    let b = dag.add_task((3, 4), &[]);
+    let c = dag.add_task((5, 1), &[a]);
+    dag.add_task((1, 0), &[b, c]);
+    for _ in 0..4 {
+        assert_eq!(dag.try_compute(), Err(Incomplete::Suspended));
+    }
+    assert_eq!(dag.output(a), Some(&2));
+    assert!(dag.is_running(c));
+
+    let serialized = serde_json::to_string(&dag).unwrap();
+    let mut deserialized: DagRunner<i32, (i32, u32), Product> =
+        serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.remaining(), 3);
+    assert_eq!(deserialized.task(c).unwrap().state(), &(5, 0));
+    assert_eq!(deserialized.compute().unwrap(), vec![2, 3, 10, 30]);
+}
+
+#[test]
+fn test_timeline_serialization() {
+    use crate::{StepOutcome, Timeline};
+    use std::time::{Duration, Instant};
+
+    let mut timeline = Timeline::new();
+    timeline.record(
+        1,
+        4,
+        StepOutcome::Cancelled,
+        Instant::now(),
+        Duration::from_micros(3),
+    );
+    let serialized = serde_json::to_string(&timeline).unwrap();
+    let deserialized: Timeline = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.events(), timeline.events());
+    assert_eq!(deserialized.to_json(), timeline.to_json());
+}
+
+#[test]
+fn test_serializable_deadline_checkpoint() {
+    use crate::SerializableDeadline;
+    use std::time::Duration;
+
+    let mut deadline = SerializableDeadline::new(Duration::from_secs(1));
+    deadline.charge(Duration::from_millis(1500));
+    let computation = DeadlineComputation::from_parts(TestContext(10), (TestState(5), deadline));
+    let serialized = serde_json::to_string(&computation).unwrap();
+    let mut deserialized: DeadlineComputation = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.state().1, deadline);
+    // The spent time survives the checkpoint, hence the budget is still exceeded.
+    assert!(matches!(
+        deserialized.try_compute(),
+        Err(Incomplete::ResourceExceeded(_))
+    ));
+    deserialized.state_mut().1.extend(Duration::from_secs(60));
+    assert_eq!(deserialized.compute().unwrap(), 10);
+}
+
+struct DeadlineStep;
+
+impl ComputationStep<TestContext, (TestState, crate::SerializableDeadline), i32> for DeadlineStep {
+    fn step(
+        context: &TestContext,
+        (state, deadline): &mut (TestState, crate::SerializableDeadline),
+    ) -> Completable<i32> {
+        deadline.measure(|| TestComputationStep::step(context, state))
+    }
+}
+
+type DeadlineComputation =
+    Computation<TestContext, (TestState, crate::SerializableDeadline), i32, DeadlineStep>;
+
+#[test]
+fn test_producer_consumer_serialization() {
+    use crate::{BoundedBuffer, ProducerConsumer};
+
+    struct CountStep;
+
+    impl GeneratorStep<u32, u32, u32> for CountStep {
+        fn step(max: &u32, count: &mut u32) -> Completable<Option<u32>> {
+            *count += 1;
+            Ok((*count <= *max).then_some(*count))
+        }
+    }
+
+    struct SumStep;
+
+    impl ComputationStep<(), BoundedBuffer<u32>, u32> for SumStep {
+        fn step(_context: &(), buffer: &mut BoundedBuffer<u32>) -> Completable<u32> {
+            // Wait until all items are produced.
+            if !buffer.is_closed() {
+                return Err(Incomplete::Suspended);
+            }
+            let mut sum = 0;
+            while let Some(item) = buffer.pop()? {
+                sum += item;
+            }
+            Ok(sum)
+        }
+    }
+
+    type Assembly = ProducerConsumer<
+        u32,
+        u32,
+        (),
+        BoundedBuffer<u32>,
+        Generator<u32, u32, u32, CountStep>,
+        Computation<(), BoundedBuffer<u32>, u32, SumStep>,
+    >;
+
+    let producer = Generator::<u32, u32, u32, CountStep>::from_parts(2, 0);
+    let consumer = Computation::from_parts((), BoundedBuffer::new(3));
+    let mut assembly: Assembly = ProducerConsumer::new(producer, consumer);
+    for _ in 0..3 {
+        assert_eq!(assembly.try_compute(), Err(Incomplete::Suspended));
+    }
+    assert_eq!(assembly.buffer_mut().len(), 2);
+
+    let serialized = serde_json::to_string(&assembly).unwrap();
+    let mut deserialized: Assembly = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.buffer_mut(), assembly.buffer_mut());
+    assert_eq!(deserialized.producer().state(), &2);
+    assert_eq!(deserialized.compute(), Ok(3));
+}
+
+#[test]
+fn test_join_serialization() {
+    type Test = Computation<TestContext, TestState, i32, TestComputationStep>;
+    let first = Test::from_parts(TestContext(3), TestState(0));
+    let second = Test::from_parts(TestContext(2), TestState(0));
+    let mut join = Join::new((first, second));
+    assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(join.try_compute(), Err(Incomplete::Suspended));
+    assert_eq!(join.outputs().1, Some(2));
+
+    let serialized = serde_json::to_string(&join).unwrap();
+    let mut restored: Join<(Test, Test), (Option<i32>, Option<i32>)> =
+        serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.compute(), Ok((3, 2)));
+    assert_eq!(join.compute(), Ok((3, 2)));
+}
+
+#[test]
+fn test_phased_serialization() {
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    enum Phase {
+        First,
+        Second,
+    }
+
+    let mut state = Phased::new(Phase::First, vec![1, 2]);
+    let _ = state.advance_to(Phase::Second);
+    state.data_mut().push(3);
+    let _ = state.stay();
+
+    let serialized = serde_json::to_string(&state).unwrap();
+    let deserialized: Phased<Phase, Vec<i32>> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, state);
+    assert_eq!(deserialized.steps_in_phase(), 1);
+}
+
+#[test]
+fn test_sub_computation_serialization() {
+    type Test = Computation<TestContext, TestState, i32, TestComputationStep>;
+    let mut sub = SubComputation::new(Test::from_parts(TestContext(3), TestState(0)));
+    assert_eq!(crate::drive_sub(&mut sub), Err(Incomplete::Suspended));
+
+    let serialized = serde_json::to_string(&sub).unwrap();
+    // The wrapper is transparent.
+    assert_eq!(serialized, serde_json::to_string(sub.inner()).unwrap());
+    let mut restored: SubComputation<Test> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.inner().state(), &TestState(1));
+    assert_eq!(crate::drive_sub(&mut restored), Err(Incomplete::Suspended));
+    assert_eq!(crate::drive_sub(&mut restored), Ok(3));
+}
+
+#[test]
+fn test_worklist_serialization() {
+    use crate::algorithms::{Worklist, WorklistState, WorklistStep};
+
+    /// Explores `x -> x + 1, x -> x * 2` below the limit, summing the nodes.
+    struct Explore;
+
+    impl WorklistStep<u32, u64, u32> for Explore {
+        fn process(node: &u32, limit: &u32, sum: &mut u64) -> Vec<u32> {
+            *sum += u64::from(*node);
+            [node + 1, node * 2]
+                .into_iter()
+                .filter(|it| it < limit)
+                .collect()
+        }
+    }
+
+    type Test = Worklist<u32, u64, u32, Explore>;
+    let mut search = Test::from_parts(20, WorklistState::new(0, [1]));
+    for _ in 0..5 {
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    let serialized = serde_json::to_string(&search).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state(), search.state());
+    assert_eq!(restored.compute(), Ok((1..20).sum()));
+}
+
+#[test]
+fn test_bisection_serialization() {
+    use crate::algorithms::{Bisection, BisectionState, BisectionStep};
+
+    /// Checks `point >= threshold`, counting up to the point one step at a time.
+    struct CountCheckStep;
+
+    impl ComputationStep<(i32, i32), i32, bool> for CountCheckStep {
+        fn step(&(point, threshold): &(i32, i32), count: &mut i32) -> Completable<bool> {
+            if *count >= point {
+                return Ok(point >= threshold);
+            }
+            *count += 1;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    type Check = Computation<(i32, i32), i32, bool, CountCheckStep>;
+
+    struct Threshold;
+
+    impl BisectionStep<i32, i32> for Threshold {
+        type Evaluation = Check;
+
+        fn evaluate(threshold: &i32, point: i32) -> Check {
+            Check::from_parts((point, *threshold), 0)
+        }
+    }
+
+    type Test = Bisection<i32, i32, Threshold>;
+    let mut search = Test::from_parts(11, BisectionState::new(0, 16));
+    for _ in 0..3 {
+        assert_eq!(search.try_compute(), Err(Incomplete::Suspended));
+    }
+    assert!(search.state().evaluation().is_running());
+
+    let serialized = serde_json::to_string(&search).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state(), search.state());
+    assert_eq!(restored.compute(), Ok((10, 11)));
+}
+
+#[test]
+fn test_map_reduce_serialization() {
+    use crate::algorithms::{MapReduce, MapReduceState, MapReduceStep};
+
+    /// Sums a chunk of numbers, one number per step.
+    struct Sum;
+
+    impl ComputationStep<Vec<i32>, (usize, i32), i32> for Sum {
+        fn step(items: &Vec<i32>, (index, sum): &mut (usize, i32)) -> Completable<i32> {
+            let Some(item) = items.get(*index) else {
+                return Ok(*sum);
+            };
+            *index += 1;
+            *sum += item;
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    impl MapReduceStep<Vec<i32>, (usize, i32), i32> for Sum {
+        fn start(_chunk: &Vec<i32>) -> (usize, i32) {
+            (0, 0)
+        }
+
+        fn reduce(left: i32, right: i32) -> i32 {
+            left + right
+        }
+    }
+
+    type Test = MapReduce<Vec<i32>, (usize, i32), i32, Sum>;
+    let chunks = vec![vec![1, 2], vec![3, 4, 5]];
+    let mut test = Test::from_parts(chunks, MapReduceState::new());
+    for _ in 0..5 {
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+    }
+    assert_eq!(test.state().reduced(), Some(&3));
+
+    let serialized = serde_json::to_string(&test).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state(), test.state());
+    assert_eq!(restored.compute(), Ok(Some(15)));
+}
+
+#[test]
+fn test_fixed_point_serialization() {
+    use crate::algorithms::{FixedPoint, FixedPointOutcome, FixedPointState, FixedPointStep};
+
+    /// Moves the state one unit towards the context.
+    struct Approach;
+
+    impl FixedPointStep<i32, i32> for Approach {
+        fn iterate(target: &i32, x: &mut i32) -> f64 {
+            let delta = (*target - *x).signum();
+            *x += delta;
+            f64::from(delta.abs())
+        }
+    }
+
+    type Test = FixedPoint<i32, i32, Approach>;
+    let state = FixedPointState::new(0, 0.0).with_max_iterations(10);
+    let mut test = Test::from_parts(3, state);
+    for _ in 0..2 {
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    let serialized = serde_json::to_string(&test).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state(), test.state());
+    assert_eq!(restored.compute(), Ok((3, FixedPointOutcome::Converged)));
+    assert_eq!(restored.state().iterations(), 4);
+}
+
+#[test]
+fn test_local_search_serialization() {
+    use crate::algorithms::{LocalSearch, LocalSearchState, LocalSearchStep};
+
+    /// Minimizes the distance to the context, moving by one in a random direction.
+    struct Distance;
+
+    impl LocalSearchStep<i64, i64> for Distance {
+        fn neighbor(_target: &i64, x: &i64, random: u64) -> i64 {
+            if random.is_multiple_of(2) {
+                x + 1
+            } else {
+                x - 1
+            }
+        }
+
+        fn cost(target: &i64, x: &i64) -> f64 {
+            (x - target).abs() as f64
+        }
+
+        fn temperature(_target: &i64, iteration: u64) -> f64 {
+            1.0 / (iteration + 1) as f64
+        }
+    }
+
+    type Test = LocalSearch<i64, i64, Distance>;
+    let mut test = Test::from_parts(3, LocalSearchState::new(0, 50).with_seed(11));
+    for _ in 0..5 {
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    // The restored search makes exactly the same random choices.
+    let serialized = serde_json::to_string(&test).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state(), test.state());
+    assert_eq!(restored.compute(), test.compute());
+    assert_eq!(restored.state(), test.state());
+}
+
+#[test]
+fn test_branch_and_bound_serialization() {
+    use crate::algorithms::{BranchAndBound, BranchAndBoundState, BranchAndBoundStep};
+
+    /// Maximizes the sum of a path in a complete binary tree of the given depth, where
+    /// the left child adds one and the right child adds two (the node is (depth, sum)).
+    struct Path;
+
+    impl BranchAndBoundStep<u32, (u32, u32), u32> for Path {
+        fn bound(depth: &u32, &(level, sum): &(u32, u32)) -> u32 {
+            sum + 2 * (depth - level)
+        }
+
+        fn evaluate(depth: &u32, &(level, sum): &(u32, u32)) -> Option<u32> {
+            (level == *depth).then_some(sum)
+        }
+
+        fn branch(depth: &u32, &(level, sum): &(u32, u32)) -> Vec<(u32, u32)> {
+            if level == *depth {
+                Vec::new()
+            } else {
+                vec![(level + 1, sum + 1), (level + 1, sum + 2)]
+            }
+        }
+    }
+
+    type Test = BranchAndBound<u32, (u32, u32), u32, Path>;
+    let mut test = Test::from_parts(4, BranchAndBoundState::new([(0, 0)]));
+    for _ in 0..2 {
+        assert_eq!(test.try_compute(), Err(Incomplete::Suspended));
+    }
+
+    let serialized = serde_json::to_string(&test).unwrap();
+    let mut restored: Test = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.state().open(), test.state().open());
+    assert_eq!(restored.compute(), Ok(Some(((4, 8), 8))));
+    assert_eq!(test.compute(), Ok(Some(((4, 8), 8))));
+    assert_eq!(restored.state().expanded(), test.state().expanded());
+}
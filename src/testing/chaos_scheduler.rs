@@ -0,0 +1,266 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, Computable, DynComputable, Generatable, Incomplete};
+use cancel_this::{CancelAtomic, Cancellable, Cancelled, on_trigger};
+use std::marker::PhantomData;
+
+/// The cause of cancellations injected by a [`ChaosScheduler`].
+pub const CHAOS_CANCELLED: &str = "ChaosCancelled";
+
+/// A [`Generatable`] that interleaves a set of computations in a (seeded) random order,
+/// producing the index and output of each computation as soon as it completes.
+///
+/// Every call to [`Generatable::try_next`] advances one randomly chosen pending computation
+/// by a single step. With the probability given by [`ChaosScheduler::with_cancellation`],
+/// the step is performed while cancellation is triggered, such that the computation observes
+/// a (spurious) cancellation. Such cancellation is reported as [`Incomplete::Cancelled`] with
+/// [`CHAOS_CANCELLED`] as the cause, and the computation is kept, such that it can be resumed
+/// later.
+///
+/// This is intended for testing cooperative algorithms which should not depend on the order
+/// in which their computations are advanced. The random choices depend only on the seed,
+/// hence a failing interleaving can be reproduced using the same seed (see
+/// [`ChaosScheduler::history`] for the sequence of advanced computations).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::testing::ChaosScheduler;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+///
+/// struct CountStep;
+///
+/// impl ComputationStep<u32, u32, u32> for CountStep {
+///     fn step(target: &u32, count: &mut u32) -> Completable<u32> {
+///         *count += 1;
+///         if *count >= *target { Ok(*count) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// type Count = Computation<u32, u32, u32, CountStep>;
+///
+/// for seed in 0..10 {
+///     let mut scheduler = ChaosScheduler::new(seed).with_cancellation(0.2);
+///     for target in [3, 5, 7] {
+///         scheduler.spawn(Count::from_parts(target, 0).dyn_computable());
+///     }
+///     let mut outputs = scheduler.run().unwrap();
+///     outputs.sort();
+///     assert_eq!(outputs, vec![(0, 3), (1, 5), (2, 7)]);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ChaosScheduler<T, C = DynComputable<T>>
+where
+    C: Computable<T>,
+{
+    tasks: Vec<Option<C>>,
+    seed: u64,
+    random: SplitMix64,
+    cancellation: f64,
+    history: Vec<usize>,
+    injected: u64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Computable<T>> ChaosScheduler<T, C> {
+    /// Create a new empty scheduler whose random choices are determined by the given `seed`.
+    pub fn new(seed: u64) -> Self {
+        ChaosScheduler {
+            tasks: Vec::new(),
+            seed,
+            random: SplitMix64(seed),
+            cancellation: 0.0,
+            history: Vec::new(),
+            injected: 0,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the `probability` (between `0.0` and `1.0`) that a step is performed with
+    /// injected cancellation.
+    pub fn with_cancellation(mut self, probability: f64) -> Self {
+        self.cancellation = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Add a new computation to the scheduler, returning its index.
+    pub fn spawn(&mut self, computation: C) -> usize {
+        self.tasks.push(Some(computation));
+        self.tasks.len() - 1
+    }
+
+    /// The seed of this scheduler.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The number of computations that are not finished yet.
+    pub fn pending(&self) -> usize {
+        self.tasks.iter().filter(|it| it.is_some()).count()
+    }
+
+    /// The indices of the computations in the order in which they were advanced.
+    pub fn history(&self) -> &[usize] {
+        &self.history
+    }
+
+    /// The number of steps that were performed with injected cancellation.
+    pub fn injected(&self) -> u64 {
+        self.injected
+    }
+
+    /// Run all computations to completion, retrying the steps interrupted by injected
+    /// cancellation. Returns the index and output of every computation in the order
+    /// of completion.
+    ///
+    /// Cancellation that was not injected by this scheduler stops the run.
+    pub fn run(&mut self) -> Cancellable<Vec<(usize, T)>> {
+        let mut outputs = Vec::new();
+        while let Some(result) = self.try_next() {
+            match result {
+                Ok(output) => outputs.push(output),
+                Err(Incomplete::Cancelled(c)) if c.cause() == CHAOS_CANCELLED => continue,
+                Err(Incomplete::Cancelled(c)) => return Err(c),
+                Err(Incomplete::ResourceExceeded(e)) => return Err(e.into()),
+                Err(_) => continue,
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+impl<T, C: Computable<T>> Iterator for ChaosScheduler<T, C> {
+    type Item = Cancellable<(usize, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, C: Computable<T>> Generatable<(usize, T)> for ChaosScheduler<T, C> {
+    fn try_next(&mut self) -> Option<Completable<(usize, T)>> {
+        let pending = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, it)| it.is_some())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            return None;
+        }
+        let index = pending[self.random.below(pending.len())];
+        let inject = self.random.unit() < self.cancellation;
+        self.history.push(index);
+        let task = self.tasks[index].as_mut().expect("Task is pending.");
+        let result = if inject {
+            self.injected += 1;
+            let trigger = CancelAtomic::new();
+            trigger.cancel();
+            on_trigger(trigger, || Ok::<_, Cancelled>(task.try_compute()))
+                .expect("Step results are never cancelled.")
+        } else {
+            task.try_compute()
+        };
+        match result {
+            Ok(output) => {
+                self.tasks[index] = None;
+                Some(Ok((index, output)))
+            }
+            Err(Incomplete::Exhausted) => {
+                self.tasks[index] = None;
+                Some(Err(Incomplete::Suspended))
+            }
+            Err(Incomplete::Cancelled(_)) if inject => {
+                Some(Err(Incomplete::Cancelled(Cancelled::new(CHAOS_CANCELLED))))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A small seeded pseudo-random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// A random number in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, test_fixtures::Count};
+
+    fn scheduler(seed: u64, cancellation: f64) -> ChaosScheduler<u32, Count> {
+        let mut scheduler = ChaosScheduler::new(seed).with_cancellation(cancellation);
+        for target in [2, 4, 6] {
+            scheduler.spawn(Count::from_parts(target, 0));
+        }
+        scheduler
+    }
+
+    #[test]
+    fn test_seeded_reproduction() {
+        let mut first = scheduler(7, 0.3);
+        let mut second = scheduler(7, 0.3);
+        assert_eq!(first.run(), second.run());
+        assert_eq!(first.history(), second.history());
+        assert_eq!(first.injected(), second.injected());
+        assert_eq!(first.seed(), 7);
+        assert_eq!(first.pending(), 0);
+        // Every task is advanced once per step, plus once per injected cancellation.
+        assert_eq!(first.history().len() as u64, 12 + first.injected());
+
+        let orders = (0..20)
+            .map(|seed| {
+                let mut scheduler = scheduler(seed, 0.0);
+                scheduler.run().unwrap();
+                scheduler.history().to_vec()
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert!(orders.len() > 1);
+    }
+
+    #[test]
+    fn test_injected_cancellation() {
+        let mut scheduler = scheduler(3, 1.0);
+        let result = scheduler.try_next().unwrap();
+        let Err(Incomplete::Cancelled(cancelled)) = result else {
+            panic!("Unexpected result: {result:?}");
+        };
+        assert_eq!(cancelled.cause(), CHAOS_CANCELLED);
+        assert_eq!(scheduler.pending(), 3);
+        assert_eq!(scheduler.injected(), 1);
+        // The iterator reports injected cancellation as an error item.
+        assert!(scheduler.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_external_cancellation_stops_run() {
+        let mut scheduler = scheduler(1, 0.0);
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(scheduler.run())).unwrap();
+        assert!(result.is_err());
+        assert_eq!(scheduler.pending(), 3);
+        assert_eq!(scheduler.run().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, Computable, Generatable, Incomplete};
+use cancel_this::{Cancellable, Cancelled};
+use std::collections::VecDeque;
+
+/// The cause of cancellation reported by [`MockStep::Cancel`].
+pub const MOCK_CANCELLED: &str = "MockCancelled";
+
+/// A single scripted outcome of a [`MockComputable`] or [`MockGeneratable`].
+///
+/// The variants can be imported using `use MockStep::*` to obtain a compact notation
+/// for scripts (e.g., `[Suspend, Suspend, Ok(5)]`). Any [`Completable`] value can be
+/// used in a script as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockStep<T> {
+    /// Return [`Incomplete::Suspended`].
+    Suspend,
+    /// Return [`Incomplete::Cancelled`] with [`MOCK_CANCELLED`] as the cause.
+    Cancel,
+    /// Return [`Incomplete::Exhausted`].
+    Exhaust,
+    /// Return the value.
+    Ok(T),
+}
+
+impl<T> From<MockStep<T>> for Completable<T> {
+    fn from(value: MockStep<T>) -> Self {
+        match value {
+            MockStep::Suspend => Err(Incomplete::Suspended),
+            MockStep::Cancel => Err(Incomplete::Cancelled(Cancelled::new(MOCK_CANCELLED))),
+            MockStep::Exhaust => Err(Incomplete::Exhausted),
+            MockStep::Ok(value) => Ok(value),
+        }
+    }
+}
+
+/// A [`Computable`] that replays a scripted sequence of outcomes, one per call to
+/// [`Computable::try_compute`]. Once the script is finished, every call returns
+/// [`Incomplete::Exhausted`].
+///
+/// This allows testing drivers, schedulers and combinators without implementing
+/// a dedicated step type.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::Incomplete;
+/// use computation_process::testing::{MockComputable, MockStep::*};
+///
+/// let mut mock = MockComputable::script([Suspend, Suspend, Ok(5)]);
+/// assert_eq!(mock.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(mock.compute().unwrap(), 5);
+/// assert_eq!(mock.calls(), 3);
+/// assert_eq!(mock.try_compute(), Err(Incomplete::Exhausted));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockComputable<T> {
+    script: VecDeque<Completable<T>>,
+    calls: usize,
+}
+
+impl<T> MockComputable<T> {
+    /// Create a computation that replays the given outcomes.
+    pub fn script<S: Into<Completable<T>>, I: IntoIterator<Item = S>>(script: I) -> Self {
+        MockComputable {
+            script: script.into_iter().map(Into::into).collect(),
+            calls: 0,
+        }
+    }
+
+    /// The number of calls to [`Computable::try_compute`] so far.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// The number of outcomes that were not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl<T> Computable<T> for MockComputable<T> {
+    fn try_compute(&mut self) -> Completable<T> {
+        self.calls += 1;
+        self.script
+            .pop_front()
+            .unwrap_or(Err(Incomplete::Exhausted))
+    }
+}
+
+/// A [`Generatable`] that replays a scripted sequence of outcomes, one per call to
+/// [`Generatable::try_next`]. Once the script is finished, the generator is exhausted
+/// (i.e., it returns `None`).
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::Incomplete;
+/// use computation_process::testing::{MockGeneratable, MockStep};
+///
+/// let mut mock = {
+///     use MockStep::*;
+///     MockGeneratable::script([Ok(1), Suspend, Ok(2), Cancel, Ok(3)])
+/// };
+/// assert_eq!(mock.try_next(), Some(Ok(1)));
+/// assert_eq!(mock.try_next(), Some(Err(Incomplete::Suspended)));
+/// assert_eq!(mock.next().unwrap(), Ok(2));
+/// assert!(mock.next().unwrap().is_err());
+/// assert_eq!(mock.next().unwrap(), Ok(3));
+/// assert_eq!(mock.next(), None);
+/// assert_eq!(mock.calls(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockGeneratable<T> {
+    script: VecDeque<Completable<T>>,
+    calls: usize,
+}
+
+impl<T> MockGeneratable<T> {
+    /// Create a generator that replays the given outcomes.
+    pub fn script<S: Into<Completable<T>>, I: IntoIterator<Item = S>>(script: I) -> Self {
+        MockGeneratable {
+            script: script.into_iter().map(Into::into).collect(),
+            calls: 0,
+        }
+    }
+
+    /// The number of calls to [`Generatable::try_next`] so far.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// The number of outcomes that were not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl<T> Iterator for MockGeneratable<T> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T> Generatable<T> for MockGeneratable<T> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        self.calls += 1;
+        self.script.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockStep::*;
+    use super::*;
+    use crate::Scheduler;
+
+    #[test]
+    fn test_computable_script() {
+        let mut mock = MockComputable::script([Suspend, Cancel, Exhaust, Ok("done")]);
+        assert_eq!(mock.remaining(), 4);
+        assert_eq!(mock.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(mock.compute().unwrap_err().cause(), MOCK_CANCELLED);
+        assert_eq!(mock.try_compute(), Err(Incomplete::Exhausted));
+        assert_eq!(mock.try_compute(), Result::Ok("done"));
+        assert_eq!(mock.remaining(), 0);
+        assert_eq!(mock.calls(), 4);
+    }
+
+    #[test]
+    fn test_raw_completable_script() {
+        let mut mock = MockComputable::script([Err(Incomplete::Suspended), Result::Ok(1)]);
+        assert_eq!(mock.compute(), Result::Ok(1));
+    }
+
+    #[test]
+    fn test_drives_scheduler() {
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(MockComputable::script([Suspend, Suspend, Ok(1)]).dyn_computable());
+        scheduler.spawn(MockComputable::script([Ok(2)]).dyn_computable());
+        scheduler.spawn(MockComputable::script([Suspend, Exhaust]).dyn_computable());
+        let outputs = scheduler.by_ref().collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(outputs, vec![(1, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn test_generatable_script() {
+        let mock = MockGeneratable::script([Ok(1), Suspend, Suspend, Ok(2)]);
+        assert_eq!(mock.remaining(), 4);
+        let items = mock.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![1, 2]);
+    }
+}
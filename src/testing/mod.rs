@@ -0,0 +1,220 @@
+//! Helpers for testing implementations of [`Computable`] and [`Generatable`].
+//!
+//! The core correctness contract of this crate is that a computation can be saved at any
+//! suspend point and later restored without changing its outcome. The functions in this
+//! module check this contract: they run a computation once without interruption, and once
+//! with a serialization round-trip (using `serde_json`) at every suspend point, and then
+//! assert that both runs produced the same result.
+//!
+//! Furthermore, [`ChaosScheduler`] interleaves a set of computations in a seeded random order
+//! (optionally injecting cancellation), which helps to discover hidden ordering assumptions
+//! of cooperative algorithms. Finally, [`MockComputable`] and [`MockGeneratable`] replay
+//! a scripted sequence of outcomes, which is useful for testing drivers and combinators.
+//!
+//! The module requires the `testing` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use computation_process::prelude::*;
+//! use computation_process::testing::assert_resume_equivalent;
+//! use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+//!
+//! struct SumStep;
+//!
+//! impl ComputationStep<Vec<u64>, (usize, u64), u64> for SumStep {
+//!     fn step(items: &Vec<u64>, (index, sum): &mut (usize, u64)) -> Completable<u64> {
+//!         let Some(item) = items.get(*index) else {
+//!             return Ok(*sum);
+//!         };
+//!         *index += 1;
+//!         *sum += item;
+//!         Err(Incomplete::Suspended)
+//!     }
+//! }
+//!
+//! type Sum = Computation<Vec<u64>, (usize, u64), u64, SumStep>;
+//!
+//! let result = assert_resume_equivalent(|| Sum::from_parts(vec![1, 2, 3], (0, 0)));
+//! assert_eq!(result, Ok(6));
+//! ```
+
+use crate::{Completable, Computable, Generatable, Incomplete};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+mod chaos_scheduler;
+mod mock;
+
+pub use chaos_scheduler::{CHAOS_CANCELLED, ChaosScheduler};
+pub use mock::{MOCK_CANCELLED, MockComputable, MockGeneratable, MockStep};
+
+/// Serialize the `value` into JSON and deserialize it back.
+///
+/// # Panics
+///
+/// Panics if the value cannot be serialized or deserialized.
+pub fn serde_round_trip<S: Serialize + DeserializeOwned>(value: &S) -> S {
+    let json =
+        serde_json::to_string(value).unwrap_or_else(|e| panic!("Cannot serialize value: {e}"));
+    serde_json::from_str(&json)
+        .unwrap_or_else(|e| panic!("Cannot deserialize value: {e}\nSerialized value: {json}"))
+}
+
+/// Assert that the computation created by `make_computation` has the same outcome
+/// (and the same number of steps) when it runs straight to completion and when it is
+/// serialized and deserialized at every suspend point. Returns the outcome.
+///
+/// The outcome is the first result of [`Computable::try_compute`] that is not
+/// [`Incomplete::Suspended`], hence computations that are cancelled (or exhausted)
+/// can be tested as well.
+///
+/// # Panics
+///
+/// Panics if the outcomes differ or if the serialization fails. Loops forever if
+/// the computation never completes.
+pub fn assert_resume_equivalent<T, C, F>(make_computation: F) -> Completable<T>
+where
+    T: PartialEq + Debug,
+    C: Computable<T> + Serialize + DeserializeOwned,
+    F: Fn() -> C,
+{
+    let (expected, expected_steps) = run_computation(make_computation(), |c| c);
+    let (actual, actual_steps) = run_computation(make_computation(), |c| serde_round_trip(&c));
+    assert_eq!(
+        expected, actual,
+        "The outcome changed when the computation was resumed from serialized states."
+    );
+    assert_eq!(
+        expected_steps, actual_steps,
+        "The number of steps changed when the computation was resumed from serialized states."
+    );
+    expected
+}
+
+/// Assert that the generator created by `make_generator` produces the same items (and ends
+/// with the same outcome) when it runs straight to exhaustion and when it is serialized and
+/// deserialized at every suspend point and after every item. Returns the items and the final
+/// outcome (`Ok(())` if the generator was exhausted, otherwise the interrupting
+/// [`Incomplete`] value).
+///
+/// # Panics
+///
+/// Panics if the items or outcomes differ or if the serialization fails. Loops forever if
+/// the generator is infinite.
+pub fn assert_generator_resume_equivalent<T, G, F>(make_generator: F) -> (Vec<T>, Completable<()>)
+where
+    T: PartialEq + Debug,
+    G: Generatable<T> + Serialize + DeserializeOwned,
+    F: Fn() -> G,
+{
+    let expected = run_generator(make_generator(), |g| g);
+    let actual = run_generator(make_generator(), |g| serde_round_trip(&g));
+    assert_eq!(
+        expected.0, actual.0,
+        "The items changed when the generator was resumed from serialized states."
+    );
+    assert_eq!(
+        expected.1, actual.1,
+        "The outcome changed when the generator was resumed from serialized states."
+    );
+    expected
+}
+
+fn run_computation<T, C: Computable<T>>(
+    mut computation: C,
+    resume: impl Fn(C) -> C,
+) -> (Completable<T>, usize) {
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        match computation.try_compute() {
+            Err(Incomplete::Suspended) => computation = resume(computation),
+            result => return (result, steps),
+        }
+    }
+}
+
+fn run_generator<T, G: Generatable<T>>(
+    mut generator: G,
+    resume: impl Fn(G) -> G,
+) -> (Vec<T>, Completable<()>) {
+    let mut items = Vec::new();
+    loop {
+        match generator.try_next() {
+            None => return (items, Ok(())),
+            Some(Ok(item)) => items.push(item),
+            Some(Err(Incomplete::Suspended)) => (),
+            Some(Err(e)) => return (items, Err(e)),
+        }
+        generator = resume(generator);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Generator, GeneratorStep, test_fixtures::Count};
+    use serde::{Deserialize, Serialize};
+    use std::cell::Cell;
+
+    #[test]
+    fn test_equivalent_computation() {
+        assert_eq!(assert_resume_equivalent(|| Count::from_parts(4, 0)), Ok(4));
+        assert_eq!(serde_round_trip(&vec![1, 2]), vec![1, 2]);
+    }
+
+    /// A computation that (incorrectly) does not serialize part of its state.
+    #[derive(Serialize, Deserialize)]
+    struct Forgetful {
+        done: u32,
+        #[serde(skip)]
+        pending: u32,
+    }
+
+    impl Computable<u32> for Forgetful {
+        fn try_compute(&mut self) -> Completable<u32> {
+            self.done += 1;
+            self.pending += 1;
+            if self.done >= 3 {
+                Ok(self.pending)
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The outcome changed")]
+    fn test_detects_lost_state() {
+        let _ = assert_resume_equivalent(|| Forgetful {
+            done: 0,
+            pending: 0,
+        });
+    }
+
+    struct RangeStep;
+
+    impl GeneratorStep<u32, u32, u32> for RangeStep {
+        fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+            *current += 1;
+            if current.is_multiple_of(2) {
+                return Err(Incomplete::Suspended);
+            }
+            Ok((*current <= *max).then_some(*current))
+        }
+    }
+
+    #[test]
+    fn test_equivalent_generator() {
+        let made = Cell::new(0);
+        let (items, outcome) = assert_generator_resume_equivalent(|| {
+            made.set(made.get() + 1);
+            Generator::<u32, u32, u32, RangeStep>::from_parts(7, 0)
+        });
+        assert_eq!(items, vec![1, 3, 5, 7]);
+        assert_eq!(outcome, Ok(()));
+        assert_eq!(made.get(), 2);
+    }
+}
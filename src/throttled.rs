@@ -0,0 +1,189 @@
+use crate::{Completable, Computable, Generatable, suspend_for};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// A [`Computable`] (or [`Generatable`]) that enforces a minimum wall-clock `interval`
+/// between the steps of another computation.
+///
+/// If the wrapper is polled before the interval since the previous inner step has elapsed,
+/// it returns [`crate::Incomplete::Suspended`] without advancing the inner computation,
+/// together with a retry hint (see [`crate::suspend_for`]). This is useful when
+/// the computation polls an external resource (files, sockets) and must be rate-limited
+/// within an interleaved schedule (e.g., in a [`crate::Scheduler`]). The first step
+/// is performed immediately.
+///
+/// Keep in mind that blocking APIs (e.g., [`Computable::compute`]) repeatedly poll the
+/// wrapper while waiting, i.e., they do not sleep.
+///
+/// See also [`crate::ComputableExt::throttled`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, Incomplete};
+/// use std::time::Duration;
+///
+/// struct PollStep;
+///
+/// impl ComputationStep<u32, u32, u32> for PollStep {
+///     fn step(target: &u32, polls: &mut u32) -> Completable<u32> {
+///         *polls += 1;
+///         if *polls >= *target { Ok(*polls) } else { Err(Incomplete::Suspended) }
+///     }
+/// }
+///
+/// let computation = Computation::<u32, u32, u32, PollStep>::from_parts(3, 0);
+/// let mut throttled = computation.throttled(Duration::from_secs(60));
+/// assert_eq!(throttled.try_compute(), Err(Incomplete::Suspended));
+/// // The interval did not elapse yet, hence the computation is not advanced.
+/// assert_eq!(throttled.try_compute(), Err(Incomplete::Suspended));
+/// assert_eq!(*throttled.inner().state(), 1);
+/// assert!(throttled.time_until_ready() > Duration::ZERO);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Throttled<T, C> {
+    inner: C,
+    interval: Duration,
+    last_step: Option<Instant>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C> Throttled<T, C> {
+    /// Wrap the `inner` computation (or generator) such that at most one inner step
+    /// is performed per `interval`.
+    pub fn new(inner: C, interval: Duration) -> Self {
+        Throttled {
+            inner,
+            interval,
+            last_step: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The minimal interval between two inner steps.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Change the minimal interval between two inner steps (applies to the next step).
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// The time remaining until the next inner step can be performed
+    /// (zero if it can be performed now).
+    pub fn time_until_ready(&self) -> Duration {
+        self.last_step
+            .map(|last| self.interval.saturating_sub(last.elapsed()))
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Mutable access to the inner computation.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Record the step if the next inner step can be performed now, otherwise return
+    /// the remaining waiting time.
+    fn acquire(&mut self) -> Result<(), Duration> {
+        let wait = self.time_until_ready();
+        if wait > Duration::ZERO {
+            return Err(wait);
+        }
+        self.last_step = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl<T, C: Computable<T>> Computable<T> for Throttled<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        if let Err(wait) = self.acquire() {
+            return Err(suspend_for(wait));
+        }
+        self.inner.try_compute()
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for Throttled<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for Throttled<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        if let Err(wait) = self.acquire() {
+            return Some(Err(suspend_for(wait)));
+        }
+        self.inner.try_next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ComputableExt, FromParts, Generator, GeneratorStep, Incomplete, StatefulRef,
+        take_retry_hint, test_fixtures::Count,
+    };
+
+    #[test]
+    fn test_steps_are_rate_limited() {
+        let mut throttled = Count::from_parts(3, 0).throttled(Duration::from_millis(5));
+        assert_eq!(throttled.time_until_ready(), Duration::ZERO);
+        let started = Instant::now();
+        assert_eq!(throttled.compute(), Ok(3));
+        // Two intervals must elapse between the three steps.
+        assert!(started.elapsed() >= Duration::from_millis(10));
+        assert_eq!(throttled.interval(), Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_skipped_polls_do_not_advance() {
+        let mut throttled = Throttled::new(Count::from_parts(10, 0), Duration::from_secs(60));
+        assert_eq!(throttled.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(take_retry_hint(), None);
+        for _ in 0..5 {
+            assert_eq!(throttled.try_compute(), Err(Incomplete::Suspended));
+            assert!(take_retry_hint().unwrap() > Duration::from_secs(59));
+        }
+        assert_eq!(*throttled.inner().state(), 1);
+        throttled.set_interval(Duration::ZERO);
+        assert_eq!(throttled.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*throttled.into_inner().state(), 2);
+    }
+
+    struct RangeStep;
+
+    impl GeneratorStep<u32, u32, u32> for RangeStep {
+        fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+            *current += 1;
+            Ok((*current <= *max).then_some(*current))
+        }
+    }
+
+    #[test]
+    fn test_throttled_generator() {
+        let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(2, 0);
+        let mut throttled = Throttled::new(generator, Duration::from_secs(60));
+        assert_eq!(throttled.try_next(), Some(Ok(1)));
+        assert_eq!(throttled.try_next(), Some(Err(Incomplete::Suspended)));
+        throttled.set_interval(Duration::from_millis(1));
+        let items = throttled.collect::<Cancellable<Vec<_>>>().unwrap();
+        assert_eq!(items, vec![2]);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{Completable, Incomplete};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// The outcome of a single step of a computation, as recorded in a [`Timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepOutcome {
+    /// The step produced the output of the computation.
+    Completed,
+    /// The step returned [`Incomplete::Suspended`].
+    Suspended,
+    /// The step returned [`Incomplete::Exhausted`].
+    Exhausted,
+    /// The step returned [`Incomplete::Cancelled`].
+    Cancelled,
+    /// The step returned [`Incomplete::ResourceExceeded`].
+    ResourceExceeded,
+}
+
+impl StepOutcome {
+    /// The outcome corresponding to the `result` of a step.
+    pub fn of<T>(result: &Completable<T>) -> Self {
+        match result {
+            Ok(_) => StepOutcome::Completed,
+            Err(Incomplete::Suspended) => StepOutcome::Suspended,
+            Err(Incomplete::Exhausted) => StepOutcome::Exhausted,
+            Err(Incomplete::Cancelled(_)) => StepOutcome::Cancelled,
+            Err(Incomplete::ResourceExceeded(_)) => StepOutcome::ResourceExceeded,
+        }
+    }
+
+    /// The name of the outcome (as used in exported timelines).
+    pub fn name(&self) -> &'static str {
+        match self {
+            StepOutcome::Completed => "Completed",
+            StepOutcome::Suspended => "Suspended",
+            StepOutcome::Exhausted => "Exhausted",
+            StepOutcome::Cancelled => "Cancelled",
+            StepOutcome::ResourceExceeded => "ResourceExceeded",
+        }
+    }
+}
+
+/// A single step recorded in a [`Timeline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimelineEvent {
+    /// The index of the task that performed the step.
+    pub task: usize,
+    /// The index of the step within the task (starting from zero).
+    pub step: u64,
+    /// The outcome of the step.
+    pub outcome: StepOutcome,
+    /// The start of the step, relative to the creation of the timeline.
+    pub start: Duration,
+    /// The duration of the step.
+    pub duration: Duration,
+}
+
+/// A record of the steps performed by a set of interleaved computations
+/// (see [`crate::Scheduler::enable_timeline`]).
+///
+/// The timeline can be exported as plain JSON ([`Timeline::to_json`]) or in the trace event
+/// format ([`Timeline::to_chrome_trace`]) understood by `chrome://tracing` or
+/// [Perfetto](https://ui.perfetto.dev), which visualizes how the computations shared
+/// the thread.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{StepOutcome, Timeline};
+/// use std::time::{Duration, Instant};
+///
+/// let mut timeline = Timeline::new();
+/// timeline.record(0, 0, StepOutcome::Suspended, Instant::now(), Duration::from_micros(5));
+/// timeline.record(1, 0, StepOutcome::Completed, Instant::now(), Duration::from_micros(3));
+/// assert_eq!(timeline.len(), 2);
+/// assert_eq!(timeline.busy_time(0), Duration::from_micros(5));
+/// assert!(timeline.to_json().contains(r#""outcome":"Completed""#));
+/// assert!(timeline.to_chrome_trace().starts_with(r#"{"traceEvents":["#));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeline {
+    #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
+    origin: Instant,
+    events: Vec<TimelineEvent>,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Timeline::new()
+    }
+}
+
+impl Timeline {
+    /// Create a new empty timeline that starts now.
+    pub fn new() -> Self {
+        Timeline {
+            origin: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Record a step of `task` which started at `started` and took `duration`.
+    pub fn record(
+        &mut self,
+        task: usize,
+        step: u64,
+        outcome: StepOutcome,
+        started: Instant,
+        duration: Duration,
+    ) {
+        self.events.push(TimelineEvent {
+            task,
+            step,
+            outcome,
+            start: started.saturating_duration_since(self.origin),
+            duration,
+        });
+    }
+
+    /// The recorded steps (in the order in which they were recorded).
+    pub fn events(&self) -> &[TimelineEvent] {
+        &self.events
+    }
+
+    /// The number of recorded steps.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no step was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The total duration of the recorded steps of `task`.
+    pub fn busy_time(&self, task: usize) -> Duration {
+        self.events
+            .iter()
+            .filter(|it| it.task == task)
+            .map(|it| it.duration)
+            .sum()
+    }
+
+    /// Export the timeline as a JSON array of events. Times are in nanoseconds.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                r#"{{"task":{},"step":{},"outcome":"{}","start_ns":{},"duration_ns":{}}}"#,
+                event.task,
+                event.step,
+                event.outcome.name(),
+                event.start.as_nanos(),
+                event.duration.as_nanos()
+            )
+            .expect("Writing to a string cannot fail.");
+        }
+        json.push(']');
+        json
+    }
+
+    /// Export the timeline in the Chrome trace event format (as "complete" events
+    /// on a single thread, one per step).
+    pub fn to_chrome_trace(&self) -> String {
+        let mut json = String::from(r#"{"traceEvents":["#);
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                r#"{{"name":"task {}","cat":"{}","ph":"X","ts":{:.3},"dur":{:.3},"pid":0,"tid":0,"args":{{"task":{},"step":{}}}}}"#,
+                event.task,
+                event.outcome.name(),
+                event.start.as_secs_f64() * 1e6,
+                event.duration.as_secs_f64() * 1e6,
+                event.task,
+                event.step
+            )
+            .expect("Writing to a string cannot fail.");
+        }
+        json.push_str(r#"],"displayTimeUnit":"ns"}"#);
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cancel_this::Cancelled;
+
+    #[test]
+    fn test_outcomes() {
+        assert_eq!(StepOutcome::of(&Ok(1)), StepOutcome::Completed);
+        let suspended: Completable<()> = Err(Incomplete::Suspended);
+        assert_eq!(StepOutcome::of(&suspended), StepOutcome::Suspended);
+        let cancelled: Completable<()> = Err(Incomplete::Cancelled(Cancelled::new("test")));
+        assert_eq!(StepOutcome::of(&cancelled), StepOutcome::Cancelled);
+        assert_eq!(StepOutcome::Exhausted.name(), "Exhausted");
+    }
+
+    #[test]
+    fn test_export() {
+        let mut timeline = Timeline::default();
+        assert!(timeline.is_empty());
+        assert_eq!(timeline.to_json(), "[]");
+        let origin = timeline.origin;
+        timeline.record(
+            2,
+            7,
+            StepOutcome::Suspended,
+            origin + Duration::from_micros(10),
+            Duration::from_nanos(1500),
+        );
+        timeline.record(
+            3,
+            0,
+            StepOutcome::Completed,
+            origin + Duration::from_micros(12),
+            Duration::from_micros(1),
+        );
+        assert_eq!(timeline.events()[0].start, Duration::from_micros(10));
+        assert_eq!(
+            timeline.to_json(),
+            r#"[{"task":2,"step":7,"outcome":"Suspended","start_ns":10000,"duration_ns":1500},{"task":3,"step":0,"outcome":"Completed","start_ns":12000,"duration_ns":1000}]"#
+        );
+        assert_eq!(
+            timeline.to_chrome_trace(),
+            r#"{"traceEvents":[{"name":"task 2","cat":"Suspended","ph":"X","ts":10.000,"dur":1.500,"pid":0,"tid":0,"args":{"task":2,"step":7}},{"name":"task 3","cat":"Completed","ph":"X","ts":12.000,"dur":1.000,"pid":0,"tid":0,"args":{"task":3,"step":0}}],"displayTimeUnit":"ns"}"#
+        );
+        assert_eq!(timeline.busy_time(3), Duration::from_micros(1));
+        assert_eq!(timeline.busy_time(5), Duration::ZERO);
+    }
+}
@@ -0,0 +1,15 @@
+/// The result of a single phase handler of a phase-based computation.
+///
+/// A phase handler either moves the computation to the `Next` phase (which also creates
+/// a suspend point), or completes the computation with an `OUTPUT` value. To stay in the
+/// current phase, the handler updates the phase data and returns [`crate::Incomplete::Suspended`].
+///
+/// This type is primarily used by the `ComputationState` derive macro (feature `derive`),
+/// but it can be also used to structure manual [`crate::ComputationStep`] implementations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transition<PHASE, OUTPUT> {
+    /// Replace the current phase with the given phase.
+    Next(PHASE),
+    /// Complete the computation with the given output.
+    Done(OUTPUT),
+}
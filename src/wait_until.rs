@@ -0,0 +1,148 @@
+use crate::{Blackboard, EventFlag, Incomplete};
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+
+/// A wake condition of a suspended computation (see [`suspend_until`]).
+///
+/// The condition is either an external [`EventFlag`], or a predicate over the
+/// [`Blackboard`] shared by the computations of a [`crate::Scheduler`].
+pub enum WaitUntil {
+    /// Wait until the flag is set.
+    Flag(EventFlag),
+    /// Wait until the predicate is satisfied by the shared [`Blackboard`].
+    Predicate(Box<dyn FnMut(&Blackboard) -> bool + Send>),
+}
+
+impl Debug for WaitUntil {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WaitUntil::Flag(flag) => f.debug_tuple("Flag").field(flag).finish(),
+            WaitUntil::Predicate(_) => f.debug_tuple("Predicate").finish_non_exhaustive(),
+        }
+    }
+}
+
+thread_local! {
+    static WAKE_CONDITION: RefCell<Option<WaitUntil>> = const { RefCell::new(None) };
+}
+
+impl WaitUntil {
+    /// Wait until the given `flag` is set.
+    pub fn flag(flag: EventFlag) -> Self {
+        WaitUntil::Flag(flag)
+    }
+
+    /// Wait until `predicate` returns `true` for the shared [`Blackboard`].
+    pub fn predicate<F: FnMut(&Blackboard) -> bool + Send + 'static>(predicate: F) -> Self {
+        WaitUntil::Predicate(Box::new(predicate))
+    }
+
+    /// Returns `true` if the condition is satisfied (and the computation should be
+    /// advanced again).
+    pub fn is_ready(&mut self, blackboard: &Blackboard) -> bool {
+        match self {
+            WaitUntil::Flag(flag) => flag.is_set(),
+            WaitUntil::Predicate(predicate) => predicate(blackboard),
+        }
+    }
+}
+
+/// Suspend the current step until the given wake `condition` is satisfied.
+///
+/// Similar to [`crate::suspend_for`], the condition is recorded on the current thread and
+/// [`Incomplete::Suspended`] is returned: `return Err(suspend_until(condition))`.
+/// Drivers that understand wake conditions (e.g., [`crate::Scheduler`]) obtain it using
+/// [`take_wake_condition`] and only poll the computation again once the condition is
+/// satisfied, making event-driven cooperative scheduling possible. Other drivers treat
+/// the outcome as a normal suspension (i.e., the computation should re-check the condition
+/// itself once it is resumed).
+///
+/// If several conditions are recorded before the driver takes them, only the last one
+/// is kept.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{
+///     Completable, Computation, ComputationStep, EventFlag, Incomplete, Scheduler, WaitUntil,
+///     suspend_until,
+/// };
+///
+/// /// Waits until the flag is set, then completes.
+/// struct WaitStep;
+///
+/// impl ComputationStep<EventFlag, u32, u32> for WaitStep {
+///     fn step(flag: &EventFlag, polls: &mut u32) -> Completable<u32> {
+///         *polls += 1;
+///         if flag.is_set() {
+///             Ok(*polls)
+///         } else {
+///             Err(suspend_until(WaitUntil::flag(flag.clone())))
+///         }
+///     }
+/// }
+///
+/// let flag = EventFlag::new();
+/// let mut scheduler = Scheduler::new();
+/// scheduler.spawn(Computation::<EventFlag, u32, u32, WaitStep>::from_parts(flag.clone(), 0));
+/// assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+/// // The computation is not polled until the flag is set.
+/// for _ in 0..10 {
+///     assert_eq!(scheduler.try_next(), Some(Err(Incomplete::Suspended)));
+/// }
+/// flag.set();
+/// assert_eq!(scheduler.try_next(), Some(Ok((0, 2))));
+/// ```
+pub fn suspend_until(condition: WaitUntil) -> Incomplete {
+    WAKE_CONDITION.with(|it| it.replace(Some(condition)));
+    Incomplete::Suspended
+}
+
+/// Take (and clear) the wake condition recorded on the current thread by [`suspend_until`].
+///
+/// Drivers should call this function before a step (to discard stale conditions) and after
+/// the step returns [`Incomplete::Suspended`].
+pub fn take_wake_condition() -> Option<WaitUntil> {
+    WAKE_CONDITION.with(|it| it.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlackboardKey;
+
+    const READY: BlackboardKey<bool> = BlackboardKey::new("ready");
+
+    #[test]
+    fn test_record_and_take() {
+        assert!(take_wake_condition().is_none());
+        let flag = EventFlag::new();
+        let _ = suspend_until(WaitUntil::predicate(|_| true));
+        assert_eq!(
+            suspend_until(WaitUntil::flag(flag.clone())),
+            Incomplete::Suspended
+        );
+        let Some(WaitUntil::Flag(recorded)) = take_wake_condition() else {
+            panic!("The last condition is kept.");
+        };
+        assert!(recorded.ptr_eq(&flag));
+        assert!(take_wake_condition().is_none());
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut blackboard = Blackboard::new();
+        let mut condition = WaitUntil::predicate(|board| board.get(READY) == Some(&true));
+        assert!(!condition.is_ready(&blackboard));
+        blackboard.insert(READY, true);
+        assert!(condition.is_ready(&blackboard));
+        assert_eq!(format!("{condition:?}"), "Predicate(..)");
+
+        let flag = EventFlag::new();
+        let mut condition = WaitUntil::flag(flag.clone());
+        assert!(!condition.is_ready(&blackboard));
+        flag.set();
+        assert!(condition.is_ready(&blackboard));
+    }
+}
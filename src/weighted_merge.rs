@@ -0,0 +1,278 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::marker::PhantomData;
+
+/// A [`Generatable`] that interleaves the items of multiple generators proportionally
+/// to their weights.
+///
+/// The merge uses the "smooth weighted round-robin" scheme: at the start of every round,
+/// each sub-generator gains credit equal to its weight, and the sub-generator with the most
+/// credit is polled first (ties are resolved by the order of sub-generators). The generator
+/// that produces the item pays the total weight of all sub-generators. As a result, a
+/// generator with weight `3` produces three items for every item of a generator with weight
+/// `1`, and the items are spread evenly instead of in bursts.
+///
+/// If the preferred sub-generator is suspended, the other sub-generators are polled in the
+/// order of their credit, so a suspended high-priority generator never blocks the merge. If all
+/// sub-generators are suspended, the merge returns [`Incomplete::Suspended`] (without starting
+/// a new round). Exhausted sub-generators are removed, and the merge is exhausted once all
+/// sub-generators are exhausted.
+///
+/// See also [`crate::Merge`] for unweighted round-robin interleaving.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep, WeightedMerge};
+///
+/// struct RepeatStep;
+///
+/// impl GeneratorStep<char, u32, char> for RepeatStep {
+///     fn step(item: &char, count: &mut u32) -> Completable<Option<char>> {
+///         *count += 1;
+///         Ok(if *count <= 6 { Some(*item) } else { None })
+///     }
+/// }
+///
+/// let merge = WeightedMerge::new(vec![
+///     (Generator::<char, u32, char, RepeatStep>::from_parts('a', 0).dyn_generatable(), 2),
+///     (Generator::<char, u32, char, RepeatStep>::from_parts('b', 0).dyn_generatable(), 1),
+/// ]);
+/// let items: String = merge.map(|it| it.unwrap()).collect();
+/// assert_eq!(items, "abaabaababbb");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>")
+)]
+pub struct WeightedMerge<T, G = DynGeneratable<T>>
+where
+    G: Generatable<T>,
+{
+    generators: Vec<G>,
+    weights: Vec<u64>,
+    credits: Vec<i64>,
+    round_open: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: PhantomData<T>,
+}
+
+impl<T, G: Generatable<T>> WeightedMerge<T, G> {
+    /// Create a new [`WeightedMerge`] of the given `(generator, weight)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any weight is zero.
+    pub fn new(generators: Vec<(G, u64)>) -> Self {
+        let mut merge = WeightedMerge {
+            generators: Vec::with_capacity(generators.len()),
+            weights: Vec::with_capacity(generators.len()),
+            credits: Vec::with_capacity(generators.len()),
+            round_open: false,
+            _phantom: Default::default(),
+        };
+        for (generator, weight) in generators {
+            merge.push(generator, weight);
+        }
+        merge
+    }
+
+    /// Add another generator with the given `weight` to this [`WeightedMerge`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is zero.
+    pub fn push(&mut self, generator: G, weight: u64) {
+        assert!(weight > 0, "Generator weight must be positive.");
+        self.generators.push(generator);
+        self.weights.push(weight);
+        self.credits.push(0);
+    }
+
+    /// The weights of the sub-generators that are not exhausted yet.
+    pub fn weights(&self) -> &[u64] {
+        &self.weights
+    }
+
+    /// The number of sub-generators that are not exhausted yet.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Returns `true` if all sub-generators are exhausted.
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.generators.remove(index);
+        self.weights.remove(index);
+        self.credits.remove(index);
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for WeightedMerge<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for WeightedMerge<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        if !self.round_open {
+            for (credit, weight) in self.credits.iter_mut().zip(&self.weights) {
+                *credit += i64::try_from(*weight).unwrap_or(i64::MAX);
+            }
+            self.round_open = true;
+        }
+
+        // Poll the generators in the order of decreasing credit.
+        let mut order = (0..self.generators.len()).collect::<Vec<_>>();
+        order.sort_by_key(|i| std::cmp::Reverse(self.credits[*i]));
+        let mut exhausted = Vec::new();
+        let mut result = None;
+        for index in order {
+            match self.generators[index].try_next() {
+                Some(Ok(item)) => {
+                    let total: u64 = self.weights.iter().sum();
+                    self.credits[index] -= i64::try_from(total).unwrap_or(i64::MAX);
+                    self.round_open = false;
+                    result = Some(Ok(item));
+                    break;
+                }
+                Some(Err(Incomplete::Suspended)) => (),
+                Some(Err(Incomplete::Exhausted)) | None => exhausted.push(index),
+                Some(Err(e)) => {
+                    result = Some(Err(e));
+                    break;
+                }
+            }
+        }
+
+        // Remove exhausted generators (from the highest index, such that indices stay valid).
+        exhausted.sort_unstable();
+        for index in exhausted.into_iter().rev() {
+            self.remove(index);
+        }
+
+        match result {
+            Some(result) => Some(result),
+            None if self.generators.is_empty() => None,
+            None => Some(Err(Incomplete::Suspended)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, Generator, GeneratorStep};
+
+    /// Produces the context item forever.
+    struct ForeverStep;
+
+    impl GeneratorStep<char, u32, char> for ForeverStep {
+        fn step(item: &char, _: &mut u32) -> Completable<Option<char>> {
+            Ok(Some(*item))
+        }
+    }
+
+    /// Produces the context item `count` times, suspending before every item.
+    struct SlowStep;
+
+    impl GeneratorStep<(char, u32), u32, char> for SlowStep {
+        fn step(context: &(char, u32), state: &mut u32) -> Completable<Option<char>> {
+            *state += 1;
+            if *state > 2 * context.1 {
+                Ok(None)
+            } else if state.is_multiple_of(2) {
+                Ok(Some(context.0))
+            } else {
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    fn forever(item: char) -> Generator<char, u32, char, ForeverStep> {
+        Generator::from_parts(item, 0)
+    }
+
+    #[test]
+    fn test_proportional_interleaving() {
+        let mut merge = WeightedMerge::new(vec![(forever('a'), 3), (forever('b'), 1)]);
+        let items: String = (0..8).map(|_| merge.try_next().unwrap().unwrap()).collect();
+        assert_eq!(items, "aabaaaba");
+
+        let mut merge = WeightedMerge::new(vec![
+            (forever('a'), 1),
+            (forever('b'), 5),
+            (forever('c'), 2),
+        ]);
+        let items: String = (0..80)
+            .map(|_| merge.try_next().unwrap().unwrap())
+            .collect();
+        assert_eq!(items.matches('a').count(), 10);
+        assert_eq!(items.matches('b').count(), 50);
+        assert_eq!(items.matches('c').count(), 20);
+    }
+
+    #[test]
+    fn test_low_priority_not_starved() {
+        let mut merge = WeightedMerge::new(vec![(forever('a'), 100), (forever('b'), 1)]);
+        let items: String = (0..101)
+            .map(|_| merge.try_next().unwrap().unwrap())
+            .collect();
+        assert_eq!(items.matches('b').count(), 1);
+    }
+
+    #[test]
+    fn test_suspended_generator_is_skipped() {
+        let slow = Generator::<(char, u32), u32, char, SlowStep>::from_parts(('s', 2), 0);
+        let fast = Generator::<(char, u32), u32, char, SlowStep>::from_parts(('f', 1), 1);
+        let mut merge = WeightedMerge::new(vec![(slow, 10), (fast, 1)]);
+        // The slow generator is suspended, hence the fast one produces the first item.
+        assert_eq!(merge.try_next(), Some(Ok('f')));
+        assert_eq!(merge.try_next(), Some(Ok('s')));
+        // The fast generator is exhausted, the slow one suspended.
+        assert_eq!(merge.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(merge.len(), 1);
+        assert_eq!(merge.weights(), &[10]);
+        assert_eq!(merge.try_next(), Some(Ok('s')));
+        assert_eq!(merge.try_next(), None);
+        assert!(merge.is_empty());
+    }
+
+    #[test]
+    fn test_push() {
+        let mut merge = WeightedMerge::new(Vec::new());
+        assert_eq!(merge.try_next(), None);
+        merge.push(forever('x').dyn_generatable(), 1);
+        assert_eq!(merge.try_next(), Some(Ok('x')));
+    }
+
+    #[test]
+    fn test_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let mut merge = WeightedMerge::new(vec![(forever('a'), 1)]);
+        let result = on_trigger(trigger, || {
+            Ok::<_, cancel_this::Cancelled>(merge.try_next())
+        })
+        .unwrap();
+        assert!(matches!(result, Some(Err(Incomplete::Cancelled(_)))));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_zero_weight() {
+        WeightedMerge::new(vec![(forever('a'), 0)]);
+    }
+}
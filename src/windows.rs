@@ -0,0 +1,134 @@
+use crate::blocking_iter::next_skip_suspended;
+use crate::{Completable, DynGeneratable, Generatable, Incomplete};
+use cancel_this::Cancellable;
+use std::collections::VecDeque;
+
+/// A [`Generatable`] that produces overlapping sliding windows of `size` consecutive items
+/// of another [`Generatable`] (analogous to `slice::windows`).
+///
+/// The first window is produced once `size` items are available. Until then, every consumed
+/// item is replaced by [`Incomplete::Suspended`], so the adapter never loops internally.
+/// If the inner generator produces fewer than `size` items, no window is produced.
+/// Suspensions and cancellation of the inner generator are passed through.
+///
+/// See also [`crate::GeneratableExt::windows`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Generator, GeneratorStep};
+///
+/// struct RangeStep;
+///
+/// impl GeneratorStep<u32, u32, u32> for RangeStep {
+///     fn step(max: &u32, current: &mut u32) -> Completable<Option<u32>> {
+///         *current += 1;
+///         if *current <= *max { Ok(Some(*current)) } else { Ok(None) }
+///     }
+/// }
+///
+/// let generator = Generator::<u32, u32, u32, RangeStep>::from_parts(4, 0);
+/// let windows = generator.windows(3).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound = "G: serde::Serialize + for<'a> serde::Deserialize<'a>, ITEM: serde::Serialize + for<'a> serde::Deserialize<'a>"
+    )
+)]
+pub struct Windows<ITEM, G = DynGeneratable<ITEM>>
+where
+    ITEM: Clone,
+    G: Generatable<ITEM>,
+{
+    generator: G,
+    size: usize,
+    window: VecDeque<ITEM>,
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Windows<ITEM, G> {
+    /// Create a new [`Windows`] adapter producing windows of `size` items of `generator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(generator: G, size: usize) -> Self {
+        assert!(size > 0, "Window size must be positive.");
+        Windows {
+            generator,
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+
+    /// The number of items in each window.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Iterator for Windows<ITEM, G> {
+    type Item = Cancellable<Vec<ITEM>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_skip_suspended(self)
+    }
+}
+
+impl<ITEM: Clone, G: Generatable<ITEM>> Generatable<Vec<ITEM>> for Windows<ITEM, G> {
+    fn try_next(&mut self) -> Option<Completable<Vec<ITEM>>> {
+        match self.generator.try_next()? {
+            Ok(item) => {
+                if self.window.len() == self.size {
+                    self.window.pop_front();
+                }
+                self.window.push_back(item);
+                if self.window.len() == self.size {
+                    Some(Ok(self.window.iter().cloned().collect()))
+                } else {
+                    Some(Err(Incomplete::Suspended))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromParts, GeneratableExt, test_fixtures::Items};
+
+    #[test]
+    fn test_windows_with_suspension() {
+        let mut windows = Windows::new(Items::from_parts(vec![1, 2, 0, 3, 4], 0), 2);
+        assert_eq!(windows.size(), 2);
+        assert_eq!(windows.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(windows.try_next(), Some(Ok(vec![1, 2])));
+        assert_eq!(windows.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(windows.try_next(), Some(Ok(vec![2, 3])));
+        assert_eq!(windows.try_next(), Some(Ok(vec![3, 4])));
+        assert_eq!(windows.try_next(), None);
+    }
+
+    #[test]
+    fn test_short_generator() {
+        let windows = Items::from_parts(vec![1, 2], 0).windows(3);
+        assert_eq!(windows.collect::<Cancellable<Vec<_>>>(), Ok(vec![]));
+        let windows = Items::from_parts(vec![1, 2], 0).windows(1);
+        assert_eq!(
+            windows.collect::<Cancellable<Vec<_>>>(),
+            Ok(vec![vec![1], vec![2]])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Window size must be positive.")]
+    fn test_zero_size() {
+        Items::from_parts(vec![], 0).windows(0);
+    }
+}
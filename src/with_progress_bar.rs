@@ -0,0 +1,202 @@
+use crate::{Completable, Computable, Generatable, Incomplete, Progress};
+use cancel_this::Cancellable;
+use indicatif::ProgressBar;
+use std::marker::PhantomData;
+
+/// A [`Computable`] (or [`Generatable`]) that reports the [`Progress`] of another
+/// computation using an [`indicatif::ProgressBar`].
+///
+/// After every step of the inner computation (i.e., at every suspend point), the length
+/// and the position of the bar are updated from [`Progress::total`] and
+/// [`Progress::completed`]. Once the computation completes (or the generator has no more
+/// items), the bar is finished. If the computation is cancelled, exhausted, or exceeds its
+/// resources, the bar is abandoned (i.e., it stays visible in its current state).
+///
+/// Requires the `indicatif` feature. See also [`crate::ComputableExt::with_progress_bar`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{IterGenerator, Pump, WithProgressBar};
+/// use indicatif::ProgressBar;
+///
+/// let pump = Pump::new(IterGenerator::new(1..=10), Vec::new());
+/// let mut wrapped = WithProgressBar::new(pump, ProgressBar::hidden());
+/// assert_eq!(wrapped.compute(), Ok(10));
+/// assert!(wrapped.bar().is_finished());
+/// assert_eq!(wrapped.bar().position(), 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithProgressBar<T, C> {
+    inner: C,
+    bar: ProgressBar,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C: Progress> WithProgressBar<T, C> {
+    /// Wrap the `inner` computation (or generator), reporting its progress using `bar`.
+    pub fn new(inner: C, bar: ProgressBar) -> Self {
+        let wrapped = WithProgressBar {
+            inner,
+            bar,
+            _phantom: Default::default(),
+        };
+        wrapped.update();
+        wrapped
+    }
+
+    /// The progress bar updated by this wrapper.
+    pub fn bar(&self) -> &ProgressBar {
+        &self.bar
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Mutable access to the inner computation.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Unwrap the inner computation and the progress bar.
+    pub fn into_parts(self) -> (C, ProgressBar) {
+        (self.inner, self.bar)
+    }
+
+    /// Copy the current progress of the inner computation into the bar.
+    fn update(&self) {
+        match self.inner.total() {
+            Some(total) => self.bar.set_length(total),
+            None => self.bar.unset_length(),
+        }
+        self.bar.set_position(self.inner.completed());
+    }
+
+    /// Update the bar according to the outcome of an inner step which did not complete.
+    fn incomplete(&self, error: &Incomplete) {
+        self.update();
+        if !matches!(error, Incomplete::Suspended) {
+            self.bar.abandon();
+        }
+    }
+}
+
+impl<T, C: Computable<T> + Progress> Computable<T> for WithProgressBar<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        match self.inner.try_compute() {
+            Ok(result) => {
+                self.update();
+                self.bar.finish();
+                Ok(result)
+            }
+            Err(e) => {
+                self.incomplete(&e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<T, G: Generatable<T> + Progress> Iterator for WithProgressBar<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T> + Progress> Generatable<T> for WithProgressBar<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        match self.inner.try_next() {
+            None => {
+                self.update();
+                self.bar.finish();
+                None
+            }
+            Some(Ok(item)) => {
+                self.update();
+                Some(Ok(item))
+            }
+            Some(Err(e)) => {
+                self.incomplete(&e);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputableExt, IterGenerator, Pump};
+    use cancel_this::{CancelAtomic, Cancelled, on_trigger};
+
+    /// Counts the produced items out of a known total.
+    struct Counted {
+        items: IterGenerator<std::ops::Range<u64>>,
+        produced: u64,
+    }
+
+    impl Iterator for Counted {
+        type Item = Cancellable<u64>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            crate::blocking_iter::next_skip_suspended(self)
+        }
+    }
+
+    impl Generatable<u64> for Counted {
+        fn try_next(&mut self) -> Option<Completable<u64>> {
+            let item = self.items.try_next();
+            if let Some(Ok(_)) = item {
+                self.produced += 1;
+            }
+            item
+        }
+    }
+
+    impl Progress for Counted {
+        fn completed(&self) -> u64 {
+            self.produced
+        }
+
+        fn total(&self) -> Option<u64> {
+            Some(4)
+        }
+    }
+
+    #[test]
+    fn test_generator_progress() {
+        let items = IterGenerator::new(0..4).suspend_every(2);
+        let inner = Counted { items, produced: 0 };
+        let mut wrapped = WithProgressBar::new(inner, ProgressBar::hidden());
+        assert_eq!(wrapped.bar().length(), Some(4));
+        assert_eq!(wrapped.try_next(), Some(Ok(0)));
+        assert_eq!(wrapped.try_next(), Some(Ok(1)));
+        assert_eq!(wrapped.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(wrapped.bar().position(), 2);
+        assert!(!wrapped.bar().is_finished());
+        let rest = wrapped.by_ref().collect::<Result<Vec<_>, _>>();
+        assert_eq!(rest, Ok(vec![2, 3]));
+        assert!(wrapped.bar().is_finished());
+        assert_eq!(wrapped.bar().position(), 4);
+    }
+
+    #[test]
+    fn test_cancelled_abandons() {
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let pump = Pump::new(IterGenerator::new(1..=3), Vec::new());
+        let mut wrapped = pump.with_progress_bar(ProgressBar::hidden());
+        let result = on_trigger(trigger, || Ok::<_, Cancelled>(wrapped.try_compute()))
+            .expect("Cancellation is reported by the computation.");
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+        assert!(wrapped.bar().is_finished());
+        assert_eq!(wrapped.bar().position(), 0);
+        let (pump, _) = wrapped.into_parts();
+        assert_eq!(pump.moved(), 0);
+    }
+}
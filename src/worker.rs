@@ -0,0 +1,313 @@
+use crate::{Computable, Forkable, Incomplete};
+use cancel_this::{CancelAtomic, Cancellable, on_trigger};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::thread::JoinHandle;
+
+/// The status of a computation driven by a [`WorkerHandle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkerStatus {
+    /// The computation is still running.
+    Running,
+    /// The computation completed successfully; the result can be obtained using
+    /// [`WorkerHandle::join`].
+    Completed,
+    /// The computation was canceled.
+    Cancelled,
+    /// The worker thread panicked.
+    Panicked,
+}
+
+const RUNNING: u8 = 0;
+const COMPLETED: u8 = 1;
+const CANCELLED: u8 = 2;
+
+/// Requests sent from the [`WorkerHandle`] to the worker thread.
+enum Command<C> {
+    Snapshot(Sender<C>),
+}
+
+/// A handle to a computation that is driven to completion by a dedicated worker thread.
+///
+/// The handle allows the caller to poll the status of the computation, request snapshots of
+/// the computation (taken at a suspend point), cancel it, or block until its result is known.
+/// Dropping the handle does not stop the worker; use [`WorkerHandle::cancel`] to stop it.
+///
+/// See [`spawn_worker`] and [`spawn_worker_with_snapshots`].
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::{FnComputation, Incomplete, spawn_worker};
+///
+/// let computation = FnComputation::new(1_000u64, (0u64, 0u64), |limit, (i, sum)| {
+///     if *i == *limit {
+///         return Ok(*sum);
+///     }
+///     *i += 1;
+///     *sum += *i;
+///     Err(Incomplete::Suspended)
+/// });
+///
+/// let worker = spawn_worker(computation);
+/// assert_eq!(worker.join().unwrap(), 500_500);
+/// ```
+pub struct WorkerHandle<T, C> {
+    thread: JoinHandle<Cancellable<T>>,
+    trigger: CancelAtomic,
+    status: Arc<AtomicU8>,
+    steps: Arc<AtomicUsize>,
+    commands: Option<Sender<Command<C>>>,
+    _phantom: PhantomData<T>,
+}
+
+/// Move `computation` to a new worker thread which drives it to completion.
+///
+/// The returned handle does not support snapshots; see [`spawn_worker_with_snapshots`].
+///
+/// # Panics
+///
+/// The worker thread panics if the computation is exhausted before it completes.
+/// The panic is propagated by [`WorkerHandle::join`].
+pub fn spawn_worker<T, C>(computation: C) -> WorkerHandle<T, C>
+where
+    T: Send + 'static,
+    C: Computable<T> + Send + 'static,
+{
+    spawn(computation, None)
+}
+
+/// Same as [`spawn_worker`], but the returned handle also supports
+/// [`WorkerHandle::snapshot`] using [`Forkable::fork`].
+pub fn spawn_worker_with_snapshots<T, C>(computation: C) -> WorkerHandle<T, C>
+where
+    T: Send + 'static,
+    C: Computable<T> + Forkable + Send + 'static,
+{
+    spawn(computation, Some(C::fork))
+}
+
+fn spawn<T, C>(mut computation: C, fork: Option<fn(&C) -> C>) -> WorkerHandle<T, C>
+where
+    T: Send + 'static,
+    C: Computable<T> + Send + 'static,
+{
+    let trigger = CancelAtomic::new();
+    let status = Arc::new(AtomicU8::new(RUNNING));
+    let steps = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = channel::<Command<C>>();
+    let thread = {
+        let (trigger, status, steps) = (trigger.clone(), status.clone(), steps.clone());
+        std::thread::spawn(move || {
+            let result = on_trigger(trigger, || drive(&mut computation, fork, &receiver, &steps));
+            let code = if result.is_ok() { COMPLETED } else { CANCELLED };
+            status.store(code, Ordering::SeqCst);
+            result
+        })
+    };
+    WorkerHandle {
+        thread,
+        trigger,
+        status,
+        steps,
+        commands: fork.map(|_| sender),
+        _phantom: Default::default(),
+    }
+}
+
+/// The main loop of the worker thread.
+fn drive<T, C: Computable<T>>(
+    computation: &mut C,
+    fork: Option<fn(&C) -> C>,
+    commands: &Receiver<Command<C>>,
+    steps: &AtomicUsize,
+) -> Cancellable<T> {
+    loop {
+        // We are at a suspend point, hence it is safe to process the pending requests.
+        while let Ok(Command::Snapshot(reply)) = commands.try_recv() {
+            if let Some(fork) = fork {
+                // The requester may have given up waiting, which is fine.
+                let _ = reply.send(fork(computation));
+            }
+        }
+        let result = computation.try_compute();
+        steps.fetch_add(1, Ordering::Relaxed);
+        match result {
+            Ok(value) => return Ok(value),
+            Err(Incomplete::Suspended) => continue,
+            Err(Incomplete::Cancelled(c)) => return Err(c),
+            Err(Incomplete::ResourceExceeded(e)) => return Err(e.into()),
+            Err(Incomplete::Exhausted) => {
+                panic!("Worker computation is exhausted.")
+            }
+        }
+    }
+}
+
+impl<T, C> WorkerHandle<T, C> {
+    /// The current status of the computation.
+    pub fn status(&self) -> WorkerStatus {
+        match self.status.load(Ordering::SeqCst) {
+            COMPLETED => WorkerStatus::Completed,
+            CANCELLED => WorkerStatus::Cancelled,
+            _ if self.thread.is_finished() => {
+                // The status could be updated right before the thread finished.
+                match self.status.load(Ordering::SeqCst) {
+                    COMPLETED => WorkerStatus::Completed,
+                    CANCELLED => WorkerStatus::Cancelled,
+                    _ => WorkerStatus::Panicked,
+                }
+            }
+            _ => WorkerStatus::Running,
+        }
+    }
+
+    /// Returns `true` if the worker thread has finished (regardless of the outcome).
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// The number of steps (calls to [`Computable::try_compute`]) performed so far.
+    pub fn steps(&self) -> usize {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    /// Request cancellation of the computation. The computation stops at the next
+    /// cancellation check.
+    pub fn cancel(&self) {
+        self.trigger.cancel();
+    }
+
+    /// Obtain a copy of the computation, taken by the worker at its next suspend point.
+    ///
+    /// Blocks until the snapshot is available. Returns `None` if the handle does not support
+    /// snapshots (see [`spawn_worker_with_snapshots`]), or if the computation finished before
+    /// reaching another suspend point.
+    pub fn snapshot(&self) -> Option<C> {
+        let commands = self.commands.as_ref()?;
+        let (reply, response) = channel();
+        commands.send(Command::Snapshot(reply)).ok()?;
+        response.recv().ok()
+    }
+
+    /// Block until the computation finishes and return its result.
+    ///
+    /// # Panics
+    ///
+    /// Propagates the panic of the worker thread (if any).
+    pub fn join(self) -> Cancellable<T> {
+        match self.thread.join() {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Completable, Computation, ComputationStep, FromParts, StatefulRef};
+    use std::sync::Barrier;
+
+    /// Counts to the context value.
+    struct CountStep;
+
+    impl ComputationStep<u64, u64, u64> for CountStep {
+        fn step(target: &u64, count: &mut u64) -> Completable<u64> {
+            if *count >= *target {
+                Ok(*count)
+            } else {
+                *count += 1;
+                Err(Incomplete::Suspended)
+            }
+        }
+    }
+
+    type Count = Computation<u64, u64, u64, CountStep>;
+
+    /// Blocks on a barrier after the given number of steps.
+    struct BlockingComputation {
+        steps: usize,
+        block_at: usize,
+        barrier: Arc<Barrier>,
+    }
+
+    impl Computable<usize> for BlockingComputation {
+        fn try_compute(&mut self) -> Completable<usize> {
+            cancel_this::is_cancelled!()?;
+            self.steps += 1;
+            if self.steps == self.block_at {
+                self.barrier.wait();
+            }
+            Err(Incomplete::Suspended)
+        }
+    }
+
+    #[test]
+    fn test_worker_completes() {
+        let worker = spawn_worker(Count::from_parts(100, 0));
+        assert!(worker.snapshot().is_none());
+        assert_eq!(worker.join(), Ok(100));
+    }
+
+    #[test]
+    fn test_worker_status_and_cancel() {
+        let barrier = Arc::new(Barrier::new(2));
+        let worker = spawn_worker(BlockingComputation {
+            steps: 0,
+            block_at: 10,
+            barrier: barrier.clone(),
+        });
+        barrier.wait();
+        assert_eq!(worker.status(), WorkerStatus::Running);
+        assert!(worker.steps() >= 9);
+        worker.cancel();
+        while !worker.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(worker.status(), WorkerStatus::Cancelled);
+        assert!(worker.join().is_err());
+    }
+
+    #[test]
+    fn test_worker_snapshot() {
+        let worker = spawn_worker_with_snapshots(Count::from_parts(u64::MAX, 0));
+        let first = worker.snapshot().unwrap();
+        let second = worker.snapshot().unwrap();
+        assert!(*second.state() >= *first.state());
+        worker.cancel();
+        assert!(worker.join().is_err());
+
+        // The snapshot is an independent computation.
+        let (target, state) = first.into_parts();
+        assert_eq!(target, u64::MAX);
+        let mut copy = Count::from_parts(state + 3, state);
+        assert_eq!(copy.compute(), Ok(state + 3));
+    }
+
+    #[test]
+    fn test_worker_completed_status() {
+        let worker = spawn_worker(Count::from_parts(5, 0));
+        while !worker.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(worker.status(), WorkerStatus::Completed);
+        assert_eq!(worker.steps(), 6);
+        assert_eq!(worker.join(), Ok(5));
+    }
+
+    #[test]
+    fn test_worker_panic() {
+        let mut exhausted = crate::ComputableIdentity::from(1);
+        assert_eq!(exhausted.try_compute(), Ok(1));
+        let worker = spawn_worker(exhausted);
+        while !worker.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(worker.status(), WorkerStatus::Panicked);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| worker.join()));
+        assert!(result.is_err());
+    }
+}
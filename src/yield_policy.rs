@@ -0,0 +1,375 @@
+use crate::{Completable, Computable, Generatable, Incomplete};
+use cancel_this::{Cancellable, is_cancelled};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// Decides how often [`yield_point`] suspends the current step.
+///
+/// A policy is configured by the *driver* of a computation (see [`YieldPolicy::scope`] or
+/// [`Yielding`]), while the step functions only call [`yield_point!`](crate::yield_point!)
+/// inside their long-running loops. This way, well-behaved algorithms suspend with a uniform
+/// granularity without hard-coding any thresholds.
+///
+/// A policy can limit the number of yield points per step, the wall-clock time per step,
+/// or both (whichever is reached first). The default policy never suspends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YieldPolicy {
+    iterations: Option<u64>,
+    elapsed: Option<Duration>,
+}
+
+/// The policy that is active on the current thread, together with its progress.
+struct ActivePolicy {
+    policy: YieldPolicy,
+    iterations: u64,
+    started: Instant,
+}
+
+thread_local! {
+    static ACTIVE_POLICY: RefCell<Option<ActivePolicy>> = const { RefCell::new(None) };
+}
+
+impl YieldPolicy {
+    /// A policy that never suspends (only cancellation is checked).
+    pub fn never() -> Self {
+        YieldPolicy::default()
+    }
+
+    /// A policy that suspends once every `count` yield points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn iterations(count: u64) -> Self {
+        YieldPolicy::default().with_iterations(count)
+    }
+
+    /// A policy that suspends at the first yield point after `duration` has elapsed
+    /// since the start of the step.
+    pub fn elapsed(duration: Duration) -> Self {
+        YieldPolicy::default().with_elapsed(duration)
+    }
+
+    /// Update the iteration limit of this policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn with_iterations(mut self, count: u64) -> Self {
+        assert!(count > 0, "Yield iteration count must be positive.");
+        self.iterations = Some(count);
+        self
+    }
+
+    /// Update the time limit of this policy.
+    pub fn with_elapsed(mut self, duration: Duration) -> Self {
+        self.elapsed = Some(duration);
+        self
+    }
+
+    /// The iteration limit of this policy (if any).
+    pub fn iteration_limit(&self) -> Option<u64> {
+        self.iterations
+    }
+
+    /// The time limit of this policy (if any).
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Run `action` with this policy active on the current thread.
+    ///
+    /// The iteration counter and the timer start when `action` starts, i.e., `action`
+    /// is typically a single step of a computation. Once the `action` finishes,
+    /// the previously active policy (if any) is restored.
+    pub fn scope<R, F: FnOnce() -> R>(self, action: F) -> R {
+        let active = ActivePolicy {
+            policy: self,
+            iterations: 0,
+            started: Instant::now(),
+        };
+        let previous = ACTIVE_POLICY.with(|it| it.replace(Some(active)));
+        // Restore the previous policy even if the action panics.
+        struct Restore(Option<Option<ActivePolicy>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take().unwrap_or_default();
+                ACTIVE_POLICY.with(|it| it.replace(previous));
+            }
+        }
+        let _restore = Restore(Some(previous));
+        action()
+    }
+}
+
+/// Check for cancellation and ask the active [`YieldPolicy`] whether the current step
+/// should suspend.
+///
+/// Returns `Err(Incomplete::Cancelled)` if the computation is cancelled and
+/// `Err(Incomplete::Suspended)` if the policy limit was reached (the limit is then reset,
+/// since the step is expected to return). Without an active policy, the function only
+/// checks for cancellation. Typically used through the [`yield_point!`](crate::yield_point!)
+/// macro.
+pub fn yield_point() -> Completable<()> {
+    is_cancelled!()?;
+    let suspend = ACTIVE_POLICY.with(|it| {
+        let mut active = it.borrow_mut();
+        let Some(active) = active.as_mut() else {
+            return false;
+        };
+        active.iterations += 1;
+        let by_iterations = active
+            .policy
+            .iterations
+            .is_some_and(|limit| active.iterations >= limit);
+        let by_time = active
+            .policy
+            .elapsed
+            .is_some_and(|limit| active.started.elapsed() >= limit);
+        if by_iterations || by_time {
+            active.iterations = 0;
+            active.started = Instant::now();
+        }
+        by_iterations || by_time
+    });
+    if suspend {
+        Err(Incomplete::Suspended)
+    } else {
+        Ok(())
+    }
+}
+
+/// A cooperative yield point for step functions.
+///
+/// Expands to [`yield_point()`](fn@crate::yield_point) and is meant to be used with
+/// the `?` operator inside a loop of a step function: `yield_point!()?`. The step then
+/// returns [`Incomplete::Suspended`] whenever the [`YieldPolicy`] configured by the driver
+/// says so, and [`Incomplete::Cancelled`] if the computation is cancelled.
+///
+/// # Example
+///
+/// ```rust
+/// use computation_process::prelude::*;
+/// use computation_process::{Completable, Computation, ComputationStep, YieldPolicy, yield_point};
+///
+/// struct SumStep;
+///
+/// impl ComputationStep<u64, (u64, u64), u64> for SumStep {
+///     fn step(limit: &u64, (i, sum): &mut (u64, u64)) -> Completable<u64> {
+///         while *i < *limit {
+///             *sum += *i;
+///             *i += 1;
+///             yield_point!()?;
+///         }
+///         Ok(*sum)
+///     }
+/// }
+///
+/// let mut computation = Computation::<u64, (u64, u64), u64, SumStep>::from_parts(100, (0, 0))
+///     .with_yield_policy(YieldPolicy::iterations(10));
+/// let mut steps = 1;
+/// while computation.try_compute().is_err() {
+///     steps += 1;
+/// }
+/// assert_eq!(steps, 11);
+/// ```
+#[macro_export]
+macro_rules! yield_point {
+    () => {
+        $crate::yield_point()
+    };
+}
+
+/// A [`Computable`] (or [`Generatable`]) that runs every step of the inner computation
+/// with the given [`YieldPolicy`] active.
+///
+/// See also [`crate::ComputableExt::with_yield_policy`].
+#[derive(Debug, Clone)]
+pub struct Yielding<T, C> {
+    inner: C,
+    policy: YieldPolicy,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, C> Yielding<T, C> {
+    /// Wrap the `inner` computation (or generator) such that it runs with `policy`.
+    pub fn new(inner: C, policy: YieldPolicy) -> Self {
+        Yielding {
+            inner,
+            policy,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// The policy used by this wrapper.
+    pub fn policy(&self) -> YieldPolicy {
+        self.policy
+    }
+
+    /// Access to the inner computation.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the inner computation.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<T, C: Computable<T>> Computable<T> for Yielding<T, C> {
+    fn try_compute(&mut self) -> Completable<T> {
+        let inner = &mut self.inner;
+        self.policy.scope(|| inner.try_compute())
+    }
+}
+
+impl<T, G: Generatable<T>> Iterator for Yielding<T, G> {
+    type Item = Cancellable<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::blocking_iter::next_skip_suspended(self)
+    }
+}
+
+impl<T, G: Generatable<T>> Generatable<T> for Yielding<T, G> {
+    fn try_next(&mut self) -> Option<Completable<T>> {
+        let inner = &mut self.inner;
+        self.policy.scope(|| inner.try_next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ComputableExt, Computation, ComputationStep, FromParts, Generator, GeneratorStep,
+        StatefulRef,
+    };
+
+    struct CountStep;
+
+    impl ComputationStep<u64, u64, u64> for CountStep {
+        fn step(limit: &u64, count: &mut u64) -> Completable<u64> {
+            while *count < *limit {
+                *count += 1;
+                yield_point!()?;
+            }
+            Ok(*count)
+        }
+    }
+
+    type Count = Computation<u64, u64, u64, CountStep>;
+
+    struct SlowStep;
+
+    impl ComputationStep<u64, u64, u64> for SlowStep {
+        fn step(limit: &u64, count: &mut u64) -> Completable<u64> {
+            while *count < *limit {
+                *count += 1;
+                std::thread::sleep(Duration::from_millis(2));
+                yield_point!()?;
+            }
+            Ok(*count)
+        }
+    }
+
+    struct EvenStep;
+
+    impl GeneratorStep<u64, u64, u64> for EvenStep {
+        fn step(limit: &u64, value: &mut u64) -> Completable<Option<u64>> {
+            loop {
+                if *value >= *limit {
+                    return Ok(None);
+                }
+                *value += 1;
+                if value.is_multiple_of(2) {
+                    return Ok(Some(*value));
+                }
+                yield_point!()?;
+            }
+        }
+    }
+
+    #[test]
+    fn test_without_policy() {
+        let mut count = Count::from_parts(1000, 0);
+        assert_eq!(count.try_compute(), Ok(1000));
+    }
+
+    #[test]
+    fn test_iteration_policy() {
+        let mut count = Count::from_parts(10, 0).with_yield_policy(YieldPolicy::iterations(3));
+        assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*count.inner().state(), 3);
+        assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*count.inner().state(), 6);
+        assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(count.try_compute(), Ok(10));
+    }
+
+    #[test]
+    fn test_time_policy() {
+        let policy = YieldPolicy::elapsed(Duration::from_millis(5));
+        assert_eq!(policy.time_limit(), Some(Duration::from_millis(5)));
+        assert_eq!(policy.iteration_limit(), None);
+        let mut slow =
+            Computation::<u64, u64, u64, SlowStep>::from_parts(100, 0).with_yield_policy(policy);
+        assert_eq!(slow.try_compute(), Err(Incomplete::Suspended));
+        let done = *slow.inner().state();
+        assert!((1..100).contains(&done));
+    }
+
+    #[test]
+    fn test_combined_policy() {
+        let policy = YieldPolicy::elapsed(Duration::from_secs(3600)).with_iterations(4);
+        let mut count = Count::from_parts(10, 0).with_yield_policy(policy);
+        assert_eq!(count.try_compute(), Err(Incomplete::Suspended));
+        assert_eq!(*count.inner().state(), 4);
+    }
+
+    #[test]
+    fn test_scope_is_restored() {
+        let outer = YieldPolicy::iterations(1);
+        outer.scope(|| {
+            YieldPolicy::never().scope(|| {
+                assert_eq!(yield_point(), Ok(()));
+                assert_eq!(yield_point(), Ok(()));
+            });
+            assert_eq!(yield_point(), Err(Incomplete::Suspended));
+        });
+        assert_eq!(yield_point(), Ok(()));
+    }
+
+    #[test]
+    fn test_cancellation() {
+        use cancel_this::{CancelAtomic, on_trigger};
+
+        let trigger = CancelAtomic::new();
+        trigger.cancel();
+        let result = on_trigger(trigger, || {
+            let mut count = Count::from_parts(10, 0).with_yield_policy(YieldPolicy::never());
+            Ok::<_, cancel_this::Cancelled>(count.try_compute())
+        })
+        .unwrap();
+        assert!(matches!(result, Err(Incomplete::Cancelled(_))));
+    }
+
+    #[test]
+    fn test_generator() {
+        let generator = Generator::<u64, u64, u64, EvenStep>::from_parts(10, 0);
+        let mut yielding = Yielding::new(generator, YieldPolicy::iterations(1));
+        assert_eq!(yielding.try_next(), Some(Err(Incomplete::Suspended)));
+        assert_eq!(yielding.try_next(), Some(Ok(2)));
+        let rest: Vec<u64> = yielding.map(|it| it.unwrap()).collect();
+        assert_eq!(rest, vec![4, 6, 8, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_zero_iterations() {
+        YieldPolicy::iterations(0);
+    }
+}